@@ -12,6 +12,21 @@ pub const STREAMS_STORAGE_KEY: &str = "streams";
 pub const SEARCH_HISTORY_STORAGE_KEY: &str = "search_history";
 pub const NOTIFICATIONS_STORAGE_KEY: &str = "notifications";
 pub const DISMISSED_EVENTS_STORAGE_KEY: &str = "dismissed_events";
+pub const OUTBOX_STORAGE_KEY: &str = "outbox";
+pub const ACCOUNTS_STORAGE_KEY: &str = "accounts";
+pub const PLAYLISTS_STORAGE_KEY: &str = "playlists";
+pub const SMART_LISTS_STORAGE_KEY: &str = "smart_lists";
+pub const WATCH_HISTORY_STORAGE_KEY: &str = "watch_history";
+pub const LOCAL_SEARCH_STORAGE_KEY: &str = "local_search";
+/// The storage keys which are encrypted when the `Env` supplies a storage encryption key
+pub const ENCRYPTED_STORAGE_KEYS: [&str; 6] = [
+    PROFILE_STORAGE_KEY,
+    LIBRARY_RECENT_STORAGE_KEY,
+    LIBRARY_STORAGE_KEY,
+    STREAMS_STORAGE_KEY,
+    OUTBOX_STORAGE_KEY,
+    WATCH_HISTORY_STORAGE_KEY,
+];
 pub const LIBRARY_COLLECTION_NAME: &str = "libraryItem";
 pub const SEARCH_EXTRA_NAME: &str = "search";
 /// `https://{ADDON_UR}/meta/...` resource
@@ -24,18 +39,45 @@ pub const ADDON_MANIFEST_PATH: &str = "/manifest.json";
 pub const ADDON_LEGACY_PATH: &str = "/stremio/v1";
 pub const CATALOG_PAGE_SIZE: usize = 100;
 pub const CATALOG_PREVIEW_SIZE: usize = 100;
+/// The number of items shown in each row of the board
+pub const BOARD_ROW_ITEMS_COUNT: usize = 10;
 pub const LIBRARY_RECENT_COUNT: usize = 200;
 pub const NOTIFICATION_ITEMS_COUNT: usize = 100;
+/// In hours, the longest interval between the notifications polls (a week)
+pub const NOTIFICATIONS_MAX_POLL_INTERVAL: u32 = 168;
+/// In minutes, the biggest offset of a time zone from UTC
+pub const QUIET_HOURS_MAX_UTC_OFFSET: i32 = 14 * 60;
+/// The number of series in the library whose meta items are fetched for the calendar
+pub const CALENDAR_META_ITEMS_COUNT: usize = 20;
+/// The number of local search results merged into the global search results
+pub const GLOBAL_SEARCH_LOCAL_ITEMS_COUNT: usize = 20;
+/// The maximum number of items in the watch history, the oldest ones are pruned above it
+pub const WATCH_HISTORY_MAX_ITEMS: usize = 1000;
+/// The maximum number of queries in the search history,
+/// the least recently searched ones are pruned first
+pub const SEARCH_HISTORY_MAX_ITEMS: usize = 200;
+/// The number of search history suggestions for the typed query
+pub const SEARCH_HISTORY_SUGGESTIONS_COUNT: usize = 5;
+/// The number of library items the "For You" taste profile is built from
+pub const FOR_YOU_SEEDS_COUNT: usize = 20;
+/// In days, the weight of a library item in the "For You" taste profile halves over this period
+pub const FOR_YOU_RECENCY_HALF_LIFE: f64 = 30.0;
 
 /// A `LibraryItem` is considered watched once we've watched more than the `duration * threshold`:
 ///
 /// `LibraryItem.state.time_watched` > `LibraryItem.state.duration` * [`WATCHED_THRESHOLD_COEF`]
 pub const WATCHED_THRESHOLD_COEF: f64 = 0.7;
 pub const CREDITS_THRESHOLD_COEF: f64 = 0.9;
+/// The maximum number of videos of a `LibraryItem` with a resume point,
+/// the least recently watched ones are dropped first
+pub const LIBRARY_ITEM_RESUME_POINTS_COUNT: usize = 20;
+/// In milliseconds, the videos watched for less start over without a resume point
+pub const RESUME_POINT_MIN_TIME_OFFSET: u64 = 10_000;
 /// The latest migration scheme version
-pub const SCHEMA_VERSION: u32 = 14;
+pub const SCHEMA_VERSION: u32 = 19;
 pub const IMDB_LINK_CATEGORY: &str = "imdb";
 pub const GENRES_LINK_CATEGORY: &str = "Genres";
+pub const CAST_LINK_CATEGORY: &str = "Cast";
 pub const CINEMETA_TOP_CATALOG_ID: &str = "top";
 /// Only found in Cinemeta catalogs, i.e. [`CINEMETA_CATALOGS_URL`](struct@CINEMETA_CATALOGS_URL)
 pub const CINEMETA_FEED_CATALOG_ID: &str = "feed.json";
@@ -54,6 +96,19 @@ pub const URI_COMPONENT_ENCODE_SET: &AsciiSet = &NON_ALPHANUMERIC
 
 /// In milliseconds
 pub const PLAYER_IGNORE_SEEK_AFTER: u64 = 600_000;
+/// In milliseconds, the largest subtitles offset looked for when syncing
+pub const SUBTITLES_SYNC_MAX_OFFSET: i64 = 60_000;
+/// In milliseconds, how far a cue can be from the reference to count as a match
+pub const SUBTITLES_SYNC_MATCH_TOLERANCE: i64 = 500;
+/// The subtitles are synced only when at least this many cues match the reference
+pub const SUBTITLES_SYNC_MIN_MATCHED_CUES: usize = 5;
+
+/// After this time (in milliseconds) a running scheduler job which didn't finish is considered stale
+pub const SCHEDULER_JOB_TIMEOUT: u64 = 300_000;
+/// The maximum delay (in milliseconds) between retries of a failing scheduler job
+pub const SCHEDULER_MAX_BACKOFF: u64 = 86_400_000;
+/// The longest interval (in milliseconds) between runs of a scheduler job (a week)
+pub const SCHEDULER_MAX_INTERVAL: u64 = 604_800_000;
 
 pub static BASE64: base64::engine::general_purpose::GeneralPurpose =
     base64::engine::general_purpose::STANDARD;
@@ -1,3 +1,5 @@
+use std::iter;
+
 use percent_encoding::utf8_percent_encode;
 use regex::Regex;
 use serde::Serialize;
@@ -11,7 +13,7 @@ use crate::{
     types::{
         addon::{ExtraValue, ResourcePath, ResourceRequest},
         library::LibraryItem,
-        profile::Settings,
+        profile::{Settings, SmartList},
         query_params_encode,
         resource::{MetaItem, MetaItemPreview, Stream, StreamSource, Video},
         streams::StreamsItem,
@@ -580,36 +582,60 @@ impl From<&String> for LibraryDeepLinks {
 
 impl From<(&String, &LibraryRequest)> for LibraryDeepLinks {
     fn from((root, request): (&String, &LibraryRequest)) -> Self {
+        let sort = serde_json::to_value(&request.sort).unwrap();
+        let watch_status = request
+            .watch_status
+            .map(|watch_status| serde_json::to_value(watch_status).unwrap());
+        let min_rating = request.min_rating.map(|min_rating| min_rating.to_string());
+        let query_params = iter::once(("sort", sort.as_str().unwrap()))
+            .chain(
+                request
+                    .smart_list
+                    .as_deref()
+                    .map(|smart_list| ("smartList", smart_list)),
+            )
+            .chain(
+                watch_status
+                    .as_ref()
+                    .and_then(|watch_status| watch_status.as_str())
+                    .map(|watch_status| ("watchStatus", watch_status)),
+            )
+            .chain(
+                min_rating
+                    .as_deref()
+                    .map(|min_rating| ("minRating", min_rating)),
+            )
+            .collect::<Vec<_>>();
         LibraryDeepLinks {
             library: match &request.r#type {
                 Some(r#type) => format!(
                     "stremio:///{}/{}?{}",
                     root,
                     utf8_percent_encode(r#type, URI_COMPONENT_ENCODE_SET),
-                    query_params_encode(&[(
-                        "sort",
-                        serde_json::to_value(&request.sort)
-                            .unwrap()
-                            .as_str()
-                            .unwrap()
-                    )]),
-                ),
-                _ => format!(
-                    "stremio:///{}?{}",
-                    root,
-                    query_params_encode(&[(
-                        "sort",
-                        serde_json::to_value(&request.sort)
-                            .unwrap()
-                            .as_str()
-                            .unwrap()
-                    )]),
+                    query_params_encode(&query_params),
                 ),
+                _ => format!("stremio:///{}?{}", root, query_params_encode(&query_params)),
             },
         }
     }
 }
 
+impl From<(&String, &SmartList)> for LibraryDeepLinks {
+    fn from((root, smart_list): (&String, &SmartList)) -> Self {
+        LibraryDeepLinks::from((
+            root,
+            &LibraryRequest {
+                r#type: smart_list.query.r#type.to_owned(),
+                sort: Default::default(),
+                page: Default::default(),
+                smart_list: Some(smart_list.id.to_owned()),
+                watch_status: None,
+                min_rating: None,
+            },
+        ))
+    }
+}
+
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SearchHistoryItemDeepLinks {
@@ -0,0 +1,531 @@
+//! The upcoming episodes of the series in the library
+//!
+//! The videos are collected from the meta items of the most recently watched series
+//! and from the `last-videos` catalogs of all the series in the library.
+//! They can be exported as an iCalendar ([RFC 5545](https://www.rfc-editor.org/rfc/rfc5545))
+//! file with an all-day event for every video.
+
+use std::collections::HashSet;
+use std::fmt::Write;
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc};
+use itertools::Itertools;
+use percent_encoding::utf8_percent_encode;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    constants::{
+        CALENDAR_META_ITEMS_COUNT, LAST_VIDEOS_IDS_EXTRA_PROP, META_RESOURCE_NAME,
+        NOTIFICATION_ITEMS_COUNT, URI_COMPONENT_ENCODE_SET,
+    },
+    models::{
+        common::{
+            eq_update, is_allowed_by, resource_update, resources_update_with_vector_content,
+            Loadable, ResourceAction, ResourceLoadable, ResourcesAction,
+        },
+        ctx::{Ctx, CtxError, OtherError},
+    },
+    runtime::{
+        msg::{Action, ActionCalendar, ActionLoad, Event, Internal, Msg},
+        Effects, Env, UpdateWithCtx,
+    },
+    types::{
+        addon::{AggrRequest, ExtraType, ResourcePath, ResourceRequest},
+        library::{LibraryBucket, LibraryItem},
+        profile::{ParentalControls, Profile},
+        resource::{MetaItem, MetaItemPreview, Video},
+    },
+};
+
+/// The domain part of the unique ids of the iCalendar events
+const ICALENDAR_UID_DOMAIN: &str = "stremio.com";
+/// The maximum length of an iCalendar content line in octets, without the line break
+const ICALENDAR_LINE_LENGTH: usize = 75;
+
+#[derive(Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub enum Grouping {
+    #[default]
+    Day,
+    /// Weeks start on Monday
+    Week,
+    Month,
+}
+
+#[derive(Default, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Selected {
+    #[serde(default)]
+    pub grouping: Grouping,
+}
+
+#[derive(Clone, PartialEq, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CalendarItem {
+    pub meta_item: MetaItemPreview,
+    /// The request of the meta item, used for the deep links of the video
+    pub request: ResourceRequest,
+    pub video: Video,
+}
+
+#[derive(Clone, PartialEq, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CalendarGroup {
+    /// The first day of the group
+    pub start: NaiveDate,
+    /// The earliest released first
+    pub items: Vec<CalendarItem>,
+}
+
+#[derive(Default, Clone, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Calendar {
+    pub selected: Option<Selected>,
+    /// The meta items of the most recently watched series
+    pub meta_items: Vec<ResourceLoadable<MetaItem>>,
+    /// The `last-videos` catalogs of all the series
+    pub catalogs: Vec<ResourceLoadable<Vec<MetaItem>>>,
+    /// The earliest first, without the days, weeks or months with no releases
+    pub groups: Vec<CalendarGroup>,
+}
+
+impl<E: Env + 'static> UpdateWithCtx<E> for Calendar {
+    fn update(&mut self, msg: &Msg, ctx: &Ctx) -> Effects {
+        match msg {
+            Msg::Action(Action::Load(ActionLoad::Calendar(selected))) => {
+                let selected_effects = eq_update(&mut self.selected, Some(selected.to_owned()));
+                let meta_items_effects = meta_items_update::<E>(
+                    &mut self.meta_items,
+                    &self.selected,
+                    &ctx.library,
+                    &ctx.profile,
+                    ctx.parental_controls(),
+                );
+                let catalogs_effects = catalogs_update::<E>(
+                    &mut self.catalogs,
+                    &self.selected,
+                    &ctx.library,
+                    &ctx.profile,
+                    ctx.parental_controls(),
+                );
+                let groups_effects = groups_update::<E>(
+                    &mut self.groups,
+                    &self.selected,
+                    &self.meta_items,
+                    &self.catalogs,
+                    &ctx.library,
+                    ctx.parental_controls(),
+                );
+                selected_effects
+                    .join(meta_items_effects)
+                    .join(catalogs_effects)
+                    .join(groups_effects)
+            }
+            Msg::Action(Action::Unload) => {
+                let selected_effects = eq_update(&mut self.selected, None);
+                let meta_items_effects = eq_update(&mut self.meta_items, vec![]);
+                let catalogs_effects = eq_update(&mut self.catalogs, vec![]);
+                let groups_effects = eq_update(&mut self.groups, vec![]);
+                selected_effects
+                    .join(meta_items_effects)
+                    .join(catalogs_effects)
+                    .join(groups_effects)
+            }
+            Msg::Internal(Internal::ResourceRequestResult(request, result)) => {
+                let meta_items_effects = match self
+                    .meta_items
+                    .iter_mut()
+                    .find(|meta_item| meta_item.request == *request)
+                {
+                    Some(meta_item) => resource_update::<E, _>(
+                        meta_item,
+                        ResourceAction::ResourceRequestResult { request, result },
+                    ),
+                    None => Effects::none().unchanged(),
+                };
+                let catalogs_effects = resources_update_with_vector_content::<E, _>(
+                    &mut self.catalogs,
+                    ResourcesAction::ResourceRequestResult { request, result },
+                );
+                if meta_items_effects.has_changed || catalogs_effects.has_changed {
+                    let groups_effects = groups_update::<E>(
+                        &mut self.groups,
+                        &self.selected,
+                        &self.meta_items,
+                        &self.catalogs,
+                        &ctx.library,
+                        ctx.parental_controls(),
+                    );
+                    meta_items_effects
+                        .join(catalogs_effects)
+                        .join(groups_effects)
+                } else {
+                    meta_items_effects.join(catalogs_effects)
+                }
+            }
+            Msg::Action(Action::Calendar(ActionCalendar::ExportICalendar))
+                if !is_loaded(&self.selected, &self.meta_items, &self.catalogs) =>
+            {
+                // the file would miss the videos of the series which are still loading
+                Effects::msg(Msg::Event(Event::Error {
+                    error: CtxError::from(OtherError::CalendarNotLoaded),
+                    source: Box::new(Event::ICalendarExported {
+                        content: String::new(),
+                    }),
+                }))
+                .unchanged()
+            }
+            Msg::Action(Action::Calendar(ActionCalendar::ExportICalendar)) => {
+                let items = upcoming_items(
+                    &self.meta_items,
+                    &self.catalogs,
+                    &ctx.library,
+                    ctx.parental_controls(),
+                    E::now(),
+                );
+                Effects::msg(Msg::Event(Event::ICalendarExported {
+                    content: icalendar(&items, E::now()),
+                }))
+                .unchanged()
+            }
+            Msg::Internal(Internal::LibraryChanged(_))
+            | Msg::Internal(Internal::ParentalControlsChanged) => groups_update::<E>(
+                &mut self.groups,
+                &self.selected,
+                &self.meta_items,
+                &self.catalogs,
+                &ctx.library,
+                ctx.parental_controls(),
+            ),
+            _ => Effects::none().unchanged(),
+        }
+    }
+}
+
+impl Grouping {
+    /// The first day of the day, week or month the date is in
+    pub fn start(&self, date: NaiveDate) -> NaiveDate {
+        match self {
+            Grouping::Day => date,
+            Grouping::Week => date - Duration::days(date.weekday().num_days_from_monday().into()),
+            Grouping::Month => date.with_day(1).unwrap_or(date),
+        }
+    }
+}
+
+/// Whether the calendar is loaded and all its meta items and catalogs have answered
+fn is_loaded(
+    selected: &Option<Selected>,
+    meta_items: &[ResourceLoadable<MetaItem>],
+    catalogs: &[ResourceLoadable<Vec<MetaItem>>],
+) -> bool {
+    selected.is_some()
+        && meta_items
+            .iter()
+            .all(|meta_item| matches!(&meta_item.content, Some(content) if !content.is_loading()))
+        && catalogs
+            .iter()
+            .all(|catalog| matches!(&catalog.content, Some(content) if !content.is_loading()))
+}
+
+/// The series in the library, the most recently watched first
+pub fn calendar_library_items<'a>(
+    library: &'a LibraryBucket,
+    parental_controls: Option<&'a ParentalControls>,
+) -> impl Iterator<Item = &'a LibraryItem> + 'a {
+    library
+        .items
+        .values()
+        .filter(|library_item| {
+            library_item.r#type == "series" && !library_item.removed && !library_item.temp
+        })
+        .filter(move |library_item| is_allowed_by(*library_item, parental_controls))
+        .sorted_by(|a, b| {
+            b.state
+                .last_watched
+                .unwrap_or(b.mtime)
+                .cmp(&a.state.last_watched.unwrap_or(a.mtime))
+                .then(a.id.cmp(&b.id))
+        })
+}
+
+/// The videos released from today on, the earliest first
+pub fn upcoming_items<'a>(
+    meta_items: &'a [ResourceLoadable<MetaItem>],
+    catalogs: &'a [ResourceLoadable<Vec<MetaItem>>],
+    library: &'a LibraryBucket,
+    parental_controls: Option<&'a ParentalControls>,
+    now: DateTime<Utc>,
+) -> Vec<CalendarItem> {
+    let library_ids = calendar_library_items(library, parental_controls)
+        .map(|library_item| &library_item.id)
+        .collect::<HashSet<_>>();
+    let today = now.date_naive();
+    let from_meta_items = meta_items.iter().filter_map(|meta_item| match meta_item {
+        ResourceLoadable {
+            request,
+            content: Some(Loadable::Ready(meta_item)),
+        } => Some((meta_item, request.to_owned())),
+        _ => None,
+    });
+    let from_catalogs = catalogs
+        .iter()
+        .filter_map(|catalog| match catalog {
+            ResourceLoadable {
+                request,
+                content: Some(Loadable::Ready(meta_items)),
+            } => Some((request, meta_items)),
+            _ => None,
+        })
+        .flat_map(|(request, meta_items)| {
+            meta_items.iter().map(|meta_item| {
+                let meta_request = ResourceRequest::new(
+                    request.base.to_owned(),
+                    ResourcePath::without_extra(
+                        META_RESOURCE_NAME,
+                        &meta_item.preview.r#type,
+                        &meta_item.preview.id,
+                    ),
+                );
+                (meta_item, meta_request)
+            })
+        });
+    from_meta_items
+        .chain(from_catalogs)
+        .filter(|(meta_item, _)| library_ids.contains(&meta_item.preview.id))
+        .flat_map(|(meta_item, request)| {
+            meta_item
+                .videos
+                .iter()
+                .filter(|video| {
+                    video
+                        .released
+                        .map_or(false, |released| released.date_naive() >= today)
+                })
+                .map(move |video| CalendarItem {
+                    meta_item: meta_item.preview.to_owned(),
+                    request: request.to_owned(),
+                    video: video.to_owned(),
+                })
+        })
+        // the meta items come first, so their videos are preferred over the catalogs ones
+        .unique_by(|item| (item.meta_item.id.to_owned(), item.video.id.to_owned()))
+        .sorted_by(|a, b| {
+            a.video
+                .released
+                .cmp(&b.video.released)
+                .then(a.meta_item.name.cmp(&b.meta_item.name))
+                .then(a.video.id.cmp(&b.video.id))
+        })
+        .collect()
+}
+
+fn meta_items_update<E: Env + 'static>(
+    meta_items: &mut Vec<ResourceLoadable<MetaItem>>,
+    selected: &Option<Selected>,
+    library: &LibraryBucket,
+    profile: &Profile,
+    parental_controls: Option<&ParentalControls>,
+) -> Effects {
+    let requests = match selected {
+        Some(_) => calendar_library_items(library, parental_controls)
+            .take(CALENDAR_META_ITEMS_COUNT)
+            .filter_map(|library_item| {
+                // only the first addon which provides the meta item is requested
+                AggrRequest::AllOfResource(ResourcePath::without_extra(
+                    META_RESOURCE_NAME,
+                    &library_item.r#type,
+                    &library_item.id,
+                ))
+                .plan(&profile.addons)
+                .into_iter()
+                .map(|(_, request)| request)
+                .next()
+            })
+            .collect::<Vec<_>>(),
+        None => vec![],
+    };
+    let mut next_meta_items = requests
+        .into_iter()
+        .map(|request| {
+            meta_items
+                .iter()
+                .find(|meta_item| meta_item.request == request && meta_item.content.is_some())
+                .cloned()
+                .unwrap_or(ResourceLoadable {
+                    request,
+                    content: None,
+                })
+        })
+        .collect::<Vec<_>>();
+    let effects = next_meta_items
+        .iter_mut()
+        .filter(|meta_item| meta_item.content.is_none())
+        .map(|meta_item| {
+            let request = meta_item.request.to_owned();
+            resource_update::<E, _>(
+                meta_item,
+                ResourceAction::ResourceRequested { request: &request },
+            )
+        })
+        .fold(Effects::none().unchanged(), |effects, meta_item_effects| {
+            effects.join(meta_item_effects.unchanged())
+        });
+    effects.join(eq_update(meta_items, next_meta_items))
+}
+
+fn catalogs_update<E: Env + 'static>(
+    catalogs: &mut Vec<ResourceLoadable<Vec<MetaItem>>>,
+    selected: &Option<Selected>,
+    library: &LibraryBucket,
+    profile: &Profile,
+    parental_controls: Option<&ParentalControls>,
+) -> Effects {
+    let id_types = calendar_library_items(library, parental_controls)
+        .map(|library_item| (library_item.id.to_owned(), library_item.r#type.to_owned()))
+        .collect::<Vec<_>>();
+    match selected {
+        Some(_) if !id_types.is_empty() => resources_update_with_vector_content::<E, _>(
+            catalogs,
+            ResourcesAction::request(
+                &AggrRequest::CatalogsFiltered(vec![ExtraType::Ids {
+                    extra_name: LAST_VIDEOS_IDS_EXTRA_PROP.name.to_owned(),
+                    id_types,
+                    limit: Some(NOTIFICATION_ITEMS_COUNT),
+                }]),
+                &profile.addons,
+            ),
+        ),
+        _ => eq_update(catalogs, vec![]),
+    }
+}
+
+fn groups_update<E: Env + 'static>(
+    groups: &mut Vec<CalendarGroup>,
+    selected: &Option<Selected>,
+    meta_items: &[ResourceLoadable<MetaItem>],
+    catalogs: &[ResourceLoadable<Vec<MetaItem>>],
+    library: &LibraryBucket,
+    parental_controls: Option<&ParentalControls>,
+) -> Effects {
+    let next_groups = match selected {
+        Some(selected) => {
+            upcoming_items(meta_items, catalogs, library, parental_controls, E::now())
+                .into_iter()
+                .filter_map(|item| {
+                    let released = item.video.released?;
+                    Some((selected.grouping.start(released.date_naive()), item))
+                })
+                // the items are sorted by the release date, so are the groups
+                .group_by(|(start, _)| *start)
+                .into_iter()
+                .map(|(start, items)| CalendarGroup {
+                    start,
+                    items: items.map(|(_, item)| item).collect(),
+                })
+                .collect()
+        }
+        None => vec![],
+    };
+    eq_update(groups, next_groups)
+}
+
+/// Serializes the items into an iCalendar file
+pub fn icalendar(items: &[CalendarItem], now: DateTime<Utc>) -> String {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_owned(),
+        "VERSION:2.0".to_owned(),
+        "PRODID:-//Stremio//Stremio Core//EN".to_owned(),
+        "CALSCALE:GREGORIAN".to_owned(),
+        "METHOD:PUBLISH".to_owned(),
+    ];
+    let dtstamp = now.format("%Y%m%dT%H%M%SZ").to_string();
+    for item in items {
+        let released = match item.video.released {
+            Some(released) => released.date_naive(),
+            None => continue,
+        };
+        lines.push("BEGIN:VEVENT".to_owned());
+        lines.push(format!("UID:{}", icalendar_uid(item)));
+        lines.push(format!("DTSTAMP:{dtstamp}"));
+        lines.push(format!("DTSTART;VALUE=DATE:{}", released.format("%Y%m%d")));
+        lines.push(format!(
+            "DTEND;VALUE=DATE:{}",
+            (released + Duration::days(1)).format("%Y%m%d")
+        ));
+        lines.push(format!(
+            "SUMMARY:{}",
+            icalendar_text(&icalendar_summary(item))
+        ));
+        if let Some(overview) = item
+            .video
+            .overview
+            .as_ref()
+            .filter(|overview| !overview.is_empty())
+        {
+            lines.push(format!("DESCRIPTION:{}", icalendar_text(overview)));
+        }
+        lines.push(format!(
+            "URL:stremio:///detail/{}/{}/{}",
+            utf8_percent_encode(&item.meta_item.r#type, URI_COMPONENT_ENCODE_SET),
+            utf8_percent_encode(&item.meta_item.id, URI_COMPONENT_ENCODE_SET),
+            utf8_percent_encode(&item.video.id, URI_COMPONENT_ENCODE_SET)
+        ));
+        lines.push("END:VEVENT".to_owned());
+    }
+    lines.push("END:VCALENDAR".to_owned());
+    lines.iter().fold(String::new(), |mut content, line| {
+        let _ = write!(content, "{}\r\n", icalendar_fold(line));
+        content
+    })
+}
+
+/// Stable across exports, so that the calendar applications update the existing events
+fn icalendar_uid(item: &CalendarItem) -> String {
+    format!(
+        "{}.{}@{ICALENDAR_UID_DOMAIN}",
+        utf8_percent_encode(&item.meta_item.id, URI_COMPONENT_ENCODE_SET),
+        utf8_percent_encode(&item.video.id, URI_COMPONENT_ENCODE_SET)
+    )
+}
+
+/// The name of the series followed by the episode, e.g. `Name S01E02 - Title`
+fn icalendar_summary(item: &CalendarItem) -> String {
+    let episode = item
+        .video
+        .series_info
+        .as_ref()
+        .map(|series_info| format!(" S{:02}E{:02}", series_info.season, series_info.episode))
+        .unwrap_or_default();
+    let title = Some(&item.video.title)
+        .filter(|title| !title.is_empty())
+        .map(|title| format!(" - {title}"))
+        .unwrap_or_default();
+    format!("{}{episode}{title}", item.meta_item.name)
+}
+
+/// Escapes a TEXT value
+fn icalendar_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+        .replace('\r', "")
+}
+
+/// Splits a content line longer than [`ICALENDAR_LINE_LENGTH`] octets, without breaking UTF-8 characters
+fn icalendar_fold(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len());
+    let mut length = 0;
+    for char in line.chars() {
+        if length + char.len_utf8() > ICALENDAR_LINE_LENGTH {
+            folded.push_str("\r\n ");
+            // the leading space counts towards the length of the continuation line
+            length = 1;
+        }
+        folded.push(char);
+        length += char.len_utf8();
+    }
+    folded
+}
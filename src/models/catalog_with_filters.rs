@@ -1,7 +1,7 @@
 use crate::constants::{SKIP_EXTRA_PROP, TYPE_PRIORITIES};
 use crate::models::common::{
-    compare_with_priorities, eq_update, resource_update_with_vector_content, ResourceAction,
-    ResourceLoadable,
+    compare_with_priorities, eq_update, parental_controls_update_with_vector_content,
+    resource_update_with_vector_content, ParentalControlsAdapter, ResourceAction, ResourceLoadable,
 };
 use crate::models::ctx::Ctx;
 use crate::runtime::msg::{Action, ActionCatalogWithFilters, ActionLoad, Internal, Msg};
@@ -137,7 +137,7 @@ impl<T: CatalogResourceAdapter> CatalogWithFilters<T> {
 impl<E, T> UpdateWithCtx<E> for CatalogWithFilters<T>
 where
     E: Env + 'static,
-    T: CatalogResourceAdapter + PartialEq,
+    T: CatalogResourceAdapter + ParentalControlsAdapter + PartialEq,
     Vec<T>: TryFrom<ResourceResponse, Error = &'static str>,
 {
     fn update(&mut self, msg: &Msg, ctx: &Ctx) -> Effects {
@@ -200,10 +200,12 @@ where
                 .iter_mut()
                 .find(|page| page.request == *request)
                 .map(|page| {
-                    resource_update_with_vector_content::<E, _>(
+                    let page_effects = resource_update_with_vector_content::<E, _>(
                         page,
                         ResourceAction::ResourceRequestResult { request, result },
-                    )
+                    );
+                    parental_controls_update_with_vector_content(page, ctx.parental_controls());
+                    page_effects
                 })
                 .map(|catalog_effects| {
                     let selectable_effects = selectable_update(
@@ -221,6 +223,24 @@ where
                 &self.catalog,
                 &ctx.profile,
             ),
+            Msg::Internal(Internal::ParentalControlsChanged) => match self.selected.as_ref() {
+                // the hidden items are not kept, so the catalog is requested again
+                Some(selected) => {
+                    let catalog_effects = catalog_update::<E, _>(
+                        &mut self.catalog,
+                        CatalogPageRequest::First,
+                        &selected.request,
+                    );
+                    let selectable_effects = selectable_update(
+                        &mut self.selectable,
+                        &self.selected,
+                        &self.catalog,
+                        &ctx.profile,
+                    );
+                    catalog_effects.join(selectable_effects)
+                }
+                _ => Effects::none().unchanged(),
+            },
             Msg::Internal(Internal::LibraryChanged(_)) => Effects::none(),
             _ => Effects::none().unchanged(),
         }
@@ -1,14 +1,19 @@
-use crate::constants::SKIP_EXTRA_PROP;
+use crate::constants::{CATALOG_PREVIEW_SIZE, SKIP_EXTRA_PROP};
 use crate::models::common::{
-    eq_update, resource_update_with_vector_content, Loadable, ResourceAction, ResourceLoadable,
+    eq_update, is_allowed_by, parental_controls_update_with_vector_content,
+    resource_update_with_vector_content, Loadable, ResourceAction, ResourceLoadable,
 };
 use crate::models::ctx::Ctx;
+use crate::models::library_with_filters::Sort;
 use crate::runtime::msg::{Action, ActionCatalogsWithExtra, ActionLoad, Internal, Msg};
 use crate::runtime::{EffectFuture, Effects, Env, EnvFutureExt, UpdateWithCtx};
 use crate::types::addon::{AggrRequest, ExtraExt, ExtraValue, ResourcePath, ResourceRequest};
-use crate::types::profile::Profile;
+use crate::types::library::{LibraryBucket, LibraryItem};
+use crate::types::notifications::NotificationsBucket;
+use crate::types::profile::{ParentalControls, Profile, SmartList};
 use crate::types::resource::MetaItemPreview;
 use futures::FutureExt;
+use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 use std::ops::Range;
 
@@ -23,10 +28,22 @@ pub type CatalogPage<T> = ResourceLoadable<Vec<T>>;
 
 pub type Catalog<T> = Vec<CatalogPage<T>>;
 
+/// A [`SmartList`] shown alongside the addon catalogs
+#[derive(Clone, PartialEq, Eq, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SmartListCatalog {
+    pub smart_list: SmartList,
+    /// The most recently watched items of the list
+    pub items: Vec<LibraryItem>,
+}
+
 #[derive(Default, Clone, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
 pub struct CatalogsWithExtra {
     pub selected: Option<Selected>,
     pub catalogs: Vec<Catalog<MetaItemPreview>>,
+    /// Only on the board, they are not shown when searching
+    pub smart_lists: Vec<SmartListCatalog>,
 }
 
 impl<E: Env + 'static> UpdateWithCtx<E> for CatalogsWithExtra {
@@ -36,6 +53,14 @@ impl<E: Env + 'static> UpdateWithCtx<E> for CatalogsWithExtra {
                 let selected_effects = selected_update(&mut self.selected, selected);
                 let catalogs_effects =
                     catalogs_update::<E>(&mut self.catalogs, &self.selected, None, &ctx.profile);
+                let smart_lists_effects = smart_lists_update(
+                    &mut self.smart_lists,
+                    &self.selected,
+                    &ctx.profile.smart_lists,
+                    &ctx.library,
+                    &ctx.notifications,
+                    ctx.parental_controls(),
+                );
                 let search_effects = match &self.selected {
                     Some(Selected { extra, .. }) => match extra
                         .iter()
@@ -51,12 +76,18 @@ impl<E: Env + 'static> UpdateWithCtx<E> for CatalogsWithExtra {
                     },
                     None => Effects::none().unchanged(),
                 };
-                selected_effects.join(catalogs_effects).join(search_effects)
+                selected_effects
+                    .join(catalogs_effects)
+                    .join(smart_lists_effects)
+                    .join(search_effects)
             }
             Msg::Action(Action::Unload) => {
                 let selected_effects = eq_update(&mut self.selected, None);
                 let catalogs_effects = eq_update(&mut self.catalogs, vec![]);
-                selected_effects.join(catalogs_effects)
+                let smart_lists_effects = eq_update(&mut self.smart_lists, vec![]);
+                selected_effects
+                    .join(catalogs_effects)
+                    .join(smart_lists_effects)
             }
             Msg::Action(Action::CatalogsWithExtra(ActionCatalogsWithExtra::LoadRange(range))) => {
                 catalogs_update::<E>(
@@ -141,21 +172,107 @@ impl<E: Env + 'static> UpdateWithCtx<E> for CatalogsWithExtra {
                 .iter_mut()
                 .find_map(|catalog| catalog.last_mut().filter(|page| page.request == *request))
                 .map(|page| {
-                    resource_update_with_vector_content::<E, _>(
+                    let page_effects = resource_update_with_vector_content::<E, _>(
                         page,
                         ResourceAction::ResourceRequestResult { request, result },
-                    )
+                    );
+                    parental_controls_update_with_vector_content(page, ctx.parental_controls());
+                    page_effects
                 })
                 .unwrap_or_else(|| Effects::none().unchanged()),
             Msg::Internal(Internal::ProfileChanged) => {
-                catalogs_update::<E>(&mut self.catalogs, &self.selected, None, &ctx.profile)
+                let catalogs_effects =
+                    catalogs_update::<E>(&mut self.catalogs, &self.selected, None, &ctx.profile);
+                let smart_lists_effects = smart_lists_update(
+                    &mut self.smart_lists,
+                    &self.selected,
+                    &ctx.profile.smart_lists,
+                    &ctx.library,
+                    &ctx.notifications,
+                    ctx.parental_controls(),
+                );
+                catalogs_effects.join(smart_lists_effects)
             }
-            Msg::Internal(Internal::LibraryChanged(_)) => Effects::none(),
+            Msg::Internal(Internal::ParentalControlsChanged) => {
+                let smart_lists_effects = smart_lists_update(
+                    &mut self.smart_lists,
+                    &self.selected,
+                    &ctx.profile.smart_lists,
+                    &ctx.library,
+                    &ctx.notifications,
+                    ctx.parental_controls(),
+                );
+                // the hidden items are not kept, so the loaded catalogs are requested again
+                let catalogs_effects = match self.catalogs.iter().rposition(
+                    |catalog| matches!(catalog.first(), Some(page) if page.content.is_some()),
+                ) {
+                    Some(last_loaded) => {
+                        self.catalogs.clear();
+                        catalogs_update::<E>(
+                            &mut self.catalogs,
+                            &self.selected,
+                            Some(&(0..last_loaded)),
+                            &ctx.profile,
+                        )
+                    }
+                    None => Effects::none().unchanged(),
+                };
+                catalogs_effects.join(smart_lists_effects)
+            }
+            // the watched state of the catalog items is shown from the library
+            Msg::Internal(Internal::LibraryChanged(_)) => Effects::none().join(smart_lists_update(
+                &mut self.smart_lists,
+                &self.selected,
+                &ctx.profile.smart_lists,
+                &ctx.library,
+                &ctx.notifications,
+                ctx.parental_controls(),
+            )),
+            Msg::Internal(Internal::NotificationsChanged) => smart_lists_update(
+                &mut self.smart_lists,
+                &self.selected,
+                &ctx.profile.smart_lists,
+                &ctx.library,
+                &ctx.notifications,
+                ctx.parental_controls(),
+            ),
             _ => Effects::none().unchanged(),
         }
     }
 }
 
+fn smart_lists_update(
+    smart_list_catalogs: &mut Vec<SmartListCatalog>,
+    selected: &Option<Selected>,
+    smart_lists: &[SmartList],
+    library: &LibraryBucket,
+    notifications: &NotificationsBucket,
+    parental_controls: Option<&ParentalControls>,
+) -> Effects {
+    let next_smart_list_catalogs = match selected {
+        Some(selected) if selected.extra.is_empty() => smart_lists
+            .iter()
+            .filter(|smart_list| {
+                selected.r#type.is_none() || selected.r#type == smart_list.query.r#type
+            })
+            .map(|smart_list| SmartListCatalog {
+                smart_list: smart_list.to_owned(),
+                items: library
+                    .items
+                    .values()
+                    .filter(|library_item| smart_list.query.matches(library_item, notifications))
+                    .filter(|library_item| is_allowed_by(*library_item, parental_controls))
+                    .sorted_by(|a, b| Sort::LastWatched.sort_items(a, b))
+                    .take(CATALOG_PREVIEW_SIZE)
+                    .cloned()
+                    .collect(),
+            })
+            .collect(),
+        _ => vec![],
+    };
+    eq_update(smart_list_catalogs, next_smart_list_catalogs)
+}
+
 fn selected_update(selected: &mut Option<Selected>, next_selected: &Selected) -> Effects {
     let mut next_selected = next_selected.to_owned();
     next_selected.extra = next_selected.extra.remove_all(&SKIP_EXTRA_PROP);
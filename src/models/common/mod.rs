@@ -10,5 +10,11 @@ pub use eq_update::*;
 mod loadable;
 pub use loadable::*;
 
+mod parental_controls;
+pub use parental_controls::*;
+
 mod resource_loadable;
 pub use resource_loadable::*;
+
+mod search_query;
+pub use search_query::*;
@@ -0,0 +1,83 @@
+use crate::models::common::{Loadable, ResourceError, ResourceLoadable};
+use crate::models::local_search::Searchable;
+use crate::types::addon::DescriptorPreview;
+use crate::types::library::LibraryItem;
+use crate::types::profile::{Certification, ParentalControls};
+use crate::types::resource::{MetaItem, MetaItemPreview};
+
+/// Content which can be hidden by the [`ParentalControls`].
+pub trait ParentalControlsAdapter {
+    fn is_allowed(&self, parental_controls: &ParentalControls) -> bool;
+}
+
+impl ParentalControlsAdapter for MetaItemPreview {
+    fn is_allowed(&self, parental_controls: &ParentalControls) -> bool {
+        parental_controls.allows(self.certification(), self.genres())
+    }
+}
+
+impl ParentalControlsAdapter for MetaItem {
+    fn is_allowed(&self, parental_controls: &ParentalControls) -> bool {
+        self.preview.is_allowed(parental_controls)
+    }
+}
+
+impl ParentalControlsAdapter for LibraryItem {
+    fn is_allowed(&self, parental_controls: &ParentalControls) -> bool {
+        parental_controls.allows(self.certification(), self.genres())
+    }
+}
+
+impl ParentalControlsAdapter for Searchable {
+    fn is_allowed(&self, parental_controls: &ParentalControls) -> bool {
+        parental_controls.allows(
+            self.certification.as_deref().and_then(Certification::parse),
+            self.genres.iter().map(String::as_str),
+        )
+    }
+}
+
+/// Addons have no maturity rating
+impl ParentalControlsAdapter for DescriptorPreview {
+    fn is_allowed(&self, _parental_controls: &ParentalControls) -> bool {
+        true
+    }
+}
+
+/// Returns `true` when there are no parental controls to apply.
+pub fn is_allowed_by<T: ParentalControlsAdapter>(
+    item: &T,
+    parental_controls: Option<&ParentalControls>,
+) -> bool {
+    parental_controls
+        .map(|parental_controls| item.is_allowed(parental_controls))
+        .unwrap_or(true)
+}
+
+/// Replaces the loaded content with [`ResourceError::Restricted`] if it is not allowed.
+pub fn parental_controls_update<T: ParentalControlsAdapter>(
+    resource: &mut ResourceLoadable<T>,
+    parental_controls: Option<&ParentalControls>,
+) {
+    let restricted = match &resource.content {
+        Some(Loadable::Ready(content)) => !is_allowed_by(content, parental_controls),
+        _ => false,
+    };
+    if restricted {
+        resource.content = Some(Loadable::Err(ResourceError::Restricted));
+    }
+}
+
+/// Removes the items which are not allowed from the loaded content,
+/// the content becomes [`ResourceError::Restricted`] if none of the items are allowed.
+pub fn parental_controls_update_with_vector_content<T: ParentalControlsAdapter>(
+    resource: &mut ResourceLoadable<Vec<T>>,
+    parental_controls: Option<&ParentalControls>,
+) {
+    if let Some(Loadable::Ready(items)) = &mut resource.content {
+        items.retain(|item| is_allowed_by(item, parental_controls));
+        if items.is_empty() {
+            resource.content = Some(Loadable::Err(ResourceError::Restricted));
+        }
+    }
+}
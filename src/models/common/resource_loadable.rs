@@ -15,6 +15,8 @@ pub enum ResourceError {
     EmptyContent,
     UnexpectedResponse(String),
     Env(EnvError),
+    /// The content is hidden by the parental controls
+    Restricted,
 }
 
 impl fmt::Display for ResourceError {
@@ -25,6 +27,7 @@ impl fmt::Display for ResourceError {
                 write!(f, "UnexpectedResponse: {message}")
             }
             ResourceError::Env(error) => write!(f, "Env: {}", error.message()),
+            ResourceError::Restricted => write!(f, "Restricted"),
         }
     }
 }
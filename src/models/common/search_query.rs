@@ -0,0 +1,370 @@
+use std::cmp::min;
+
+/// A search query with its filters, e.g. `dark type:series year:2017`
+#[derive(Default, Clone, PartialEq, Eq, Debug)]
+pub struct SearchQuery {
+    /// The normalized text of the query, see [`normalize_search_text`]
+    pub text: String,
+    /// `type:<type>`
+    pub r#type: Option<String>,
+    /// `year:<year>`
+    pub year: Option<u32>,
+}
+
+impl SearchQuery {
+    /// Parses the known `key:value` filters out of the query,
+    /// the rest of it is the text to search for.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stremio_core::models::common::SearchQuery;
+    ///
+    /// let query = SearchQuery::parse("Dark TYPE:Series year:2017");
+    /// assert_eq!(query.text, "dark");
+    /// assert_eq!(query.r#type.as_deref(), Some("series"));
+    /// assert_eq!(query.year, Some(2017));
+    /// ```
+    pub fn parse(query: &str) -> Self {
+        let mut search_query = SearchQuery::default();
+        let text = query
+            .split_whitespace()
+            .filter(|word| match word.split_once(':') {
+                Some((key, value)) if key.eq_ignore_ascii_case("type") && !value.is_empty() => {
+                    search_query.r#type = Some(value.to_lowercase());
+                    false
+                }
+                Some((key, value)) if key.eq_ignore_ascii_case("year") => match value.parse() {
+                    Ok(year) => {
+                        search_query.year = Some(year);
+                        false
+                    }
+                    Err(_) => true,
+                },
+                _ => true,
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+        search_query.text = normalize_search_text(&text);
+        search_query
+    }
+
+    pub fn is_filtered(&self) -> bool {
+        self.r#type.is_some() || self.year.is_some()
+    }
+
+    /// Whether the `type` and `release_info` of an item match the filters of the query
+    pub fn filters_match(&self, r#type: &str, release_info: Option<&str>) -> bool {
+        let type_matches = self
+            .r#type
+            .as_ref()
+            .map(|query_type| query_type.eq_ignore_ascii_case(r#type))
+            .unwrap_or(true);
+        let year_matches = self
+            .year
+            .map(|year| {
+                release_info
+                    .map(|release_info| release_info_contains_year(release_info, year))
+                    .unwrap_or_default()
+            })
+            .unwrap_or(true);
+        type_matches && year_matches
+    }
+}
+
+/// `2019`, `2008-2013` and `2019-` (still running) are the supported formats
+fn release_info_contains_year(release_info: &str, year: u32) -> bool {
+    let years = release_info
+        .split(|c: char| !c.is_ascii_digit())
+        .filter(|part| part.len() == 4)
+        .filter_map(|part| part.parse::<u32>().ok())
+        .collect::<Vec<_>>();
+    let is_open_range = release_info
+        .trim_end()
+        .ends_with(|c: char| c == '-' || c == '–');
+    match years.as_slice() {
+        [start] if is_open_range => *start <= year,
+        [released] => *released == year,
+        [start, end, ..] => (*start..=*end).contains(&year),
+        [] => false,
+    }
+}
+
+/// Lowercases the text, removes the diacritics and the punctuation
+/// and transliterates Cyrillic and Japanese kana to Latin.
+///
+/// # Examples
+///
+/// ```
+/// use stremio_core::models::common::normalize_search_text;
+///
+/// assert_eq!(normalize_search_text("Amélie"), "amelie");
+/// assert_eq!(normalize_search_text("Брат 2"), "brat 2");
+/// assert_eq!(normalize_search_text("ナルト"), "naruto");
+/// assert_eq!(normalize_search_text("Spider-Man: No Way Home"), "spider man no way home");
+/// ```
+pub fn normalize_search_text(text: &str) -> String {
+    let mut transliterated = String::with_capacity(text.len());
+    // the small `tsu` doubles the consonant of the next kana
+    let mut sokuon = false;
+    for c in text.chars().flat_map(char::to_lowercase) {
+        let kana = katakana_to_hiragana(c);
+        if kana == 'っ' {
+            sokuon = true;
+            continue;
+        }
+        if let Some(vowel) = small_y_kana(kana) {
+            // e.g. `きゃ` is `kya` and `しゃ` is `sha`
+            if transliterated.ends_with('i') {
+                transliterated.pop();
+                if !(transliterated.ends_with("sh")
+                    || transliterated.ends_with("ch")
+                    || transliterated.ends_with('j'))
+                {
+                    transliterated.push('y');
+                }
+            } else {
+                transliterated.push('y');
+            }
+            transliterated.push(vowel);
+            continue;
+        }
+        match hiragana(kana).or_else(|| cyrillic(c)).or_else(|| latin(c)) {
+            Some(romanized) => {
+                if std::mem::take(&mut sokuon) {
+                    match romanized.strip_prefix("ch") {
+                        Some(_) => transliterated.push('t'),
+                        None => transliterated.extend(romanized.chars().next()),
+                    }
+                }
+                transliterated.push_str(romanized);
+            }
+            // the long vowel mark is dropped, `ラーメン` is commonly written as `ramen`
+            None if kana == 'ー' => {}
+            // combining diacritical marks
+            None if ('\u{300}'..='\u{36f}').contains(&c) => {}
+            None if c.is_alphanumeric() => transliterated.push(c),
+            None => transliterated.push(' '),
+        }
+    }
+
+    transliterated
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Whether every word of the query matches a word of the text,
+/// allowing a typo in words of 4 or more characters and two typos in words of 8 or more.
+///
+/// The last word of the query can also be the beginning of a word, as it may be still typed.
+/// Both the query and the text should be normalized with [`normalize_search_text`].
+pub fn fuzzy_matches(query: &str, text: &str) -> bool {
+    let text_words = text
+        .split(' ')
+        .map(|word| word.chars().collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    let query_words = query.split(' ').collect::<Vec<_>>();
+    !query.is_empty()
+        && query_words.iter().enumerate().all(|(index, query_word)| {
+            let query_word = query_word.chars().collect::<Vec<_>>();
+            let max_distance = match query_word.len() {
+                0..=3 => 0,
+                4..=7 => 1,
+                _ => 2,
+            };
+            let is_last = index == query_words.len() - 1;
+            text_words.iter().any(|text_word| {
+                let text_word = if is_last {
+                    &text_word[..min(text_word.len(), query_word.len())]
+                } else {
+                    text_word.as_slice()
+                };
+                edit_distance(&query_word, text_word) <= max_distance
+            })
+        })
+}
+
+/// The Levenshtein distance between two words
+fn edit_distance(a: &[char], b: &[char]) -> usize {
+    let mut previous = (0..=b.len()).collect::<Vec<_>>();
+    let mut current = vec![0; b.len() + 1];
+    for (i, a_char) in a.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != b_char);
+            current[j + 1] = min(substitution, min(previous[j + 1], current[j]) + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
+
+fn katakana_to_hiragana(c: char) -> char {
+    match c {
+        'ァ'..='ヶ' => char::from_u32(c as u32 - 0x60).unwrap_or(c),
+        _ => c,
+    }
+}
+
+fn small_y_kana(c: char) -> Option<char> {
+    match c {
+        'ゃ' => Some('a'),
+        'ゅ' => Some('u'),
+        'ょ' => Some('o'),
+        _ => None,
+    }
+}
+
+fn hiragana(c: char) -> Option<&'static str> {
+    let latin = match c {
+        'あ' | 'ぁ' => "a",
+        'い' | 'ぃ' => "i",
+        'う' | 'ぅ' => "u",
+        'え' | 'ぇ' => "e",
+        'お' | 'ぉ' => "o",
+        'か' | 'ゕ' => "ka",
+        'き' => "ki",
+        'く' => "ku",
+        'け' | 'ゖ' => "ke",
+        'こ' => "ko",
+        'が' => "ga",
+        'ぎ' => "gi",
+        'ぐ' => "gu",
+        'げ' => "ge",
+        'ご' => "go",
+        'さ' => "sa",
+        'し' => "shi",
+        'す' => "su",
+        'せ' => "se",
+        'そ' => "so",
+        'ざ' => "za",
+        'じ' | 'ぢ' => "ji",
+        'ず' | 'づ' => "zu",
+        'ぜ' => "ze",
+        'ぞ' => "zo",
+        'た' => "ta",
+        'ち' => "chi",
+        'つ' => "tsu",
+        'て' => "te",
+        'と' => "to",
+        'だ' => "da",
+        'で' => "de",
+        'ど' => "do",
+        'な' => "na",
+        'に' => "ni",
+        'ぬ' => "nu",
+        'ね' => "ne",
+        'の' => "no",
+        'は' => "ha",
+        'ひ' => "hi",
+        'ふ' => "fu",
+        'へ' => "he",
+        'ほ' => "ho",
+        'ば' => "ba",
+        'び' => "bi",
+        'ぶ' => "bu",
+        'べ' => "be",
+        'ぼ' => "bo",
+        'ぱ' => "pa",
+        'ぴ' => "pi",
+        'ぷ' => "pu",
+        'ぺ' => "pe",
+        'ぽ' => "po",
+        'ま' => "ma",
+        'み' => "mi",
+        'む' => "mu",
+        'め' => "me",
+        'も' => "mo",
+        'や' => "ya",
+        'ゆ' => "yu",
+        'よ' => "yo",
+        'ら' => "ra",
+        'り' => "ri",
+        'る' => "ru",
+        'れ' => "re",
+        'ろ' => "ro",
+        'わ' | 'ゎ' => "wa",
+        'ゐ' => "wi",
+        'ゑ' => "we",
+        'を' => "wo",
+        'ん' => "n",
+        'ゔ' => "vu",
+        _ => return None,
+    };
+    Some(latin)
+}
+
+fn cyrillic(c: char) -> Option<&'static str> {
+    let latin = match c {
+        'а' => "a",
+        'б' => "b",
+        'в' => "v",
+        'г' | 'ґ' => "g",
+        'д' => "d",
+        'е' | 'ё' | 'э' => "e",
+        'є' => "ye",
+        'ж' => "zh",
+        'з' => "z",
+        'и' | 'і' => "i",
+        'ї' => "yi",
+        'й' | 'ы' => "y",
+        'ј' => "j",
+        'к' => "k",
+        'л' => "l",
+        'љ' => "lj",
+        'м' => "m",
+        'н' => "n",
+        'њ' => "nj",
+        'о' => "o",
+        'п' => "p",
+        'р' => "r",
+        'с' => "s",
+        'т' => "t",
+        'ћ' => "c",
+        'ђ' => "dj",
+        'у' | 'ў' => "u",
+        'ф' => "f",
+        'х' => "kh",
+        'ц' => "ts",
+        'ч' => "ch",
+        'џ' => "dz",
+        'ш' => "sh",
+        'щ' => "shch",
+        'ъ' | 'ь' => "",
+        'ю' => "yu",
+        'я' => "ya",
+        _ => return None,
+    };
+    Some(latin)
+}
+
+fn latin(c: char) -> Option<&'static str> {
+    let latin = match c {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' | 'ă' | 'ą' => "a",
+        'æ' => "ae",
+        'ç' | 'ć' | 'ĉ' | 'ċ' | 'č' => "c",
+        'ď' | 'đ' | 'ð' => "d",
+        'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ĕ' | 'ė' | 'ę' | 'ě' => "e",
+        'ĝ' | 'ğ' | 'ġ' | 'ģ' => "g",
+        'ĥ' | 'ħ' => "h",
+        'ì' | 'í' | 'î' | 'ï' | 'ĩ' | 'ī' | 'ĭ' | 'į' | 'ı' => "i",
+        'ĵ' => "j",
+        'ķ' => "k",
+        'ĺ' | 'ļ' | 'ľ' | 'ŀ' | 'ł' => "l",
+        'ñ' | 'ń' | 'ņ' | 'ň' => "n",
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' | 'ŏ' | 'ő' => "o",
+        'œ' => "oe",
+        'ŕ' | 'ŗ' | 'ř' => "r",
+        'ś' | 'ŝ' | 'ş' | 'š' | 'ș' => "s",
+        'ß' => "ss",
+        'ţ' | 'ť' | 'ŧ' | 'ț' => "t",
+        'þ' => "th",
+        'ù' | 'ú' | 'û' | 'ü' | 'ũ' | 'ū' | 'ŭ' | 'ů' | 'ű' | 'ų' => "u",
+        'ŵ' => "w",
+        'ý' | 'ÿ' | 'ŷ' => "y",
+        'ź' | 'ż' | 'ž' => "z",
+        _ => return None,
+    };
+    Some(latin)
+}
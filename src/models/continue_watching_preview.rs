@@ -3,7 +3,10 @@ use serde::Serialize;
 
 use crate::{
     constants::CATALOG_PREVIEW_SIZE,
-    models::{common::eq_update, ctx::Ctx},
+    models::{
+        common::{eq_update, is_allowed_by},
+        ctx::Ctx,
+    },
     runtime::{
         msg::{Internal, Msg},
         Effects, Env, UpdateWithCtx,
@@ -11,6 +14,7 @@ use crate::{
     types::{
         library::{LibraryBucket, LibraryItem},
         notifications::NotificationsBucket,
+        profile::ParentalControls,
     },
 };
 
@@ -31,9 +35,13 @@ pub struct ContinueWatchingPreview {
 }
 
 impl ContinueWatchingPreview {
-    pub fn new(library: &LibraryBucket, notifications: &NotificationsBucket) -> (Self, Effects) {
+    pub fn new(
+        library: &LibraryBucket,
+        notifications: &NotificationsBucket,
+        parental_controls: Option<&ParentalControls>,
+    ) -> (Self, Effects) {
         let mut items = vec![];
-        let effects = library_items_update(&mut items, library, notifications);
+        let effects = library_items_update(&mut items, library, notifications, parental_controls);
         (Self { items }, effects.unchanged())
     }
 }
@@ -45,9 +53,14 @@ impl<E: Env + 'static> UpdateWithCtx<E> for ContinueWatchingPreview {
             // library has changed
             Msg::Internal(Internal::LibraryChanged(true))
             // notifications have been updated
-            | Msg::Internal(Internal::NotificationsChanged) => {
-                library_items_update(&mut self.items, &ctx.library, &ctx.notifications)
-            }
+            | Msg::Internal(Internal::NotificationsChanged)
+            // parental controls have been changed
+            | Msg::Internal(Internal::ParentalControlsChanged) => library_items_update(
+                &mut self.items,
+                &ctx.library,
+                &ctx.notifications,
+                ctx.parental_controls(),
+            ),
             _ => Effects::none().unchanged(),
         }
     }
@@ -57,10 +70,12 @@ fn library_items_update(
     cw_items: &mut Vec<Item>,
     library: &LibraryBucket,
     notifications: &NotificationsBucket,
+    parental_controls: Option<&ParentalControls>,
 ) -> Effects {
     let next_cw_items = library
         .items
         .values()
+        .filter(|library_item| is_allowed_by(*library_item, parental_controls))
         .filter_map(|library_item| {
             let library_notification = notifications
                 .items
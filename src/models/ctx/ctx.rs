@@ -1,11 +1,14 @@
-use crate::constants::LIBRARY_COLLECTION_NAME;
+use crate::constants::{LIBRARY_COLLECTION_NAME, PLAYLISTS_STORAGE_KEY, SMART_LISTS_STORAGE_KEY};
 use crate::models::common::{DescriptorLoadable, Loadable, ResourceLoadable};
 use crate::models::ctx::{
-    update_events, update_library, update_notifications, update_profile, update_search_history,
-    update_streams, update_trakt_addon, CtxError,
+    push_account_to_storage, switch_account, update_accounts, update_events, update_library,
+    update_notifications, update_outbox, update_parental_controls, update_playlists,
+    update_profile, update_scheduler, update_search_history, update_streams, update_trakt_addon,
+    update_watch_history, CtxError,
 };
 use crate::runtime::msg::{Action, ActionCtx, CtxAuthResponse, Event, Internal, Msg};
 use crate::runtime::{Effect, EffectFuture, Effects, Env, EnvFutureExt, Update};
+use crate::types::accounts::{account_storage_key, Account, AccountsBucket};
 use crate::types::api::{
     fetch_api, APIRequest, APIResult, AuthRequest, AuthResponse, CollectionResponse,
     DatastoreCommand, DatastoreRequest, LibraryItemsResponse, SuccessResponse,
@@ -13,10 +16,14 @@ use crate::types::api::{
 use crate::types::events::{DismissedEventsBucket, Events};
 use crate::types::library::LibraryBucket;
 use crate::types::notifications::NotificationsBucket;
-use crate::types::profile::{Auth, AuthKey, Profile};
+use crate::types::outbox::OutboxBucket;
+use crate::types::playlists::PlaylistsBucket;
+use crate::types::profile::{Auth, AuthKey, ParentalControls, Profile, SmartList};
 use crate::types::resource::MetaItem;
+use crate::types::scheduler::Scheduler;
 use crate::types::search_history::SearchHistoryBucket;
 use crate::types::streams::StreamsBucket;
+use crate::types::watch_history::WatchHistoryBucket;
 
 #[cfg(test)]
 use derivative::Derivative;
@@ -52,6 +59,17 @@ pub struct Ctx {
     #[serde(skip)]
     pub dismissed_events: DismissedEventsBucket,
     #[serde(skip)]
+    pub outbox: OutboxBucket,
+    #[serde(skip)]
+    pub accounts: AccountsBucket,
+    #[serde(skip)]
+    pub playlists: PlaylistsBucket,
+    #[serde(skip)]
+    pub watch_history: WatchHistoryBucket,
+    /// Whether the parental controls have been lifted with the PIN
+    #[serde(skip)]
+    pub parental_controls_unlocked: bool,
+    #[serde(skip)]
     #[cfg_attr(test, derivative(Default(value = "CtxStatus::Ready")))]
     pub status: CtxStatus,
     #[serde(skip)]
@@ -60,9 +78,11 @@ pub struct Ctx {
     #[serde(skip)]
     pub notification_catalogs: Vec<ResourceLoadable<Vec<MetaItem>>>,
     pub events: Events,
+    pub scheduler: Scheduler,
 }
 
 impl Ctx {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         profile: Profile,
         library: LibraryBucket,
@@ -70,13 +90,24 @@ impl Ctx {
         notifications: NotificationsBucket,
         search_history: SearchHistoryBucket,
         dismissed_events: DismissedEventsBucket,
+        outbox: OutboxBucket,
+        accounts: AccountsBucket,
+        playlists: PlaylistsBucket,
+        watch_history: WatchHistoryBucket,
     ) -> Self {
+        let mut scheduler = Scheduler::default();
+        scheduler.update_intervals(&profile.settings);
         Self {
             profile,
             library,
             streams,
             search_history,
             dismissed_events,
+            outbox,
+            accounts,
+            playlists,
+            watch_history,
+            parental_controls_unlocked: false,
             notifications,
             trakt_addon: None,
             notification_catalogs: vec![],
@@ -85,8 +116,16 @@ impl Ctx {
                 modal: Loadable::Loading,
                 notification: Loadable::Loading,
             },
+            scheduler,
         }
     }
+    /// The parental controls which should be applied to the content,
+    /// `None` when they are disabled or unlocked.
+    pub fn parental_controls(&self) -> Option<&ParentalControls> {
+        Some(&self.profile.parental_controls).filter(|parental_controls| {
+            parental_controls.is_enabled() && !self.parental_controls_unlocked
+        })
+    }
 }
 
 impl<E: Env + 'static> Update<E> for Ctx {
@@ -102,8 +141,15 @@ impl<E: Env + 'static> Update<E> for Ctx {
                     Some(auth_key) => Effects::one(delete_session::<E>(auth_key)).unchanged(),
                     _ => Effects::none().unchanged(),
                 };
+                let accounts_effects =
+                    update_accounts::<E>(&mut self.accounts, &self.profile, &self.status, msg);
                 let profile_effects =
                     update_profile::<E>(&mut self.profile, &mut self.streams, &self.status, msg);
+                let parental_controls_effects = update_parental_controls::<E>(
+                    &mut self.profile,
+                    &mut self.parental_controls_unlocked,
+                    msg,
+                );
                 let library_effects =
                     update_library::<E>(&mut self.library, &self.profile, &self.status, msg);
                 let streams_effects = update_streams::<E>(&mut self.streams, &self.status, msg);
@@ -125,19 +171,48 @@ impl<E: Env + 'static> Update<E> for Ctx {
                     &self.status,
                     msg,
                 );
+                let outbox_effects =
+                    update_outbox::<E>(&mut self.outbox, &self.profile, &self.status, msg);
+                let playlists_effects =
+                    update_playlists::<E>(&mut self.playlists, &self.status, msg);
+                let watch_history_effects =
+                    update_watch_history::<E>(&mut self.watch_history, &self.status, msg);
+                let scheduler_effects =
+                    update_scheduler::<E>(&mut self.scheduler, &self.profile, &self.status, msg);
                 self.status = CtxStatus::Ready;
                 Effects::msg(Msg::Event(Event::UserLoggedOut { uid }))
                     .unchanged()
                     .join(session_effects)
                     .join(profile_effects)
+                    .join(parental_controls_effects)
                     .join(library_effects)
                     .join(streams_effects)
                     .join(search_history_effects)
                     .join(events_effects)
                     .join(trakt_addon_effects)
                     .join(notifications_effects)
+                    .join(outbox_effects)
+                    .join(playlists_effects)
+                    .join(watch_history_effects)
+                    .join(scheduler_effects)
+                    .join(accounts_effects)
             }
             Msg::Internal(Internal::CtxAuthResult(auth_request, result)) => {
+                // keep the data of the previous account, so that it can be switched back to
+                let park_account_effects = match (&self.status, result) {
+                    (
+                        CtxStatus::Loading(loading_auth_request),
+                        Ok(CtxAuthResponse { auth, .. }),
+                    ) if loading_auth_request == auth_request
+                        && self.profile.uid().as_ref() != Some(&auth.user.id) =>
+                    {
+                        self.accounts.upsert(Account::new(&self.profile, E::now()));
+                        Effects::one(push_account_to_storage::<E>(self)).unchanged()
+                    }
+                    _ => Effects::none().unchanged(),
+                };
+                let accounts_effects =
+                    update_accounts::<E>(&mut self.accounts, &self.profile, &self.status, msg);
                 let profile_effects =
                     update_profile::<E>(&mut self.profile, &mut self.streams, &self.status, msg);
                 let library_effects =
@@ -161,6 +236,14 @@ impl<E: Env + 'static> Update<E> for Ctx {
                     update_search_history::<E>(&mut self.search_history, &self.status, msg);
                 let events_effects =
                     update_events::<E>(&mut self.events, &mut self.dismissed_events, msg);
+                let outbox_effects =
+                    update_outbox::<E>(&mut self.outbox, &self.profile, &self.status, msg);
+                let playlists_effects =
+                    update_playlists::<E>(&mut self.playlists, &self.status, msg);
+                let watch_history_effects =
+                    update_watch_history::<E>(&mut self.watch_history, &self.status, msg);
+                let scheduler_effects =
+                    update_scheduler::<E>(&mut self.scheduler, &self.profile, &self.status, msg);
                 let ctx_effects = match &self.status {
                     CtxStatus::Loading(loading_auth_request)
                         if loading_auth_request == auth_request =>
@@ -229,11 +312,130 @@ impl<E: Env + 'static> Update<E> for Ctx {
                     .join(notifications_effects)
                     .join(search_history_effects)
                     .join(events_effects)
+                    .join(outbox_effects)
+                    .join(playlists_effects)
+                    .join(watch_history_effects)
+                    .join(scheduler_effects)
+                    .join(park_account_effects)
+                    .join(accounts_effects)
                     .join(ctx_effects)
             }
+            Msg::Action(Action::Ctx(ActionCtx::SwitchAccount(uid))) => {
+                let error = if matches!(self.status, CtxStatus::Loading(_)) {
+                    Some(OtherError::LoginInProgress)
+                } else if *uid == self.profile.uid() {
+                    Some(OtherError::AccountAlreadyActive)
+                } else if self.accounts.get(uid).is_none() {
+                    Some(OtherError::AccountNotFound)
+                } else {
+                    None
+                };
+                if let Some(error) = error {
+                    Effects::msg(Msg::Event(Event::Error {
+                        error: CtxError::from(error),
+                        source: Box::new(Event::AccountSwitched {
+                            uid: uid.to_owned(),
+                        }),
+                    }))
+                    .unchanged()
+                } else {
+                    self.accounts.upsert(Account::new(&self.profile, E::now()));
+                    Effects::one(switch_account::<E>(self, uid))
+                        .unchanged()
+                        .join(Effects::msg(Msg::Internal(Internal::AccountsChanged)))
+                }
+            }
+            Msg::Internal(Internal::AccountSwitchResult(uid, result)) => match result.as_ref() {
+                Ok(response)
+                    if response
+                        .profile
+                        .as_ref()
+                        .map(Profile::uid)
+                        .unwrap_or_default()
+                        == *uid =>
+                {
+                    // keep the restrictions of the device, switching accounts should not bypass them
+                    self.profile = Profile {
+                        parental_controls: self.profile.parental_controls.to_owned(),
+                        ..response.profile.to_owned().unwrap_or_default()
+                    };
+                    self.library = response
+                        .library
+                        .to_owned()
+                        .unwrap_or_else(|| LibraryBucket::new(uid.to_owned(), vec![]));
+                    self.streams = response
+                        .streams
+                        .to_owned()
+                        .unwrap_or_else(|| StreamsBucket::new(uid.to_owned()));
+                    self.notifications = response
+                        .notifications
+                        .to_owned()
+                        .unwrap_or_else(|| NotificationsBucket::new::<E>(uid.to_owned(), vec![]));
+                    self.search_history = response
+                        .search_history
+                        .to_owned()
+                        .unwrap_or_else(|| SearchHistoryBucket::new(uid.to_owned()));
+                    self.dismissed_events = response
+                        .dismissed_events
+                        .to_owned()
+                        .unwrap_or_else(|| DismissedEventsBucket::new(uid.to_owned()));
+                    self.outbox = response
+                        .outbox
+                        .to_owned()
+                        .unwrap_or_else(|| OutboxBucket::new(uid.to_owned()));
+                    self.playlists = response
+                        .playlists
+                        .to_owned()
+                        .unwrap_or_else(|| PlaylistsBucket::new(uid.to_owned()));
+                    self.watch_history = response
+                        .watch_history
+                        .to_owned()
+                        .unwrap_or_else(|| WatchHistoryBucket::new(uid.to_owned()));
+                    self.status = CtxStatus::Ready;
+                    self.trakt_addon = None;
+                    self.notification_catalogs = vec![];
+                    self.scheduler.reset();
+                    self.accounts.upsert(Account::new(&self.profile, E::now()));
+                    Effects::msgs(vec![
+                        Msg::Event(Event::AccountSwitched {
+                            uid: uid.to_owned(),
+                        }),
+                        Msg::Internal(Internal::ProfileChanged),
+                        Msg::Internal(Internal::LibraryChanged(false)),
+                        Msg::Internal(Internal::StreamsChanged(false)),
+                        Msg::Internal(Internal::NotificationsChanged),
+                        Msg::Internal(Internal::SearchHistoryChanged),
+                        Msg::Internal(Internal::DismissedEventsChanged),
+                        Msg::Internal(Internal::OutboxChanged),
+                        Msg::Internal(Internal::PlaylistsChanged),
+                        Msg::Internal(Internal::WatchHistoryChanged),
+                        Msg::Internal(Internal::AccountsChanged),
+                        Msg::Internal(Internal::ParentalControlsChanged),
+                    ])
+                }
+                Ok(_) => Effects::msg(Msg::Event(Event::Error {
+                    error: CtxError::from(OtherError::AccountNotFound),
+                    source: Box::new(Event::AccountSwitched {
+                        uid: uid.to_owned(),
+                    }),
+                }))
+                .unchanged(),
+                Err(error) => Effects::msg(Msg::Event(Event::Error {
+                    error: error.to_owned(),
+                    source: Box::new(Event::AccountSwitched {
+                        uid: uid.to_owned(),
+                    }),
+                }))
+                .unchanged(),
+            },
             _ => {
                 let profile_effects =
                     update_profile::<E>(&mut self.profile, &mut self.streams, &self.status, msg);
+                let parental_controls_effects = update_parental_controls::<E>(
+                    &mut self.profile,
+                    &mut self.parental_controls_unlocked,
+                    msg,
+                );
                 let library_effects =
                     update_library::<E>(&mut self.library, &self.profile, &self.status, msg);
                 let streams_effects = update_streams::<E>(&mut self.streams, &self.status, msg);
@@ -255,6 +457,16 @@ impl<E: Env + 'static> Update<E> for Ctx {
                     update_search_history::<E>(&mut self.search_history, &self.status, msg);
                 let events_effects =
                     update_events::<E>(&mut self.events, &mut self.dismissed_events, msg);
+                let outbox_effects =
+                    update_outbox::<E>(&mut self.outbox, &self.profile, &self.status, msg);
+                let playlists_effects =
+                    update_playlists::<E>(&mut self.playlists, &self.status, msg);
+                let watch_history_effects =
+                    update_watch_history::<E>(&mut self.watch_history, &self.status, msg);
+                let scheduler_effects =
+                    update_scheduler::<E>(&mut self.scheduler, &self.profile, &self.status, msg);
+                let accounts_effects =
+                    update_accounts::<E>(&mut self.accounts, &self.profile, &self.status, msg);
                 profile_effects
                     .join(library_effects)
                     .join(streams_effects)
@@ -262,6 +474,12 @@ impl<E: Env + 'static> Update<E> for Ctx {
                     .join(notifications_effects)
                     .join(search_history_effects)
                     .join(events_effects)
+                    .join(outbox_effects)
+                    .join(playlists_effects)
+                    .join(watch_history_effects)
+                    .join(scheduler_effects)
+                    .join(accounts_effects)
+                    .join(parental_controls_effects)
             }
         }
     }
@@ -325,8 +543,35 @@ fn authenticate<E: Env + 'static>(auth_request: &AuthRequest) -> Effect {
                     })
             };
 
-            let (addon_collection_result, datastore_library_result) =
-                future::join(addon_collection_fut, datastore_library_fut).await;
+            let playlists_fut = async {
+                let uid = Some(auth.user.id.to_owned());
+                E::get_storage::<PlaylistsBucket>(&account_storage_key(PLAYLISTS_STORAGE_KEY, &uid))
+                    .await
+                    .map_err(CtxError::from)
+            };
+
+            let smart_lists_fut = async {
+                let uid = Some(auth.user.id.to_owned());
+                E::get_storage::<Vec<SmartList>>(&account_storage_key(
+                    SMART_LISTS_STORAGE_KEY,
+                    &uid,
+                ))
+                .await
+                .map_err(CtxError::from)
+            };
+
+            let (
+                addon_collection_result,
+                datastore_library_result,
+                playlists_result,
+                smart_lists_result,
+            ) = future::join4(
+                addon_collection_fut,
+                datastore_library_fut,
+                playlists_fut,
+                smart_lists_fut,
+            )
+            .await;
 
             if let Err(error) = addon_collection_result.as_ref() {
                 error!("Failed to fetch Addon collection from API: {error:?}");
@@ -334,11 +579,19 @@ fn authenticate<E: Env + 'static>(auth_request: &AuthRequest) -> Effect {
             if let Err(error) = datastore_library_result.as_ref() {
                 error!("Failed to fetch LibraryItems for user from API: {error:?}");
             }
+            if let Err(error) = playlists_result.as_ref() {
+                error!("Failed to read the user's playlists from the storage: {error:?}");
+            }
+            if let Err(error) = smart_lists_result.as_ref() {
+                error!("Failed to read the user's smart lists from the storage: {error:?}");
+            }
 
             Ok(CtxAuthResponse {
                 auth,
                 addons_result: addon_collection_result,
                 library_items_result: datastore_library_result,
+                playlists: playlists_result.ok().flatten(),
+                smart_lists: smart_lists_result.ok().flatten(),
             })
         }
         .map(enclose!((auth_request) move |result| {
@@ -1,5 +1,7 @@
+use crate::constants::{NOTIFICATIONS_MAX_POLL_INTERVAL, SCHEDULER_MAX_INTERVAL};
 use crate::runtime::EnvError;
 use crate::types::api::APIError;
+use crate::types::library::{MAX_RATING, MIN_RATING};
 use serde::ser::{SerializeStruct, Serializer};
 use serde::Serialize;
 
@@ -40,6 +42,21 @@ pub enum OtherError {
     AddonConfigurationRequired,
     UserAddonsAreLocked,
     UserLibraryIsMissing,
+    AccountNotFound,
+    ParentalControlsLocked,
+    InvalidParentalControlsPin,
+    SmartListNotFound,
+    PlaylistNotFound,
+    PlaylistAlreadyExists,
+    PlaylistItemNotFound,
+    InvalidRating,
+    InvalidNotificationsPollInterval,
+    InvalidQuietHours,
+    CalendarNotLoaded,
+    InvalidSchedulerJobInterval,
+    InvalidPlaylist,
+    AccountAlreadyActive,
+    LoginInProgress,
 }
 
 impl OtherError {
@@ -53,6 +70,21 @@ impl OtherError {
             OtherError::AddonConfigurationRequired => "Addon requires configuration".to_owned(),
             OtherError::UserAddonsAreLocked => "Fetching Addons from the API failed and we have defaulted the addons to the officials ones until the request succeeds".to_owned(),
             OtherError::UserLibraryIsMissing => "Fetching Library from the API failed and we have defaulted to empty library until the request succeeds".to_owned(),
+            OtherError::AccountNotFound => "Account is not stored on this device".to_owned(),
+            OtherError::ParentalControlsLocked => "Parental controls are locked".to_owned(),
+            OtherError::InvalidParentalControlsPin => "Invalid parental controls PIN".to_owned(),
+            OtherError::SmartListNotFound => "Smart list is not found".to_owned(),
+            OtherError::PlaylistNotFound => "Playlist is not found".to_owned(),
+            OtherError::PlaylistAlreadyExists => "Playlist already exists".to_owned(),
+            OtherError::PlaylistItemNotFound => "Item is not in the playlist".to_owned(),
+            OtherError::InvalidRating => format!("Rating must be between {MIN_RATING} and {MAX_RATING}"),
+            OtherError::InvalidNotificationsPollInterval => format!("Notifications poll interval must be between 1 and {NOTIFICATIONS_MAX_POLL_INTERVAL} hours"),
+            OtherError::InvalidQuietHours => "Quiet hours must be between 0 and 23 with an offset from UTC of at most 14 hours".to_owned(),
+            OtherError::CalendarNotLoaded => "Calendar is not loaded yet".to_owned(),
+            OtherError::InvalidSchedulerJobInterval => format!("Scheduler job interval must be between 1 and {SCHEDULER_MAX_INTERVAL} milliseconds"),
+            OtherError::InvalidPlaylist => "Playlist id and name must not be empty".to_owned(),
+            OtherError::AccountAlreadyActive => "Account is already active".to_owned(),
+            OtherError::LoginInProgress => "Logging in is in progress".to_owned(),
         }
     }
     pub fn code(&self) -> u64 {
@@ -65,6 +97,21 @@ impl OtherError {
             OtherError::AddonConfigurationRequired => 6,
             OtherError::UserAddonsAreLocked => 7,
             OtherError::UserLibraryIsMissing => 8,
+            OtherError::AccountNotFound => 9,
+            OtherError::ParentalControlsLocked => 10,
+            OtherError::InvalidParentalControlsPin => 11,
+            OtherError::SmartListNotFound => 12,
+            OtherError::PlaylistNotFound => 13,
+            OtherError::PlaylistAlreadyExists => 14,
+            OtherError::PlaylistItemNotFound => 15,
+            OtherError::InvalidRating => 16,
+            OtherError::InvalidNotificationsPollInterval => 17,
+            OtherError::InvalidQuietHours => 18,
+            OtherError::CalendarNotLoaded => 19,
+            OtherError::InvalidSchedulerJobInterval => 20,
+            OtherError::InvalidPlaylist => 21,
+            OtherError::AccountAlreadyActive => 22,
+            OtherError::LoginInProgress => 23,
         }
     }
 }
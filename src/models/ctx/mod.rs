@@ -1,3 +1,6 @@
+mod update_accounts;
+use update_accounts::*;
+
 mod update_events;
 use update_events::*;
 
@@ -7,18 +10,33 @@ use update_library::*;
 mod update_notifications;
 use update_notifications::*;
 
+mod update_outbox;
+use update_outbox::*;
+
+mod update_parental_controls;
+use update_parental_controls::*;
+
+mod update_playlists;
+use update_playlists::*;
+
 mod update_profile;
 use update_profile::*;
 
 mod update_streams;
 use update_streams::*;
 
+mod update_scheduler;
+use update_scheduler::*;
+
 mod update_search_history;
 use update_search_history::*;
 
 mod update_trakt_addon;
 use update_trakt_addon::*;
 
+mod update_watch_history;
+use update_watch_history::*;
+
 mod error;
 pub use error::*;
 
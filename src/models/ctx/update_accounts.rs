@@ -0,0 +1,250 @@
+use enclose::enclose;
+use futures::{future, try_join, FutureExt, TryFutureExt};
+
+use crate::constants::{
+    ACCOUNTS_STORAGE_KEY, DISMISSED_EVENTS_STORAGE_KEY, LIBRARY_STORAGE_KEY,
+    NOTIFICATIONS_STORAGE_KEY, OUTBOX_STORAGE_KEY, PLAYLISTS_STORAGE_KEY, PROFILE_STORAGE_KEY,
+    SEARCH_HISTORY_STORAGE_KEY, STREAMS_STORAGE_KEY, WATCH_HISTORY_STORAGE_KEY,
+};
+use crate::models::ctx::{Ctx, CtxError, CtxStatus};
+use crate::runtime::msg::{
+    AccountStorageResponse, Action, ActionCtx, CtxAuthResponse, Event, Internal, Msg,
+};
+use crate::runtime::{Effect, EffectFuture, Effects, Env, EnvFuture, EnvFutureExt};
+use crate::types::accounts::{account_storage_key, Account, AccountsBucket};
+use crate::types::profile::{Profile, UID};
+
+/// The storage keys of all the data which belongs to an account.
+const ACCOUNT_STORAGE_KEYS: [&str; 9] = [
+    PROFILE_STORAGE_KEY,
+    LIBRARY_STORAGE_KEY,
+    STREAMS_STORAGE_KEY,
+    NOTIFICATIONS_STORAGE_KEY,
+    SEARCH_HISTORY_STORAGE_KEY,
+    DISMISSED_EVENTS_STORAGE_KEY,
+    OUTBOX_STORAGE_KEY,
+    PLAYLISTS_STORAGE_KEY,
+    WATCH_HISTORY_STORAGE_KEY,
+];
+
+pub fn update_accounts<E: Env + 'static>(
+    accounts: &mut AccountsBucket,
+    profile: &Profile,
+    status: &CtxStatus,
+    msg: &Msg,
+) -> Effects {
+    match msg {
+        // the profile is not reset yet, so it still holds the account which is logged out
+        Msg::Action(Action::Ctx(ActionCtx::Logout)) | Msg::Internal(Internal::Logout) => {
+            let uid = profile.uid();
+            let accounts_effects = if uid.is_some() && accounts.remove(&uid) {
+                Effects::msg(Msg::Internal(Internal::AccountsChanged))
+            } else {
+                Effects::none().unchanged()
+            };
+            let delete_account_effects = if uid.is_some() {
+                Effects::one(delete_account_from_storage::<E>(&uid)).unchanged()
+            } else {
+                Effects::none().unchanged()
+            };
+            // restore the data of the guest if it was stored when the user logged in
+            let guest_effects = if accounts.get(&None).is_some() {
+                Effects::one(load_account::<E>(None)).unchanged()
+            } else {
+                Effects::none().unchanged()
+            };
+            accounts_effects
+                .join(delete_account_effects)
+                .join(guest_effects)
+        }
+        Msg::Internal(Internal::CtxAuthResult(auth_request, result)) => match (status, result) {
+            (CtxStatus::Loading(loading_auth_request), Ok(CtxAuthResponse { auth, .. }))
+                if loading_auth_request == auth_request =>
+            {
+                accounts.upsert(Account {
+                    uid: Some(auth.user.id.to_owned()),
+                    email: Some(auth.user.email.to_owned()),
+                    avatar: auth.user.avatar.to_owned(),
+                    last_used: E::now(),
+                });
+                Effects::msg(Msg::Internal(Internal::AccountsChanged))
+            }
+            _ => Effects::none().unchanged(),
+        },
+        Msg::Internal(Internal::AccountsChanged) => {
+            Effects::one(push_accounts_to_storage::<E>(accounts)).unchanged()
+        }
+        _ => Effects::none().unchanged(),
+    }
+}
+
+/// Stores the data of the active account under its own keys,
+/// so that it can be restored when switching back to it.
+pub fn push_account_to_storage<E: Env + 'static>(ctx: &Ctx) -> Effect {
+    let uid = ctx.profile.uid();
+    EffectFuture::Sequential(
+        push_account_to_storage_future::<E>(ctx)
+            .map(move |result| match result {
+                Ok(_) => Msg::Event(Event::AccountPushedToStorage { uid }),
+                Err(error) => Msg::Event(Event::Error {
+                    error,
+                    source: Box::new(Event::AccountPushedToStorage { uid }),
+                }),
+            })
+            .boxed_env(),
+    )
+    .into()
+}
+
+/// Stores the data of the active account and loads the data of the account with the given `uid`.
+pub fn switch_account<E: Env + 'static>(ctx: &Ctx, uid: &UID) -> Effect {
+    EffectFuture::Sequential(
+        push_account_to_storage_future::<E>(ctx)
+            .and_then(enclose!((uid) move |_| load_account_future::<E>(uid)))
+            .map(enclose!((uid) move |result| {
+                Msg::Internal(Internal::AccountSwitchResult(uid, Box::new(result)))
+            }))
+            .boxed_env(),
+    )
+    .into()
+}
+
+fn load_account<E: Env + 'static>(uid: UID) -> Effect {
+    EffectFuture::Sequential(
+        load_account_future::<E>(uid.to_owned())
+            .map(move |result| Msg::Internal(Internal::AccountSwitchResult(uid, Box::new(result))))
+            .boxed_env(),
+    )
+    .into()
+}
+
+fn push_account_to_storage_future<E: Env + 'static>(
+    ctx: &Ctx,
+) -> EnvFuture<'static, Result<(), CtxError>> {
+    let uid = ctx.profile.uid();
+    future::try_join_all(vec![
+        E::set_encrypted_storage(
+            &account_storage_key(PROFILE_STORAGE_KEY, &uid),
+            Some(&ctx.profile),
+        ),
+        E::set_encrypted_storage(
+            &account_storage_key(LIBRARY_STORAGE_KEY, &uid),
+            Some(&ctx.library),
+        ),
+        E::set_encrypted_storage(
+            &account_storage_key(STREAMS_STORAGE_KEY, &uid),
+            Some(&ctx.streams),
+        ),
+        E::set_storage(
+            &account_storage_key(NOTIFICATIONS_STORAGE_KEY, &uid),
+            Some(&ctx.notifications),
+        ),
+        E::set_storage(
+            &account_storage_key(SEARCH_HISTORY_STORAGE_KEY, &uid),
+            Some(&ctx.search_history),
+        ),
+        E::set_storage(
+            &account_storage_key(DISMISSED_EVENTS_STORAGE_KEY, &uid),
+            Some(&ctx.dismissed_events),
+        ),
+        E::set_encrypted_storage(
+            &account_storage_key(OUTBOX_STORAGE_KEY, &uid),
+            Some(&ctx.outbox),
+        ),
+        E::set_storage(
+            &account_storage_key(PLAYLISTS_STORAGE_KEY, &uid),
+            Some(&ctx.playlists),
+        ),
+        E::set_encrypted_storage(
+            &account_storage_key(WATCH_HISTORY_STORAGE_KEY, &uid),
+            Some(&ctx.watch_history),
+        ),
+    ])
+    .map_ok(|_| ())
+    .map_err(CtxError::from)
+    .boxed_env()
+}
+
+fn load_account_future<E: Env + 'static>(
+    uid: UID,
+) -> EnvFuture<'static, Result<AccountStorageResponse, CtxError>> {
+    async move {
+        let (
+            profile,
+            library,
+            streams,
+            notifications,
+            search_history,
+            dismissed_events,
+            outbox,
+            playlists,
+            watch_history,
+        ) = try_join!(
+            E::get_encrypted_storage(&account_storage_key(PROFILE_STORAGE_KEY, &uid)),
+            E::get_encrypted_storage(&account_storage_key(LIBRARY_STORAGE_KEY, &uid)),
+            E::get_encrypted_storage(&account_storage_key(STREAMS_STORAGE_KEY, &uid)),
+            E::get_storage(&account_storage_key(NOTIFICATIONS_STORAGE_KEY, &uid)),
+            E::get_storage(&account_storage_key(SEARCH_HISTORY_STORAGE_KEY, &uid)),
+            E::get_storage(&account_storage_key(DISMISSED_EVENTS_STORAGE_KEY, &uid)),
+            E::get_encrypted_storage(&account_storage_key(OUTBOX_STORAGE_KEY, &uid)),
+            E::get_storage(&account_storage_key(PLAYLISTS_STORAGE_KEY, &uid)),
+            E::get_encrypted_storage(&account_storage_key(WATCH_HISTORY_STORAGE_KEY, &uid)),
+        )
+        .map_err(CtxError::from)?;
+        Ok::<_, CtxError>(AccountStorageResponse {
+            profile,
+            library,
+            streams,
+            notifications,
+            search_history,
+            dismissed_events,
+            outbox,
+            playlists,
+            watch_history,
+        })
+    }
+    .boxed_env()
+}
+
+/// Deletes the stored data of the account, except for the playlists.
+/// They're only stored on the device, so they're kept for the next login.
+fn delete_account_from_storage<E: Env + 'static>(uid: &UID) -> Effect {
+    EffectFuture::Sequential(
+        future::try_join_all(
+            ACCOUNT_STORAGE_KEYS
+                .iter()
+                .filter(|key| **key != PLAYLISTS_STORAGE_KEY)
+                .map(|key| E::set_storage::<()>(&account_storage_key(key, uid), None)),
+        )
+        .map(enclose!((uid) move |result| match result {
+            Ok(_) => Msg::Event(Event::AccountDeletedFromStorage { uid }),
+            Err(error) => Msg::Event(Event::Error {
+                error: CtxError::from(error),
+                source: Box::new(Event::AccountDeletedFromStorage { uid }),
+            }),
+        }))
+        .boxed_env(),
+    )
+    .into()
+}
+
+fn push_accounts_to_storage<E: Env + 'static>(accounts: &AccountsBucket) -> Effect {
+    let uids = accounts
+        .items
+        .iter()
+        .map(|account| &account.uid)
+        .cloned()
+        .collect::<Vec<_>>();
+    EffectFuture::Sequential(
+        E::set_storage(ACCOUNTS_STORAGE_KEY, Some(&accounts))
+            .map(move |result| match result {
+                Ok(_) => Msg::Event(Event::AccountsPushedToStorage { uids }),
+                Err(error) => Msg::Event(Event::Error {
+                    error: CtxError::from(error),
+                    source: Box::new(Event::AccountsPushedToStorage { uids }),
+                }),
+            })
+            .boxed_env(),
+    )
+    .into()
+}
@@ -23,7 +23,7 @@ pub fn update_events<E: Env + 'static>(
             *dismissed_events = next_dismissed_events;
             Effects::msg(Msg::Internal(Internal::DismissedEventsChanged))
         }
-        Msg::Action(Action::Ctx(ActionCtx::GetEvents)) => {
+        Msg::Action(Action::Ctx(ActionCtx::GetEvents)) | Msg::Internal(Internal::GetEvents) => {
             let modal_effects = eq_update(&mut events.modal, Loadable::Loading);
             let notification_effects = eq_update(&mut events.notification, Loadable::Loading);
             let requests_effects = Effects::many(vec![get_modal::<E>(), get_notification::<E>()]);
@@ -10,7 +10,7 @@ use crate::{
         LIBRARY_COLLECTION_NAME, LIBRARY_RECENT_COUNT, LIBRARY_RECENT_STORAGE_KEY,
         LIBRARY_STORAGE_KEY,
     },
-    models::ctx::{CtxError, CtxStatus, OtherError},
+    models::ctx::{push_to_api, CtxError, CtxStatus, OtherError},
     runtime::{
         msg::{Action, ActionCtx, CtxAuthResponse, Event, Internal, Msg},
         Effect, EffectFuture, Effects, Env, EnvFutureExt,
@@ -18,9 +18,12 @@ use crate::{
     types::{
         api::{
             fetch_api, APIResult, DatastoreCommand, DatastoreRequest, LibraryItemModified,
-            LibraryItemsResponse, SuccessResponse,
+            LibraryItemsResponse,
         },
-        library::{LibraryBucket, LibraryBucketRef, LibraryItem},
+        library::{
+            LibraryBucket, LibraryBucketRef, LibraryItem, StoredValue, MAX_RATING, MIN_RATING,
+        },
+        outbox::OutboxRequest,
         profile::{AuthKey, Profile},
     },
 };
@@ -88,6 +91,9 @@ pub fn update_library<E: Env + 'static>(
             Some(library_item) => {
                 let mut library_item = library_item.to_owned();
                 library_item.state.time_offset = 0;
+                if let Some(video_id) = &library_item.state.video_id {
+                    library_item.state.resume_points.remove(video_id);
+                }
 
                 Effects::msg(Msg::Internal(Internal::UpdateLibraryItem(library_item)))
                     .join(Effects::msg(Msg::Event(Event::LibraryItemRewinded {
@@ -132,7 +138,8 @@ pub fn update_library<E: Env + 'static>(
                 .unchanged(),
             }
         }
-        Msg::Action(Action::Ctx(ActionCtx::SyncLibraryWithAPI)) => match auth_key {
+        Msg::Action(Action::Ctx(ActionCtx::SyncLibraryWithAPI))
+        | Msg::Internal(Internal::SyncLibraryWithAPI) => match auth_key {
             Some(auth_key) => Effects::one(plan_sync_with_api::<E>(library, auth_key)).unchanged(),
             _ => Effects::msg(Msg::Event(Event::Error {
                 error: CtxError::from(OtherError::UserNotLoggedIn),
@@ -143,6 +150,71 @@ pub fn update_library<E: Env + 'static>(
             }))
             .unchanged(),
         },
+        Msg::Action(Action::Ctx(ActionCtx::SetLibraryItemWatchStatus { id, status })) => {
+            match library.items.get(id) {
+                Some(library_item) => {
+                    let mut library_item = library_item.to_owned();
+                    library_item.state.watch_status = status.map(StoredValue::Known);
+                    Effects::msg(Msg::Internal(Internal::UpdateLibraryItem(library_item)))
+                        .join(Effects::msg(Msg::Event(
+                            Event::LibraryItemWatchStatusUpdated { id: id.to_owned() },
+                        )))
+                        .unchanged()
+                }
+                _ => Effects::msg(Msg::Event(Event::Error {
+                    error: CtxError::from(OtherError::LibraryItemNotFound),
+                    source: Box::new(Event::LibraryItemWatchStatusUpdated { id: id.to_owned() }),
+                }))
+                .unchanged(),
+            }
+        }
+        Msg::Action(Action::Ctx(ActionCtx::RateLibraryItem { id, rating })) => {
+            if rating.map_or(false, |rating| !(MIN_RATING..=MAX_RATING).contains(&rating)) {
+                return Effects::msg(Msg::Event(Event::Error {
+                    error: CtxError::from(OtherError::InvalidRating),
+                    source: Box::new(Event::LibraryItemRated { id: id.to_owned() }),
+                }))
+                .unchanged();
+            }
+            match library.items.get(id) {
+                Some(library_item) => {
+                    let mut library_item = library_item.to_owned();
+                    library_item.state.rating = rating.map(StoredValue::Known);
+                    Effects::msg(Msg::Internal(Internal::UpdateLibraryItem(library_item)))
+                        .join(Effects::msg(Msg::Event(Event::LibraryItemRated {
+                            id: id.to_owned(),
+                        })))
+                        .unchanged()
+                }
+                _ => Effects::msg(Msg::Event(Event::Error {
+                    error: CtxError::from(OtherError::LibraryItemNotFound),
+                    source: Box::new(Event::LibraryItemRated { id: id.to_owned() }),
+                }))
+                .unchanged(),
+            }
+        }
+        Msg::Action(Action::Ctx(ActionCtx::SetLibraryItemNotes { id, notes })) => {
+            match library.items.get(id) {
+                Some(library_item) => {
+                    let mut library_item = library_item.to_owned();
+                    library_item.state.notes = notes
+                        .as_ref()
+                        .map(|notes| notes.trim())
+                        .filter(|notes| !notes.is_empty())
+                        .map(|notes| StoredValue::Known(notes.to_owned()));
+                    Effects::msg(Msg::Internal(Internal::UpdateLibraryItem(library_item)))
+                        .join(Effects::msg(Msg::Event(Event::LibraryItemNotesUpdated {
+                            id: id.to_owned(),
+                        })))
+                        .unchanged()
+                }
+                _ => Effects::msg(Msg::Event(Event::Error {
+                    error: CtxError::from(OtherError::LibraryItemNotFound),
+                    source: Box::new(Event::LibraryItemNotesUpdated { id: id.to_owned() }),
+                }))
+                .unchanged(),
+            }
+        }
         Msg::Action(Action::Ctx(ActionCtx::LibraryItemMarkAsWatched { id, is_watched })) => {
             match library.items.get(id) {
                 Some(library_item) => {
@@ -304,7 +376,7 @@ fn update_and_push_items_to_storage<E: Env + 'static>(
     let push_to_storage_future = if library.items.len() <= LIBRARY_RECENT_COUNT {
         Either::Left(
             future::try_join_all(vec![
-                E::set_storage(LIBRARY_RECENT_STORAGE_KEY, Some(&library)),
+                E::set_encrypted_storage(LIBRARY_RECENT_STORAGE_KEY, Some(&library)),
                 E::set_storage::<()>(LIBRARY_STORAGE_KEY, None),
             ])
             .map_ok(|_| ()),
@@ -312,18 +384,18 @@ fn update_and_push_items_to_storage<E: Env + 'static>(
     } else {
         let (recent_items, other_items) = library.split_items_by_recent();
         if are_items_in_recent {
-            Either::Right(Either::Left(E::set_storage(
+            Either::Right(Either::Left(E::set_encrypted_storage(
                 LIBRARY_RECENT_STORAGE_KEY,
                 Some(&LibraryBucketRef::new(&library.uid, &recent_items)),
             )))
         } else {
             Either::Right(Either::Right(
                 future::try_join_all(vec![
-                    E::set_storage(
+                    E::set_encrypted_storage(
                         LIBRARY_RECENT_STORAGE_KEY,
                         Some(&LibraryBucketRef::new(&library.uid, &recent_items)),
                     ),
-                    E::set_storage(
+                    E::set_encrypted_storage(
                         LIBRARY_STORAGE_KEY,
                         Some(&LibraryBucketRef::new(&library.uid, &other_items)),
                     ),
@@ -351,11 +423,11 @@ fn push_library_to_storage<E: Env + 'static>(library: &LibraryBucket) -> Effect
     let (recent_items, other_items) = library.split_items_by_recent();
     EffectFuture::Sequential(
         future::try_join_all(vec![
-            E::set_storage(
+            E::set_encrypted_storage(
                 LIBRARY_RECENT_STORAGE_KEY,
                 Some(&LibraryBucketRef::new(&library.uid, &recent_items)),
             ),
-            E::set_storage(
+            E::set_encrypted_storage(
                 LIBRARY_STORAGE_KEY,
                 Some(&LibraryBucketRef::new(&library.uid, &other_items)),
             ),
@@ -373,28 +445,7 @@ fn push_library_to_storage<E: Env + 'static>(library: &LibraryBucket) -> Effect
 }
 
 fn push_items_to_api<E: Env + 'static>(items: Vec<LibraryItem>, auth_key: &AuthKey) -> Effect {
-    let ids = items.iter().map(|item| &item.id).cloned().collect();
-    EffectFuture::Concurrent(
-        fetch_api::<E, _, _, SuccessResponse>(&DatastoreRequest {
-            auth_key: auth_key.to_owned(),
-            collection: LIBRARY_COLLECTION_NAME.to_owned(),
-            command: DatastoreCommand::Put { changes: items },
-        })
-        .map_err(CtxError::from)
-        .and_then(|result| match result {
-            APIResult::Ok(result) => future::ok(result),
-            APIResult::Err(error) => future::err(CtxError::from(error)),
-        })
-        .map(move |result| match result {
-            Ok(_) => Msg::Event(Event::LibraryItemsPushedToAPI { ids }),
-            Err(error) => Msg::Event(Event::Error {
-                error,
-                source: Box::new(Event::LibraryItemsPushedToAPI { ids }),
-            }),
-        })
-        .boxed_env(),
-    )
-    .into()
+    push_to_api::<E>(OutboxRequest::LibraryItemsPut(items), auth_key)
 }
 
 fn pull_items_from_api<E: Env + 'static>(ids: Vec<String>, auth_key: &AuthKey) -> Effect {
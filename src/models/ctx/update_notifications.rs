@@ -3,11 +3,13 @@ use std::collections::{hash_map::Entry, HashMap};
 use chrono::{DateTime, Duration, Utc};
 use futures::FutureExt;
 use lazysort::SortedBy;
-use once_cell::sync::Lazy;
 use tracing::trace;
 
 use crate::{
-    constants::{LAST_VIDEOS_IDS_EXTRA_PROP, NOTIFICATIONS_STORAGE_KEY, NOTIFICATION_ITEMS_COUNT},
+    constants::{
+        LAST_VIDEOS_IDS_EXTRA_PROP, NOTIFICATIONS_MAX_POLL_INTERVAL, NOTIFICATIONS_STORAGE_KEY,
+        NOTIFICATION_ITEMS_COUNT,
+    },
     models::{
         common::{
             eq_update, resources_update_with_vector_content, Loadable, ResourceLoadable,
@@ -21,15 +23,13 @@ use crate::{
     },
     types::{
         addon::{AggrRequest, ExtraType},
-        library::LibraryBucket,
+        library::{LibraryBucket, LibraryItem},
         notifications::{NotificationItem, NotificationsBucket},
-        profile::Profile,
-        resource::{MetaItem, MetaItemId, VideoId},
+        profile::{NotificationRule, NotificationSettings, Profile},
+        resource::{MetaItem, MetaItemId, Video, VideoId},
     },
 };
 
-static REQUEST_LAST_VIDEOS_EVERY: Lazy<Duration> = Lazy::new(|| Duration::hours(6));
-
 pub fn update_notifications<E: Env + 'static>(
     notifications: &mut NotificationsBucket,
     notification_catalogs: &mut Vec<ResourceLoadable<Vec<MetaItem>>>,
@@ -43,20 +43,43 @@ pub fn update_notifications<E: Env + 'static>(
             Effects::msg(Msg::Internal(Internal::PullNotifications)).unchanged()
         }
         Msg::Internal(Internal::PullNotifications) => {
+            let notification_settings = &profile.settings.notifications;
+            let request_last_videos_every = Duration::hours(
+                notification_settings
+                    .poll_interval
+                    .clamp(1, NOTIFICATIONS_MAX_POLL_INTERVAL)
+                    .into(),
+            );
             let (reason, should_make_request) = match notifications.last_updated {
-                Some(last_updated) if last_updated + *REQUEST_LAST_VIDEOS_EVERY <= E::now() => (
-                    format!(
-                        "`true` since {last_updated} + {hours} hours <= {now}",
-                        hours = REQUEST_LAST_VIDEOS_EVERY.num_hours(),
-                        now = E::now()
-                    ),
-                    true,
-                ),
+                _ if notification_settings
+                    .quiet_hours
+                    .map(|quiet_hours| quiet_hours.contains(&E::now()))
+                    .unwrap_or_default() =>
+                {
+                    (
+                        format!("`false` since {now} is in the quiet hours", now = E::now()),
+                        false,
+                    )
+                }
+                Some(last_updated)
+                    if last_updated
+                        .checked_add_signed(request_last_videos_every)
+                        .map_or(false, |next_update| next_update <= E::now()) =>
+                {
+                    (
+                        format!(
+                            "`true` since {last_updated} + {hours} hours <= {now}",
+                            hours = request_last_videos_every.num_hours(),
+                            now = E::now()
+                        ),
+                        true,
+                    )
+                }
                 None => ("`true` since last updated is `None`".to_string(), true),
                 Some(last_updated) => (
                     format!(
                         "`false` since {last_updated} + {hours} hours > {now}",
-                        hours = REQUEST_LAST_VIDEOS_EVERY.num_hours(),
+                        hours = request_last_videos_every.num_hours(),
                         now = E::now()
                     ),
                     false,
@@ -67,14 +90,18 @@ pub fn update_notifications<E: Env + 'static>(
                 name = "Notifications",
                 reason = reason,
                 last_updated = notifications.last_updated.as_ref().map(ToString::to_string),
-                hours = REQUEST_LAST_VIDEOS_EVERY.num_hours(),
+                hours = request_last_videos_every.num_hours(),
                 "Should last-videos addon resource be called? {should_make_request}"
             );
 
             let sorted_library_items_id_types = library
                 .items
                 .values()
-                .filter(|library_item| library_item.should_pull_notifications())
+                .filter(|library_item| {
+                    library_item.should_pull_notifications()
+                        && notification_settings.rule(&library_item.id)
+                            != NotificationRule::Disabled
+                })
                 .sorted_by(|a, b| b.mtime.cmp(&a.mtime))
                 .map(|library_item| (library_item.id.to_owned(), library_item.r#type.to_owned()))
                 .collect::<Vec<_>>();
@@ -110,6 +137,7 @@ pub fn update_notifications<E: Env + 'static>(
                 &mut notifications.items,
                 notification_catalogs,
                 library,
+                &profile.settings.notifications,
             );
 
             // because notifications are getting loaded by forcing new requests
@@ -167,6 +195,7 @@ pub fn update_notifications<E: Env + 'static>(
                     &mut notifications.items,
                     notification_catalogs,
                     library,
+                    &profile.settings.notifications,
                 )
             } else {
                 Effects::none().unchanged()
@@ -195,6 +224,7 @@ fn update_notification_items<E: Env + 'static>(
     notification_items: &mut HashMap<MetaItemId, HashMap<VideoId, NotificationItem>>,
     notification_catalogs: &[ResourceLoadable<Vec<MetaItem>>],
     library: &LibraryBucket,
+    notification_settings: &NotificationSettings,
 ) -> Effects {
     let selected_catalogs = notification_catalogs
         .iter()
@@ -235,8 +265,9 @@ fn update_notification_items<E: Env + 'static>(
             .items
             .iter()
             .fold(HashMap::new(), |mut map, (meta_id, library_item)| {
+                let rule = notification_settings.rule(&library_item.id);
                 // Exit early if we don't need to pull notifications for the library item
-                if !library_item.should_pull_notifications() {
+                if !library_item.should_pull_notifications() || rule == NotificationRule::Disabled {
                     return map;
                 }
 
@@ -291,6 +322,7 @@ fn update_notification_items<E: Env + 'static>(
                 // meta items videos
                 meta_item
                     .videos_iter()
+                    .filter(|video| video_matches_rule(rule, meta_item, library_item, video))
                     .filter_map(
                         |video| match (&library_item.state.last_watched, video.released) {
                             (Some(last_watched), Some(video_released)) => {
@@ -343,6 +375,67 @@ fn update_notification_items<E: Env + 'static>(
     eq_update(notification_items, next_notification_items)
 }
 
+/// Whether the video should be notified for according to the [`NotificationRule`].
+///
+/// The release date is checked separately against the `last_watched` of the [`LibraryItem`].
+fn video_matches_rule(
+    rule: NotificationRule,
+    meta_item: &MetaItem,
+    library_item: &LibraryItem,
+    video: &Video,
+) -> bool {
+    match rule {
+        NotificationRule::AllNewVideos => true,
+        NotificationRule::Disabled => false,
+        NotificationRule::AfterLastWatched => {
+            let last_watched_series_info = library_item
+                .state
+                .video_id
+                .as_ref()
+                .and_then(|video_id| meta_item.videos.iter().find(|video| &video.id == video_id))
+                .and_then(|video| video.series_info.as_ref());
+            match (last_watched_series_info, &video.series_info) {
+                (Some(last_watched), Some(series_info)) => {
+                    (series_info.season, series_info.episode)
+                        > (last_watched.season, last_watched.episode)
+                }
+                // without a known last watched video we can only rely on the release date
+                _ => true,
+            }
+        }
+        NotificationRule::NewSeasonsOnly => {
+            let series_info = match &video.series_info {
+                Some(series_info) => series_info,
+                None => return false,
+            };
+            let season_videos = meta_item
+                .videos
+                .iter()
+                .filter(|video| {
+                    video
+                        .series_info
+                        .as_ref()
+                        .map(|other| other.season == series_info.season)
+                        .unwrap_or_default()
+                })
+                .collect::<Vec<_>>();
+            // the season should not have started when the item was last watched
+            let season_started = season_videos.iter().any(|video| {
+                match (&video.released, &library_item.state.last_watched) {
+                    (Some(released), Some(last_watched)) => released <= last_watched,
+                    _ => false,
+                }
+            });
+            let first_episode = season_videos
+                .iter()
+                .filter_map(|video| video.series_info.as_ref())
+                .map(|series_info| series_info.episode)
+                .min();
+            !season_started && first_episode == Some(series_info.episode)
+        }
+    }
+}
+
 fn push_notifications_to_storage<E: Env + 'static>(notifications: &NotificationsBucket) -> Effect {
     let ids = notifications.items.keys().cloned().collect();
     EffectFuture::Sequential(
@@ -0,0 +1,184 @@
+use enclose::enclose;
+use futures::{future, FutureExt, TryFutureExt};
+
+use crate::constants::{LIBRARY_COLLECTION_NAME, OUTBOX_STORAGE_KEY};
+use crate::models::ctx::{CtxError, CtxStatus};
+use crate::runtime::msg::{Action, ActionCtx, CtxAuthResponse, Event, Internal, Msg};
+use crate::runtime::{Effect, EffectFuture, Effects, Env, EnvError, EnvFutureExt};
+use crate::types::api::{
+    fetch_api, APIRequest, APIResult, DatastoreCommand, DatastoreRequest, SuccessResponse,
+};
+use crate::types::outbox::{OutboxBucket, OutboxRequest};
+use crate::types::profile::{AuthKey, Profile};
+
+pub fn update_outbox<E: Env + 'static>(
+    outbox: &mut OutboxBucket,
+    profile: &Profile,
+    status: &CtxStatus,
+    msg: &Msg,
+) -> Effects {
+    match msg {
+        Msg::Action(Action::Ctx(ActionCtx::Logout)) | Msg::Internal(Internal::Logout) => {
+            let discarded_effects = match outbox.pending_count() {
+                0 => Effects::none().unchanged(),
+                pending_count => Effects::msg(Msg::Event(Event::OutboxDiscarded {
+                    uid: outbox.uid.to_owned(),
+                    pending_count,
+                }))
+                .unchanged(),
+            };
+            let next_outbox = OutboxBucket::default();
+            let outbox_effects = if *outbox != next_outbox {
+                *outbox = next_outbox;
+                Effects::msg(Msg::Internal(Internal::OutboxChanged))
+            } else {
+                Effects::none().unchanged()
+            };
+            discarded_effects.join(outbox_effects)
+        }
+        Msg::Action(Action::Ctx(ActionCtx::FlushOutbox)) | Msg::Internal(Internal::FlushOutbox) => {
+            match profile.auth_key() {
+                Some(auth_key) if !outbox.items.is_empty() => Effects::many(
+                    outbox
+                        .items
+                        .iter()
+                        .map(|request| push_to_api::<E>(request.to_owned(), auth_key))
+                        .collect(),
+                )
+                .unchanged(),
+                _ => Effects::none().unchanged(),
+            }
+        }
+        Msg::Internal(Internal::APIPushResult(auth_key, request, result))
+            if profile.auth_key() == Some(auth_key) =>
+        {
+            let source = push_event(request);
+            match result {
+                Ok(_) => {
+                    let outbox_effects = if outbox.remove_superseded(request) {
+                        Effects::msg(Msg::Internal(Internal::OutboxChanged))
+                    } else {
+                        Effects::none().unchanged()
+                    };
+                    Effects::msg(Msg::Event(source))
+                        .unchanged()
+                        .join(outbox_effects)
+                }
+                Err(error) => {
+                    let outbox_effects = match error {
+                        // the request didn't reach the API, keep it for a retry
+                        CtxError::Env(EnvError::Fetch(_)) => {
+                            outbox.enqueue(request.to_owned());
+                            Effects::msg(Msg::Internal(Internal::OutboxChanged))
+                        }
+                        // the API rejected the request, retrying it won't help
+                        _ if outbox.remove_superseded(request) => {
+                            Effects::msg(Msg::Internal(Internal::OutboxChanged))
+                        }
+                        _ => Effects::none().unchanged(),
+                    };
+                    Effects::msg(Msg::Event(Event::Error {
+                        error: error.to_owned(),
+                        source: Box::new(source),
+                    }))
+                    .unchanged()
+                    .join(outbox_effects)
+                }
+            }
+        }
+        Msg::Internal(Internal::CtxAuthResult(auth_request, result)) => match (status, result) {
+            (CtxStatus::Loading(loading_auth_request), Ok(CtxAuthResponse { auth, .. }))
+                if loading_auth_request == auth_request =>
+            {
+                let uid = Some(auth.user.id.to_owned());
+                if outbox.uid != uid {
+                    *outbox = OutboxBucket::new(uid);
+                    Effects::msg(Msg::Internal(Internal::OutboxChanged))
+                } else {
+                    // the same user has logged in again, retry the pending changes
+                    Effects::msg(Msg::Internal(Internal::FlushOutbox)).unchanged()
+                }
+            }
+            _ => Effects::none().unchanged(),
+        },
+        Msg::Internal(Internal::OutboxChanged) => {
+            Effects::one(push_outbox_to_storage::<E>(outbox)).unchanged()
+        }
+        _ => Effects::none().unchanged(),
+    }
+}
+
+/// Pushes the mutation to the API with the given auth key.
+///
+/// Resolves with [`Internal::APIPushResult`], so that failed requests end up in the outbox.
+pub fn push_to_api<E: Env + 'static>(request: OutboxRequest, auth_key: &AuthKey) -> Effect {
+    let fetch_fut = match &request {
+        OutboxRequest::SaveUser { user, .. } => {
+            fetch_api::<E, _, _, SuccessResponse>(&APIRequest::SaveUser {
+                auth_key: auth_key.to_owned(),
+                user: user.to_owned(),
+            })
+        }
+        OutboxRequest::AddonCollectionSet { addons, .. } => {
+            fetch_api::<E, _, _, SuccessResponse>(&APIRequest::AddonCollectionSet {
+                auth_key: auth_key.to_owned(),
+                addons: addons.to_owned(),
+            })
+        }
+        OutboxRequest::LibraryItemsPut(items) => {
+            fetch_api::<E, _, _, SuccessResponse>(&DatastoreRequest {
+                auth_key: auth_key.to_owned(),
+                collection: LIBRARY_COLLECTION_NAME.to_owned(),
+                command: DatastoreCommand::Put {
+                    changes: items.to_owned(),
+                },
+            })
+        }
+    };
+    EffectFuture::Concurrent(
+        fetch_fut
+            .map_err(CtxError::from)
+            .and_then(|result| match result {
+                APIResult::Ok(_) => future::ok(()),
+                APIResult::Err(error) => future::err(CtxError::from(error)),
+            })
+            .map(enclose!((auth_key) move |result| {
+                Msg::Internal(Internal::APIPushResult(auth_key, request, result))
+            }))
+            .boxed_env(),
+    )
+    .into()
+}
+
+fn push_event(request: &OutboxRequest) -> Event {
+    match request {
+        OutboxRequest::SaveUser { user, .. } => Event::UserPushedToAPI {
+            uid: Some(user.id.to_owned()),
+        },
+        OutboxRequest::AddonCollectionSet { addons, .. } => Event::AddonsPushedToAPI {
+            transport_urls: addons
+                .iter()
+                .map(|addon| &addon.transport_url)
+                .cloned()
+                .collect(),
+        },
+        OutboxRequest::LibraryItemsPut(items) => Event::LibraryItemsPushedToAPI {
+            ids: items.iter().map(|item| &item.id).cloned().collect(),
+        },
+    }
+}
+
+fn push_outbox_to_storage<E: Env + 'static>(outbox: &OutboxBucket) -> Effect {
+    EffectFuture::Sequential(
+        E::set_encrypted_storage(OUTBOX_STORAGE_KEY, Some(&outbox))
+            .map(enclose!((outbox.uid => uid) move |result| match result {
+                Ok(_) => Msg::Event(Event::OutboxPushedToStorage { uid }),
+                Err(error) => Msg::Event(Event::Error {
+                    error: CtxError::from(error),
+                    source: Box::new(Event::OutboxPushedToStorage { uid }),
+                })
+            }))
+            .boxed_env(),
+    )
+    .into()
+}
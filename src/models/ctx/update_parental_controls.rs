@@ -0,0 +1,101 @@
+use crate::models::ctx::{CtxError, OtherError};
+use crate::runtime::msg::{Action, ActionCtx, Event, Internal, Msg};
+use crate::runtime::{Effects, Env};
+use crate::types::profile::{ParentalControls, Profile};
+
+pub fn update_parental_controls<E: Env + 'static>(
+    profile: &mut Profile,
+    unlocked: &mut bool,
+    msg: &Msg,
+) -> Effects {
+    match msg {
+        Msg::Action(Action::Ctx(ActionCtx::Logout)) | Msg::Internal(Internal::Logout) => {
+            lock_effects(unlocked)
+        }
+        Msg::Action(Action::Ctx(ActionCtx::UpdateParentalControls {
+            max_certification,
+            blocked_genres,
+            block_unrated,
+        })) => {
+            if profile.parental_controls.has_pin() && !*unlocked {
+                return locked_error_effects();
+            }
+
+            let next_parental_controls = ParentalControls {
+                max_certification: *max_certification,
+                blocked_genres: blocked_genres.to_owned(),
+                block_unrated: *block_unrated,
+                pin_hash: profile.parental_controls.pin_hash.to_owned(),
+                pin_salt: profile.parental_controls.pin_salt.to_owned(),
+            };
+            if profile.parental_controls != next_parental_controls {
+                profile.parental_controls = next_parental_controls;
+                Effects::msg(Msg::Event(Event::ParentalControlsUpdated))
+                    .join(Effects::msg(Msg::Internal(Internal::ProfileChanged)))
+                    .join(Effects::msg(Msg::Internal(
+                        Internal::ParentalControlsChanged,
+                    )))
+            } else {
+                Effects::msg(Msg::Event(Event::ParentalControlsUpdated)).unchanged()
+            }
+        }
+        Msg::Action(Action::Ctx(ActionCtx::SetParentalControlsPin(pin))) => {
+            if profile.parental_controls.has_pin() && !*unlocked {
+                return locked_error_effects();
+            }
+
+            profile.parental_controls.set_pin(pin.as_deref());
+            Effects::msg(Msg::Event(Event::ParentalControlsUpdated))
+                .join(Effects::msg(Msg::Internal(Internal::ProfileChanged)))
+        }
+        Msg::Action(Action::Ctx(ActionCtx::UnlockParentalControls(pin))) => {
+            if !profile.parental_controls.verify_pin(pin) {
+                Effects::msg(Msg::Event(Event::Error {
+                    error: CtxError::from(OtherError::InvalidParentalControlsPin),
+                    source: Box::new(Event::ParentalControlsUnlocked),
+                }))
+                .unchanged()
+            } else {
+                let rehash_effects = if profile.parental_controls.has_legacy_pin() {
+                    profile.parental_controls.set_pin(Some(pin));
+                    Effects::msg(Msg::Internal(Internal::ProfileChanged))
+                } else {
+                    Effects::none().unchanged()
+                };
+                let unlock_effects = if !*unlocked {
+                    *unlocked = true;
+                    Effects::msg(Msg::Internal(Internal::ParentalControlsChanged))
+                } else {
+                    Effects::none().unchanged()
+                };
+                Effects::msg(Msg::Event(Event::ParentalControlsUnlocked))
+                    .unchanged()
+                    .join(rehash_effects)
+                    .join(unlock_effects)
+            }
+        }
+        Msg::Action(Action::Ctx(ActionCtx::LockParentalControls)) => {
+            Effects::msg(Msg::Event(Event::ParentalControlsLocked))
+                .unchanged()
+                .join(lock_effects(unlocked))
+        }
+        _ => Effects::none().unchanged(),
+    }
+}
+
+fn lock_effects(unlocked: &mut bool) -> Effects {
+    if *unlocked {
+        *unlocked = false;
+        Effects::msg(Msg::Internal(Internal::ParentalControlsChanged))
+    } else {
+        Effects::none().unchanged()
+    }
+}
+
+fn locked_error_effects() -> Effects {
+    Effects::msg(Msg::Event(Event::Error {
+        error: CtxError::from(OtherError::ParentalControlsLocked),
+        source: Box::new(Event::ParentalControlsUpdated),
+    }))
+    .unchanged()
+}
@@ -0,0 +1,243 @@
+use enclose::enclose;
+use futures::FutureExt;
+
+use crate::constants::PLAYLISTS_STORAGE_KEY;
+use crate::models::common::eq_update;
+use crate::models::ctx::{CtxError, CtxStatus, OtherError};
+use crate::runtime::msg::{Action, ActionCtx, CtxAuthResponse, Event, Internal, Msg};
+use crate::runtime::{Effect, EffectFuture, Effects, Env, EnvFutureExt};
+use crate::types::accounts::account_storage_key;
+use crate::types::playlists::{Playlist, PlaylistsBucket};
+
+pub fn update_playlists<E: Env + 'static>(
+    playlists: &mut PlaylistsBucket,
+    status: &CtxStatus,
+    msg: &Msg,
+) -> Effects {
+    match msg {
+        Msg::Action(Action::Ctx(ActionCtx::Logout)) | Msg::Internal(Internal::Logout) => {
+            // the playlists are not synced with the API,
+            // keep them on the device until the user logs in again
+            let keep_playlists_effects = if playlists.uid.is_some() {
+                Effects::one(push_account_playlists_to_storage::<E>(playlists)).unchanged()
+            } else {
+                Effects::none().unchanged()
+            };
+            let playlists_effects = eq_update(playlists, PlaylistsBucket::default());
+            let playlists_effects = if playlists_effects.has_changed {
+                Effects::msg(Msg::Internal(Internal::PlaylistsChanged))
+            } else {
+                playlists_effects
+            };
+            keep_playlists_effects.join(playlists_effects)
+        }
+        Msg::Action(Action::Ctx(ActionCtx::CreatePlaylist { id, name })) => {
+            let event = Event::PlaylistCreated { id: id.to_owned() };
+            if id.trim().is_empty() || name.trim().is_empty() {
+                return playlist_error_effects(OtherError::InvalidPlaylist, event);
+            }
+            if playlists.get(id).is_some() {
+                return playlist_error_effects(OtherError::PlaylistAlreadyExists, event);
+            }
+            playlists
+                .items
+                .push(Playlist::new(id.to_owned(), name.to_owned(), E::now()));
+            Effects::msg(Msg::Event(event))
+                .join(Effects::msg(Msg::Internal(Internal::PlaylistsChanged)))
+        }
+        Msg::Action(Action::Ctx(ActionCtx::RenamePlaylist { id, name })) => {
+            let event = Event::PlaylistRenamed { id: id.to_owned() };
+            if name.trim().is_empty() {
+                return playlist_error_effects(OtherError::InvalidPlaylist, event);
+            }
+            match playlists.get_mut(id) {
+                Some(playlist) if playlist.name == *name => {
+                    Effects::msg(Msg::Event(event)).unchanged()
+                }
+                Some(playlist) => {
+                    name.clone_into(&mut playlist.name);
+                    playlist.mtime = E::now();
+                    Effects::msg(Msg::Event(event))
+                        .join(Effects::msg(Msg::Internal(Internal::PlaylistsChanged)))
+                }
+                None => playlist_error_effects(OtherError::PlaylistNotFound, event),
+            }
+        }
+        Msg::Action(Action::Ctx(ActionCtx::MovePlaylist { id, index })) => {
+            let event = Event::PlaylistMoved { id: id.to_owned() };
+            match playlists
+                .items
+                .iter()
+                .position(|playlist| playlist.id == *id)
+            {
+                Some(position) => {
+                    let index = (*index).min(playlists.items.len() - 1);
+                    if position == index {
+                        return Effects::msg(Msg::Event(event)).unchanged();
+                    }
+                    let playlist = playlists.items.remove(position);
+                    playlists.items.insert(index, playlist);
+                    Effects::msg(Msg::Event(event))
+                        .join(Effects::msg(Msg::Internal(Internal::PlaylistsChanged)))
+                }
+                None => playlist_error_effects(OtherError::PlaylistNotFound, event),
+            }
+        }
+        Msg::Action(Action::Ctx(ActionCtx::DeletePlaylist(id))) => {
+            let event = Event::PlaylistDeleted { id: id.to_owned() };
+            match playlists
+                .items
+                .iter()
+                .position(|playlist| playlist.id == *id)
+            {
+                Some(position) => {
+                    playlists.items.remove(position);
+                    Effects::msg(Msg::Event(event))
+                        .join(Effects::msg(Msg::Internal(Internal::PlaylistsChanged)))
+                }
+                None => playlist_error_effects(OtherError::PlaylistNotFound, event),
+            }
+        }
+        Msg::Action(Action::Ctx(ActionCtx::AddToPlaylist { id, meta_item })) => {
+            let event = Event::PlaylistItemAdded {
+                id: id.to_owned(),
+                meta_item_id: meta_item.id.to_owned(),
+            };
+            match playlists.get_mut(id) {
+                Some(playlist) if playlist.contains(&meta_item.id) => {
+                    Effects::msg(Msg::Event(event)).unchanged()
+                }
+                Some(playlist) => {
+                    playlist.items.push(meta_item.to_owned());
+                    playlist.mtime = E::now();
+                    Effects::msg(Msg::Event(event))
+                        .join(Effects::msg(Msg::Internal(Internal::PlaylistsChanged)))
+                }
+                None => playlist_error_effects(OtherError::PlaylistNotFound, event),
+            }
+        }
+        Msg::Action(Action::Ctx(ActionCtx::RemoveFromPlaylist { id, meta_item_id })) => {
+            let event = Event::PlaylistItemRemoved {
+                id: id.to_owned(),
+                meta_item_id: meta_item_id.to_owned(),
+            };
+            let playlist = match playlists.get_mut(id) {
+                Some(playlist) => playlist,
+                None => return playlist_error_effects(OtherError::PlaylistNotFound, event),
+            };
+            match playlist
+                .items
+                .iter()
+                .position(|item| item.id == *meta_item_id)
+            {
+                Some(position) => {
+                    playlist.items.remove(position);
+                    playlist.mtime = E::now();
+                    Effects::msg(Msg::Event(event))
+                        .join(Effects::msg(Msg::Internal(Internal::PlaylistsChanged)))
+                }
+                None => playlist_error_effects(OtherError::PlaylistItemNotFound, event),
+            }
+        }
+        Msg::Action(Action::Ctx(ActionCtx::MovePlaylistItem {
+            id,
+            meta_item_id,
+            index,
+        })) => {
+            let event = Event::PlaylistItemMoved {
+                id: id.to_owned(),
+                meta_item_id: meta_item_id.to_owned(),
+            };
+            let playlist = match playlists.get_mut(id) {
+                Some(playlist) => playlist,
+                None => return playlist_error_effects(OtherError::PlaylistNotFound, event),
+            };
+            match playlist
+                .items
+                .iter()
+                .position(|item| item.id == *meta_item_id)
+            {
+                Some(position) => {
+                    let index = (*index).min(playlist.items.len() - 1);
+                    if position == index {
+                        return Effects::msg(Msg::Event(event)).unchanged();
+                    }
+                    let item = playlist.items.remove(position);
+                    playlist.items.insert(index, item);
+                    playlist.mtime = E::now();
+                    Effects::msg(Msg::Event(event))
+                        .join(Effects::msg(Msg::Internal(Internal::PlaylistsChanged)))
+                }
+                None => playlist_error_effects(OtherError::PlaylistItemNotFound, event),
+            }
+        }
+        Msg::Internal(Internal::CtxAuthResult(auth_request, result)) => match (status, result) {
+            (
+                CtxStatus::Loading(loading_auth_request),
+                Ok(CtxAuthResponse {
+                    auth,
+                    playlists: stored_playlists,
+                    ..
+                }),
+            ) if loading_auth_request == auth_request => {
+                let uid = Some(auth.user.id.to_owned());
+                if playlists.uid == uid {
+                    return Effects::none().unchanged();
+                }
+                // restore the playlists which were kept when the user logged out
+                *playlists = stored_playlists
+                    .to_owned()
+                    .filter(|stored_playlists| stored_playlists.uid == uid)
+                    .unwrap_or_else(|| PlaylistsBucket::new(uid));
+                Effects::msg(Msg::Internal(Internal::PlaylistsChanged))
+            }
+            _ => Effects::none().unchanged(),
+        },
+        Msg::Internal(Internal::PlaylistsChanged) => {
+            Effects::one(push_playlists_to_storage::<E>(playlists)).unchanged()
+        }
+        _ => Effects::none().unchanged(),
+    }
+}
+
+fn playlist_error_effects(error: OtherError, source: Event) -> Effects {
+    Effects::msg(Msg::Event(Event::Error {
+        error: CtxError::from(error),
+        source: Box::new(source),
+    }))
+    .unchanged()
+}
+
+/// Stores the playlists under the keys of their account, see [`account_storage_key`].
+fn push_account_playlists_to_storage<E: Env + 'static>(playlists: &PlaylistsBucket) -> Effect {
+    EffectFuture::Sequential(
+        E::set_storage(
+            &account_storage_key(PLAYLISTS_STORAGE_KEY, &playlists.uid),
+            Some(&playlists),
+        )
+        .map(enclose!((playlists.uid => uid) move |result| match result {
+            Ok(_) => Msg::Event(Event::PlaylistsPushedToStorage { uid }),
+            Err(error) => Msg::Event(Event::Error {
+                error: CtxError::from(error),
+                source: Box::new(Event::PlaylistsPushedToStorage { uid }),
+            })
+        }))
+        .boxed_env(),
+    )
+    .into()
+}
+
+fn push_playlists_to_storage<E: Env + 'static>(playlists: &PlaylistsBucket) -> Effect {
+    EffectFuture::Sequential(
+        E::set_storage(PLAYLISTS_STORAGE_KEY, Some(&playlists))
+            .map(enclose!((playlists.uid => uid) move |result| match result {
+                Ok(_) => Msg::Event(Event::PlaylistsPushedToStorage { uid }),
+                Err(error) => Msg::Event(Event::Error {
+                    error: CtxError::from(error),
+                    source: Box::new(Event::PlaylistsPushedToStorage { uid }),
+                })
+            }))
+            .boxed_env(),
+    )
+    .into()
+}
@@ -3,15 +3,18 @@ use std::collections::HashSet;
 use enclose::enclose;
 use futures::{future, FutureExt, TryFutureExt};
 
-use crate::constants::{OFFICIAL_ADDONS, PROFILE_STORAGE_KEY};
-use crate::models::ctx::{CtxError, CtxStatus, OtherError};
+use crate::constants::{
+    NOTIFICATIONS_MAX_POLL_INTERVAL, OFFICIAL_ADDONS, PROFILE_STORAGE_KEY, SMART_LISTS_STORAGE_KEY,
+};
+use crate::models::ctx::{push_to_api, CtxError, CtxStatus, OtherError};
 use crate::runtime::msg::{Action, ActionCtx, CtxAuthResponse, Event, Internal, Msg};
 use crate::runtime::{Effect, EffectFuture, Effects, Env, EnvFutureExt};
+use crate::types::accounts::account_storage_key;
 use crate::types::addon::Descriptor;
-use crate::types::api::{
-    fetch_api, APIError, APIRequest, APIResult, CollectionResponse, SuccessResponse,
-};
+use crate::types::api::{fetch_api, APIError, APIRequest, APIResult, CollectionResponse};
+use crate::types::outbox::OutboxRequest;
 use crate::types::profile::{Auth, AuthKey, Profile, Settings, User};
+use crate::types::scheduler::SchedulerJob;
 use crate::types::streams::StreamsBucket;
 
 pub fn update_profile<E: Env + 'static>(
@@ -22,13 +25,25 @@ pub fn update_profile<E: Env + 'static>(
 ) -> Effects {
     match msg {
         Msg::Action(Action::Ctx(ActionCtx::Logout)) | Msg::Internal(Internal::Logout) => {
-            let next_profile = Profile::default();
-            if *profile != next_profile {
+            // the smart lists are not synced with the API,
+            // keep them on the device until the user logs in again
+            let keep_smart_lists_effects = if profile.auth.is_some() {
+                Effects::one(push_account_smart_lists_to_storage::<E>(profile)).unchanged()
+            } else {
+                Effects::none().unchanged()
+            };
+            // keep the restrictions of the device, logging out should not bypass them
+            let next_profile = Profile {
+                parental_controls: profile.parental_controls.to_owned(),
+                ..Default::default()
+            };
+            let profile_effects = if *profile != next_profile {
                 *profile = next_profile;
                 Effects::msg(Msg::Internal(Internal::ProfileChanged))
             } else {
                 Effects::none().unchanged()
-            }
+            };
+            keep_smart_lists_effects.join(profile_effects)
         }
         Msg::Action(Action::Ctx(ActionCtx::PushUserToAPI)) => match &profile.auth {
             Some(Auth { key, user }) => {
@@ -40,7 +55,8 @@ pub fn update_profile<E: Env + 'static>(
             }))
             .unchanged(),
         },
-        Msg::Action(Action::Ctx(ActionCtx::PullUserFromAPI)) => match profile.auth_key() {
+        Msg::Action(Action::Ctx(ActionCtx::PullUserFromAPI))
+        | Msg::Internal(Internal::PullUserFromAPI) => match profile.auth_key() {
             Some(auth_key) => Effects::one(pull_user_from_api::<E>(auth_key)).unchanged(),
             _ => Effects::msg(Msg::Event(Event::Error {
                 error: CtxError::from(OtherError::UserNotLoggedIn),
@@ -66,7 +82,8 @@ pub fn update_profile<E: Env + 'static>(
             }))
             .unchanged(),
         },
-        Msg::Action(Action::Ctx(ActionCtx::PullAddonsFromAPI)) => match profile.auth_key() {
+        Msg::Action(Action::Ctx(ActionCtx::PullAddonsFromAPI))
+        | Msg::Internal(Internal::PullAddonsFromAPI) => match profile.auth_key() {
             Some(auth_key) => Effects::one(pull_addons_from_api::<E>(auth_key)).unchanged(),
             _ => {
                 let next_addons = profile
@@ -222,7 +239,34 @@ pub fn update_profile<E: Env + 'static>(
             .unchanged(),
         },
         Msg::Action(Action::Ctx(ActionCtx::UpdateSettings(settings))) => {
-            if profile.settings != *settings {
+            let error = if !(1..=NOTIFICATIONS_MAX_POLL_INTERVAL)
+                .contains(&settings.notifications.poll_interval)
+            {
+                Some(OtherError::InvalidNotificationsPollInterval)
+            } else if !settings
+                .scheduler_job_intervals
+                .values()
+                .all(|interval| SchedulerJob::is_valid_interval(*interval))
+            {
+                Some(OtherError::InvalidSchedulerJobInterval)
+            } else if !settings
+                .notifications
+                .quiet_hours
+                .map_or(true, |quiet_hours| quiet_hours.is_valid())
+            {
+                Some(OtherError::InvalidQuietHours)
+            } else {
+                None
+            };
+            if let Some(error) = error {
+                Effects::msg(Msg::Event(Event::Error {
+                    error: CtxError::from(error),
+                    source: Box::new(Event::SettingsUpdated {
+                        settings: settings.to_owned(),
+                    }),
+                }))
+                .unchanged()
+            } else if profile.settings != *settings {
                 settings.clone_into(&mut profile.settings);
                 Effects::msg(Msg::Event(Event::SettingsUpdated {
                     settings: settings.to_owned(),
@@ -235,6 +279,69 @@ pub fn update_profile<E: Env + 'static>(
                 .unchanged()
             }
         }
+        Msg::Action(Action::Ctx(ActionCtx::UpdateSchedulerJobInterval { job, interval })) => {
+            let intervals = &mut profile.settings.scheduler_job_intervals;
+            if !SchedulerJob::is_valid_interval(*interval) {
+                Effects::msg(Msg::Event(Event::Error {
+                    error: CtxError::from(OtherError::InvalidSchedulerJobInterval),
+                    source: Box::new(Event::SettingsUpdated {
+                        settings: profile.settings.to_owned(),
+                    }),
+                }))
+                .unchanged()
+            } else if intervals.get(job) != Some(interval) {
+                intervals.insert(*job, *interval);
+                Effects::msg(Msg::Event(Event::SettingsUpdated {
+                    settings: profile.settings.to_owned(),
+                }))
+                .join(Effects::msg(Msg::Internal(Internal::ProfileChanged)))
+            } else {
+                Effects::msg(Msg::Event(Event::SettingsUpdated {
+                    settings: profile.settings.to_owned(),
+                }))
+                .unchanged()
+            }
+        }
+        Msg::Action(Action::Ctx(ActionCtx::SaveSmartList(smart_list))) => {
+            let position = profile
+                .smart_lists
+                .iter()
+                .position(|saved_smart_list| saved_smart_list.id == smart_list.id);
+            let saved_effects = Effects::msg(Msg::Event(Event::SmartListSaved {
+                id: smart_list.id.to_owned(),
+            }));
+            match position {
+                Some(position) if profile.smart_lists[position] == *smart_list => {
+                    saved_effects.unchanged()
+                }
+                Some(position) => {
+                    smart_list.clone_into(&mut profile.smart_lists[position]);
+                    saved_effects.join(Effects::msg(Msg::Internal(Internal::ProfileChanged)))
+                }
+                None => {
+                    profile.smart_lists.push(smart_list.to_owned());
+                    saved_effects.join(Effects::msg(Msg::Internal(Internal::ProfileChanged)))
+                }
+            }
+        }
+        Msg::Action(Action::Ctx(ActionCtx::DeleteSmartList(id))) => {
+            match profile
+                .smart_lists
+                .iter()
+                .position(|smart_list| smart_list.id == *id)
+            {
+                Some(position) => {
+                    profile.smart_lists.remove(position);
+                    Effects::msg(Msg::Event(Event::SmartListDeleted { id: id.to_owned() }))
+                        .join(Effects::msg(Msg::Internal(Internal::ProfileChanged)))
+                }
+                None => Effects::msg(Msg::Event(Event::Error {
+                    error: CtxError::from(OtherError::SmartListNotFound),
+                    source: Box::new(Event::SmartListDeleted { id: id.to_owned() }),
+                }))
+                .unchanged(),
+            }
+        }
         Msg::Internal(Internal::ProfileChanged) => {
             Effects::one(push_profile_to_storage::<E>(profile)).unchanged()
         }
@@ -282,14 +389,24 @@ pub fn update_profile<E: Env + 'static>(
                 Ok(CtxAuthResponse {
                     auth,
                     addons_result,
+                    smart_lists,
                     ..
                 }),
             ) if loading_auth_request == auth_request => {
+                // restore the smart lists which were kept when the user logged out
+                let smart_lists = if profile.uid().as_ref() == Some(&auth.user.id) {
+                    profile.smart_lists.to_owned()
+                } else {
+                    smart_lists.to_owned().unwrap_or_default()
+                };
                 let next_profile = Profile {
                     auth: Some(auth.to_owned()),
                     addons: addons_result.to_owned().unwrap_or(OFFICIAL_ADDONS.clone()),
                     addons_locked: addons_result.is_err(),
                     settings: Settings::default(),
+                    // keep the restrictions of the device, logging in should not bypass them
+                    parental_controls: profile.parental_controls.to_owned(),
+                    smart_lists,
                 };
                 if *profile != next_profile {
                     *profile = next_profile;
@@ -387,32 +504,13 @@ pub fn update_profile<E: Env + 'static>(
 }
 
 fn push_addons_to_api<E: Env + 'static>(addons: Vec<Descriptor>, auth_key: &AuthKey) -> Effect {
-    let transport_urls = addons
-        .iter()
-        .map(|addon| &addon.transport_url)
-        .cloned()
-        .collect();
-    let request = APIRequest::AddonCollectionSet {
-        auth_key: auth_key.to_owned(),
-        addons,
-    };
-    EffectFuture::Concurrent(
-        fetch_api::<E, _, _, SuccessResponse>(&request)
-            .map_err(CtxError::from)
-            .and_then(|result| match result {
-                APIResult::Ok(result) => future::ok(result),
-                APIResult::Err(error) => future::err(CtxError::from(error)),
-            })
-            .map(move |result| match result {
-                Ok(_) => Msg::Event(Event::AddonsPushedToAPI { transport_urls }),
-                Err(error) => Msg::Event(Event::Error {
-                    error,
-                    source: Box::new(Event::AddonsPushedToAPI { transport_urls }),
-                }),
-            })
-            .boxed_env(),
+    push_to_api::<E>(
+        OutboxRequest::AddonCollectionSet {
+            addons,
+            mtime: E::now(),
+        },
+        auth_key,
     )
-    .into()
 }
 
 fn pull_user_from_api<E: Env + 'static>(auth_key: &AuthKey) -> Effect {
@@ -433,28 +531,13 @@ fn pull_user_from_api<E: Env + 'static>(auth_key: &AuthKey) -> Effect {
 }
 
 fn push_user_to_api<E: Env + 'static>(user: User, auth_key: &AuthKey) -> Effect {
-    let uid = Some(user.id.to_owned());
-    let request = APIRequest::SaveUser {
-        auth_key: auth_key.to_owned(),
-        user,
-    };
-    EffectFuture::Concurrent(
-        fetch_api::<E, _, _, SuccessResponse>(&request)
-            .map_err(CtxError::from)
-            .and_then(|result| match result {
-                APIResult::Ok(result) => future::ok(result),
-                APIResult::Err(error) => future::err(CtxError::from(error)),
-            })
-            .map(move |result| match result {
-                Ok(_) => Msg::Event(Event::UserPushedToAPI { uid }),
-                Err(error) => Msg::Event(Event::Error {
-                    error,
-                    source: Box::new(Event::UserPushedToAPI { uid }),
-                }),
-            })
-            .boxed_env(),
+    push_to_api::<E>(
+        OutboxRequest::SaveUser {
+            user,
+            mtime: E::now(),
+        },
+        auth_key,
     )
-    .into()
 }
 
 fn pull_addons_from_api<E: Env + 'static>(auth_key: &AuthKey) -> Effect {
@@ -478,7 +561,7 @@ fn pull_addons_from_api<E: Env + 'static>(auth_key: &AuthKey) -> Effect {
 
 fn push_profile_to_storage<E: Env + 'static>(profile: &Profile) -> Effect {
     EffectFuture::Sequential(
-        E::set_storage(PROFILE_STORAGE_KEY, Some(profile))
+        E::set_encrypted_storage(PROFILE_STORAGE_KEY, Some(profile))
             .map(enclose!((profile.uid() => uid) move |result| match result {
                 Ok(_) => Msg::Event(Event::ProfilePushedToStorage { uid }),
                 Err(error) => Msg::Event(Event::Error {
@@ -491,6 +574,27 @@ fn push_profile_to_storage<E: Env + 'static>(profile: &Profile) -> Effect {
     .into()
 }
 
+/// Stores the smart lists under the keys of their account, see [`account_storage_key`].
+fn push_account_smart_lists_to_storage<E: Env + 'static>(profile: &Profile) -> Effect {
+    let uid = profile.uid();
+    let smart_lists = (!profile.smart_lists.is_empty()).then_some(&profile.smart_lists);
+    EffectFuture::Sequential(
+        E::set_storage(
+            &account_storage_key(SMART_LISTS_STORAGE_KEY, &uid),
+            smart_lists,
+        )
+        .map(move |result| match result {
+            Ok(_) => Msg::Event(Event::ProfilePushedToStorage { uid }),
+            Err(error) => Msg::Event(Event::Error {
+                error: CtxError::from(error),
+                source: Box::new(Event::ProfilePushedToStorage { uid }),
+            }),
+        })
+        .boxed_env(),
+    )
+    .into()
+}
+
 fn addon_upgrade_error_effects(addon: &Descriptor, error: OtherError) -> Effects {
     addon_action_error_effects(
         error,
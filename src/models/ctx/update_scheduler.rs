@@ -0,0 +1,96 @@
+use crate::models::ctx::CtxStatus;
+use crate::runtime::msg::{Action, ActionCtx, Internal, Msg};
+use crate::runtime::{Effects, Env};
+use crate::types::api::APIRequest;
+use crate::types::profile::Profile;
+use crate::types::scheduler::{Scheduler, SchedulerJob};
+
+pub fn update_scheduler<E: Env + 'static>(
+    scheduler: &mut Scheduler,
+    profile: &Profile,
+    status: &CtxStatus,
+    msg: &Msg,
+) -> Effects {
+    match msg {
+        Msg::Action(Action::Ctx(ActionCtx::SchedulerTick)) => {
+            let due_jobs = scheduler.start_due_jobs(E::now(), profile.auth_key().is_some());
+            if due_jobs.is_empty() {
+                Effects::none().unchanged()
+            } else {
+                Effects::msgs(
+                    due_jobs
+                        .into_iter()
+                        .map(|job| Msg::Internal(job.into()))
+                        .collect(),
+                )
+            }
+        }
+        Msg::Internal(Internal::ProfileChanged) => {
+            if scheduler.update_intervals(&profile.settings) {
+                Effects::none()
+            } else {
+                Effects::none().unchanged()
+            }
+        }
+        Msg::Action(Action::Ctx(ActionCtx::Logout)) | Msg::Internal(Internal::Logout) => {
+            scheduler.reset();
+            Effects::none()
+        }
+        Msg::Internal(Internal::CtxAuthResult(auth_request, Ok(_))) => match status {
+            CtxStatus::Loading(loading_auth_request) if loading_auth_request == auth_request => {
+                // authentication already pulls the user, the addons and the library
+                let now = E::now();
+                scheduler.reset();
+                [
+                    SchedulerJob::SyncLibraryWithAPI,
+                    SchedulerJob::PullAddonsFromAPI,
+                    SchedulerJob::PullUserFromAPI,
+                ]
+                .iter()
+                .filter_map(|job| scheduler.jobs.get_mut(job))
+                .for_each(|job_state| job_state.finish(now, true));
+                Effects::none()
+            }
+            _ => Effects::none().unchanged(),
+        },
+        Msg::Internal(Internal::LibrarySyncPlanResult(_, result)) => {
+            finish_job::<E>(scheduler, SchedulerJob::SyncLibraryWithAPI, result.is_ok())
+        }
+        Msg::Internal(Internal::AddonsAPIResult(APIRequest::AddonCollectionGet { .. }, result)) => {
+            finish_job::<E>(scheduler, SchedulerJob::PullAddonsFromAPI, result.is_ok())
+        }
+        // without a user, addons are upgraded locally and there is no result to wait for
+        Msg::Internal(Internal::PullAddonsFromAPI) if profile.auth_key().is_none() => {
+            finish_job::<E>(scheduler, SchedulerJob::PullAddonsFromAPI, true)
+        }
+        Msg::Internal(Internal::UserAPIResult(APIRequest::GetUser { .. }, result)) => {
+            finish_job::<E>(scheduler, SchedulerJob::PullUserFromAPI, result.is_ok())
+        }
+        // notifications are pulled from many addons and there is no single result to wait for
+        Msg::Internal(Internal::PullNotifications) => {
+            finish_job::<E>(scheduler, SchedulerJob::PullNotifications, true)
+        }
+        // failed pushes are kept in the outbox until the next run
+        Msg::Internal(Internal::FlushOutbox) => {
+            finish_job::<E>(scheduler, SchedulerJob::FlushOutbox, true)
+        }
+        Msg::Internal(Internal::GetModalResult(_, result)) => {
+            finish_job::<E>(scheduler, SchedulerJob::GetEvents, result.is_ok())
+        }
+        _ => Effects::none().unchanged(),
+    }
+}
+
+fn finish_job<E: Env + 'static>(
+    scheduler: &mut Scheduler,
+    job: SchedulerJob,
+    success: bool,
+) -> Effects {
+    match scheduler.jobs.get_mut(&job) {
+        Some(job_state) => {
+            job_state.finish(E::now(), success);
+            Effects::none()
+        }
+        _ => Effects::none().unchanged(),
+    }
+}
@@ -22,9 +22,27 @@ pub fn update_search_history<E: Env + 'static>(
             search_history.items.clear();
             Effects::msg(Msg::Internal(Internal::SearchHistoryChanged))
         }
-        Msg::Internal(Internal::CatalogsWithExtraSearch { query }) => {
-            search_history.items.insert(query.to_owned(), E::now());
-            Effects::msg(Msg::Internal(Internal::SearchHistoryChanged))
+        Msg::Action(Action::Ctx(ActionCtx::RemoveSearchHistoryItem(query))) => {
+            match search_history.items.remove(query) {
+                Some(_) => Effects::msg(Msg::Internal(Internal::SearchHistoryChanged)),
+                _ => Effects::none().unchanged(),
+            }
+        }
+        Msg::Action(Action::Ctx(ActionCtx::PauseSearchHistory(paused))) => {
+            if search_history.paused != *paused {
+                search_history.paused = *paused;
+                Effects::msg(Msg::Internal(Internal::SearchHistoryChanged))
+            } else {
+                Effects::none().unchanged()
+            }
+        }
+        Msg::Internal(Internal::CatalogsWithExtraSearch { query })
+        | Msg::Internal(Internal::GlobalSearch { query }) => {
+            if search_history.record(query, E::now()) {
+                Effects::msg(Msg::Internal(Internal::SearchHistoryChanged))
+            } else {
+                Effects::none().unchanged()
+            }
         }
         Msg::Internal(Internal::CtxAuthResult(auth_request, result)) => match (status, result) {
             (CtxStatus::Loading(loading_auth_request), Ok(CtxAuthResponse { auth, .. }))
@@ -104,7 +104,7 @@ pub fn update_streams<E: Env + 'static>(
 
 fn push_streams_to_storage<E: Env + 'static>(streams: &StreamsBucket) -> Effect {
     EffectFuture::Sequential(
-        E::set_storage(STREAMS_STORAGE_KEY, Some(&streams))
+        E::set_encrypted_storage(STREAMS_STORAGE_KEY, Some(&streams))
             .map(enclose!((streams.uid => uid) move |result| match result {
                 Ok(_) => Msg::Event(Event::StreamsPushedToStorage { uid }),
                 Err(error) => Msg::Event(Event::Error {
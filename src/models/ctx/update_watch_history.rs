@@ -0,0 +1,59 @@
+use enclose::enclose;
+use futures::FutureExt;
+
+use crate::constants::WATCH_HISTORY_STORAGE_KEY;
+use crate::models::ctx::{CtxError, CtxStatus};
+use crate::runtime::msg::{Action, ActionCtx, CtxAuthResponse, Event, Internal, Msg};
+use crate::runtime::{Effect, EffectFuture, Effects, Env, EnvFutureExt};
+use crate::types::watch_history::WatchHistoryBucket;
+
+pub fn update_watch_history<E: Env + 'static>(
+    watch_history: &mut WatchHistoryBucket,
+    status: &CtxStatus,
+    msg: &Msg,
+) -> Effects {
+    match msg {
+        Msg::Action(Action::Ctx(ActionCtx::Logout)) | Msg::Internal(Internal::Logout) => {
+            *watch_history = WatchHistoryBucket::default();
+            Effects::msg(Msg::Internal(Internal::WatchHistoryChanged))
+        }
+        Msg::Action(Action::Ctx(ActionCtx::ClearWatchHistory)) => {
+            watch_history.items.clear();
+            Effects::msg(Msg::Internal(Internal::WatchHistoryChanged))
+        }
+        Msg::Internal(Internal::WatchHistoryItemRecorded(watch_history_item)) => {
+            watch_history.push(watch_history_item.to_owned());
+            Effects::msg(Msg::Internal(Internal::WatchHistoryChanged))
+        }
+        Msg::Internal(Internal::CtxAuthResult(auth_request, result)) => match (status, result) {
+            (CtxStatus::Loading(loading_auth_request), Ok(CtxAuthResponse { auth, .. }))
+                if loading_auth_request == auth_request =>
+            {
+                *watch_history = WatchHistoryBucket::new(Some(auth.user.id.to_owned()));
+                Effects::msg(Msg::Internal(Internal::WatchHistoryChanged))
+            }
+            _ => Effects::none().unchanged(),
+        },
+        Msg::Internal(Internal::WatchHistoryChanged) => {
+            Effects::one(push_watch_history_to_storage::<E>(watch_history)).unchanged()
+        }
+        _ => Effects::none().unchanged(),
+    }
+}
+
+fn push_watch_history_to_storage<E: Env + 'static>(watch_history: &WatchHistoryBucket) -> Effect {
+    EffectFuture::Sequential(
+        E::set_encrypted_storage(WATCH_HISTORY_STORAGE_KEY, Some(&watch_history))
+            .map(
+                enclose!((watch_history.uid => uid) move |result| match result {
+                    Ok(_) => Msg::Event(Event::WatchHistoryPushedToStorage { uid }),
+                    Err(error) => Msg::Event(Event::Error {
+                        error: CtxError::from(error),
+                        source: Box::new(Event::WatchHistoryPushedToStorage { uid }),
+                    })
+                }),
+            )
+            .boxed_env(),
+    )
+    .into()
+}
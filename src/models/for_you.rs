@@ -0,0 +1,437 @@
+//! "For You" recommendations computed on-device
+//!
+//! The scoring is deterministic and only uses the user's library and the installed catalogs:
+//!
+//! 1. The most relevant library items are picked as seeds, each one weighted by:
+//!     - engagement: `0.5` plus up to `0.5` for the watched ratio (`1.0` once watched)
+//!     - recency: halves every [`FOR_YOU_RECENCY_HALF_LIFE`] days since it was last watched
+//!     - the user's rating: from `0.0` for [`MIN_RATING`] to `2.0` for [`MAX_RATING`], `1.0` if unrated
+//!     - dropped items ([`WatchStatus::Dropped`]) are never used as seeds
+//! 2. The meta items of the seeds are fetched and their genres and cast are summed up
+//!    by the seed weights into a taste profile, normalized to `0.0..=1.0`.
+//! 3. Every catalog item which is not in the library is scored by the average affinity
+//!    of its genres ([`GENRES_WEIGHT`]) and the summed affinity of its cast capped
+//!    at `1.0` ([`CAST_WEIGHT`]).
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    constants::{
+        CATALOG_PREVIEW_SIZE, FOR_YOU_RECENCY_HALF_LIFE, FOR_YOU_SEEDS_COUNT, META_RESOURCE_NAME,
+    },
+    models::{
+        common::{
+            eq_update, is_allowed_by, resource_update, resources_update_with_vector_content,
+            Loadable, ResourceAction, ResourceLoadable, ResourcesAction,
+        },
+        ctx::Ctx,
+    },
+    runtime::{
+        msg::{Action, ActionLoad, Internal, Msg},
+        Effects, Env, UpdateWithCtx,
+    },
+    types::{
+        addon::{AggrRequest, ResourcePath, ResourceRequest},
+        library::{LibraryBucket, LibraryItem, WatchStatus, MAX_RATING, MIN_RATING},
+        profile::{ParentalControls, Profile},
+        resource::{MetaItem, MetaItemPreview},
+    },
+};
+
+/// The weight of the genres in the score of a recommendation
+pub const GENRES_WEIGHT: f64 = 0.7;
+/// The weight of the cast in the score of a recommendation
+pub const CAST_WEIGHT: f64 = 0.3;
+/// The maximum number of reasons given for a recommendation
+const REASONS_COUNT: usize = 3;
+
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Selected {
+    /// Only recommend items of this type
+    pub r#type: Option<String>,
+}
+
+/// A genre or a cast member shared with the taste profile
+#[derive(Clone, PartialEq, Eq, Serialize, Debug)]
+#[serde(tag = "type", content = "name", rename_all = "camelCase")]
+pub enum Reason {
+    Genre(String),
+    Cast(String),
+}
+
+#[derive(Clone, PartialEq, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Recommendation {
+    pub meta_item: MetaItemPreview,
+    /// The catalog request the item was found in
+    pub request: ResourceRequest,
+    /// From `0.0` to `1.0`
+    pub score: f64,
+    /// The strongest matches with the taste profile first
+    pub reasons: Vec<Reason>,
+}
+
+#[derive(Default, Clone, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ForYou {
+    pub selected: Option<Selected>,
+    /// The meta items of the library items the taste profile is built from
+    pub seeds: Vec<ResourceLoadable<MetaItem>>,
+    /// The catalogs the recommendations are picked from
+    pub catalogs: Vec<ResourceLoadable<Vec<MetaItemPreview>>>,
+    pub items: Vec<Recommendation>,
+}
+
+impl<E: Env + 'static> UpdateWithCtx<E> for ForYou {
+    fn update(&mut self, msg: &Msg, ctx: &Ctx) -> Effects {
+        match msg {
+            Msg::Action(Action::Load(ActionLoad::ForYou(selected))) => {
+                let selected_effects = eq_update(&mut self.selected, Some(selected.to_owned()));
+                let seeds_effects = seeds_update::<E>(
+                    &mut self.seeds,
+                    &self.selected,
+                    &ctx.library,
+                    &ctx.profile,
+                    ctx.parental_controls(),
+                );
+                let catalogs_effects =
+                    catalogs_update::<E>(&mut self.catalogs, &self.selected, &ctx.profile);
+                let items_effects = items_update::<E>(
+                    &mut self.items,
+                    &self.selected,
+                    &self.seeds,
+                    &self.catalogs,
+                    &ctx.library,
+                    ctx.parental_controls(),
+                );
+                selected_effects
+                    .join(seeds_effects)
+                    .join(catalogs_effects)
+                    .join(items_effects)
+            }
+            Msg::Action(Action::Unload) => {
+                let selected_effects = eq_update(&mut self.selected, None);
+                let seeds_effects = eq_update(&mut self.seeds, vec![]);
+                let catalogs_effects = eq_update(&mut self.catalogs, vec![]);
+                let items_effects = eq_update(&mut self.items, vec![]);
+                selected_effects
+                    .join(seeds_effects)
+                    .join(catalogs_effects)
+                    .join(items_effects)
+            }
+            Msg::Internal(Internal::ResourceRequestResult(request, result)) => {
+                let seeds_effects =
+                    match self.seeds.iter_mut().find(|seed| seed.request == *request) {
+                        Some(seed) => resource_update::<E, _>(
+                            seed,
+                            ResourceAction::ResourceRequestResult { request, result },
+                        ),
+                        None => Effects::none().unchanged(),
+                    };
+                let catalogs_effects = resources_update_with_vector_content::<E, _>(
+                    &mut self.catalogs,
+                    ResourcesAction::ResourceRequestResult { request, result },
+                );
+                if seeds_effects.has_changed || catalogs_effects.has_changed {
+                    let items_effects = items_update::<E>(
+                        &mut self.items,
+                        &self.selected,
+                        &self.seeds,
+                        &self.catalogs,
+                        &ctx.library,
+                        ctx.parental_controls(),
+                    );
+                    seeds_effects.join(catalogs_effects).join(items_effects)
+                } else {
+                    seeds_effects.join(catalogs_effects)
+                }
+            }
+            // the seed weights and the items already in the library depend on it
+            Msg::Internal(Internal::LibraryChanged(_))
+            | Msg::Internal(Internal::ParentalControlsChanged) => items_update::<E>(
+                &mut self.items,
+                &self.selected,
+                &self.seeds,
+                &self.catalogs,
+                &ctx.library,
+                ctx.parental_controls(),
+            ),
+            _ => Effects::none().unchanged(),
+        }
+    }
+}
+
+/// The weight of the library item in the taste profile, `0.0` if it should not be used
+pub fn seed_weight(library_item: &LibraryItem, now: DateTime<Utc>) -> f64 {
+    if library_item.removed
+        || library_item.temp
+        || library_item.state.watch_status() == Some(WatchStatus::Dropped)
+    {
+        return 0.0;
+    }
+    let engagement = if library_item.watched() {
+        1.0
+    } else {
+        0.5 + library_item.progress().min(100.0) / 200.0
+    };
+    let last_watched = library_item
+        .state
+        .last_watched
+        .unwrap_or(library_item.mtime);
+    let days = (now - last_watched).num_seconds().max(0) as f64 / 86_400.0;
+    let recency = 0.5_f64.powf(days / FOR_YOU_RECENCY_HALF_LIFE);
+    let rating = library_item.state.rating().map_or(1.0, |rating| {
+        f64::from(rating.clamp(MIN_RATING, MAX_RATING) - MIN_RATING)
+            / f64::from(MAX_RATING - MIN_RATING)
+            * 2.0
+    });
+    engagement * recency * rating
+}
+
+fn seeds<'a>(
+    selected: &'a Selected,
+    library: &'a LibraryBucket,
+    parental_controls: Option<&'a ParentalControls>,
+    now: DateTime<Utc>,
+) -> impl Iterator<Item = (&'a LibraryItem, f64)> + 'a {
+    library
+        .items
+        .values()
+        .filter(move |library_item| match &selected.r#type {
+            Some(r#type) => library_item.r#type == *r#type,
+            None => true,
+        })
+        .filter(move |library_item| is_allowed_by(*library_item, parental_controls))
+        .map(move |library_item| (library_item, seed_weight(library_item, now)))
+        .filter(|(_, weight)| *weight > 0.0)
+        .sorted_by(|(a, a_weight), (b, b_weight)| {
+            b_weight.total_cmp(a_weight).then(a.id.cmp(&b.id))
+        })
+        .take(FOR_YOU_SEEDS_COUNT)
+}
+
+fn seeds_update<E: Env + 'static>(
+    seeds: &mut Vec<ResourceLoadable<MetaItem>>,
+    selected: &Option<Selected>,
+    library: &LibraryBucket,
+    profile: &Profile,
+    parental_controls: Option<&ParentalControls>,
+) -> Effects {
+    let requests = match selected {
+        Some(selected) => self::seeds(selected, library, parental_controls, E::now())
+            .filter_map(|(library_item, _)| {
+                // only the first addon which provides the meta item is requested
+                AggrRequest::AllOfResource(ResourcePath::without_extra(
+                    META_RESOURCE_NAME,
+                    &library_item.r#type,
+                    &library_item.id,
+                ))
+                .plan(&profile.addons)
+                .into_iter()
+                .map(|(_, request)| request)
+                .next()
+            })
+            .collect::<Vec<_>>(),
+        None => vec![],
+    };
+    let mut next_seeds = requests
+        .into_iter()
+        .map(|request| {
+            seeds
+                .iter()
+                .find(|seed| seed.request == request && seed.content.is_some())
+                .cloned()
+                .unwrap_or(ResourceLoadable {
+                    request,
+                    content: None,
+                })
+        })
+        .collect::<Vec<_>>();
+    let effects = next_seeds
+        .iter_mut()
+        .filter(|seed| seed.content.is_none())
+        .map(|seed| {
+            let request = seed.request.to_owned();
+            resource_update::<E, _>(
+                seed,
+                ResourceAction::ResourceRequested { request: &request },
+            )
+        })
+        .fold(Effects::none().unchanged(), |effects, seed_effects| {
+            effects.join(seed_effects.unchanged())
+        });
+    effects.join(eq_update(seeds, next_seeds))
+}
+
+fn catalogs_update<E: Env + 'static>(
+    catalogs: &mut Vec<ResourceLoadable<Vec<MetaItemPreview>>>,
+    selected: &Option<Selected>,
+    profile: &Profile,
+) -> Effects {
+    match selected {
+        Some(selected) => resources_update_with_vector_content::<E, _>(
+            catalogs,
+            ResourcesAction::request(
+                &AggrRequest::AllCatalogs {
+                    extra: &vec![],
+                    r#type: &selected.r#type,
+                },
+                &profile.addons,
+            ),
+        ),
+        None => eq_update(catalogs, vec![]),
+    }
+}
+
+fn items_update<E: Env + 'static>(
+    items: &mut Vec<Recommendation>,
+    selected: &Option<Selected>,
+    seeds: &[ResourceLoadable<MetaItem>],
+    catalogs: &[ResourceLoadable<Vec<MetaItemPreview>>],
+    library: &LibraryBucket,
+    parental_controls: Option<&ParentalControls>,
+) -> Effects {
+    let next_items = match selected {
+        Some(selected) => {
+            let weights = self::seeds(selected, library, parental_controls, E::now())
+                .map(|(library_item, weight)| (library_item.id.as_str(), weight))
+                .collect::<HashMap<_, _>>();
+            let profile = TasteProfile::new(
+                seeds
+                    .iter()
+                    .filter_map(|seed| match &seed.content {
+                        Some(Loadable::Ready(meta_item)) => Some(&meta_item.preview),
+                        _ => None,
+                    })
+                    .filter_map(|meta_item| {
+                        weights
+                            .get(meta_item.id.as_str())
+                            .map(|weight| (meta_item, *weight))
+                    }),
+            );
+            catalogs
+                .iter()
+                .filter_map(|catalog| match &catalog.content {
+                    Some(Loadable::Ready(meta_items)) => Some(
+                        meta_items
+                            .iter()
+                            .map(move |meta_item| (meta_item, &catalog.request)),
+                    ),
+                    _ => None,
+                })
+                .flatten()
+                .unique_by(|(meta_item, _)| &meta_item.id)
+                .filter(|(meta_item, _)| match &selected.r#type {
+                    Some(r#type) => meta_item.r#type == *r#type,
+                    None => true,
+                })
+                .filter(|(meta_item, _)| {
+                    library
+                        .items
+                        .get(&meta_item.id)
+                        .map_or(true, |library_item| library_item.removed)
+                })
+                .filter(|(meta_item, _)| is_allowed_by(*meta_item, parental_controls))
+                .filter_map(|(meta_item, request)| profile.recommend(meta_item, request))
+                // the sorting is stable, equally scored items keep the catalogs order
+                .sorted_by(|a, b| b.score.total_cmp(&a.score))
+                .take(CATALOG_PREVIEW_SIZE)
+                .collect()
+        }
+        None => vec![],
+    };
+    eq_update(items, next_items)
+}
+
+/// The normalized affinities to the genres and the cast of the seeds
+struct TasteProfile {
+    genres: HashMap<String, f64>,
+    cast: HashMap<String, f64>,
+}
+
+impl TasteProfile {
+    fn new<'a>(seeds: impl Iterator<Item = (&'a MetaItemPreview, f64)>) -> Self {
+        let mut genres = HashMap::<String, f64>::new();
+        let mut cast = HashMap::<String, f64>::new();
+        let mut total_weight = 0.0;
+        for (meta_item, weight) in seeds {
+            total_weight += weight;
+            for genre in meta_item.genres().map(str::to_lowercase).unique() {
+                *genres.entry(genre).or_default() += weight;
+            }
+            for name in meta_item.cast().map(str::to_lowercase).unique() {
+                *cast.entry(name).or_default() += weight;
+            }
+        }
+        if total_weight > 0.0 {
+            genres
+                .values_mut()
+                .chain(cast.values_mut())
+                .for_each(|affinity| *affinity /= total_weight);
+        }
+        TasteProfile { genres, cast }
+    }
+    fn recommend(
+        &self,
+        meta_item: &MetaItemPreview,
+        request: &ResourceRequest,
+    ) -> Option<Recommendation> {
+        let genres = meta_item
+            .genres()
+            .unique_by(|genre| genre.to_lowercase())
+            .map(|genre| (genre, self.genres.get(&genre.to_lowercase()).copied()))
+            .collect::<Vec<_>>();
+        let cast = meta_item
+            .cast()
+            .unique_by(|name| name.to_lowercase())
+            .filter_map(|name| {
+                self.cast
+                    .get(&name.to_lowercase())
+                    .map(|affinity| (name, *affinity))
+            })
+            .collect::<Vec<_>>();
+        let genres_score = if genres.is_empty() {
+            0.0
+        } else {
+            genres
+                .iter()
+                .filter_map(|(_, affinity)| *affinity)
+                .sum::<f64>()
+                / genres.len() as f64
+        };
+        let cast_score = cast
+            .iter()
+            .map(|(_, affinity)| affinity)
+            .sum::<f64>()
+            .min(1.0);
+        let score = GENRES_WEIGHT * genres_score + CAST_WEIGHT * cast_score;
+        if score <= 0.0 {
+            return None;
+        }
+        let reasons = genres
+            .into_iter()
+            .filter_map(|(genre, affinity)| {
+                affinity.map(|affinity| (Reason::Genre(genre.to_owned()), GENRES_WEIGHT * affinity))
+            })
+            .chain(
+                cast.into_iter().map(|(name, affinity)| {
+                    (Reason::Cast(name.to_owned()), CAST_WEIGHT * affinity)
+                }),
+            )
+            .sorted_by(|(_, a), (_, b)| b.total_cmp(a))
+            .map(|(reason, _)| reason)
+            .take(REASONS_COUNT)
+            .collect();
+        Some(Recommendation {
+            meta_item: meta_item.to_owned(),
+            request: request.to_owned(),
+            score,
+            reasons,
+        })
+    }
+}
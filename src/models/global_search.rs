@@ -0,0 +1,325 @@
+//! Search over the local feeds, the library and the addon catalogs
+//!
+//! The local results are queried from the [`LocalSearch`] model, sharing its feeds and index
+//! with the autocompletion, while the addon catalogs are requested with the `search` extra.
+//! The results are de-duplicated by the meta id and re-ranked every time an addon answers.
+//!
+//! [`LocalSearch`]: crate::models::local_search::LocalSearch
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    constants::{GLOBAL_SEARCH_LOCAL_ITEMS_COUNT, IMDB_LINK_CATEGORY},
+    models::{
+        common::{
+            eq_update, fuzzy_matches, is_allowed_by, normalize_search_text,
+            resources_update_with_vector_content, Loadable, ResourceLoadable, ResourcesAction,
+            SearchQuery,
+        },
+        ctx::Ctx,
+        local_search::{Searchable, SearchableSource},
+    },
+    runtime::{
+        msg::{Action, ActionLoad, Internal, Msg},
+        Effects, Env, UpdateWithCtx,
+    },
+    types::{
+        addon::{AggrRequest, ExtraValue},
+        library::LibraryBucket,
+        profile::ParentalControls,
+        resource::MetaItemPreview,
+    },
+};
+
+#[derive(Default, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Selected {
+    pub query: String,
+}
+
+#[derive(Clone, PartialEq, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GlobalSearchItem {
+    pub searchable: Searchable,
+    /// All the sources the item was found in, in the order they answered
+    pub sources: Vec<SearchableSource>,
+}
+
+#[derive(Default, Clone, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GlobalSearch {
+    pub selected: Option<Selected>,
+    /// The results of the `LocalSearch` for the selected query
+    #[serde(skip)]
+    pub local_items: Vec<Searchable>,
+    /// The addon catalogs supporting the `search` extra
+    pub catalogs: Vec<ResourceLoadable<Vec<MetaItemPreview>>>,
+    /// The ranked results of all the sources
+    pub items: Vec<GlobalSearchItem>,
+}
+
+impl<E: Env + 'static> UpdateWithCtx<E> for GlobalSearch {
+    fn update(&mut self, msg: &Msg, ctx: &Ctx) -> Effects {
+        match msg {
+            Msg::Action(Action::Load(ActionLoad::GlobalSearch(selected))) => {
+                let selected_effects = eq_update(&mut self.selected, Some(selected.to_owned()));
+                // the results of the previous query are not shown for the new one
+                let local_items_effects = if selected_effects.has_changed {
+                    eq_update(&mut self.local_items, vec![]).unchanged()
+                } else {
+                    Effects::none().unchanged()
+                };
+                let local_search_effects = local_search_query(&self.selected);
+                let catalogs_effects =
+                    catalogs_update::<E>(&mut self.catalogs, &self.selected, ctx);
+                let items_effects = items_update(
+                    &mut self.items,
+                    &self.selected,
+                    &self.local_items,
+                    &self.catalogs,
+                    &ctx.library,
+                    ctx.parental_controls(),
+                );
+                let search_history_effects = match &self.selected {
+                    Some(selected)
+                        if selected_effects.has_changed && !selected.query.trim().is_empty() =>
+                    {
+                        Effects::msg(Msg::Internal(Internal::GlobalSearch {
+                            query: selected.query.to_owned(),
+                        }))
+                        .unchanged()
+                    }
+                    _ => Effects::none().unchanged(),
+                };
+                selected_effects
+                    .join(local_items_effects)
+                    .join(local_search_effects)
+                    .join(catalogs_effects)
+                    .join(items_effects)
+                    .join(search_history_effects)
+            }
+            Msg::Action(Action::Unload) => {
+                let selected_effects = eq_update(&mut self.selected, None);
+                let local_items_effects = eq_update(&mut self.local_items, vec![]).unchanged();
+                let catalogs_effects = eq_update(&mut self.catalogs, vec![]);
+                let items_effects = eq_update(&mut self.items, vec![]);
+                selected_effects
+                    .join(local_items_effects)
+                    .join(catalogs_effects)
+                    .join(items_effects)
+            }
+            Msg::Internal(Internal::ResourceRequestResult(request, result)) => {
+                let catalogs_effects = resources_update_with_vector_content::<E, _>(
+                    &mut self.catalogs,
+                    ResourcesAction::ResourceRequestResult { request, result },
+                );
+                // the partial results are shown as every addon answers
+                let items_effects = if catalogs_effects.has_changed {
+                    items_update(
+                        &mut self.items,
+                        &self.selected,
+                        &self.local_items,
+                        &self.catalogs,
+                        &ctx.library,
+                        ctx.parental_controls(),
+                    )
+                } else {
+                    Effects::none().unchanged()
+                };
+                catalogs_effects.join(items_effects)
+            }
+            Msg::Internal(Internal::LocalSearchQueryResult { query, results })
+                if self
+                    .selected
+                    .as_ref()
+                    .map_or(false, |selected| selected.query == *query) =>
+            {
+                let local_items_effects = eq_update(&mut self.local_items, results.to_owned());
+                let items_effects = if local_items_effects.has_changed {
+                    items_update(
+                        &mut self.items,
+                        &self.selected,
+                        &self.local_items,
+                        &self.catalogs,
+                        &ctx.library,
+                        ctx.parental_controls(),
+                    )
+                } else {
+                    Effects::none().unchanged()
+                };
+                local_items_effects.unchanged().join(items_effects)
+            }
+            Msg::Internal(Internal::LocalSearchStorageResult(_))
+            | Msg::Internal(Internal::LoadLocalSearchResult(..)) => {
+                // the feeds of the local search have changed
+                local_search_query(&self.selected)
+            }
+            Msg::Internal(Internal::LibraryChanged(_))
+            | Msg::Internal(Internal::ParentalControlsChanged) => {
+                // the library ranks the items and the parental controls filter the catalogs,
+                // while the local items are queried again as they depend on both
+                let items_effects = items_update(
+                    &mut self.items,
+                    &self.selected,
+                    &self.local_items,
+                    &self.catalogs,
+                    &ctx.library,
+                    ctx.parental_controls(),
+                );
+                local_search_query(&self.selected).join(items_effects)
+            }
+            _ => Effects::none().unchanged(),
+        }
+    }
+}
+
+/// Queries the `LocalSearch` model, which answers with [`Internal::LocalSearchQueryResult`]
+fn local_search_query(selected: &Option<Selected>) -> Effects {
+    match selected {
+        Some(selected) => Effects::msg(Msg::Internal(Internal::LocalSearchQuery {
+            query: selected.query.to_owned(),
+            max_results: GLOBAL_SEARCH_LOCAL_ITEMS_COUNT,
+        }))
+        .unchanged(),
+        None => Effects::none().unchanged(),
+    }
+}
+
+fn catalogs_update<E: Env + 'static>(
+    catalogs: &mut Vec<ResourceLoadable<Vec<MetaItemPreview>>>,
+    selected: &Option<Selected>,
+    ctx: &Ctx,
+) -> Effects {
+    match selected {
+        Some(selected) if !selected.query.trim().is_empty() => {
+            let extra = vec![ExtraValue {
+                name: "search".to_owned(),
+                value: selected.query.to_owned(),
+            }];
+            resources_update_with_vector_content::<E, _>(
+                catalogs,
+                ResourcesAction::request(
+                    &AggrRequest::AllCatalogs {
+                        extra: &extra,
+                        r#type: &None,
+                    },
+                    &ctx.profile.addons,
+                ),
+            )
+        }
+        _ => eq_update(catalogs, vec![]),
+    }
+}
+
+fn items_update(
+    items: &mut Vec<GlobalSearchItem>,
+    selected: &Option<Selected>,
+    local_items: &[Searchable],
+    catalogs: &[ResourceLoadable<Vec<MetaItemPreview>>],
+    library: &LibraryBucket,
+    parental_controls: Option<&ParentalControls>,
+) -> Effects {
+    let query = match selected {
+        Some(selected) => SearchQuery::parse(&selected.query),
+        None => return eq_update(items, vec![]),
+    };
+    let local_items = local_items
+        .iter()
+        .map(|searchable| (searchable.to_owned(), searchable.source.to_owned()));
+    let catalogs_items = catalogs.iter().flat_map(|catalog| {
+        catalog
+            .content
+            .iter()
+            .filter_map(Loadable::ready)
+            .flatten()
+            .filter(move |meta_item| is_allowed_by(*meta_item, parental_controls))
+            .map(move |meta_item| {
+                let source = SearchableSource::Addon {
+                    transport_url: catalog.request.base.to_owned(),
+                };
+                (searchable_from_meta_item(meta_item, &source), source)
+            })
+    });
+
+    let mut indexes = HashMap::new();
+    let mut next_items: Vec<GlobalSearchItem> = vec![];
+    for (searchable, source) in local_items.chain(catalogs_items) {
+        if !query.filters_match(&searchable.r#type, searchable.release_info.as_deref()) {
+            continue;
+        }
+        match indexes.get(&searchable.id) {
+            Some(index) => {
+                let item: &mut GlobalSearchItem = &mut next_items[*index];
+                if !item.sources.contains(&source) {
+                    item.sources.push(source);
+                }
+            }
+            None => {
+                indexes.insert(searchable.id.to_owned(), next_items.len());
+                next_items.push(GlobalSearchItem {
+                    searchable,
+                    sources: vec![source],
+                });
+            }
+        }
+    }
+
+    // the sort is stable, so equally ranked items keep the order they answered in
+    next_items.sort_by_cached_key(|item| {
+        std::cmp::Reverse((
+            relevance(&query, &item.searchable),
+            library
+                .items
+                .get(&item.searchable.id)
+                .map(|library_item| !library_item.removed)
+                .unwrap_or_default(),
+            item.sources.len(),
+        ))
+    });
+
+    eq_update(items, next_items)
+}
+
+/// How well the titles of the item match the text of the query,
+/// from an exact match (`3`) to no match at all (`0`)
+fn relevance(query: &SearchQuery, searchable: &Searchable) -> u8 {
+    std::iter::once(&searchable.name)
+        .chain(searchable.aka.iter())
+        .map(|title| normalize_search_text(title))
+        .map(|title| {
+            if title == query.text {
+                3
+            } else if title.starts_with(&query.text) {
+                2
+            } else if fuzzy_matches(&query.text, &title) {
+                1
+            } else {
+                0
+            }
+        })
+        .max()
+        .unwrap_or_default()
+}
+
+fn searchable_from_meta_item(meta_item: &MetaItemPreview, source: &SearchableSource) -> Searchable {
+    Searchable {
+        id: meta_item.id.to_owned(),
+        name: meta_item.name.to_owned(),
+        r#type: meta_item.r#type.to_owned(),
+        poster: meta_item.poster.to_owned(),
+        imdb_rating: meta_item
+            .links
+            .iter()
+            .find(|link| link.category == IMDB_LINK_CATEGORY)
+            .and_then(|link| link.name.parse().ok()),
+        popularity: None,
+        release_info: meta_item.release_info.to_owned(),
+        genres: meta_item.genres().map(ToOwned::to_owned).collect(),
+        aka: vec![],
+        // the parental controls are applied to the meta item itself
+        certification: None,
+        source: source.to_owned(),
+    }
+}
@@ -1,15 +1,17 @@
 use crate::constants::{CATALOG_PAGE_SIZE, TYPE_PRIORITIES};
-use crate::models::common::{compare_with_priorities, eq_update};
+use crate::models::common::{compare_with_priorities, eq_update, is_allowed_by};
 use crate::models::ctx::Ctx;
 use crate::models::library_with_filters::{LibraryFilter, Sort};
 use crate::runtime::msg::{Action, ActionLibraryByType, ActionLoad, Internal, Msg};
 use crate::runtime::{Effects, Env, UpdateWithCtx};
-use crate::types::library::{LibraryBucket, LibraryItem};
+use crate::types::library::{LibraryBucket, LibraryItem, WatchStatus};
 use crate::types::notifications::NotificationsBucket;
+use crate::types::profile::ParentalControls;
 use derivative::Derivative;
 use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::iter;
 use std::marker::PhantomData;
 use strum::IntoEnumIterator;
 
@@ -17,6 +19,9 @@ use strum::IntoEnumIterator;
 pub struct Selected {
     #[serde(default)]
     pub sort: Sort,
+    /// Only the items with this [`WatchStatus`]
+    #[serde(default)]
+    pub watch_status: Option<WatchStatus>,
 }
 
 #[derive(Clone, PartialEq, Eq, Serialize, Debug)]
@@ -25,9 +30,16 @@ pub struct SelectableSort {
     pub selected: bool,
 }
 
+#[derive(Clone, PartialEq, Eq, Serialize, Debug)]
+pub struct SelectableWatchStatus {
+    pub watch_status: Option<WatchStatus>,
+    pub selected: bool,
+}
+
 #[derive(Default, Clone, PartialEq, Eq, Serialize, Debug)]
 pub struct Selectable {
     pub sorts: Vec<SelectableSort>,
+    pub watch_statuses: Vec<SelectableWatchStatus>,
 }
 
 pub type CatalogPage = Vec<LibraryItem>;
@@ -71,6 +83,7 @@ impl<E: Env + 'static, F: LibraryFilter> UpdateWithCtx<E> for LibraryByType<F> {
                     &self.selected,
                     &ctx.library,
                     &ctx.notifications,
+                    ctx.parental_controls(),
                 );
                 selected_effects
                     .join(selectable_effects)
@@ -101,6 +114,7 @@ impl<E: Env + 'static, F: LibraryFilter> UpdateWithCtx<E> for LibraryByType<F> {
                                 &self.selected,
                                 &ctx.library,
                                 &ctx.notifications,
+                                ctx.parental_controls(),
                             );
                             catalog.push(page);
                             Effects::none()
@@ -110,12 +124,15 @@ impl<E: Env + 'static, F: LibraryFilter> UpdateWithCtx<E> for LibraryByType<F> {
                     _ => Effects::none().unchanged(),
                 }
             }
-            Msg::Internal(Internal::LibraryChanged(_)) => catalogs_update::<F>(
-                &mut self.catalogs,
-                &self.selected,
-                &ctx.library,
-                &ctx.notifications,
-            ),
+            Msg::Internal(Internal::LibraryChanged(_) | Internal::ParentalControlsChanged) => {
+                catalogs_update::<F>(
+                    &mut self.catalogs,
+                    &self.selected,
+                    &ctx.library,
+                    &ctx.notifications,
+                    ctx.parental_controls(),
+                )
+            }
             _ => Effects::none().unchanged(),
         }
     }
@@ -131,8 +148,19 @@ fn selectable_update(selectable: &mut Selectable, selected: &Option<Selected>) -
                 .unwrap_or_default(),
         })
         .collect();
+    let selectable_watch_statuses = iter::once(None)
+        .chain(WatchStatus::iter().map(Some))
+        .map(|watch_status| SelectableWatchStatus {
+            watch_status,
+            selected: selected
+                .as_ref()
+                .map(|selected| selected.watch_status == watch_status)
+                .unwrap_or_default(),
+        })
+        .collect();
     let next_selectable = Selectable {
         sorts: selectable_sorts,
+        watch_statuses: selectable_watch_statuses,
     };
     eq_update(selectable, next_selectable)
 }
@@ -142,6 +170,7 @@ fn catalogs_update<F: LibraryFilter>(
     selected: &Option<Selected>,
     library: &LibraryBucket,
     notifications: &NotificationsBucket,
+    parental_controls: Option<&ParentalControls>,
 ) -> Effects {
     let catalogs_size = catalogs.iter().fold(HashMap::new(), |mut result, catalog| {
         let r#type = catalog
@@ -158,6 +187,8 @@ fn catalogs_update<F: LibraryFilter>(
             .items
             .values()
             .filter(|library_item| F::predicate(library_item, notifications))
+            .filter(|library_item| is_allowed_by(*library_item, parental_controls))
+            .filter(|library_item| is_with_watch_status(library_item, selected.watch_status))
             .fold(
                 HashMap::<&str, Vec<LibraryItem>>::new(),
                 |mut result, library_item| {
@@ -202,13 +233,16 @@ fn next_page<F: LibraryFilter>(
     selected: &Option<Selected>,
     library: &LibraryBucket,
     notifications: &NotificationsBucket,
+    parental_controls: Option<&ParentalControls>,
 ) -> CatalogPage {
     match selected {
         Some(selected) => library
             .items
             .values()
             .filter(|library_item| F::predicate(library_item, notifications))
+            .filter(|library_item| is_allowed_by(*library_item, parental_controls))
             .filter(|library_item: &&LibraryItem| library_item.r#type == *r#type)
+            .filter(|library_item| is_with_watch_status(library_item, selected.watch_status))
             .sorted_by(|a, b| selected.sort.sort_items(a, b))
             .skip(skip)
             .take(CATALOG_PAGE_SIZE)
@@ -217,3 +251,9 @@ fn next_page<F: LibraryFilter>(
         _ => vec![],
     }
 }
+
+fn is_with_watch_status(library_item: &LibraryItem, watch_status: Option<WatchStatus>) -> bool {
+    watch_status.map_or(true, |watch_status| {
+        library_item.state.watch_status() == Some(watch_status)
+    })
+}
@@ -9,7 +9,7 @@ use strum::{EnumIter, IntoEnumIterator};
 use crate::{
     constants::{CATALOG_PAGE_SIZE, TYPE_PRIORITIES},
     models::{
-        common::{compare_with_priorities, eq_update},
+        common::{compare_with_priorities, eq_update, is_allowed_by},
         ctx::Ctx,
     },
     runtime::{
@@ -17,8 +17,9 @@ use crate::{
         Effects, Env, UpdateWithCtx,
     },
     types::{
-        library::{LibraryBucket, LibraryItem},
+        library::{LibraryBucket, LibraryItem, WatchStatus},
         notifications::NotificationsBucket,
+        profile::{ParentalControls, SmartList},
     },
 };
 
@@ -60,6 +61,10 @@ pub enum Sort {
     TimesWatched,
     Watched,
     NotWatched,
+    /// Grouped by the [`WatchStatus`], the items without a status are last
+    WatchStatus,
+    /// Highest user rating first, the unrated items are last
+    Rating,
 }
 
 impl Sort {
@@ -86,6 +91,18 @@ impl Sort {
                 // when a new item is added to the library, `last_watched` is always set to now
                 // same as `ctime`
                 .then(a.ctime.cmp(&b.ctime)),
+            Sort::WatchStatus => a
+                .state
+                .watch_status()
+                .is_none()
+                .cmp(&b.state.watch_status().is_none())
+                .then(a.state.watch_status().cmp(&b.state.watch_status()))
+                .then(b.state.last_watched.cmp(&a.state.last_watched)),
+            Sort::Rating => b
+                .state
+                .rating()
+                .cmp(&a.state.rating())
+                .then(b.state.last_watched.cmp(&a.state.last_watched)),
             Sort::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
             Sort::NameReverse => b.name.to_lowercase().cmp(&a.name.to_lowercase()),
         }
@@ -99,6 +116,15 @@ pub struct LibraryRequest {
     pub sort: Sort,
     #[serde(default)]
     pub page: LibraryRequestPage,
+    /// Only the items matching the [`SmartList`] with this id
+    #[serde(default)]
+    pub smart_list: Option<String>,
+    /// Only the items with this [`WatchStatus`]
+    #[serde(default)]
+    pub watch_status: Option<WatchStatus>,
+    /// Only the items rated by the user with at least this rating
+    #[serde(default)]
+    pub min_rating: Option<u8>,
 }
 
 #[derive(Clone, Deref, PartialEq, Eq, Serialize, Deserialize, Debug)]
@@ -129,6 +155,13 @@ pub struct SelectableSort {
     pub request: LibraryRequest,
 }
 
+#[derive(Clone, PartialEq, Eq, Serialize, Debug)]
+pub struct SelectableWatchStatus {
+    pub watch_status: Option<WatchStatus>,
+    pub selected: bool,
+    pub request: LibraryRequest,
+}
+
 #[derive(Clone, PartialEq, Eq, Serialize, Debug)]
 pub struct SelectablePage {
     pub request: LibraryRequest,
@@ -138,6 +171,7 @@ pub struct SelectablePage {
 pub struct Selectable {
     pub types: Vec<SelectableType>,
     pub sorts: Vec<SelectableSort>,
+    pub watch_statuses: Vec<SelectableWatchStatus>,
     pub next_page: Option<SelectablePage>,
 }
 
@@ -152,10 +186,21 @@ pub struct LibraryWithFilters<F> {
 }
 
 impl<F: LibraryFilter> LibraryWithFilters<F> {
-    pub fn new(library: &LibraryBucket, notifications: &NotificationsBucket) -> (Self, Effects) {
+    pub fn new(
+        library: &LibraryBucket,
+        notifications: &NotificationsBucket,
+        parental_controls: Option<&ParentalControls>,
+    ) -> (Self, Effects) {
         let selected = None;
         let mut selectable = Selectable::default();
-        let effects = selectable_update::<F>(&mut selectable, &selected, library, notifications);
+        let effects = selectable_update::<F>(
+            &mut selectable,
+            &selected,
+            library,
+            notifications,
+            parental_controls,
+            &[],
+        );
         (
             Self {
                 selectable,
@@ -177,12 +222,16 @@ impl<E: Env + 'static, F: LibraryFilter> UpdateWithCtx<E> for LibraryWithFilters
                     &self.selected,
                     &ctx.library,
                     &ctx.notifications,
+                    ctx.parental_controls(),
+                    &ctx.profile.smart_lists,
                 );
                 let catalog_effects = catalog_update::<F>(
                     &mut self.catalog,
                     &self.selected,
                     &ctx.library,
                     &ctx.notifications,
+                    ctx.parental_controls(),
+                    &ctx.profile.smart_lists,
                 );
                 selected_effects
                     .join(selectable_effects)
@@ -195,12 +244,16 @@ impl<E: Env + 'static, F: LibraryFilter> UpdateWithCtx<E> for LibraryWithFilters
                     &self.selected,
                     &ctx.library,
                     &ctx.notifications,
+                    ctx.parental_controls(),
+                    &ctx.profile.smart_lists,
                 );
                 let catalog_effects = catalog_update::<F>(
                     &mut self.catalog,
                     &self.selected,
                     &ctx.library,
                     &ctx.notifications,
+                    ctx.parental_controls(),
+                    &ctx.profile.smart_lists,
                 );
                 selected_effects
                     .join(selectable_effects)
@@ -218,12 +271,16 @@ impl<E: Env + 'static, F: LibraryFilter> UpdateWithCtx<E> for LibraryWithFilters
                             &self.selected,
                             &ctx.library,
                             &ctx.notifications,
+                            ctx.parental_controls(),
+                            &ctx.profile.smart_lists,
                         );
                         let catalog_effects = catalog_update::<F>(
                             &mut self.catalog,
                             &self.selected,
                             &ctx.library,
                             &ctx.notifications,
+                            ctx.parental_controls(),
+                            &ctx.profile.smart_lists,
                         );
                         selected_effects
                             .join(selectable_effects)
@@ -232,18 +289,26 @@ impl<E: Env + 'static, F: LibraryFilter> UpdateWithCtx<E> for LibraryWithFilters
                     _ => Effects::none().unchanged(),
                 }
             }
-            Msg::Internal(Internal::LibraryChanged(_)) => {
+            Msg::Internal(
+                Internal::LibraryChanged(_)
+                | Internal::ParentalControlsChanged
+                | Internal::ProfileChanged,
+            ) => {
                 let selectable_effects = selectable_update::<F>(
                     &mut self.selectable,
                     &self.selected,
                     &ctx.library,
                     &ctx.notifications,
+                    ctx.parental_controls(),
+                    &ctx.profile.smart_lists,
                 );
                 let catalog_effects = catalog_update::<F>(
                     &mut self.catalog,
                     &self.selected,
                     &ctx.library,
                     &ctx.notifications,
+                    ctx.parental_controls(),
+                    &ctx.profile.smart_lists,
                 );
                 selectable_effects.join(catalog_effects)
             }
@@ -257,11 +322,14 @@ fn selectable_update<F: LibraryFilter>(
     selected: &Option<Selected>,
     library: &LibraryBucket,
     notifications: &NotificationsBucket,
+    parental_controls: Option<&ParentalControls>,
+    smart_lists: &[SmartList],
 ) -> Effects {
     let selectable_types = library
         .items
         .values()
         .filter(|library_item| F::predicate(library_item, notifications))
+        .filter(|library_item| is_allowed_by(*library_item, parental_controls))
         .map(|library_item| &library_item.r#type)
         .unique()
         .sorted_by(|a, b| compare_with_priorities(a.as_str(), b.as_str(), &*TYPE_PRIORITIES))
@@ -277,6 +345,15 @@ fn selectable_update<F: LibraryFilter>(
                     .map(|selected| selected.request.sort.to_owned())
                     .unwrap_or_default(),
                 page: LibraryRequestPage::default(),
+                smart_list: selected
+                    .as_ref()
+                    .and_then(|selected| selected.request.smart_list.to_owned()),
+                watch_status: selected
+                    .as_ref()
+                    .and_then(|selected| selected.request.watch_status),
+                min_rating: selected
+                    .as_ref()
+                    .and_then(|selected| selected.request.min_rating),
             },
             selected: selected
                 .as_ref()
@@ -292,6 +369,15 @@ fn selectable_update<F: LibraryFilter>(
                 .map(|selected| selected.request.sort.to_owned())
                 .unwrap_or_default(),
             page: LibraryRequestPage::default(),
+            smart_list: selected
+                .as_ref()
+                .and_then(|selected| selected.request.smart_list.to_owned()),
+            watch_status: selected
+                .as_ref()
+                .and_then(|selected| selected.request.watch_status),
+            min_rating: selected
+                .as_ref()
+                .and_then(|selected| selected.request.min_rating),
         },
         selected: selected
             .as_ref()
@@ -309,6 +395,15 @@ fn selectable_update<F: LibraryFilter>(
                     .and_then(|selected| selected.request.r#type.to_owned()),
                 sort: sort.to_owned(),
                 page: LibraryRequestPage::default(),
+                smart_list: selected
+                    .as_ref()
+                    .and_then(|selected| selected.request.smart_list.to_owned()),
+                watch_status: selected
+                    .as_ref()
+                    .and_then(|selected| selected.request.watch_status),
+                min_rating: selected
+                    .as_ref()
+                    .and_then(|selected| selected.request.min_rating),
             },
             selected: selected
                 .as_ref()
@@ -316,15 +411,47 @@ fn selectable_update<F: LibraryFilter>(
                 .unwrap_or_default(),
         })
         .collect();
+    let selectable_watch_statuses = iter::once(None)
+        .chain(WatchStatus::iter().map(Some))
+        .map(|watch_status| SelectableWatchStatus {
+            watch_status,
+            request: LibraryRequest {
+                r#type: selected
+                    .as_ref()
+                    .and_then(|selected| selected.request.r#type.to_owned()),
+                sort: selected
+                    .as_ref()
+                    .map(|selected| selected.request.sort.to_owned())
+                    .unwrap_or_default(),
+                page: LibraryRequestPage::default(),
+                smart_list: selected
+                    .as_ref()
+                    .and_then(|selected| selected.request.smart_list.to_owned()),
+                watch_status,
+                min_rating: selected
+                    .as_ref()
+                    .and_then(|selected| selected.request.min_rating),
+            },
+            selected: selected
+                .as_ref()
+                .map(|selected| selected.request.watch_status == watch_status)
+                .unwrap_or_default(),
+        })
+        .collect();
     let next_page = match selected {
         Some(selected) => library
             .items
             .values()
             .filter(|library_item| F::predicate(library_item, notifications))
+            .filter(|library_item| is_allowed_by(*library_item, parental_controls))
             .filter(|library_item| match &selected.request.r#type {
                 Some(r#type) => library_item.r#type == *r#type,
                 None => true,
             })
+            .filter(|library_item| {
+                is_in_smart_list(&selected.request, library_item, notifications, smart_lists)
+            })
+            .filter(|library_item| is_with_watch_status_and_rating(&selected.request, library_item))
             .nth(selected.request.page.get() * CATALOG_PAGE_SIZE)
             .map(|_| SelectablePage {
                 request: LibraryRequest {
@@ -339,6 +466,7 @@ fn selectable_update<F: LibraryFilter>(
     let next_selectable = Selectable {
         types: selectable_types,
         sorts: selectable_sorts,
+        watch_statuses: selectable_watch_statuses,
         next_page,
     };
     eq_update(selectable, next_selectable)
@@ -349,16 +477,23 @@ fn catalog_update<F: LibraryFilter>(
     selected: &Option<Selected>,
     library: &LibraryBucket,
     notifications: &NotificationsBucket,
+    parental_controls: Option<&ParentalControls>,
+    smart_lists: &[SmartList],
 ) -> Effects {
     let next_catalog = match selected {
         Some(selected) => library
             .items
             .values()
             .filter(|library_item| F::predicate(library_item, notifications))
+            .filter(|library_item| is_allowed_by(*library_item, parental_controls))
             .filter(|library_item| match &selected.request.r#type {
                 Some(r#type) => library_item.r#type == *r#type,
                 None => true,
             })
+            .filter(|library_item| {
+                is_in_smart_list(&selected.request, library_item, notifications, smart_lists)
+            })
+            .filter(|library_item| is_with_watch_status_and_rating(&selected.request, library_item))
             .sorted_by(|a, b| selected.request.sort.sort_items(a, b))
             .take(selected.request.page.get() * CATALOG_PAGE_SIZE)
             .cloned()
@@ -368,6 +503,34 @@ fn catalog_update<F: LibraryFilter>(
     eq_update(catalog, next_catalog)
 }
 
+fn is_in_smart_list(
+    request: &LibraryRequest,
+    library_item: &LibraryItem,
+    notifications: &NotificationsBucket,
+    smart_lists: &[SmartList],
+) -> bool {
+    match &request.smart_list {
+        Some(id) => smart_lists
+            .iter()
+            .find(|smart_list| smart_list.id == *id)
+            .map(|smart_list| smart_list.query.matches(library_item, notifications))
+            .unwrap_or_default(),
+        None => true,
+    }
+}
+
+/// Whether the item has the watch status and at least the minimum rating of the request
+fn is_with_watch_status_and_rating(request: &LibraryRequest, library_item: &LibraryItem) -> bool {
+    request.watch_status.map_or(true, |watch_status| {
+        library_item.state.watch_status() == Some(watch_status)
+    }) && request.min_rating.map_or(true, |min_rating| {
+        library_item
+            .state
+            .rating()
+            .map_or(false, |rating| rating >= min_rating)
+    })
+}
+
 #[cfg(test)]
 mod test {
     use chrono::{Duration, Utc};
@@ -404,6 +567,8 @@ mod test {
                 ..Default::default()
             },
             behavior_hints: crate::types::resource::MetaItemBehaviorHints::default(),
+            genres: vec![],
+            certification: None,
         };
         let watched_movie_1_week_ago = LibraryItem {
             id: "tt15398776".into(),
@@ -422,6 +587,8 @@ mod test {
                 ..Default::default()
             },
             behavior_hints: crate::types::resource::MetaItemBehaviorHints::default(),
+            genres: vec![],
+            certification: None,
         };
 
         let not_watched_movie_added_3_weeks_ago = LibraryItem {
@@ -441,6 +608,8 @@ mod test {
                 ..Default::default()
             },
             behavior_hints: crate::types::resource::MetaItemBehaviorHints::default(),
+            genres: vec![],
+            certification: None,
         };
 
         let not_watched_movie_added_2_weeks_ago = LibraryItem {
@@ -460,6 +629,8 @@ mod test {
                 ..Default::default()
             },
             behavior_hints: crate::types::resource::MetaItemBehaviorHints::default(),
+            genres: vec![],
+            certification: None,
         };
 
         let watched_movie_1_week_ago_marked_not_watched = LibraryItem {
@@ -479,6 +650,8 @@ mod test {
                 ..Default::default()
             },
             behavior_hints: crate::types::resource::MetaItemBehaviorHints::default(),
+            genres: vec![],
+            certification: None,
         };
 
         // Sort by Watched - first library items that are Watched by latest `last_watched` desc
@@ -1,9 +1,14 @@
 //! Local autocompletion search
 
+use std::cmp::Ordering;
+
+use chrono::{DateTime, Duration, Utc};
 use enclose::enclose;
 use futures::FutureExt;
 use http::Request;
+use itertools::Itertools;
 use num::{rational::Ratio, ToPrimitive};
+use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 use serde_with::{serde_as, DefaultOnError, NoneAsEmptyString};
 use url::Url;
@@ -11,15 +16,21 @@ use url::Url;
 use localsearch::{self, LocalSearch as Searcher, DEFAULT_SCORE_THRESHOLD};
 
 use crate::{
-    constants::{CINEMETA_CATALOGS_URL, CINEMETA_FEED_CATALOG_ID},
+    constants::{
+        CINEMETA_CATALOGS_URL, CINEMETA_FEED_CATALOG_ID, LOCAL_SEARCH_STORAGE_KEY,
+        SEARCH_HISTORY_SUGGESTIONS_COUNT,
+    },
     models::{
-        common::{eq_update, Loadable},
-        ctx::Ctx,
+        common::{
+            eq_update, fuzzy_matches, is_allowed_by, normalize_search_text, Loadable, SearchQuery,
+        },
+        ctx::{Ctx, CtxError},
     },
     runtime::{
-        msg::{Action, ActionLoad, ActionSearch, Internal, Msg},
+        msg::{Action, ActionLoad, ActionSearch, Event, Internal, Msg},
         Effect, EffectFuture, Effects, Env, EnvError, EnvFutureExt, UpdateWithCtx,
     },
+    types::{addon::Descriptor, library::LibraryBucket, profile::ParentalControls},
 };
 
 pub use imdb_rating::*;
@@ -29,9 +40,23 @@ const INDEX_OPTIONS: IndexOptions = IndexOptions {
     popularity_weight: 0.5,
 };
 
+/// The boost of the items in the user's library.
+///
+/// It's higher than the maximum boost of the catalogs' items (`e`),
+/// so the library items come first when they match equally well.
+const LIBRARY_BOOST: f64 = 3.0;
+
+/// How many times more items are searched for when the query has filters,
+/// as some of them will be filtered out
+const FILTERED_SEARCH_CANDIDATES_FACTOR: usize = 5;
+
+/// How long the persisted feeds are used before fetching them again
+static REFRESH_FEEDS_EVERY: Lazy<Duration> = Lazy::new(|| Duration::days(1));
+
 /// The response returned when fetching the searchable items list.
 ///
-/// Currently this is fetched from Cinemeta's `feed.json`
+/// This is fetched from Cinemeta's `feed.json` or from the
+/// [`searchable_feed`](crate::types::addon::ManifestBehaviorHints::searchable_feed) of an addon.
 #[derive(Deserialize, Serialize, Clone)]
 #[serde(transparent)]
 pub struct SearchableItemsResponse(pub Vec<Searchable>);
@@ -42,6 +67,19 @@ pub struct IndexOptions {
     popularity_weight: f64,
 }
 
+/// Where a [`Searchable`] comes from
+#[derive(Default, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[serde(tag = "type")]
+pub enum SearchableSource {
+    #[default]
+    Cinemeta,
+    Library,
+    Addon {
+        #[serde(rename = "transportUrl")]
+        transport_url: Url,
+    },
+}
+
 /// A searchable item
 #[serde_as]
 #[derive(Clone, PartialEq, Serialize, Deserialize, Debug)]
@@ -62,21 +100,101 @@ pub struct Searchable {
     pub imdb_rating: Option<ImdbRating>,
     pub popularity: Option<u64>,
     pub release_info: Option<String>,
+    #[serde(default)]
+    pub genres: Vec<String>,
+    /// Alternate and original titles
+    #[serde(default)]
+    pub aka: Vec<String>,
+    /// Used by the parental controls, e.g. `PG-13`
+    #[serde(default)]
+    #[serde_as(deserialize_as = "DefaultOnError")]
+    pub certification: Option<String>,
+    /// Set when loading the items, feeds don't have it
+    #[serde(default)]
+    pub source: SearchableSource,
+}
+
+/// A [`Searchable`] with one of its normalized titles, see [`normalize_search_text`].
+///
+/// An item is indexed once for each of its titles.
+#[derive(Clone, PartialEq, Debug)]
+pub struct IndexedSearchable {
+    pub key: String,
+    pub searchable: Searchable,
+}
+
+impl IndexedSearchable {
+    pub fn from_searchables<'a>(
+        searchables: impl IntoIterator<Item = &'a Searchable>,
+    ) -> Vec<IndexedSearchable> {
+        searchables
+            .into_iter()
+            .flat_map(|searchable| {
+                std::iter::once(&searchable.name)
+                    .chain(searchable.aka.iter())
+                    .map(|title| normalize_search_text(title))
+                    .filter(|key| !key.is_empty())
+                    .unique()
+                    .map(|key| IndexedSearchable {
+                        key,
+                        searchable: searchable.to_owned(),
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+}
+
+/// The [`Searchable`] items of a single feed, as they are persisted in the storage
+#[derive(Clone, PartialEq, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchableFeed {
+    /// Cinemeta's catalogs url or the transport url of the addon
+    pub url: Url,
+    pub last_updated: DateTime<Utc>,
+    pub items: Vec<Searchable>,
+}
+
+impl SearchableFeed {
+    pub fn is_stale<E: Env>(&self) -> bool {
+        self.last_updated + *REFRESH_FEEDS_EVERY <= E::now()
+    }
 }
 
 /// Local search functionality for the search engine's suggestions when typing
 #[derive(Default, Serialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct LocalSearch {
-    /// The Searchable items that will be used for the local search.
+    /// The feeds loaded from the storage and refreshed when stale.
+    ///
+    /// `None` until the storage has been read.
+    #[serde(skip)]
+    pub feeds: Option<Vec<SearchableFeed>>,
+    /// The Searchable items of the catalogs that will be used for the local search.
     #[serde(skip)]
     pub current_records: Vec<Searchable>,
+    /// The Searchable items of the user's library.
+    ///
+    /// They are indexed separately, so a change in the library
+    /// does not rebuild the index of the catalogs.
+    #[serde(skip)]
+    pub library_records: Vec<Searchable>,
     /// The results of the search autocompletion
     pub search_results: Vec<Searchable>,
+    /// The queries of the search history starting with the typed one
+    pub search_history_results: Vec<String>,
+    /// The `current_records` with their normalized titles, built together with the `searcher`
+    #[serde(skip)]
+    pub indexed_records: Vec<IndexedSearchable>,
+    #[serde(skip)]
+    pub library_indexed_records: Vec<IndexedSearchable>,
+    /// The index of the `current_records`, rebuilt when they change
     #[serde(skip)]
-    pub searcher: Option<Searcher<Searchable>>,
+    pub searcher: Option<Searcher<IndexedSearchable>>,
+    #[serde(skip)]
+    pub library_searcher: Option<Searcher<IndexedSearchable>>,
     /// A loadable resource in order to be able to search for items while
-    /// a new set of items is being loaded (i.e. refreshed)
+    /// a new set of Cinemeta items is being loaded (i.e. refreshed)
     pub latest_records: Option<Loadable<Vec<Searchable>, EnvError>>,
 }
 
@@ -84,20 +202,24 @@ impl LocalSearch {
     pub fn new<E: Env + 'static>() -> (Self, Effects) {
         (
             Self {
+                feeds: None,
                 current_records: vec![],
+                library_records: vec![],
+                indexed_records: vec![],
+                library_indexed_records: vec![],
                 search_results: vec![],
+                search_history_results: vec![],
                 searcher: None,
+                library_searcher: None,
                 latest_records: None,
             },
             Effects::none().unchanged(),
         )
     }
 
-    /// fetches the `/feed.js` using the given [`Url`]
-    fn get_searchable_items<E: Env + 'static>(url: &Url) -> Effect {
-        let endpoint = url
-            .join(CINEMETA_FEED_CATALOG_ID)
-            .expect("url builder failed");
+    /// fetches the feed at `path` relative to the given [`Url`]
+    fn get_searchable_items<E: Env + 'static>(url: &Url, path: &str) -> Effect {
+        let endpoint = url.join(path).expect("url builder failed");
 
         let request = Request::get(endpoint.as_str())
             .body(())
@@ -117,7 +239,236 @@ impl LocalSearch {
         .into()
     }
 
-    fn index(&self, index_options: IndexOptions, score_threshold: f64) -> Searcher<Searchable> {
+    fn pull_feeds_from_storage<E: Env + 'static>() -> Effect {
+        EffectFuture::Concurrent(
+            E::get_storage(LOCAL_SEARCH_STORAGE_KEY)
+                .map(|result| Msg::Internal(Internal::LocalSearchStorageResult(result)))
+                .boxed_env(),
+        )
+        .into()
+    }
+
+    fn push_feeds_to_storage<E: Env + 'static>(feeds: &[SearchableFeed]) -> Effect {
+        let urls = feeds
+            .iter()
+            .map(|feed| feed.url.to_owned())
+            .collect::<Vec<_>>();
+        EffectFuture::Sequential(
+            E::set_storage(LOCAL_SEARCH_STORAGE_KEY, Some(&feeds))
+                .map(move |result| match result {
+                    Ok(_) => Msg::Event(Event::LocalSearchPushedToStorage { urls }),
+                    Err(error) => Msg::Event(Event::Error {
+                        error: CtxError::from(error),
+                        source: Box::new(Event::LocalSearchPushedToStorage { urls }),
+                    }),
+                })
+                .boxed_env(),
+        )
+        .into()
+    }
+
+    /// The url and the path of Cinemeta's feed and of the feeds of the addons which declare one
+    fn feed_urls(addons: &[Descriptor]) -> Vec<(Url, String)> {
+        let addons_feeds = addons.iter().filter_map(|addon| {
+            addon
+                .manifest
+                .behavior_hints
+                .searchable_feed
+                .as_ref()
+                .map(|path| (addon.transport_url.to_owned(), path.to_owned()))
+        });
+
+        std::iter::once((
+            CINEMETA_CATALOGS_URL.to_owned(),
+            CINEMETA_FEED_CATALOG_ID.to_owned(),
+        ))
+        .chain(addons_feeds)
+        .collect()
+    }
+
+    /// Drops the feeds of uninstalled addons and fetches the missing and the stale ones
+    fn refresh_feeds<E: Env + 'static>(&mut self, addons: &[Descriptor]) -> Effects {
+        let feed_urls = Self::feed_urls(addons);
+        let feeds = self
+            .feeds
+            .iter()
+            .flatten()
+            .filter(|feed| feed_urls.iter().any(|(url, _)| url == &feed.url))
+            .cloned()
+            .collect::<Vec<_>>();
+        let feeds_effects = self.update_feeds::<E>(feeds);
+
+        let stale_feed_urls = feed_urls
+            .into_iter()
+            .filter(|(url, _)| {
+                self.feeds
+                    .iter()
+                    .flatten()
+                    .find(|feed| &feed.url == url)
+                    .map(|feed| feed.is_stale::<E>())
+                    .unwrap_or(true)
+            })
+            .collect::<Vec<_>>();
+
+        let last_records_effects = if stale_feed_urls
+            .iter()
+            .any(|(url, _)| url == &*CINEMETA_CATALOGS_URL)
+        {
+            eq_update(&mut self.latest_records, Some(Loadable::Loading))
+        } else {
+            Effects::none().unchanged()
+        };
+
+        let fetch_effects = Effects::many(
+            stale_feed_urls
+                .iter()
+                .map(|(url, path)| Self::get_searchable_items::<E>(url, path))
+                .collect(),
+        )
+        .unchanged();
+
+        feeds_effects.join(last_records_effects).join(fetch_effects)
+    }
+
+    /// Replaces the feeds and the records of the search, persisting the feeds if they changed
+    fn update_feeds<E: Env + 'static>(&mut self, feeds: Vec<SearchableFeed>) -> Effects {
+        let current_records = feeds
+            .iter()
+            .flat_map(|feed| feed.items.iter())
+            .cloned()
+            .collect::<Vec<_>>();
+        let feeds_effects = eq_update(&mut self.feeds, Some(feeds));
+        let storage_effects = match &self.feeds {
+            Some(feeds) if feeds_effects.has_changed => {
+                Effects::one(Self::push_feeds_to_storage::<E>(feeds)).unchanged()
+            }
+            _ => Effects::none().unchanged(),
+        };
+
+        // Due to LocalSearch not implementing PartialEq, we handle the effects
+        // based on the current records effects.
+        let current_records_effects = eq_update(&mut self.current_records, current_records);
+        if current_records_effects.has_changed {
+            // the index is built as the feeds load, so the searches never wait for it
+            self.indexed_records = IndexedSearchable::from_searchables(&self.current_records);
+            self.searcher = if self.current_records.is_empty() {
+                None
+            } else {
+                Some(self.index(INDEX_OPTIONS, DEFAULT_SCORE_THRESHOLD))
+            };
+        }
+
+        feeds_effects
+            .unchanged()
+            .join(storage_effects)
+            .join(current_records_effects)
+    }
+
+    fn source(url: &Url) -> SearchableSource {
+        if url == &*CINEMETA_CATALOGS_URL {
+            SearchableSource::Cinemeta
+        } else {
+            SearchableSource::Addon {
+                transport_url: url.to_owned(),
+            }
+        }
+    }
+
+    fn update_library_records(&mut self, library: &LibraryBucket) -> Effects {
+        let library_records = library
+            .items
+            .values()
+            .filter(|library_item| !library_item.removed && !library_item.temp)
+            .map(|library_item| Searchable {
+                id: library_item.id.to_owned(),
+                name: library_item.name.to_owned(),
+                r#type: library_item.r#type.to_owned(),
+                poster: library_item.poster.to_owned(),
+                imdb_rating: None,
+                popularity: None,
+                release_info: None,
+                genres: vec![],
+                aka: vec![],
+                certification: None,
+                source: SearchableSource::Library,
+            })
+            .filter(|searchable| !searchable.name.is_empty())
+            .sorted_by(|a, b| a.id.cmp(&b.id))
+            .collect::<Vec<_>>();
+
+        let library_records_effects = eq_update(&mut self.library_records, library_records);
+        if library_records_effects.has_changed || self.library_searcher.is_none() {
+            self.library_indexed_records =
+                IndexedSearchable::from_searchables(&self.library_records);
+            self.library_searcher = Some(
+                Searcher::builder(self.library_indexed_records.clone(), |item| &item.key)
+                    .boost_computer(|_| LIBRARY_BOOST)
+                    .score_threshold(DEFAULT_SCORE_THRESHOLD)
+                    .build(),
+            );
+        }
+
+        // the library records are not serialized
+        library_records_effects.unchanged()
+    }
+
+    /// Searches the records of the catalogs and of the library,
+    /// `None` if none of them have been loaded yet
+    pub fn search(
+        &self,
+        search_query: &str,
+        max_results: usize,
+        parental_controls: Option<&ParentalControls>,
+    ) -> Option<Vec<Searchable>> {
+        // we first need to load the Searchable records from Cinemeta or the library
+        if self.searcher.is_none() && self.library_searcher.is_none() {
+            return None;
+        }
+
+        let query = SearchQuery::parse(search_query);
+        let candidates = if query.is_filtered() {
+            max_results.saturating_mul(FILTERED_SEARCH_CANDIDATES_FACTOR)
+        } else {
+            max_results
+        };
+        let index_matches = self
+            .searcher
+            .iter()
+            .chain(self.library_searcher.iter())
+            .flat_map(|searcher| searcher.search(&query.text, candidates))
+            .sorted_by(|(_, score_a), (_, score_b)| {
+                score_b.partial_cmp(score_a).unwrap_or(Ordering::Equal)
+            })
+            .map(|(indexed, _score)| &indexed.searchable);
+        // the titles with typos which the index did not match,
+        // only scanned when there are not enough results
+        let typo_matches = self
+            .library_indexed_records
+            .iter()
+            .chain(self.indexed_records.iter())
+            .filter(|indexed| fuzzy_matches(&query.text, &indexed.key))
+            .map(|indexed| &indexed.searchable);
+        Some(
+            index_matches
+                .chain(typo_matches)
+                // the same item can be both in the library and in the catalogs
+                // or matched by more than one of its titles
+                .unique_by(|searchable| &searchable.id)
+                .filter(|searchable| {
+                    query.filters_match(&searchable.r#type, searchable.release_info.as_deref())
+                })
+                .filter(|searchable| is_allowed_by(*searchable, parental_controls))
+                .take(max_results)
+                .cloned()
+                .collect(),
+        )
+    }
+
+    fn index(
+        &self,
+        index_options: IndexOptions,
+        score_threshold: f64,
+    ) -> Searcher<IndexedSearchable> {
         let max_imdb_rating = self
             .current_records
             .iter()
@@ -134,7 +485,7 @@ impl LocalSearch {
             .max_by(|popularity_a, popularity_b| popularity_a.partial_cmp(popularity_b).unwrap())
             .unwrap_or_default();
 
-        let score_computer = move |searchable: &Searchable| {
+        let score_computer = move |IndexedSearchable { searchable, .. }: &IndexedSearchable| {
             let imdb_rating_boost = searchable
                 .imdb_rating
                 .map(|imdb_rating| {
@@ -158,7 +509,7 @@ impl LocalSearch {
             imdb_rating_boost * popularity_boost
         };
 
-        Searcher::builder(self.current_records.clone(), |item| &item.name)
+        Searcher::builder(self.indexed_records.clone(), |item| &item.key)
             .boost_computer(score_computer)
             .score_threshold(score_threshold)
             .build()
@@ -166,78 +517,141 @@ impl LocalSearch {
 }
 
 impl<E: Env + 'static> UpdateWithCtx<E> for LocalSearch {
-    fn update(&mut self, msg: &Msg, _ctx: &Ctx) -> Effects {
+    fn update(&mut self, msg: &Msg, ctx: &Ctx) -> Effects {
         match msg {
             Msg::Action(Action::Load(ActionLoad::LocalSearch)) => {
-                let load_feed_effect = Self::get_searchable_items::<E>(&CINEMETA_CATALOGS_URL);
+                // the persisted feeds are used before refreshing the stale ones
+                let feeds_effects = if self.feeds.is_some() {
+                    self.refresh_feeds::<E>(&ctx.profile.addons)
+                } else {
+                    Effects::one(Self::pull_feeds_from_storage::<E>()).unchanged()
+                };
+
+                let library_records_effects = self.update_library_records(&ctx.library);
+
+                feeds_effects.join(library_records_effects)
+            }
+            Msg::Internal(Internal::LocalSearchStorageResult(result)) => {
+                let feeds = match result {
+                    Ok(feeds) => feeds.to_owned().unwrap_or_default(),
+                    // the feeds will be fetched again
+                    Err(_) => vec![],
+                };
 
                 let last_records_effects =
-                    eq_update(&mut self.latest_records, Some(Loadable::Loading));
+                    match feeds.iter().find(|feed| feed.url == *CINEMETA_CATALOGS_URL) {
+                        Some(feed) => eq_update(
+                            &mut self.latest_records,
+                            Some(Loadable::Ready(feed.items.to_owned())),
+                        ),
+                        None => Effects::none().unchanged(),
+                    };
+
+                // the storage already has these feeds
+                self.feeds = Some(feeds);
 
-                Effects::one(load_feed_effect)
-                    .unchanged()
-                    .join(last_records_effects)
+                last_records_effects.join(self.refresh_feeds::<E>(&ctx.profile.addons))
             }
+            Msg::Internal(Internal::LibraryChanged(_)) => self.update_library_records(&ctx.library),
             Msg::Action(Action::Search(ActionSearch::Search {
                 search_query,
                 max_results,
             })) => {
-                match &self.searcher {
-                    // local search can be performed
-                    Some(searcher) => {
-                        let new_search_results = searcher
-                            .search(search_query, *max_results)
-                            .into_iter()
-                            .map(|(searchable, _score)| searchable.to_owned())
-                            .collect();
-
-                        eq_update(&mut self.search_results, new_search_results)
-                    }
-                    // we first need to load the Searchable records from Cinemeta
-                    None => Effects::none().unchanged(),
-                }
+                let search_history_results = ctx
+                    .search_history
+                    .suggestions(search_query, &E::now(), SEARCH_HISTORY_SUGGESTIONS_COUNT)
+                    .into_iter()
+                    .cloned()
+                    .collect();
+                let search_history_effects =
+                    eq_update(&mut self.search_history_results, search_history_results);
+
+                let search_results_effects =
+                    match self.search(search_query, *max_results, ctx.parental_controls()) {
+                        Some(search_results) => eq_update(&mut self.search_results, search_results),
+                        None => Effects::none().unchanged(),
+                    };
+
+                search_results_effects.join(search_history_effects)
+            }
+            Msg::Internal(Internal::LocalSearchQuery { query, max_results }) => {
+                // the local search may not have been loaded yet
+                let feeds_effects = if self.feeds.is_none() {
+                    Effects::one(Self::pull_feeds_from_storage::<E>()).unchanged()
+                } else {
+                    Effects::none().unchanged()
+                };
+                let library_records_effects = if self.library_searcher.is_none() {
+                    self.update_library_records(&ctx.library)
+                } else {
+                    Effects::none().unchanged()
+                };
+                let results = self
+                    .search(query, *max_results, ctx.parental_controls())
+                    .unwrap_or_default();
+
+                feeds_effects.join(library_records_effects).join(
+                    Effects::msg(Msg::Internal(Internal::LocalSearchQueryResult {
+                        query: query.to_owned(),
+                        results,
+                    }))
+                    .unchanged(),
+                )
+            }
+            Msg::Internal(Internal::SearchHistoryChanged) => {
+                // the removed queries are no longer suggested
+                let search_history_results = self
+                    .search_history_results
+                    .iter()
+                    .filter(|query| ctx.search_history.items.contains_key(*query))
+                    .cloned()
+                    .collect();
+                eq_update(&mut self.search_history_results, search_history_results)
             }
-            Msg::Internal(Internal::LoadLocalSearchResult(_url, result)) => {
+            Msg::Internal(Internal::LoadLocalSearchResult(url, result)) => {
+                let source = Self::source(url);
                 match result {
                     Ok(searchable) => {
                         // filters out any `Searchable` items without a `name`
                         let searchable = searchable
                             .iter()
-                            .filter_map(|searchable| {
-                                if !searchable.name.is_empty() {
-                                    Some(searchable.to_owned())
-                                } else {
-                                    None
-                                }
+                            .filter(|searchable| !searchable.name.is_empty())
+                            .map(|searchable| Searchable {
+                                source: source.to_owned(),
+                                ..searchable.to_owned()
                             })
                             .collect::<Vec<_>>();
 
                         // update the latest records, used for refreshing the list
-                        let last_records_effects = eq_update(
-                            &mut self.latest_records,
-                            Some(Loadable::Ready(searchable.to_owned())),
-                        );
-
-                        // and the current, used for the search itself
-                        let current_records_effects =
-                            eq_update(&mut self.current_records, searchable);
-
-                        // Due to LocalSearch not implementing PartialEq, we handle the effects
-                        // based on the current records effects.
-                        let searcher_effects = if current_records_effects.has_changed {
-                            let searcher = self.index(INDEX_OPTIONS, DEFAULT_SCORE_THRESHOLD);
-
-                            self.searcher = Some(searcher);
-                            Effects::none()
+                        let last_records_effects = if source == SearchableSource::Cinemeta {
+                            eq_update(
+                                &mut self.latest_records,
+                                Some(Loadable::Ready(searchable.to_owned())),
+                            )
                         } else {
                             Effects::none().unchanged()
                         };
 
-                        last_records_effects
-                            .join(current_records_effects)
-                            .join(searcher_effects)
+                        // and the feeds, used for the search itself,
+                        // replacing only the feed of the same url
+                        let feed = SearchableFeed {
+                            url: url.to_owned(),
+                            last_updated: E::now(),
+                            items: searchable,
+                        };
+                        let feeds = self
+                            .feeds
+                            .iter()
+                            .flatten()
+                            .filter(|current| current.url != *url)
+                            .cloned()
+                            .chain(std::iter::once(feed))
+                            .collect::<Vec<_>>();
+                        let feeds_effects = self.update_feeds::<E>(feeds);
+
+                        last_records_effects.join(feeds_effects)
                     }
-                    Err(error) => {
+                    Err(error) if source == SearchableSource::Cinemeta => {
                         // update the latest records, but leave the current_records
                         // this will ensure that the user can still search locally with autocomplete
                         eq_update(
@@ -245,8 +659,19 @@ impl<E: Env + 'static> UpdateWithCtx<E> for LocalSearch {
                             Some(Loadable::Err(error.to_owned())),
                         )
                     }
+                    // an addon's feed failing to load leaves its current records
+                    Err(_) => Effects::none().unchanged(),
                 }
             }
+            Msg::Internal(Internal::ParentalControlsChanged) => {
+                let search_results = self
+                    .search_results
+                    .iter()
+                    .filter(|searchable| is_allowed_by(*searchable, ctx.parental_controls()))
+                    .cloned()
+                    .collect();
+                eq_update(&mut self.search_results, search_results)
+            }
             _ => Effects::none().unchanged(),
         }
     }
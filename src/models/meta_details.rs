@@ -8,8 +8,9 @@ use crate::{
     constants::{LIBRARY_COLLECTION_NAME, META_RESOURCE_NAME, STREAM_RESOURCE_NAME},
     models::{
         common::{
-            eq_update, resources_update, resources_update_with_vector_content, Loadable,
-            ResourceLoadable, ResourcesAction,
+            eq_update, parental_controls_update, resources_update,
+            resources_update_with_vector_content, Loadable, ResourceError, ResourceLoadable,
+            ResourcesAction,
         },
         ctx::Ctx,
     },
@@ -62,8 +63,12 @@ impl<E: Env + 'static> UpdateWithCtx<E> for MetaDetails {
                     selected_guess_stream_update(&mut self.selected, &self.meta_items);
                 let meta_streams_effects =
                     meta_streams_update(&mut self.meta_streams, &self.selected, &self.meta_items);
-                let streams_effects =
-                    streams_update::<E>(&mut self.streams, &self.selected, &ctx.profile);
+                let streams_effects = streams_update::<E>(
+                    &mut self.streams,
+                    &self.selected,
+                    &self.meta_items,
+                    &ctx.profile,
+                );
                 let suggested_stream_effects = suggested_stream_update(
                     &mut self.suggested_stream,
                     &self.selected,
@@ -149,13 +154,19 @@ impl<E: Env + 'static> UpdateWithCtx<E> for MetaDetails {
                     &mut self.meta_items,
                     ResourcesAction::ResourceRequestResult { request, result },
                 );
+                self.meta_items.iter_mut().for_each(|meta_item| {
+                    parental_controls_update(meta_item, ctx.parental_controls())
+                });
                 let selected_override_effects =
                     selected_guess_stream_update(&mut self.selected, &self.meta_items);
-                let streams_effects = if selected_override_effects.has_changed {
-                    streams_update::<E>(&mut self.streams, &self.selected, &ctx.profile)
-                } else {
-                    Effects::default()
-                };
+                // the streams are requested for the overridden `stream_path`
+                // and dropped if the meta item is restricted
+                let streams_effects = streams_update::<E>(
+                    &mut self.streams,
+                    &self.selected,
+                    &self.meta_items,
+                    &ctx.profile,
+                );
                 let meta_streams_effects =
                     meta_streams_update(&mut self.meta_streams, &self.selected, &self.meta_items);
                 let suggested_stream_effects = suggested_stream_update(
@@ -210,13 +221,53 @@ impl<E: Env + 'static> UpdateWithCtx<E> for MetaDetails {
                     watched_update(&mut self.watched, &self.meta_items, &self.library_item);
                 library_item_effects.join(watched_effects)
             }
+            Msg::Internal(Internal::ParentalControlsChanged) => {
+                // request the meta items again as the restricted ones were replaced with an error
+                let meta_items_effects = match &self.selected {
+                    Some(Selected { meta_path, .. }) => resources_update::<E, _>(
+                        &mut self.meta_items,
+                        ResourcesAction::force_request(
+                            &AggrRequest::AllOfResource(meta_path.to_owned()),
+                            &ctx.profile.addons,
+                        ),
+                    ),
+                    _ => Effects::none().unchanged(),
+                };
+                let meta_streams_effects =
+                    meta_streams_update(&mut self.meta_streams, &self.selected, &self.meta_items);
+                let suggested_stream_effects = suggested_stream_update(
+                    &mut self.suggested_stream,
+                    &self.selected,
+                    &self.meta_items,
+                    &self.meta_streams,
+                    &self.streams,
+                    &ctx.streams,
+                );
+                let library_item_effects = library_item_update::<E>(
+                    &mut self.library_item,
+                    &self.selected,
+                    &self.meta_items,
+                    &ctx.library,
+                );
+                let watched_effects =
+                    watched_update(&mut self.watched, &self.meta_items, &self.library_item);
+                meta_items_effects
+                    .join(meta_streams_effects)
+                    .join(suggested_stream_effects)
+                    .join(library_item_effects)
+                    .join(watched_effects)
+            }
             Msg::Internal(Internal::ProfileChanged) => {
                 let meta_items_effects =
                     meta_items_update::<E>(&mut self.meta_items, &self.selected, &ctx.profile);
                 let meta_streams_effects =
                     meta_streams_update(&mut self.meta_streams, &self.selected, &self.meta_items);
-                let streams_effects =
-                    streams_update::<E>(&mut self.streams, &self.selected, &ctx.profile);
+                let streams_effects = streams_update::<E>(
+                    &mut self.streams,
+                    &self.selected,
+                    &self.meta_items,
+                    &ctx.profile,
+                );
                 let suggested_stream_effects = suggested_stream_update(
                     &mut self.suggested_stream,
                     &self.selected,
@@ -410,13 +461,14 @@ fn meta_streams_update(
 fn streams_update<E: Env + 'static>(
     streams: &mut Vec<ResourceLoadable<Vec<Stream>>>,
     selected: &Option<Selected>,
+    meta_items: &[ResourceLoadable<MetaItem>],
     profile: &Profile,
 ) -> Effects {
     match selected {
         Some(Selected {
             stream_path: Some(stream_path),
             ..
-        }) => resources_update_with_vector_content::<E, _>(
+        }) if !is_restricted(meta_items) => resources_update_with_vector_content::<E, _>(
             streams,
             ResourcesAction::ResourcesRequested {
                 request: &AggrRequest::AllOfResource(stream_path.to_owned()),
@@ -451,6 +503,7 @@ fn suggested_stream_update(
 ) -> Effects {
     let all_streams = [meta_streams, streams].concat();
     let next_suggested_stream = match selected {
+        _ if is_restricted(meta_items) => None,
         Some(Selected {
             stream_path: Some(stream_path),
             ..
@@ -507,6 +560,17 @@ fn suggested_stream_update(
     eq_update(suggested_stream, next_suggested_stream)
 }
 
+/// Whether the meta item is restricted by the parental controls,
+/// its streams are neither requested nor suggested then.
+fn is_restricted(meta_items: &[ResourceLoadable<MetaItem>]) -> bool {
+    meta_items.iter().any(|meta_item| {
+        matches!(
+            meta_item.content,
+            Some(Loadable::Err(ResourceError::Restricted))
+        )
+    })
+}
+
 fn library_item_update<E: Env + 'static>(
     library_item: &mut Option<LibraryItem>,
     selected: &Option<Selected>,
@@ -2,10 +2,13 @@ pub mod common;
 pub mod ctx;
 
 pub mod addon_details;
+pub mod calendar;
 pub mod catalog_with_filters;
 pub mod catalogs_with_extra;
 pub mod continue_watching_preview;
 pub mod data_export;
+pub mod for_you;
+pub mod global_search;
 pub mod installed_addons_with_filters;
 pub mod library_by_type;
 pub mod library_with_filters;
@@ -13,4 +16,7 @@ pub mod link;
 pub mod local_search;
 pub mod meta_details;
 pub mod player;
+pub mod playlists;
 pub mod streaming_server;
+pub mod watch_history;
+pub mod watch_stats;
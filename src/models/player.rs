@@ -11,9 +11,9 @@ use crate::constants::{
     VIDEO_HASH_EXTRA_PROP, VIDEO_SIZE_EXTRA_PROP, WATCHED_THRESHOLD_COEF,
 };
 use crate::models::common::{
-    eq_update, resource_update, resource_update_with_vector_content,
-    resources_update_with_vector_content, Loadable, ResourceAction, ResourceLoadable,
-    ResourcesAction,
+    eq_update, is_allowed_by, parental_controls_update, resource_update,
+    resource_update_with_vector_content, resources_update_with_vector_content, Loadable,
+    ResourceAction, ResourceError, ResourceLoadable, ResourcesAction,
 };
 use crate::models::ctx::{Ctx, CtxError};
 use crate::runtime::msg::{Action, ActionLoad, ActionPlayer, Event, Internal, Msg};
@@ -23,11 +23,17 @@ use crate::types::api::{
     fetch_api, APIRequest, APIResult, SeekLog, SeekLogRequest, SkipGapsRequest, SkipGapsResponse,
     SuccessResponse,
 };
-use crate::types::library::{LibraryBucket, LibraryItem};
-use crate::types::player::{IntroData, IntroOutro};
-use crate::types::profile::{Profile, Settings as ProfileSettings};
-use crate::types::resource::{MetaItem, SeriesInfo, Stream, StreamSource, Subtitles, Video};
+use crate::types::library::{LibraryBucket, LibraryItem, ResumePoint};
+use crate::types::player::{
+    is_same_language, normalize_language, IntroData, IntroOutro, PlayerSubtitles, SubtitlesSource,
+};
+use crate::types::profile::{ParentalControls, Profile, Settings as ProfileSettings};
+use crate::types::resource::{
+    MetaItem, MetaItemPreview, SeriesInfo, Stream, StreamSource, Subtitles, Video,
+};
 use crate::types::streams::{StreamItemState, StreamsBucket, StreamsItemKey};
+use crate::types::subtitles::{CueTiming, SubtitlesSync};
+use crate::types::watch_history::WatchHistoryItem;
 
 use stremio_watched_bitfield::WatchedBitField;
 
@@ -73,6 +79,10 @@ pub struct VideoParams {
     pub hash: Option<String>,
     pub size: Option<u64>,
     pub filename: Option<String>,
+    /// When speech is heard in the video, e.g. from the voice activity of the audio track,
+    /// used as the reference for syncing the subtitles
+    #[serde(default)]
+    pub timing_hints: Vec<CueTiming>,
 }
 
 #[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
@@ -85,6 +95,36 @@ pub struct Selected {
     pub subtitles_path: Option<ResourcePath>,
 }
 
+/// An entry of the playback [`Player::queue`]
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct QueueItem {
+    /// The meta item can differ from the selected one, e.g. a movie after an episode
+    pub meta_request: ResourceRequest,
+    pub meta_item: MetaItemPreview,
+    /// For the meta items without videos, e.g. movies, the video id is the meta item id
+    pub video: Video,
+    /// When `None` the user picks one of the streams of the video
+    #[serde(default)]
+    pub stream: Option<Stream>,
+}
+
+impl QueueItem {
+    /// Whether the item is the one selected in the player
+    pub fn is_selected(&self, selected: &Selected) -> bool {
+        selected
+            .meta_request
+            .as_ref()
+            .map(|meta_request| &meta_request.path.id)
+            == Some(&self.meta_request.path.id)
+            && selected
+                .stream_request
+                .as_ref()
+                .map(|stream_request| &stream_request.path.id)
+                == Some(&self.video.id)
+    }
+}
+
 #[derive(Clone, Derivative, Serialize, Debug)]
 #[derivative(Default)]
 #[serde(rename_all = "camelCase")]
@@ -93,11 +133,33 @@ pub struct Player {
     pub video_params: Option<VideoParams>,
     pub meta_item: Option<ResourceLoadable<MetaItem>>,
     pub subtitles: Vec<ResourceLoadable<Vec<Subtitles>>>,
+    /// The subtitles of the stream and of all the addons, without duplicates,
+    /// ranked by the previously selected track and the preferred languages
+    pub aggregated_subtitles: Vec<PlayerSubtitles>,
+    /// The recommended subtitles out of [`Player::aggregated_subtitles`],
+    /// `None` if there are no subtitles in the preferred languages
+    pub selected_subtitles: Option<PlayerSubtitles>,
+    /// The correction proposed for the subtitles the user asked to sync,
+    /// the delay is stored in the stream state when the user accepts it
+    pub subtitles_sync: Option<SubtitlesSync>,
     pub next_video: Option<Video>,
+    /// The videos to play after the current one, before the next video of the series.
+    ///
+    /// Kept when the player is unloaded, so it can be built from the other pages.
+    pub queue: Vec<QueueItem>,
+    /// The queued item played after the current video, its video and stream are the
+    /// [`Player::next_video`].
+    ///
+    /// It's popped from the queue when the current video ends,
+    /// but kept here until the next video is loaded.
+    pub next_queue_item: Option<QueueItem>,
     pub next_streams: Option<ResourceLoadable<Vec<Stream>>>,
     pub next_stream: Option<Stream>,
     pub series_info: Option<SeriesInfo>,
     pub library_item: Option<LibraryItem>,
+    /// Where the selected video was left off when it was loaded,
+    /// for choosing between resuming and starting over
+    pub resume_point: Option<ResumePoint>,
     pub stream_state: Option<StreamItemState>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub intro_outro: Option<IntroOutro>,
@@ -107,6 +169,9 @@ pub struct Player {
     pub analytics_context: Option<AnalyticsContext>,
     #[serde(skip_serializing)]
     pub load_time: Option<DateTime<Utc>>,
+    /// When the playback of the current video has started
+    #[serde(skip_serializing)]
+    pub watch_history_start: Option<DateTime<Utc>>,
     #[serde(skip_serializing)]
     #[derivative(Default(value = "Utc.timestamp_opt(0, 0).unwrap()"))]
     pub push_library_item_time: DateTime<Utc>,
@@ -126,6 +191,11 @@ impl<E: Env + 'static> UpdateWithCtx<E> for Player {
     fn update(&mut self, msg: &Msg, ctx: &Ctx) -> Effects {
         match msg {
             Msg::Action(Action::Load(ActionLoad::Player(selected))) => {
+                let watch_history_effects = watch_history_update::<E>(
+                    &mut self.watch_history_start,
+                    &self.selected,
+                    &self.library_item,
+                );
                 let item_state_update_effects = if self
                     .selected
                     .as_ref()
@@ -136,11 +206,18 @@ impl<E: Env + 'static> UpdateWithCtx<E> for Player {
                         .as_ref()
                         .map(|meta_request| &meta_request.path.id)
                 {
-                    item_state_update(&mut self.library_item, &self.next_video)
+                    item_state_update(
+                        &mut self.library_item,
+                        &self.next_video,
+                        &self.next_queue_item,
+                    )
                 } else {
                     Effects::none().unchanged()
                 };
                 let selected_effects = eq_update(&mut self.selected, Some(*selected.to_owned()));
+                let queue_effects = queue_update(&mut self.queue, &self.selected);
+                let next_queue_item_effects =
+                    eq_update(&mut self.next_queue_item, self.queue.first().cloned());
                 let meta_item_effects = match &selected.meta_request {
                     Some(meta_request) => match &mut self.meta_item {
                         Some(meta_item) => resource_update::<E, _>(
@@ -166,29 +243,52 @@ impl<E: Env + 'static> UpdateWithCtx<E> for Player {
                     },
                     _ => eq_update(&mut self.meta_item, None),
                 };
+                let parental_controls_effects = parental_controls_selected_update(
+                    &mut self.selected,
+                    &mut self.meta_item,
+                    &ctx.library,
+                    ctx.parental_controls(),
+                );
                 let stream_state_effects = eq_update(&mut self.stream_state, None);
                 let video_params_effects = eq_update(&mut self.video_params, None);
+                let subtitles_sync_effects = eq_update(&mut self.subtitles_sync, None);
                 let subtitles_effects = subtitles_update::<E>(
                     &mut self.subtitles,
                     &self.selected,
                     &self.video_params,
                     &ctx.profile.addons,
                 );
+                let aggregated_subtitles_effects = aggregated_subtitles_update(
+                    &mut self.aggregated_subtitles,
+                    &self.selected,
+                    &self.subtitles,
+                    &self.stream_state,
+                    &ctx.profile.settings,
+                );
+                let selected_subtitles_effects = selected_subtitles_update(
+                    &mut self.selected_subtitles,
+                    &self.aggregated_subtitles,
+                    &self.stream_state,
+                    &ctx.profile.settings,
+                );
                 let next_video_effects = next_video_update(
                     &mut self.next_video,
                     &self.next_stream,
                     &self.selected,
                     &self.meta_item,
+                    &self.next_queue_item,
                     &ctx.profile.settings,
                 );
                 let next_streams_effects = next_streams_update::<E>(
                     &mut self.next_streams,
                     &self.next_video,
+                    &self.next_queue_item,
                     &self.selected,
                 );
                 let next_stream_effects = next_stream_update(
                     &mut self.next_stream,
                     &self.next_streams,
+                    &self.next_queue_item,
                     &self.selected,
                     &ctx.profile.settings,
                 );
@@ -213,6 +313,8 @@ impl<E: Env + 'static> UpdateWithCtx<E> for Player {
                     &self.meta_item,
                     &ctx.library,
                 );
+                let resume_point_effects =
+                    resume_point_update(&mut self.resume_point, &self.selected, &self.library_item);
                 let watched_effects =
                     watched_update(&mut self.watched, &self.meta_item, &self.library_item);
 
@@ -265,20 +367,28 @@ impl<E: Env + 'static> UpdateWithCtx<E> for Player {
                 self.paused = None;
                 item_state_update_effects
                     .join(selected_effects)
+                    .join(queue_effects)
+                    .join(next_queue_item_effects)
                     .join(meta_item_effects)
+                    .join(parental_controls_effects)
                     .join(stream_state_effects)
                     .join(video_params_effects)
                     .join(subtitles_effects)
+                    .join(aggregated_subtitles_effects)
+                    .join(selected_subtitles_effects)
+                    .join(subtitles_sync_effects)
                     .join(next_video_effects)
                     .join(next_streams_effects)
                     .join(next_stream_effects)
                     .join(update_streams_effects)
                     .join(series_info_effects)
                     .join(library_item_effects)
+                    .join(resume_point_effects)
                     .join(watched_effects)
                     .join(skip_gaps_effects)
                     .join(intro_outro_update_effects)
                     .join(notification_effects)
+                    .join(watch_history_effects)
             }
             Msg::Action(Action::Unload) => {
                 let ended_effects = if !self.ended && self.selected.is_some() {
@@ -300,8 +410,16 @@ impl<E: Env + 'static> UpdateWithCtx<E> for Player {
                     None,
                 );
 
-                let item_state_update_effects =
-                    item_state_update(&mut self.library_item, &self.next_video);
+                let watch_history_effects = watch_history_update::<E>(
+                    &mut self.watch_history_start,
+                    &self.selected,
+                    &self.library_item,
+                );
+                let item_state_update_effects = item_state_update(
+                    &mut self.library_item,
+                    &self.next_video,
+                    &self.next_queue_item,
+                );
                 let push_to_library_effects = match &self.library_item {
                     Some(library_item) => Effects::msg(Msg::Internal(Internal::UpdateLibraryItem(
                         library_item.to_owned(),
@@ -314,11 +432,17 @@ impl<E: Env + 'static> UpdateWithCtx<E> for Player {
                 let meta_item_effects = eq_update(&mut self.meta_item, None);
                 let stream_state_effects = eq_update(&mut self.stream_state, None);
                 let subtitles_effects = eq_update(&mut self.subtitles, vec![]);
+                let aggregated_subtitles_effects =
+                    eq_update(&mut self.aggregated_subtitles, vec![]);
+                let selected_subtitles_effects = eq_update(&mut self.selected_subtitles, None);
+                let subtitles_sync_effects = eq_update(&mut self.subtitles_sync, None);
                 let next_video_effects = eq_update(&mut self.next_video, None);
+                let next_queue_item_effects = eq_update(&mut self.next_queue_item, None);
                 let next_streams_effects = eq_update(&mut self.next_streams, None);
                 let next_stream_effects = eq_update(&mut self.next_stream, None);
                 let series_info_effects = eq_update(&mut self.series_info, None);
                 let library_item_effects = eq_update(&mut self.library_item, None);
+                let resume_point_effects = eq_update(&mut self.resume_point, None);
                 let watched_effects = eq_update(&mut self.watched, None);
                 let skip_gaps_effects = eq_update(&mut self.skip_gaps, None);
                 self.analytics_context = None;
@@ -328,6 +452,7 @@ impl<E: Env + 'static> UpdateWithCtx<E> for Player {
                 self.paused = None;
 
                 seek_history_effects
+                    .join(watch_history_effects)
                     .join(item_state_update_effects)
                     .join(push_to_library_effects)
                     .join(selected_effects)
@@ -335,11 +460,16 @@ impl<E: Env + 'static> UpdateWithCtx<E> for Player {
                     .join(meta_item_effects)
                     .join(stream_state_effects)
                     .join(subtitles_effects)
+                    .join(aggregated_subtitles_effects)
+                    .join(selected_subtitles_effects)
+                    .join(subtitles_sync_effects)
                     .join(next_video_effects)
+                    .join(next_queue_item_effects)
                     .join(next_streams_effects)
                     .join(next_stream_effects)
                     .join(series_info_effects)
                     .join(library_item_effects)
+                    .join(resume_point_effects)
                     .join(watched_effects)
                     .join(skip_gaps_effects)
                     .join(ended_effects)
@@ -353,6 +483,19 @@ impl<E: Env + 'static> UpdateWithCtx<E> for Player {
                     &self.video_params,
                     &ctx.profile.addons,
                 );
+                let aggregated_subtitles_effects = aggregated_subtitles_update(
+                    &mut self.aggregated_subtitles,
+                    &self.selected,
+                    &self.subtitles,
+                    &self.stream_state,
+                    &ctx.profile.settings,
+                );
+                let selected_subtitles_effects = selected_subtitles_update(
+                    &mut self.selected_subtitles,
+                    &self.aggregated_subtitles,
+                    &self.stream_state,
+                    &ctx.profile.settings,
+                );
                 let skip_gaps_effects = skip_gaps_update::<E>(
                     &ctx.profile,
                     self.selected.as_ref(),
@@ -364,8 +507,26 @@ impl<E: Env + 'static> UpdateWithCtx<E> for Player {
 
                 video_params_effects
                     .join(subtitles_effects)
+                    .join(aggregated_subtitles_effects)
+                    .join(selected_subtitles_effects)
                     .join(skip_gaps_effects)
             }
+            Msg::Action(Action::Player(ActionPlayer::SyncSubtitles { cues, reference }))
+                if self.selected.is_some() =>
+            {
+                let reference = match reference {
+                    Some(reference) => reference.as_slice(),
+                    _ => self
+                        .video_params
+                        .as_ref()
+                        .map(|video_params| video_params.timing_hints.as_slice())
+                        .unwrap_or_default(),
+                };
+                eq_update(
+                    &mut self.subtitles_sync,
+                    SubtitlesSync::estimate(cues, reference),
+                )
+            }
             Msg::Action(Action::Player(ActionPlayer::StreamStateChanged { state })) => {
                 Effects::msg(Msg::Internal(Internal::StreamStateChanged {
                     state: state.to_owned(),
@@ -397,6 +558,7 @@ impl<E: Env + 'static> UpdateWithCtx<E> for Player {
                     Some(library_item),
                 ) => {
                     let seeking = library_item.state.time_offset.abs_diff(*time) > 1000;
+                    self.watch_history_start.get_or_insert_with(E::now);
 
                     // if we've selected a new video (like the next episode)
                     library_item.state.last_watched = Some(E::now());
@@ -432,6 +594,19 @@ impl<E: Env + 'static> UpdateWithCtx<E> for Player {
                     };
                     time.clone_into(&mut library_item.state.time_offset);
                     duration.clone_into(&mut library_item.state.duration);
+                    // the videos watched up to the credits start over the next time
+                    let resume_time_offset =
+                        if *time as f64 > *duration as f64 * CREDITS_THRESHOLD_COEF {
+                            0
+                        } else {
+                            *time
+                        };
+                    library_item.state.set_resume_point(
+                        video_id,
+                        resume_time_offset,
+                        *duration,
+                        E::now(),
+                    );
                     if library_item.state.flagged_watched == 0
                         && library_item.state.time_watched as f64
                             > library_item.state.duration as f64 * WATCHED_THRESHOLD_COEF
@@ -549,6 +724,16 @@ impl<E: Env + 'static> UpdateWithCtx<E> for Player {
                     .map(|library_item| {
                         // instantly update the library item's time_offset.
                         library_item.state.time_offset = 0;
+                        if let Some(stream_request) = self
+                            .selected
+                            .as_ref()
+                            .and_then(|selected| selected.stream_request.as_ref())
+                        {
+                            library_item
+                                .state
+                                .resume_points
+                                .remove(&stream_request.path.id);
+                        }
 
                         Effects::msg(Msg::Internal(Internal::UpdateLibraryItem(
                             library_item.to_owned(),
@@ -557,6 +742,8 @@ impl<E: Env + 'static> UpdateWithCtx<E> for Player {
                     })
                     .unwrap_or(Effects::none().unchanged());
 
+                let queue_effects = pop_queue_update(&mut self.queue, &self.next_queue_item);
+
                 // Load will actually take care of loading the next video
                 seek_history_effects
                     .join(
@@ -568,18 +755,75 @@ impl<E: Env + 'static> UpdateWithCtx<E> for Player {
                         .unchanged(),
                     )
                     .join(library_item_effects)
+                    .join(queue_effects)
             }
             Msg::Action(Action::Player(ActionPlayer::Ended)) if self.selected.is_some() => {
                 self.ended = true;
+                let queue_effects = pop_queue_update(&mut self.queue, &self.next_queue_item);
                 Effects::msg(Msg::Event(Event::PlayerEnded {
                     context: self.analytics_context.as_ref().cloned().unwrap_or_default(),
                     is_binge_enabled: ctx.profile.settings.binge_watching,
                     is_playing_next_video: self.next_video.is_some(),
                 }))
                 .unchanged()
+                .join(queue_effects)
+            }
+            Msg::Action(Action::Player(
+                action @ (ActionPlayer::Enqueue(_)
+                | ActionPlayer::Dequeue { .. }
+                | ActionPlayer::MoveQueueItem { .. }
+                | ActionPlayer::SkipQueue { .. }
+                | ActionPlayer::ClearQueue),
+            )) => {
+                let queue_effects = queue_action_update(&mut self.queue, action);
+                let next_queue_item_effects =
+                    eq_update(&mut self.next_queue_item, self.queue.first().cloned());
+                let next_video_effects = next_video_update(
+                    &mut self.next_video,
+                    &self.next_stream,
+                    &self.selected,
+                    &self.meta_item,
+                    &self.next_queue_item,
+                    &ctx.profile.settings,
+                );
+                let next_streams_effects = next_streams_update::<E>(
+                    &mut self.next_streams,
+                    &self.next_video,
+                    &self.next_queue_item,
+                    &self.selected,
+                );
+                let next_stream_effects = next_stream_update(
+                    &mut self.next_stream,
+                    &self.next_streams,
+                    &self.next_queue_item,
+                    &self.selected,
+                    &ctx.profile.settings,
+                );
+                queue_effects
+                    .join(next_queue_item_effects)
+                    .join(next_video_effects)
+                    .join(next_streams_effects)
+                    .join(next_stream_effects)
             }
             Msg::Internal(Internal::StreamsChanged(_)) => {
-                stream_state_update(&mut self.stream_state, &self.selected, &ctx.streams)
+                let stream_state_effects =
+                    stream_state_update(&mut self.stream_state, &self.selected, &ctx.streams);
+                let aggregated_subtitles_effects = aggregated_subtitles_update(
+                    &mut self.aggregated_subtitles,
+                    &self.selected,
+                    &self.subtitles,
+                    &self.stream_state,
+                    &ctx.profile.settings,
+                );
+                let selected_subtitles_effects = selected_subtitles_update(
+                    &mut self.selected_subtitles,
+                    &self.aggregated_subtitles,
+                    &self.stream_state,
+                    &ctx.profile.settings,
+                );
+                stream_state_effects
+                    .join(aggregated_subtitles_effects)
+                    .join(selected_subtitles_effects)
             }
             Msg::Internal(Internal::ResourceRequestResult(request, result))
                 if self.selected.is_some() =>
@@ -593,6 +837,12 @@ impl<E: Env + 'static> UpdateWithCtx<E> for Player {
                     }
                     _ => Effects::none().unchanged(),
                 };
+                let parental_controls_effects = parental_controls_selected_update(
+                    &mut self.selected,
+                    &mut self.meta_item,
+                    &ctx.library,
+                    ctx.parental_controls(),
+                );
 
                 let update_streams_effects = match (&self.selected, &self.meta_item) {
                     (Some(selected), Some(meta_item))
@@ -616,6 +866,19 @@ impl<E: Env + 'static> UpdateWithCtx<E> for Player {
                 } else {
                     Effects::none().unchanged()
                 };
+                let aggregated_subtitles_effects = aggregated_subtitles_update(
+                    &mut self.aggregated_subtitles,
+                    &self.selected,
+                    &self.subtitles,
+                    &self.stream_state,
+                    &ctx.profile.settings,
+                );
+                let selected_subtitles_effects = selected_subtitles_update(
+                    &mut self.selected_subtitles,
+                    &self.aggregated_subtitles,
+                    &self.stream_state,
+                    &ctx.profile.settings,
+                );
 
                 let next_streams_effects = match self.next_streams.as_mut() {
                     Some(next_streams) if request.path.resource == STREAM_RESOURCE_NAME => {
@@ -632,18 +895,21 @@ impl<E: Env + 'static> UpdateWithCtx<E> for Player {
                     &self.next_stream,
                     &self.selected,
                     &self.meta_item,
+                    &self.next_queue_item,
                     &ctx.profile.settings,
                 );
 
                 let next_streams_effects = next_streams_effects.join(next_streams_update::<E>(
                     &mut self.next_streams,
                     &self.next_video,
+                    &self.next_queue_item,
                     &self.selected,
                 ));
 
                 let next_stream_effects = next_stream_update(
                     &mut self.next_stream,
                     &self.next_streams,
+                    &self.next_queue_item,
                     &self.selected,
                     &ctx.profile.settings,
                 );
@@ -691,8 +957,11 @@ impl<E: Env + 'static> UpdateWithCtx<E> for Player {
                     analytics_context.duration = duration;
                 };
                 meta_item_effects
+                    .join(parental_controls_effects)
                     .join(update_streams_effects)
                     .join(subtitles_effects)
+                    .join(aggregated_subtitles_effects)
+                    .join(selected_subtitles_effects)
                     .join(next_video_effects)
                     .join(next_streams_effects)
                     .join(next_stream_effects)
@@ -728,7 +997,20 @@ impl<E: Env + 'static> UpdateWithCtx<E> for Player {
                 if let Some(analytics_context) = &mut self.analytics_context {
                     analytics_context.has_trakt = ctx.profile.has_trakt::<E>();
                 };
-                Effects::none().unchanged()
+                let aggregated_subtitles_effects = aggregated_subtitles_update(
+                    &mut self.aggregated_subtitles,
+                    &self.selected,
+                    &self.subtitles,
+                    &self.stream_state,
+                    &ctx.profile.settings,
+                );
+                let selected_subtitles_effects = selected_subtitles_update(
+                    &mut self.selected_subtitles,
+                    &self.aggregated_subtitles,
+                    &self.stream_state,
+                    &ctx.profile.settings,
+                );
+                aggregated_subtitles_effects.join(selected_subtitles_effects)
             }
             _ => Effects::none().unchanged(),
         }
@@ -753,9 +1035,77 @@ fn push_to_library<E: Env + 'static>(
     }
 }
 
+fn queue_action_update(queue: &mut Vec<QueueItem>, action: &ActionPlayer) -> Effects {
+    let mut next_queue = queue.to_owned();
+    match action {
+        ActionPlayer::Enqueue(item) => next_queue.push(*item.to_owned()),
+        ActionPlayer::Dequeue { index } if *index < next_queue.len() => {
+            next_queue.remove(*index);
+        }
+        ActionPlayer::MoveQueueItem { from, to }
+            if *from < next_queue.len() && *to < next_queue.len() =>
+        {
+            let item = next_queue.remove(*from);
+            next_queue.insert(*to, item);
+        }
+        ActionPlayer::SkipQueue { index } => {
+            next_queue.drain(..(*index).min(next_queue.len()));
+        }
+        ActionPlayer::ClearQueue => next_queue.clear(),
+        _ => {}
+    };
+    eq_update(queue, next_queue)
+}
+
+/// Pops the next queued item from the queue, as it's going to be played next.
+///
+/// The item is kept in [`Player::next_queue_item`] until it's loaded,
+/// so it's popped only once when both `Ended` and `NextVideo` are dispatched.
+fn pop_queue_update(queue: &mut Vec<QueueItem>, next_queue_item: &Option<QueueItem>) -> Effects {
+    if next_queue_item.is_some() && queue.first() == next_queue_item.as_ref() {
+        queue.remove(0);
+        Effects::none()
+    } else {
+        Effects::none().unchanged()
+    }
+}
+
+/// Removes the selected item from the queue, as it's being played
+fn queue_update(queue: &mut Vec<QueueItem>, selected: &Option<Selected>) -> Effects {
+    match selected
+        .as_ref()
+        .and_then(|selected| queue.iter().position(|item| item.is_selected(selected)))
+    {
+        Some(position) => {
+            queue.remove(position);
+            Effects::none()
+        }
+        None => Effects::none().unchanged(),
+    }
+}
+
+fn resume_point_update(
+    resume_point: &mut Option<ResumePoint>,
+    selected: &Option<Selected>,
+    library_item: &Option<LibraryItem>,
+) -> Effects {
+    let next_resume_point = match (selected, library_item) {
+        (
+            Some(Selected {
+                stream_request: Some(stream_request),
+                ..
+            }),
+            Some(library_item),
+        ) => library_item.state.resume_point(&stream_request.path.id),
+        _ => None,
+    };
+    eq_update(resume_point, next_resume_point)
+}
+
 fn item_state_update(
     library_item: &mut Option<LibraryItem>,
     next_video: &Option<Video>,
+    next_queue_item: &Option<QueueItem>,
 ) -> Effects {
     match library_item {
         Some(library_item)
@@ -763,6 +1113,12 @@ fn item_state_update(
                 > library_item.state.duration as f64 * CREDITS_THRESHOLD_COEF =>
         {
             library_item.state.time_offset = 0;
+            // the next queued item can be of another meta item
+            let next_video = next_video.as_ref().filter(|_| {
+                next_queue_item
+                    .as_ref()
+                    .map_or(true, |item| item.meta_request.path.id == library_item.id)
+            });
             if let Some(next_video) = next_video {
                 library_item.state.video_id = Some(next_video.id.to_owned());
                 library_item.state.overall_time_watched = library_item
@@ -779,6 +1135,34 @@ fn item_state_update(
     Effects::none().unchanged()
 }
 
+/// Records the playback of the current video in the watch history, if it has been started
+fn watch_history_update<E: Env + 'static>(
+    watch_history_start: &mut Option<DateTime<Utc>>,
+    selected: &Option<Selected>,
+    library_item: &Option<LibraryItem>,
+) -> Effects {
+    match (watch_history_start.take(), selected, library_item) {
+        (Some(start), Some(selected), Some(library_item)) => match &library_item.state.video_id {
+            Some(video_id) => Effects::msg(Msg::Internal(Internal::WatchHistoryItemRecorded(
+                WatchHistoryItem {
+                    meta_id: library_item.id.to_owned(),
+                    video_id: video_id.to_owned(),
+                    r#type: library_item.r#type.to_owned(),
+                    name: library_item.name.to_owned(),
+                    stream_source: selected.stream.source.to_owned(),
+                    start,
+                    end: E::now(),
+                    time_offset: library_item.state.time_offset,
+                    duration: library_item.state.duration,
+                },
+            )))
+            .unchanged(),
+            None => Effects::none().unchanged(),
+        },
+        _ => Effects::none().unchanged(),
+    }
+}
+
 fn stream_state_update(
     state: &mut Option<StreamItemState>,
     selected: &Option<Selected>,
@@ -809,8 +1193,17 @@ fn next_video_update(
     stream: &Option<Stream>,
     selected: &Option<Selected>,
     meta_item: &Option<ResourceLoadable<MetaItem>>,
+    next_queue_item: &Option<QueueItem>,
     settings: &ProfileSettings,
 ) -> Effects {
+    // the queued items are played before the next video of the series
+    if let Some(next_queue_item) = next_queue_item {
+        let mut next_video = next_queue_item.video.to_owned();
+        if let Some(stream) = &next_queue_item.stream {
+            next_video.streams = vec![stream.to_owned()];
+        }
+        return eq_update(video, Some(next_video));
+    }
     let next_video = match (selected, meta_item) {
         (
             Some(Selected {
@@ -863,11 +1256,16 @@ fn next_video_update(
 fn next_streams_update<E>(
     next_streams: &mut Option<ResourceLoadable<Vec<Stream>>>,
     next_video: &Option<Video>,
+    next_queue_item: &Option<QueueItem>,
     selected: &Option<Selected>,
 ) -> Effects
 where
     E: Env + 'static,
 {
+    // the stream of a queued item is picked when it's enqueued
+    if next_queue_item.is_some() {
+        return eq_update(next_streams, None);
+    }
     let mut stream_request = match selected
         .as_ref()
         .and_then(|selected| selected.stream_request.as_ref())
@@ -911,9 +1309,13 @@ where
 fn next_stream_update(
     stream: &mut Option<Stream>,
     next_streams: &Option<ResourceLoadable<Vec<Stream>>>,
+    next_queue_item: &Option<QueueItem>,
     selected: &Option<Selected>,
     settings: &ProfileSettings,
 ) -> Effects {
+    if let Some(next_queue_item) = next_queue_item {
+        return eq_update(stream, next_queue_item.stream.to_owned());
+    }
     let next_stream = match (selected, next_streams) {
         (
             Some(Selected { stream, .. }),
@@ -961,6 +1363,38 @@ fn series_info_update(
     eq_update(series_info, next_series_info)
 }
 
+/// Unloads the selected stream when its meta item or library item
+/// is restricted by the parental controls, so it can't be played.
+fn parental_controls_selected_update(
+    selected: &mut Option<Selected>,
+    meta_item: &mut Option<ResourceLoadable<MetaItem>>,
+    library: &LibraryBucket,
+    parental_controls: Option<&ParentalControls>,
+) -> Effects {
+    if let Some(meta_item) = meta_item {
+        parental_controls_update(meta_item, parental_controls);
+    }
+    let meta_item_restricted = matches!(
+        meta_item,
+        Some(ResourceLoadable {
+            content: Some(Loadable::Err(ResourceError::Restricted)),
+            ..
+        })
+    );
+    let library_item_restricted = selected
+        .as_ref()
+        .and_then(|selected| selected.meta_request.as_ref())
+        .and_then(|meta_request| library.items.get(&meta_request.path.id))
+        .map_or(false, |library_item| {
+            !is_allowed_by(library_item, parental_controls)
+        });
+    if meta_item_restricted || library_item_restricted {
+        eq_update(selected, None)
+    } else {
+        Effects::none().unchanged()
+    }
+}
+
 fn library_item_update<E: Env + 'static>(
     library_item: &mut Option<LibraryItem>,
     selected: &Option<Selected>,
@@ -1076,6 +1510,108 @@ fn subtitles_update<E: Env + 'static>(
     }
 }
 
+fn aggregated_subtitles_update(
+    aggregated_subtitles: &mut Vec<PlayerSubtitles>,
+    selected: &Option<Selected>,
+    subtitles: &[ResourceLoadable<Vec<Subtitles>>],
+    stream_state: &Option<StreamItemState>,
+    settings: &ProfileSettings,
+) -> Effects {
+    let stream_subtitles = selected.iter().flat_map(|selected| {
+        selected
+            .stream
+            .subtitles
+            .iter()
+            .enumerate()
+            .map(|(position, subtitles)| PlayerSubtitles {
+                id: format!("stream_{position}"),
+                subtitles: subtitles.to_owned(),
+                source: SubtitlesSource::Stream,
+            })
+    });
+    let addons_subtitles = subtitles
+        .iter()
+        .filter_map(|subtitles| match subtitles {
+            ResourceLoadable {
+                request,
+                content: Some(Loadable::Ready(subtitles)),
+            } => Some((request, subtitles)),
+            _ => None,
+        })
+        .flat_map(|(request, subtitles)| {
+            subtitles
+                .iter()
+                .enumerate()
+                .map(move |(position, subtitles)| PlayerSubtitles {
+                    // same as the ids the apps have stored for addon subtitles
+                    id: format!("{}_{}", request.base, position),
+                    subtitles: subtitles.to_owned(),
+                    source: SubtitlesSource::Addon {
+                        transport_url: request.base.to_owned(),
+                    },
+                })
+        });
+    let subtitle_track = stream_state
+        .as_ref()
+        .and_then(|stream_state| stream_state.subtitle_track.as_ref());
+    let is_language = |subtitles: &PlayerSubtitles, lang: &Option<String>| {
+        lang.as_ref().map_or(false, |lang| {
+            is_same_language(&subtitles.subtitles.lang, lang)
+        })
+    };
+    let next_aggregated_subtitles = stream_subtitles
+        .chain(addons_subtitles)
+        .unique_by(|subtitles| {
+            (
+                subtitles.subtitles.url.to_owned(),
+                normalize_language(&subtitles.subtitles.lang),
+            )
+        })
+        // sorting is stable, the stream subtitles and the addons order are kept within a rank
+        .sorted_by_key(|subtitles| match subtitle_track {
+            Some(track) if !track.embedded && track.id == subtitles.id => 0,
+            Some(track) if is_language(subtitles, &track.language) => 1,
+            _ if is_language(subtitles, &settings.subtitles_language) => 2,
+            _ if is_language(subtitles, &settings.secondary_subtitles_language) => 3,
+            _ => 4,
+        })
+        .collect::<Vec<_>>();
+    eq_update(aggregated_subtitles, next_aggregated_subtitles)
+}
+
+fn selected_subtitles_update(
+    selected_subtitles: &mut Option<PlayerSubtitles>,
+    aggregated_subtitles: &[PlayerSubtitles],
+    stream_state: &Option<StreamItemState>,
+    settings: &ProfileSettings,
+) -> Effects {
+    let find_language = |lang: &String| {
+        aggregated_subtitles
+            .iter()
+            .find(|subtitles| is_same_language(&subtitles.subtitles.lang, lang))
+    };
+    let next_selected_subtitles = match stream_state
+        .as_ref()
+        .and_then(|stream_state| stream_state.subtitle_track.as_ref())
+    {
+        // embedded tracks are only known to the video player
+        Some(track) if track.embedded => None,
+        Some(track) => aggregated_subtitles
+            .iter()
+            .find(|subtitles| subtitles.id == track.id)
+            .or_else(|| track.language.as_ref().and_then(find_language)),
+        None => [
+            &settings.subtitles_language,
+            &settings.secondary_subtitles_language,
+        ]
+        .into_iter()
+        .flatten()
+        .find_map(find_language),
+    }
+    .cloned();
+    eq_update(selected_subtitles, next_selected_subtitles)
+}
+
 fn seek_update<E: Env + 'static>(
     selected: Option<&Selected>,
     video_params: Option<&VideoParams>,
@@ -1388,8 +1924,14 @@ mod tests {
                 video_id: None,
                 watched: None,
                 no_notif: true,
+                watch_status: None,
+                rating: None,
+                notes: None,
+                resume_points: Default::default(),
             },
             behavior_hints: Default::default(),
+            genres: vec![],
+            certification: None,
         };
         {
             let closest_duration = 11000;
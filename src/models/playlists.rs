@@ -0,0 +1,57 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::common::eq_update;
+use crate::models::ctx::Ctx;
+use crate::runtime::msg::{Action, ActionLoad, Internal, Msg};
+use crate::runtime::{Effects, Env, UpdateWithCtx};
+use crate::types::playlists::{Playlist, PlaylistsBucket};
+
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Selected {
+    /// The id of the opened playlist, `None` for only listing the playlists
+    pub id: Option<String>,
+}
+
+#[derive(Default, Clone, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Playlists {
+    pub selected: Option<Selected>,
+    /// The opened playlist, `None` if it was not found
+    pub playlist: Option<Playlist>,
+}
+
+impl<E: Env + 'static> UpdateWithCtx<E> for Playlists {
+    fn update(&mut self, msg: &Msg, ctx: &Ctx) -> Effects {
+        match msg {
+            Msg::Action(Action::Load(ActionLoad::Playlists(selected))) => {
+                let selected_effects = eq_update(&mut self.selected, Some(selected.to_owned()));
+                let playlist_effects =
+                    playlist_update(&mut self.playlist, &self.selected, &ctx.playlists);
+                selected_effects.join(playlist_effects)
+            }
+            Msg::Action(Action::Unload) => {
+                let selected_effects = eq_update(&mut self.selected, None);
+                let playlist_effects = eq_update(&mut self.playlist, None);
+                selected_effects.join(playlist_effects)
+            }
+            Msg::Internal(Internal::PlaylistsChanged) => {
+                playlist_update(&mut self.playlist, &self.selected, &ctx.playlists)
+            }
+            _ => Effects::none().unchanged(),
+        }
+    }
+}
+
+fn playlist_update(
+    playlist: &mut Option<Playlist>,
+    selected: &Option<Selected>,
+    playlists: &PlaylistsBucket,
+) -> Effects {
+    let next_playlist = selected
+        .as_ref()
+        .and_then(|selected| selected.id.as_ref())
+        .and_then(|id| playlists.get(id))
+        .cloned();
+    eq_update(playlist, next_playlist)
+}
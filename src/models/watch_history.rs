@@ -0,0 +1,103 @@
+use std::num::NonZeroUsize;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::constants::CATALOG_PAGE_SIZE;
+use crate::models::common::eq_update;
+use crate::models::ctx::Ctx;
+use crate::models::library_with_filters::LibraryRequestPage;
+use crate::runtime::msg::{Action, ActionLoad, Internal, Msg};
+use crate::runtime::{Effects, Env, UpdateWithCtx};
+use crate::types::watch_history::{WatchHistoryBucket, WatchHistoryItem};
+
+#[derive(Default, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Selected {
+    /// Only the playbacks of the meta item with this id
+    #[serde(default)]
+    pub meta_id: Option<String>,
+    /// Only the playbacks which have stopped at or after this date
+    #[serde(default)]
+    pub from: Option<DateTime<Utc>>,
+    /// Only the playbacks which have started before this date
+    #[serde(default)]
+    pub to: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub page: LibraryRequestPage,
+}
+
+#[derive(Default, Clone, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct WatchHistory {
+    pub selected: Option<Selected>,
+    /// The playbacks of the selected pages, the most recent first
+    pub items: Vec<WatchHistoryItem>,
+    pub next_page: Option<Selected>,
+}
+
+impl<E: Env + 'static> UpdateWithCtx<E> for WatchHistory {
+    fn update(&mut self, msg: &Msg, ctx: &Ctx) -> Effects {
+        match msg {
+            Msg::Action(Action::Load(ActionLoad::WatchHistory(selected))) => {
+                let selected_effects = eq_update(&mut self.selected, Some(selected.to_owned()));
+                let items_effects = items_update(
+                    &mut self.items,
+                    &mut self.next_page,
+                    &self.selected,
+                    &ctx.watch_history,
+                );
+                selected_effects.join(items_effects)
+            }
+            Msg::Action(Action::Unload) => {
+                let selected_effects = eq_update(&mut self.selected, None);
+                let items_effects = eq_update(&mut self.items, vec![]);
+                let next_page_effects = eq_update(&mut self.next_page, None);
+                selected_effects.join(items_effects).join(next_page_effects)
+            }
+            Msg::Internal(Internal::WatchHistoryChanged) => items_update(
+                &mut self.items,
+                &mut self.next_page,
+                &self.selected,
+                &ctx.watch_history,
+            ),
+            _ => Effects::none().unchanged(),
+        }
+    }
+}
+
+fn items_update(
+    items: &mut Vec<WatchHistoryItem>,
+    next_page: &mut Option<Selected>,
+    selected: &Option<Selected>,
+    watch_history: &WatchHistoryBucket,
+) -> Effects {
+    let (next_items, next_next_page) = match selected {
+        Some(selected) => {
+            let mut filtered_items = watch_history
+                .items
+                .iter()
+                .rev()
+                .filter(|item| {
+                    selected
+                        .meta_id
+                        .as_ref()
+                        .map_or(true, |meta_id| item.meta_id == *meta_id)
+                })
+                .filter(|item| selected.from.map_or(true, |from| item.end >= from))
+                .filter(|item| selected.to.map_or(true, |to| item.start < to));
+            let next_items = filtered_items
+                .by_ref()
+                .take(selected.page.get() * CATALOG_PAGE_SIZE)
+                .cloned()
+                .collect::<Vec<_>>();
+            let next_next_page = filtered_items.next().map(|_| Selected {
+                page: LibraryRequestPage(NonZeroUsize::new(selected.page.get() + 1).unwrap()),
+                ..selected.to_owned()
+            });
+            (next_items, next_next_page)
+        }
+        None => (vec![], None),
+    };
+    eq_update(items, next_items).join(eq_update(next_page, next_next_page))
+}
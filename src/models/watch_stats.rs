@@ -0,0 +1,395 @@
+//! Watch statistics and the year in review
+//!
+//! Only the aggregates stored in the [`LibraryItem`]s are available, so the items are
+//! grouped by the month and the year they were last watched.
+//!
+//! The streaks are counted from the days of the plays in the [`WatchHistoryBucket`], along with
+//! the days the items were last watched. The watch history is kept on the device and only
+//! the latest [`WATCH_HISTORY_MAX_ITEMS`] plays, so the older streaks can be shorter
+//! than they were.
+
+use std::collections::{BTreeSet, HashMap};
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc};
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    constants::WATCH_HISTORY_MAX_ITEMS,
+    models::{
+        common::{eq_update, is_allowed_by},
+        ctx::Ctx,
+    },
+    runtime::{
+        msg::{Action, ActionLoad, Internal, Msg},
+        Effects, Env, UpdateWithCtx,
+    },
+    types::{
+        library::{LibraryBucket, LibraryItem},
+        profile::ParentalControls,
+        watch_history::WatchHistoryBucket,
+    },
+};
+
+/// The number of items in the most binged and the top items lists
+const TOP_ITEMS_COUNT: usize = 10;
+
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Selected {
+    /// The year of the year in review, the current year if `None`
+    pub year: Option<i32>,
+}
+
+#[derive(Clone, PartialEq, Eq, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TypeStats {
+    pub r#type: String,
+    pub items: usize,
+    /// In milliseconds
+    pub time_watched: u64,
+}
+
+#[derive(Clone, PartialEq, Eq, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GenreStats {
+    pub genre: String,
+    /// The items with the genre, an item is counted in every genre it has
+    pub items: usize,
+    /// In milliseconds
+    pub time_watched: u64,
+}
+
+#[derive(Clone, PartialEq, Eq, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct MonthStats {
+    pub year: i32,
+    /// From 1 to 12
+    pub month: u32,
+    pub items: usize,
+    /// In milliseconds
+    pub time_watched: u64,
+}
+
+#[derive(Default, Clone, PartialEq, Eq, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Streaks {
+    /// The consecutive days with plays until today or yesterday
+    pub current: u32,
+    pub longest: u32,
+}
+
+#[derive(Clone, PartialEq, Eq, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Stats {
+    /// The items which have been played at least once
+    pub items: usize,
+    /// The items which have been watched until the end
+    pub watched_items: usize,
+    /// The time watched of the last played videos, in milliseconds
+    pub time_watched: u64,
+    /// In milliseconds
+    pub overall_time_watched: u64,
+    /// The most watched type first
+    pub types: Vec<TypeStats>,
+    /// The most watched genre first
+    pub genres: Vec<GenreStats>,
+    pub streaks: Streaks,
+    /// The months with watched items, the oldest first
+    pub months: Vec<MonthStats>,
+    /// The series with the most time watched first
+    pub most_binged: Vec<LibraryItem>,
+}
+
+#[derive(Clone, PartialEq, Eq, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct YearInReview {
+    pub year: i32,
+    pub items: usize,
+    /// In milliseconds
+    pub overall_time_watched: u64,
+    /// The most watched type first
+    pub types: Vec<TypeStats>,
+    /// The most watched genre first
+    pub genres: Vec<GenreStats>,
+    /// All the months of the year, including the ones without watched items
+    pub months: Vec<MonthStats>,
+    /// The month with the most time watched, from 1 to 12
+    pub busiest_month: Option<u32>,
+    /// The items with the most time watched first
+    pub top_items: Vec<LibraryItem>,
+}
+
+#[derive(Default, Clone, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct WatchStats {
+    pub selected: Option<Selected>,
+    pub stats: Option<Stats>,
+    pub year_in_review: Option<YearInReview>,
+}
+
+impl<E: Env + 'static> UpdateWithCtx<E> for WatchStats {
+    fn update(&mut self, msg: &Msg, ctx: &Ctx) -> Effects {
+        match msg {
+            Msg::Action(Action::Load(ActionLoad::WatchStats(selected))) => {
+                let selected_effects = eq_update(&mut self.selected, Some(selected.to_owned()));
+                let stats_effects = stats_update::<E>(
+                    &mut self.stats,
+                    &mut self.year_in_review,
+                    &self.selected,
+                    &ctx.library,
+                    &ctx.watch_history,
+                    ctx.parental_controls(),
+                );
+                selected_effects.join(stats_effects)
+            }
+            Msg::Action(Action::Unload) => {
+                let selected_effects = eq_update(&mut self.selected, None);
+                let stats_effects = eq_update(&mut self.stats, None);
+                let year_in_review_effects = eq_update(&mut self.year_in_review, None);
+                selected_effects
+                    .join(stats_effects)
+                    .join(year_in_review_effects)
+            }
+            Msg::Internal(Internal::LibraryChanged(_))
+            | Msg::Internal(Internal::WatchHistoryChanged)
+            | Msg::Internal(Internal::ParentalControlsChanged) => stats_update::<E>(
+                &mut self.stats,
+                &mut self.year_in_review,
+                &self.selected,
+                &ctx.library,
+                &ctx.watch_history,
+                ctx.parental_controls(),
+            ),
+            _ => Effects::none().unchanged(),
+        }
+    }
+}
+
+fn stats_update<E: Env + 'static>(
+    stats: &mut Option<Stats>,
+    year_in_review: &mut Option<YearInReview>,
+    selected: &Option<Selected>,
+    library: &LibraryBucket,
+    watch_history: &WatchHistoryBucket,
+    parental_controls: Option<&ParentalControls>,
+) -> Effects {
+    let (next_stats, next_year_in_review) = match selected {
+        Some(selected) => {
+            let now = E::now();
+            let library_items = library
+                .items
+                .values()
+                .filter(|library_item| library_item.r#type != "other" && !library_item.temp)
+                .filter(|library_item| is_allowed_by(*library_item, parental_controls))
+                .filter(|library_item| {
+                    library_item.state.overall_time_watched > 0 || library_item.watched()
+                })
+                .collect::<Vec<_>>();
+            let year = selected.year.unwrap_or_else(|| now.year());
+            (
+                Some(Stats::new(&library_items, watch_history, now)),
+                Some(YearInReview::new(&library_items, year)),
+            )
+        }
+        None => (None, None),
+    };
+    eq_update(stats, next_stats).join(eq_update(year_in_review, next_year_in_review))
+}
+
+impl Stats {
+    fn new(
+        library_items: &[&LibraryItem],
+        watch_history: &WatchHistoryBucket,
+        now: DateTime<Utc>,
+    ) -> Self {
+        Stats {
+            items: library_items.len(),
+            watched_items: library_items
+                .iter()
+                .filter(|library_item| library_item.watched())
+                .count(),
+            time_watched: library_items
+                .iter()
+                .map(|library_item| library_item.state.time_watched)
+                .sum(),
+            overall_time_watched: overall_time_watched(library_items.iter().copied()),
+            types: types_stats(library_items.iter().copied()),
+            genres: genres_stats(library_items.iter().copied()),
+            streaks: Streaks::new(
+                library_items
+                    .iter()
+                    .filter_map(|library_item| library_item.state.last_watched)
+                    .chain(watch_history.items.iter().map(|item| item.start))
+                    .map(|day| day.date_naive())
+                    .collect(),
+                now.date_naive(),
+            ),
+            months: library_items
+                .iter()
+                .filter_map(|library_item| {
+                    library_item.state.last_watched.map(|last_watched| {
+                        ((last_watched.year(), last_watched.month()), *library_item)
+                    })
+                })
+                .into_group_map()
+                .into_iter()
+                .map(|((year, month), library_items)| MonthStats {
+                    year,
+                    month,
+                    items: library_items.len(),
+                    time_watched: overall_time_watched(library_items.into_iter()),
+                })
+                .sorted_by_key(|month| (month.year, month.month))
+                .collect(),
+            most_binged: top_items(
+                library_items
+                    .iter()
+                    .filter(|library_item| library_item.r#type == "series")
+                    .copied(),
+            ),
+        }
+    }
+}
+
+impl Streaks {
+    fn new(days: BTreeSet<NaiveDate>, today: NaiveDate) -> Self {
+        let (longest, last_streak, last_day) = days.iter().fold(
+            (0, 0, None::<NaiveDate>),
+            |(longest, streak, previous_day), day| {
+                let streak = match previous_day {
+                    Some(previous_day) if *day - previous_day == Duration::days(1) => streak + 1,
+                    _ => 1,
+                };
+                (longest.max(streak), streak, Some(*day))
+            },
+        );
+        let current = match last_day {
+            Some(last_day) if today - last_day <= Duration::days(1) => last_streak,
+            _ => 0,
+        };
+        Streaks { current, longest }
+    }
+}
+
+impl YearInReview {
+    fn new(library_items: &[&LibraryItem], year: i32) -> Self {
+        let library_items = library_items
+            .iter()
+            .filter(|library_item| {
+                library_item
+                    .state
+                    .last_watched
+                    .map_or(false, |last_watched| last_watched.year() == year)
+            })
+            .copied()
+            .collect::<Vec<_>>();
+        let months = (1..=12)
+            .map(|month| {
+                let month_items = library_items
+                    .iter()
+                    .filter(|library_item| {
+                        library_item
+                            .state
+                            .last_watched
+                            .map_or(false, |last_watched| last_watched.month() == month)
+                    })
+                    .copied()
+                    .collect::<Vec<_>>();
+                MonthStats {
+                    year,
+                    month,
+                    items: month_items.len(),
+                    time_watched: overall_time_watched(month_items.into_iter()),
+                }
+            })
+            .collect::<Vec<_>>();
+        YearInReview {
+            year,
+            items: library_items.len(),
+            overall_time_watched: overall_time_watched(library_items.iter().copied()),
+            types: types_stats(library_items.iter().copied()),
+            genres: genres_stats(library_items.iter().copied()),
+            busiest_month: months
+                .iter()
+                .filter(|month| month.time_watched > 0)
+                // the earliest month wins a tie
+                .rev()
+                .max_by_key(|month| month.time_watched)
+                .map(|month| month.month),
+            months,
+            top_items: top_items(library_items.into_iter()),
+        }
+    }
+}
+
+fn overall_time_watched<'a>(library_items: impl Iterator<Item = &'a LibraryItem>) -> u64 {
+    library_items
+        .map(|library_item| library_item.state.overall_time_watched)
+        .sum()
+}
+
+fn types_stats<'a>(library_items: impl Iterator<Item = &'a LibraryItem>) -> Vec<TypeStats> {
+    library_items
+        .fold(
+            HashMap::<&str, TypeStats>::new(),
+            |mut types, library_item| {
+                let type_stats = types
+                    .entry(&library_item.r#type)
+                    .or_insert_with(|| TypeStats {
+                        r#type: library_item.r#type.to_owned(),
+                        items: 0,
+                        time_watched: 0,
+                    });
+                type_stats.items += 1;
+                type_stats.time_watched += library_item.state.overall_time_watched;
+                types
+            },
+        )
+        .into_values()
+        .sorted_by(|a, b| {
+            b.time_watched
+                .cmp(&a.time_watched)
+                .then(a.r#type.cmp(&b.r#type))
+        })
+        .collect()
+}
+
+fn genres_stats<'a>(library_items: impl Iterator<Item = &'a LibraryItem>) -> Vec<GenreStats> {
+    library_items
+        .fold(
+            HashMap::<&str, GenreStats>::new(),
+            |mut genres, library_item| {
+                for genre in library_item.genres().unique() {
+                    let genre_stats = genres.entry(genre).or_insert_with(|| GenreStats {
+                        genre: genre.to_owned(),
+                        items: 0,
+                        time_watched: 0,
+                    });
+                    genre_stats.items += 1;
+                    genre_stats.time_watched += library_item.state.overall_time_watched;
+                }
+                genres
+            },
+        )
+        .into_values()
+        .sorted_by(|a, b| {
+            b.time_watched
+                .cmp(&a.time_watched)
+                .then(a.genre.cmp(&b.genre))
+        })
+        .collect()
+}
+
+fn top_items<'a>(library_items: impl Iterator<Item = &'a LibraryItem>) -> Vec<LibraryItem> {
+    library_items
+        .sorted_by(|a, b| {
+            b.state
+                .overall_time_watched
+                .cmp(&a.state.overall_time_watched)
+                .then(a.id.cmp(&b.id))
+        })
+        .take(TOP_ITEMS_COUNT)
+        .cloned()
+        .collect()
+}
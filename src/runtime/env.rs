@@ -1,11 +1,16 @@
 use crate::addon_transport::{AddonHTTPTransport, AddonTransport, UnsupportedTransport};
 use crate::constants::{
-    DISMISSED_EVENTS_STORAGE_KEY, LIBRARY_RECENT_STORAGE_KEY, LIBRARY_STORAGE_KEY,
-    PROFILE_STORAGE_KEY, SCHEMA_VERSION, SCHEMA_VERSION_STORAGE_KEY, SEARCH_HISTORY_STORAGE_KEY,
-    STREAMS_STORAGE_KEY,
+    ACCOUNTS_STORAGE_KEY, DISMISSED_EVENTS_STORAGE_KEY, LIBRARY_RECENT_STORAGE_KEY,
+    LIBRARY_STORAGE_KEY, OUTBOX_STORAGE_KEY, PLAYLISTS_STORAGE_KEY, PROFILE_STORAGE_KEY,
+    SCHEMA_VERSION, SCHEMA_VERSION_STORAGE_KEY, SEARCH_HISTORY_STORAGE_KEY, STREAMS_STORAGE_KEY,
+    WATCH_HISTORY_STORAGE_KEY,
 };
 use crate::models::ctx::Ctx;
 use crate::models::streaming_server::StreamingServer;
+use crate::runtime::{
+    decrypt_storage_value, encrypt_storage_value, is_encrypted_storage_key, StorageEncryptionKey,
+};
+use crate::types::accounts::{account_storage_key, AccountsBucket};
 use chrono::{DateTime, Utc};
 use futures::{future, Future, TryFutureExt};
 use http::Request;
@@ -168,6 +173,50 @@ pub trait Env {
             _ => Box::new(UnsupportedTransport::new(transport_url.to_owned())),
         }
     }
+    /// The key used to encrypt the values of the
+    /// [`ENCRYPTED_STORAGE_KEYS`](crate::constants::ENCRYPTED_STORAGE_KEYS),
+    /// the storage is kept as plain JSON when there is none.
+    fn storage_encryption_key() -> Option<StorageEncryptionKey> {
+        None
+    }
+    /// Same as [`Env::get_storage`] but decrypts the values of the
+    /// [`ENCRYPTED_STORAGE_KEYS`](crate::constants::ENCRYPTED_STORAGE_KEYS).
+    fn get_encrypted_storage<T: for<'de> Deserialize<'de> + ConditionalSend + 'static>(
+        key: &str,
+    ) -> TryEnvFuture<Option<T>>
+    where
+        Self: Sized,
+    {
+        if !is_encrypted_storage_key(key) {
+            return Self::get_storage(key);
+        }
+        let key = key.to_owned();
+        let encryption_key = Self::storage_encryption_key();
+        Self::get_storage::<serde_json::Value>(&key)
+            .and_then(|value| async move {
+                value
+                    .map(|value| decrypt_storage_value(encryption_key.as_ref(), &key, value))
+                    .transpose()
+            })
+            .boxed_env()
+    }
+    /// Same as [`Env::set_storage`] but encrypts the values of the
+    /// [`ENCRYPTED_STORAGE_KEYS`](crate::constants::ENCRYPTED_STORAGE_KEYS)
+    /// when there is a [`Env::storage_encryption_key`].
+    fn set_encrypted_storage<T: Serialize>(key: &str, value: Option<&T>) -> TryEnvFuture<()>
+    where
+        Self: Sized,
+    {
+        match (Self::storage_encryption_key(), value) {
+            (Some(encryption_key), Some(value)) if is_encrypted_storage_key(key) => {
+                match encrypt_storage_value(&encryption_key, key, value) {
+                    Ok(value) => Self::set_storage(key, Some(&value)),
+                    Err(error) => future::err(error).boxed_env(),
+                }
+            }
+            _ => Self::set_storage(key, value),
+        }
+    }
     fn migrate_storage_schema() -> TryEnvFuture<()>
     where
         Self: Sized,
@@ -265,6 +314,36 @@ pub trait Env {
                         .await?;
                     schema_version = 14;
                 }
+                if schema_version == 14 {
+                    migrate_storage_schema_to_v15::<Self>()
+                        .map_err(|error| EnvError::StorageSchemaVersionUpgrade(Box::new(error)))
+                        .await?;
+                    schema_version = 15;
+                }
+                if schema_version == 15 {
+                    migrate_storage_schema_to_v16::<Self>()
+                        .map_err(|error| EnvError::StorageSchemaVersionUpgrade(Box::new(error)))
+                        .await?;
+                    schema_version = 16;
+                }
+                if schema_version == 16 {
+                    migrate_storage_schema_to_v17::<Self>()
+                        .map_err(|error| EnvError::StorageSchemaVersionUpgrade(Box::new(error)))
+                        .await?;
+                    schema_version = 17;
+                }
+                if schema_version == 17 {
+                    migrate_storage_schema_to_v18::<Self>()
+                        .map_err(|error| EnvError::StorageSchemaVersionUpgrade(Box::new(error)))
+                        .await?;
+                    schema_version = 18;
+                }
+                if schema_version == 18 {
+                    migrate_storage_schema_to_v19::<Self>()
+                        .map_err(|error| EnvError::StorageSchemaVersionUpgrade(Box::new(error)))
+                        .await?;
+                    schema_version = 19;
+                }
                 if schema_version != SCHEMA_VERSION {
                     panic!(
                         "Storage schema version must be upgraded from {} to {}",
@@ -594,26 +673,137 @@ fn migrate_storage_schema_to_v14<E: Env>() -> TryEnvFuture<()> {
         .boxed_env()
 }
 
+/// Encrypts the values which were stored as plain JSON,
+/// including the ones of the inactive accounts.
+///
+/// The keys are the ones which were encrypted by this version,
+/// the keys which are encrypted since then are migrated separately.
+fn migrate_storage_schema_to_v15<E: Env>() -> TryEnvFuture<()> {
+    encrypt_storage_values::<E>(&[
+        PROFILE_STORAGE_KEY,
+        LIBRARY_RECENT_STORAGE_KEY,
+        LIBRARY_STORAGE_KEY,
+        STREAMS_STORAGE_KEY,
+    ])
+    .and_then(|_| E::set_storage(SCHEMA_VERSION_STORAGE_KEY, Some(&15)))
+    .boxed_env()
+}
+
+fn migrate_storage_schema_to_v16<E: Env>() -> TryEnvFuture<()> {
+    E::set_storage::<()>(PLAYLISTS_STORAGE_KEY, None)
+        .and_then(|_| E::set_storage(SCHEMA_VERSION_STORAGE_KEY, Some(&16)))
+        .boxed_env()
+}
+
+fn migrate_storage_schema_to_v17<E: Env>() -> TryEnvFuture<()> {
+    E::set_storage::<()>(WATCH_HISTORY_STORAGE_KEY, None)
+        .and_then(|_| E::set_storage(SCHEMA_VERSION_STORAGE_KEY, Some(&17)))
+        .boxed_env()
+}
+
+/// Replaces the date of the last search of every query with the date and the count
+/// of its searches, including the search histories of the inactive accounts.
+fn migrate_storage_schema_to_v18<E: Env>() -> TryEnvFuture<()> {
+    E::get_storage::<AccountsBucket>(ACCOUNTS_STORAGE_KEY)
+        .and_then(|accounts| {
+            let keys =
+                std::iter::once(SEARCH_HISTORY_STORAGE_KEY.to_owned())
+                    .chain(accounts.unwrap_or_default().items.iter().map(|account| {
+                        account_storage_key(SEARCH_HISTORY_STORAGE_KEY, &account.uid)
+                    }))
+                    .collect::<Vec<_>>();
+            future::try_join_all(keys.into_iter().map(|key| {
+                E::get_storage::<serde_json::Value>(&key).and_then(move |mut search_history| {
+                    match search_history
+                        .as_mut()
+                        .and_then(|search_history| search_history.as_object_mut())
+                        .and_then(|search_history| search_history.get_mut("items"))
+                        .and_then(|items| items.as_object_mut())
+                    {
+                        Some(items) => {
+                            for item in items.values_mut() {
+                                *item = serde_json::json!({
+                                    "lastSearched": item.take(),
+                                    "count": 1,
+                                });
+                            }
+                            E::set_storage(&key, Some(&search_history))
+                        }
+                        None => future::ok(()).boxed_env(),
+                    }
+                })
+            }))
+        })
+        .and_then(|_| E::set_storage(SCHEMA_VERSION_STORAGE_KEY, Some(&18)))
+        .boxed_env()
+}
+
+/// Encrypts the values of the keys which were stored as plain JSON,
+/// including the ones of the inactive accounts.
+fn encrypt_storage_values<E: Env>(storage_keys: &'static [&'static str]) -> TryEnvFuture<()> {
+    if E::storage_encryption_key().is_none() {
+        return future::ok(()).boxed_env();
+    }
+    E::get_storage::<AccountsBucket>(ACCOUNTS_STORAGE_KEY)
+        .and_then(move |accounts| {
+            let keys = storage_keys
+                .iter()
+                .map(|key| key.to_string())
+                .chain(
+                    accounts
+                        .unwrap_or_default()
+                        .items
+                        .iter()
+                        .flat_map(|account| {
+                            storage_keys
+                                .iter()
+                                .map(|key| account_storage_key(key, &account.uid))
+                        }),
+                )
+                .collect::<Vec<_>>();
+            future::try_join_all(keys.into_iter().map(|key| {
+                E::get_encrypted_storage::<serde_json::Value>(&key).and_then(move |value| {
+                    match value {
+                        Some(value) => E::set_encrypted_storage(&key, Some(&value)),
+                        None => future::ok(()).boxed_env(),
+                    }
+                })
+            }))
+        })
+        .map_ok(|_| ())
+        .boxed_env()
+}
+
+/// Encrypts the outbox and the watch history, which were kept as plain JSON before
+fn migrate_storage_schema_to_v19<E: Env>() -> TryEnvFuture<()> {
+    encrypt_storage_values::<E>(&[OUTBOX_STORAGE_KEY, WATCH_HISTORY_STORAGE_KEY])
+        .and_then(|_| E::set_storage(SCHEMA_VERSION_STORAGE_KEY, Some(&19)))
+        .boxed_env()
+}
+
 #[cfg(test)]
 mod test {
     use serde_json::{json, Value};
 
     use crate::{
         constants::{
-            PROFILE_STORAGE_KEY, SCHEMA_VERSION, SCHEMA_VERSION_STORAGE_KEY, STREAMS_STORAGE_KEY,
+            ACCOUNTS_STORAGE_KEY, OUTBOX_STORAGE_KEY, PROFILE_STORAGE_KEY, SCHEMA_VERSION,
+            SCHEMA_VERSION_STORAGE_KEY, SEARCH_HISTORY_STORAGE_KEY, STREAMS_STORAGE_KEY,
         },
         runtime::{
             env::{
                 migrate_storage_schema_to_v10, migrate_storage_schema_to_v11,
                 migrate_storage_schema_to_v12, migrate_storage_schema_to_v13,
-                migrate_storage_schema_to_v14, migrate_storage_schema_to_v6,
-                migrate_storage_schema_to_v7, migrate_storage_schema_to_v8,
-                migrate_storage_schema_to_v9,
+                migrate_storage_schema_to_v14, migrate_storage_schema_to_v15,
+                migrate_storage_schema_to_v16, migrate_storage_schema_to_v17,
+                migrate_storage_schema_to_v18, migrate_storage_schema_to_v19,
+                migrate_storage_schema_to_v6, migrate_storage_schema_to_v7,
+                migrate_storage_schema_to_v8, migrate_storage_schema_to_v9,
             },
-            Env,
+            EncryptedStorageValue, Env,
         },
         types::streams::StreamsBucket,
-        unit_tests::{TestEnv, STORAGE},
+        unit_tests::{TestEnv, STORAGE, STORAGE_ENCRYPTION_KEY},
     };
 
     fn set_streams_and_schema_version(streams: &Value, schema_v: u32) {
@@ -1125,4 +1315,194 @@ mod test {
             "Profile should match"
         );
     }
+
+    #[tokio::test]
+    async fn test_migration_from_14_to_15() {
+        let _test_env_guard = TestEnv::reset().expect("Should lock TestEnv");
+        *STORAGE_ENCRYPTION_KEY.write().unwrap() = Some([1; 32]);
+
+        let profile = json!({
+            "auth": {
+                "key": "auth_key"
+            }
+        });
+
+        set_profile_and_schema_version(&profile, 14);
+
+        migrate_storage_schema_to_v15::<TestEnv>()
+            .await
+            .expect("Should migrate");
+
+        {
+            let storage = STORAGE.read().expect("Should lock");
+
+            assert_eq!(
+                &15.to_string(),
+                storage
+                    .get(SCHEMA_VERSION_STORAGE_KEY)
+                    .expect("Should have the schema set"),
+                "Scheme version should now be updated"
+            );
+            let stored_profile = storage
+                .get(PROFILE_STORAGE_KEY)
+                .expect("Should have the profile set");
+            assert!(
+                serde_json::from_str::<EncryptedStorageValue>(stored_profile).is_ok(),
+                "Profile should be encrypted"
+            );
+            assert!(
+                !stored_profile.contains("auth_key"),
+                "Profile should not be readable"
+            );
+        }
+
+        assert_eq!(
+            TestEnv::get_encrypted_storage::<Value>(PROFILE_STORAGE_KEY)
+                .await
+                .expect("Should decrypt"),
+            Some(profile),
+            "Profile should be decrypted"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_migration_from_15_to_16() {
+        let _test_env_guard = TestEnv::reset().expect("Should lock TestEnv");
+
+        migrate_storage_schema_to_v16::<TestEnv>()
+            .await
+            .expect("Should migrate");
+
+        {
+            assert_storage_schema_version(16);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_migration_from_16_to_17() {
+        let _test_env_guard = TestEnv::reset().expect("Should lock TestEnv");
+
+        migrate_storage_schema_to_v17::<TestEnv>()
+            .await
+            .expect("Should migrate");
+
+        {
+            assert_storage_schema_version(17);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_migration_from_17_to_18() {
+        let _test_env_guard = TestEnv::reset().expect("Should lock TestEnv");
+
+        let search_history = json!({
+            "uid": "user_id",
+            "items": {
+                "superman": "2023-01-01T00:00:00Z"
+            }
+        });
+        let migrated_search_history = json!({
+            "uid": "user_id",
+            "items": {
+                "superman": {
+                    "lastSearched": "2023-01-01T00:00:00Z",
+                    "count": 1
+                }
+            }
+        });
+
+        {
+            let mut storage = STORAGE.write().expect("Should lock");
+            storage.insert(SCHEMA_VERSION_STORAGE_KEY.into(), 17.to_string());
+            storage.insert(
+                SEARCH_HISTORY_STORAGE_KEY.into(),
+                search_history.to_string(),
+            );
+            storage.insert(
+                ACCOUNTS_STORAGE_KEY.into(),
+                json!({ "items": [{ "uid": "user_id", "lastUsed": "2023-01-01T00:00:00Z" }] })
+                    .to_string(),
+            );
+            storage.insert(
+                format!("{SEARCH_HISTORY_STORAGE_KEY}:user_id"),
+                search_history.to_string(),
+            );
+        }
+
+        migrate_storage_schema_to_v18::<TestEnv>()
+            .await
+            .expect("Should migrate");
+
+        assert_storage_schema_version(18);
+        {
+            let storage = STORAGE.read().expect("Should lock");
+
+            assert_eq!(
+                serde_json::from_str::<Value>(
+                    storage
+                        .get(SEARCH_HISTORY_STORAGE_KEY)
+                        .expect("Should have the search history set")
+                )
+                .unwrap(),
+                migrated_search_history,
+                "Search history should be migrated"
+            );
+            assert_eq!(
+                serde_json::from_str::<Value>(
+                    storage
+                        .get(&format!("{SEARCH_HISTORY_STORAGE_KEY}:user_id"))
+                        .expect("Should have the account's search history set")
+                )
+                .unwrap(),
+                migrated_search_history,
+                "Search history of the inactive account should be migrated"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_migration_from_18_to_19() {
+        let _test_env_guard = TestEnv::reset().expect("Should lock TestEnv");
+        *STORAGE_ENCRYPTION_KEY.write().unwrap() = Some([1; 32]);
+
+        let outbox = json!({
+            "uid": "user_id",
+            "items": [{ "type": "LibraryItemsPut", "payload": [] }]
+        });
+
+        {
+            let mut storage = STORAGE.write().expect("Should lock");
+            storage.insert(SCHEMA_VERSION_STORAGE_KEY.into(), 18.to_string());
+            storage.insert(
+                ACCOUNTS_STORAGE_KEY.into(),
+                json!({ "items": [{ "uid": "user_id", "lastUsed": "2023-01-01T00:00:00Z" }] })
+                    .to_string(),
+            );
+            storage.insert(format!("{OUTBOX_STORAGE_KEY}:user_id"), outbox.to_string());
+        }
+
+        migrate_storage_schema_to_v19::<TestEnv>()
+            .await
+            .expect("Should migrate");
+
+        assert_storage_schema_version(19);
+        {
+            let storage = STORAGE.read().expect("Should lock");
+            let stored_outbox = storage
+                .get(&format!("{OUTBOX_STORAGE_KEY}:user_id"))
+                .expect("Should have the outbox set");
+            assert!(
+                serde_json::from_str::<EncryptedStorageValue>(stored_outbox).is_ok(),
+                "Outbox of the inactive account should be encrypted"
+            );
+        }
+
+        assert_eq!(
+            TestEnv::get_encrypted_storage::<Value>(&format!("{OUTBOX_STORAGE_KEY}:user_id"))
+                .await
+                .expect("Should decrypt"),
+            Some(outbox),
+            "Outbox should be decrypted"
+        );
+    }
 }
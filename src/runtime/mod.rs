@@ -9,5 +9,8 @@ pub use env::*;
 mod runtime;
 pub use runtime::*;
 
+mod storage_encryption;
+pub use storage_encryption::*;
+
 mod update;
 pub use update::*;
@@ -7,22 +7,30 @@ use crate::types::streams::StreamItemState;
 use crate::{
     models::{
         addon_details::Selected as AddonDetailsSelected,
+        calendar::Selected as CalendarSelected,
         catalog_with_filters::Selected as CatalogWithFiltersSelected,
         catalogs_with_extra::Selected as CatalogsWithExtraSelected,
+        for_you::Selected as ForYouSelected,
+        global_search::Selected as GlobalSearchSelected,
         installed_addons_with_filters::Selected as InstalledAddonsWithFiltersSelected,
         library_by_type::Selected as LibraryByTypeSelected,
         library_with_filters::Selected as LibraryWithFiltersSelected,
         meta_details::Selected as MetaDetailsSelected,
-        player::{Selected as PlayerSelected, VideoParams},
+        player::{QueueItem, Selected as PlayerSelected, VideoParams},
+        playlists::Selected as PlaylistsSelected,
         streaming_server::StatisticsRequest as StreamingServerStatisticsRequest,
+        watch_history::Selected as WatchHistorySelected,
+        watch_stats::Selected as WatchStatsSelected,
     },
     types::{
         addon::Descriptor,
         api::AuthRequest,
-        library::LibraryItemId,
-        profile::Settings as ProfileSettings,
+        library::{LibraryItemId, WatchStatus, MAX_RATING, MIN_RATING},
+        profile::{Certification, Settings as ProfileSettings, SmartList, UID},
         resource::{MetaItemId, MetaItemPreview, Video},
+        scheduler::SchedulerJob,
         streaming_server::Settings as StreamingServerSettings,
+        subtitles::CueTiming,
     },
 };
 
@@ -44,11 +52,33 @@ pub enum ActionCtx {
         id: LibraryItemId,
         is_watched: bool,
     },
+    /// Set the [`WatchStatus`] of the LibraryItem, `None` clears it.
+    SetLibraryItemWatchStatus {
+        id: LibraryItemId,
+        status: Option<WatchStatus>,
+    },
+    /// Set the user's own rating of the LibraryItem, `None` clears it.
+    ///
+    /// The rating should be within [`MIN_RATING`] and [`MAX_RATING`].
+    RateLibraryItem {
+        id: LibraryItemId,
+        rating: Option<u8>,
+    },
+    /// Set the user's own notes on the LibraryItem, `None` or empty notes clear them.
+    SetLibraryItemNotes {
+        id: LibraryItemId,
+        notes: Option<String>,
+    },
     /// If boolean is set to `true` it will disable notifications for the LibraryItem.
     ToggleLibraryItemNotifications(LibraryItemId, bool),
     /// Dismiss all Notification for a given [`MetaItemId`].
     DismissNotificationItem(MetaItemId),
     ClearSearchHistory,
+    /// Remove a single query from the search history.
+    RemoveSearchHistoryItem(String),
+    /// If boolean is set to `true` the searches will not be recorded in the search history.
+    PauseSearchHistory(bool),
+    ClearWatchHistory,
     PushUserToAPI,
     PullUserFromAPI,
     PushAddonsToAPI,
@@ -66,6 +96,77 @@ pub enum ActionCtx {
     GetEvents,
     /// Dismiss an event by id, either a Modal or Notification
     DismissEvent(String),
+    /// Run all the [`SchedulerJob`]s which are due.
+    ///
+    /// Should be dispatched periodically by the shell (e.g. every minute and on app focus),
+    /// the jobs themselves are run on their own intervals.
+    SchedulerTick,
+    /// Change the interval (in milliseconds) of a [`SchedulerJob`],
+    /// `None` disables the job. The interval is stored in the profile settings.
+    ///
+    /// Intervals of 0 or above [`SCHEDULER_MAX_INTERVAL`] are rejected.
+    ///
+    /// [`SCHEDULER_MAX_INTERVAL`]: crate::constants::SCHEDULER_MAX_INTERVAL
+    UpdateSchedulerJobInterval {
+        job: SchedulerJob,
+        interval: Option<u64>,
+    },
+    /// Retry pushing all the pending API mutations from the outbox,
+    /// should be dispatched when the connection is restored.
+    FlushOutbox,
+    /// Switch the active account to another one stored on the device (`None` for guest).
+    ///
+    /// The data of the current account is kept in the storage so it can be switched back to.
+    /// Fails while logging in or when the account is already active.
+    SwitchAccount(UID),
+    /// Requires the parental controls to be unlocked when a PIN is set.
+    UpdateParentalControls {
+        max_certification: Option<Certification>,
+        blocked_genres: Vec<String>,
+        block_unrated: bool,
+    },
+    /// Set or remove (`None`) the PIN of the parental controls.
+    ///
+    /// Requires the parental controls to be unlocked when a PIN is already set.
+    SetParentalControlsPin(Option<String>),
+    /// Temporarily lift the parental controls until they are locked again or the user logs out.
+    UnlockParentalControls(String),
+    LockParentalControls,
+    /// Add a smart list or replace the one with the same id.
+    SaveSmartList(SmartList),
+    DeleteSmartList(String),
+    CreatePlaylist {
+        id: String,
+        name: String,
+    },
+    RenamePlaylist {
+        id: String,
+        name: String,
+    },
+    /// Move the playlist to the given position in the list of playlists.
+    MovePlaylist {
+        id: String,
+        index: usize,
+    },
+    DeletePlaylist(String),
+    /// Append the meta item to the end of the playlist, if it's not already in it.
+    #[serde(rename_all = "camelCase")]
+    AddToPlaylist {
+        id: String,
+        meta_item: MetaItemPreview,
+    },
+    #[serde(rename_all = "camelCase")]
+    RemoveFromPlaylist {
+        id: String,
+        meta_item_id: MetaItemId,
+    },
+    /// Move the meta item to the given position in the playlist.
+    #[serde(rename_all = "camelCase")]
+    MovePlaylistItem {
+        id: String,
+        meta_item_id: MetaItemId,
+        index: usize,
+    },
 }
 
 #[derive(Clone, Deserialize, Debug)]
@@ -93,6 +194,16 @@ pub enum ActionLibraryWithFilters {
     LoadNextPage,
 }
 
+#[derive(Clone, Deserialize, Debug)]
+#[serde(tag = "action", content = "args")]
+pub enum ActionCalendar {
+    /// Export the upcoming episodes as an iCalendar file,
+    /// the content is dispatched with [`Event::ICalendarExported`].
+    ///
+    /// [`Event::ICalendarExported`]: crate::runtime::msg::Event::ICalendarExported
+    ExportICalendar,
+}
+
 #[derive(Clone, Deserialize, Debug)]
 #[serde(tag = "action", content = "args")]
 pub enum ActionMetaDetails {
@@ -151,6 +262,15 @@ pub enum ActionPlayer {
     StreamStateChanged {
         state: StreamItemState,
     },
+    /// Estimates the delay of the subtitles with the given cues.
+    ///
+    /// The reference is e.g. the cues of the embedded subtitles,
+    /// the `timing_hints` of the video params are used without one.
+    SyncSubtitles {
+        cues: Vec<CueTiming>,
+        #[serde(default)]
+        reference: Option<Vec<CueTiming>>,
+    },
     TimeChanged {
         time: u64,
         duration: u64,
@@ -167,15 +287,37 @@ pub enum ActionPlayer {
     /// - We've watched a movie to the last second
     /// - We've watched a movie series to the last second
     Ended,
+    /// Adds an item to the end of the queue.
+    Enqueue(Box<QueueItem>),
+    /// Removes the queue item at the index.
+    Dequeue {
+        index: usize,
+    },
+    /// Moves the queue item at `from` to the `to` index.
+    MoveQueueItem {
+        from: usize,
+        to: usize,
+    },
+    /// Drops the queue items before the index, the item at the index is played next.
+    SkipQueue {
+        index: usize,
+    },
+    ClearQueue,
 }
 
 #[derive(Clone, Deserialize, Debug)]
 #[serde(tag = "model", content = "args")]
 pub enum ActionLoad {
     AddonDetails(AddonDetailsSelected),
+    /// Loads the upcoming episodes of the series in the library
+    Calendar(CalendarSelected),
     CatalogWithFilters(Option<CatalogWithFiltersSelected>),
     CatalogsWithExtra(CatalogsWithExtraSelected),
     DataExport,
+    /// Loads the recommendations for the user based on the library
+    ForYou(ForYouSelected),
+    /// Searches the local feeds, the library and the addon catalogs at once
+    GlobalSearch(GlobalSearchSelected),
     InstalledAddonsWithFilters(InstalledAddonsWithFiltersSelected),
     LibraryWithFilters(LibraryWithFiltersSelected),
     LibraryByType(LibraryByTypeSelected),
@@ -183,6 +325,9 @@ pub enum ActionLoad {
     LocalSearch,
     MetaDetails(MetaDetailsSelected),
     Player(Box<PlayerSelected>),
+    Playlists(PlaylistsSelected),
+    WatchHistory(WatchHistorySelected),
+    WatchStats(WatchStatsSelected),
     Link,
 }
 
@@ -206,6 +351,7 @@ pub enum ActionSearch {
 pub enum Action {
     Ctx(ActionCtx),
     Link(ActionLink),
+    Calendar(ActionCalendar),
     CatalogWithFilters(ActionCatalogWithFilters),
     CatalogsWithExtra(ActionCatalogsWithExtra),
     LibraryByType(ActionLibraryByType),
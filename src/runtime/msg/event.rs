@@ -3,6 +3,7 @@ use crate::models::player::AnalyticsContext as PlayerAnalyticsContext;
 use crate::types::api::AuthRequest;
 use crate::types::library::LibraryItemId;
 use crate::types::profile::{AuthKey, Settings, UID};
+use crate::types::resource::MetaItemId;
 use serde::Serialize;
 use url::Url;
 
@@ -54,6 +55,27 @@ pub enum Event {
     DismissedEventsPushedToStorage {
         uid: UID,
     },
+    OutboxPushedToStorage {
+        uid: UID,
+    },
+    PlaylistsPushedToStorage {
+        uid: UID,
+    },
+    WatchHistoryPushedToStorage {
+        uid: UID,
+    },
+    LocalSearchPushedToStorage {
+        urls: Vec<Url>,
+    },
+    AccountsPushedToStorage {
+        uids: Vec<UID>,
+    },
+    AccountPushedToStorage {
+        uid: UID,
+    },
+    AccountDeletedFromStorage {
+        uid: UID,
+    },
     UserPulledFromAPI {
         uid: UID,
     },
@@ -88,9 +110,18 @@ pub enum Event {
     UserLoggedOut {
         uid: UID,
     },
+    /// The active account has been switched to the one with the given uid
+    AccountSwitched {
+        uid: UID,
+    },
     SessionDeleted {
         auth_key: AuthKey,
     },
+    /// The changes which were not pushed to the API yet have been discarded on logout
+    OutboxDiscarded {
+        uid: UID,
+        pending_count: usize,
+    },
     TraktAddonFetched {
         uid: UID,
     },
@@ -112,6 +143,39 @@ pub enum Event {
     SettingsUpdated {
         settings: Settings,
     },
+    ParentalControlsUpdated,
+    ParentalControlsUnlocked,
+    ParentalControlsLocked,
+    SmartListSaved {
+        id: String,
+    },
+    SmartListDeleted {
+        id: String,
+    },
+    PlaylistCreated {
+        id: String,
+    },
+    PlaylistRenamed {
+        id: String,
+    },
+    PlaylistMoved {
+        id: String,
+    },
+    PlaylistDeleted {
+        id: String,
+    },
+    PlaylistItemAdded {
+        id: String,
+        meta_item_id: MetaItemId,
+    },
+    PlaylistItemRemoved {
+        id: String,
+        meta_item_id: MetaItemId,
+    },
+    PlaylistItemMoved {
+        id: String,
+        meta_item_id: MetaItemId,
+    },
     LibraryItemAdded {
         id: LibraryItemId,
     },
@@ -124,6 +188,15 @@ pub enum Event {
     LibraryItemNotificationsToggled {
         id: LibraryItemId,
     },
+    LibraryItemWatchStatusUpdated {
+        id: LibraryItemId,
+    },
+    LibraryItemRated {
+        id: LibraryItemId,
+    },
+    LibraryItemNotesUpdated {
+        id: LibraryItemId,
+    },
     /// The LibraryItem with the given id has been marked as watched or unwatched (Overrides the previous watched state)
     LibraryItemMarkedAsWatched {
         id: LibraryItemId,
@@ -142,6 +215,10 @@ pub enum Event {
     PlayingOnDevice {
         device: String,
     },
+    /// The content of the iCalendar file with the upcoming episodes, to be saved by the shell
+    ICalendarExported {
+        content: String,
+    },
     Error {
         error: CtxError,
         source: Box<Event>,
@@ -3,7 +3,7 @@ use url::Url;
 
 use crate::models::ctx::CtxError;
 use crate::models::link::LinkError;
-use crate::models::local_search::Searchable;
+use crate::models::local_search::{Searchable, SearchableFeed};
 use crate::models::streaming_server::{PlaybackDevice, StatisticsRequest};
 use crate::runtime::EnvError;
 use crate::types::addon::{Descriptor, Manifest, ResourceRequest, ResourceResponse};
@@ -12,13 +12,19 @@ use crate::types::api::{
     GetNotificationResponse, LinkCodeResponse, LinkDataResponse, SeekLogRequest, SkipGapsRequest,
     SkipGapsResponse, SuccessResponse,
 };
+use crate::types::events::DismissedEventsBucket;
 use crate::types::library::{LibraryBucket, LibraryItem, LibraryItemId};
-use crate::types::profile::{Auth, AuthKey, Profile, User};
+use crate::types::notifications::NotificationsBucket;
+use crate::types::outbox::{OutboxBucket, OutboxRequest};
+use crate::types::playlists::PlaylistsBucket;
+use crate::types::profile::{Auth, AuthKey, Profile, SmartList, User, UID};
 use crate::types::resource::{MetaItem, Stream};
+use crate::types::search_history::SearchHistoryBucket;
 use crate::types::streaming_server::{
     DeviceInfo, GetHTTPSResponse, NetworkInfo, SettingsResponse, Statistics,
 };
-use crate::types::streams::StreamItemState;
+use crate::types::streams::{StreamItemState, StreamsBucket};
+use crate::types::watch_history::{WatchHistoryBucket, WatchHistoryItem};
 
 pub type CtxStorageResponse = (
     Option<Profile>,
@@ -31,10 +37,30 @@ pub struct CtxAuthResponse {
     pub auth: Auth,
     pub addons_result: Result<Vec<Descriptor>, CtxError>,
     pub library_items_result: Result<Vec<LibraryItem>, CtxError>,
+    /// The playlists which were kept on the device when the user logged out,
+    /// they are not synced with the API.
+    pub playlists: Option<PlaylistsBucket>,
+    /// The smart lists which were kept on the device when the user logged out,
+    /// they are not synced with the API.
+    pub smart_lists: Option<Vec<SmartList>>,
 }
 
 pub type LibraryPlanResponse = (Vec<String>, Vec<String>);
 
+/// The data of an account loaded from the storage when switching to it.
+#[derive(Debug)]
+pub struct AccountStorageResponse {
+    pub profile: Option<Profile>,
+    pub library: Option<LibraryBucket>,
+    pub streams: Option<StreamsBucket>,
+    pub notifications: Option<NotificationsBucket>,
+    pub search_history: Option<SearchHistoryBucket>,
+    pub dismissed_events: Option<DismissedEventsBucket>,
+    pub outbox: Option<OutboxBucket>,
+    pub playlists: Option<PlaylistsBucket>,
+    pub watch_history: Option<WatchHistoryBucket>,
+}
+
 //
 // Those messages are meant to be dispatched and handled only inside stremio-core crate
 //
@@ -52,6 +78,14 @@ pub enum Internal {
     LibraryPullResult(DatastoreRequest, Result<Vec<LibraryItem>, CtxError>),
     /// Dispatched when expired session is detected
     Logout,
+    /// Dispatched by the scheduler when the library sync job is due.
+    SyncLibraryWithAPI,
+    /// Dispatched by the scheduler when the addons pull job is due.
+    PullAddonsFromAPI,
+    /// Dispatched by the scheduler when the user pull job is due.
+    PullUserFromAPI,
+    /// Dispatched by the scheduler when the events job is due.
+    GetEvents,
     /// Internal event dispatched on user action or login
     /// to install the addon if it's not present
     InstallTraktAddon,
@@ -76,6 +110,10 @@ pub enum Internal {
     CatalogsWithExtraSearch {
         query: String,
     },
+    /// Dispatched when searching with the global search.
+    GlobalSearch {
+        query: String,
+    },
     /// Dispatched when library item needs to be updated in the memory, storage and API.
     UpdateLibraryItem(LibraryItem),
     /// Dispatched when some of auth, addons or settings changed.
@@ -141,6 +179,19 @@ pub enum Internal {
     SkipGapsResult(SkipGapsRequest, Result<SkipGapsResponse, CtxError>),
     /// The result of querying the data for LocalSearch
     LoadLocalSearchResult(Url, Result<Vec<Searchable>, EnvError>),
+    /// The feeds of the LocalSearch persisted in the storage
+    LocalSearchStorageResult(Result<Option<Vec<SearchableFeed>>, EnvError>),
+    /// Dispatched when another model searches the records of the LocalSearch,
+    /// e.g. the global search
+    LocalSearchQuery {
+        query: String,
+        max_results: usize,
+    },
+    /// The records of the LocalSearch matching the query
+    LocalSearchQueryResult {
+        query: String,
+        results: Vec<Searchable>,
+    },
     /// Result for getModal request
     GetModalResult(APIRequest, Result<Option<GetModalResponse>, CtxError>),
     /// Result for getNotification request
@@ -150,4 +201,24 @@ pub enum Internal {
     ),
     /// When dismissed events changed
     DismissedEventsChanged,
+    /// Result for pushing a mutation to the API.
+    ///
+    /// Requests which failed because of the connection are kept in the outbox.
+    APIPushResult(AuthKey, OutboxRequest, Result<(), CtxError>),
+    /// Dispatched when the outbox of pending API mutations has changed.
+    OutboxChanged,
+    /// Result for loading the data of an account when switching to it.
+    AccountSwitchResult(UID, Box<Result<AccountStorageResponse, CtxError>>),
+    /// Dispatched when the accounts stored on the device have changed.
+    AccountsChanged,
+    /// Dispatched when the playlists have changed.
+    PlaylistsChanged,
+    /// Dispatched by the player when the playback of a video has stopped.
+    WatchHistoryItemRecorded(WatchHistoryItem),
+    /// Dispatched when the watch history has changed.
+    WatchHistoryChanged,
+    /// Dispatched when the parental controls have changed or have been locked or unlocked.
+    ParentalControlsChanged,
+    /// Dispatched by the scheduler when the pending API mutations should be retried.
+    FlushOutbox,
 }
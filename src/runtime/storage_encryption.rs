@@ -0,0 +1,103 @@
+use base64::Engine;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use serde::{Deserialize, Serialize};
+
+use crate::constants::{BASE64, ENCRYPTED_STORAGE_KEYS};
+use crate::runtime::EnvError;
+
+const NONCE_SIZE: usize = 12;
+
+/// A 256-bit key supplied by the [`Env`](crate::runtime::Env) implementation,
+/// e.g. kept in the OS keychain.
+pub type StorageEncryptionKey = [u8; 32];
+
+/// The form in which an encrypted value is kept in the storage.
+///
+/// `value` is the base64 encoded nonce followed by the ChaCha20-Poly1305 ciphertext of the JSON.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct EncryptedStorageValue {
+    pub encrypted: String,
+}
+
+/// Whether the value stored under this key should be encrypted.
+///
+/// The keys of the inactive accounts are encrypted the same way as the active ones.
+///
+/// # Examples
+///
+/// ```
+/// use stremio_core::runtime::is_encrypted_storage_key;
+///
+/// assert!(is_encrypted_storage_key("profile"));
+/// assert!(is_encrypted_storage_key("profile:user_id"));
+/// assert!(!is_encrypted_storage_key("search_history"));
+/// ```
+pub fn is_encrypted_storage_key(key: &str) -> bool {
+    let key = key.split_once(':').map(|(key, _)| key).unwrap_or(key);
+    ENCRYPTED_STORAGE_KEYS.contains(&key)
+}
+
+/// Encrypts the JSON of the value, the storage key is authenticated with it
+/// so the value can't be moved under another key.
+pub fn encrypt_storage_value<T: Serialize>(
+    encryption_key: &StorageEncryptionKey,
+    key: &str,
+    value: &T,
+) -> Result<EncryptedStorageValue, EnvError> {
+    let plaintext = serde_json::to_vec(value)?;
+    let cipher = ChaCha20Poly1305::new(Key::from_slice(encryption_key));
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(
+            &nonce,
+            Payload {
+                msg: &plaintext,
+                aad: key.as_bytes(),
+            },
+        )
+        .map_err(|_| EnvError::StorageWriteError(format!("Failed to encrypt {key}")))?;
+    let mut encrypted = nonce.to_vec();
+    encrypted.extend(ciphertext);
+    Ok(EncryptedStorageValue {
+        encrypted: BASE64.encode(encrypted),
+    })
+}
+
+/// Decrypts a value stored with [`encrypt_storage_value`].
+///
+/// Values which were stored before the encryption was enabled are plain JSON
+/// and they are deserialized as they are.
+pub fn decrypt_storage_value<T: for<'de> Deserialize<'de>>(
+    encryption_key: Option<&StorageEncryptionKey>,
+    key: &str,
+    value: serde_json::Value,
+) -> Result<T, EnvError> {
+    let encrypted = match EncryptedStorageValue::deserialize(&value) {
+        Ok(EncryptedStorageValue { encrypted }) => encrypted,
+        Err(_) => return serde_json::from_value(value).map_err(EnvError::from),
+    };
+    let encryption_key = encryption_key
+        .ok_or_else(|| EnvError::StorageReadError(format!("No encryption key to decrypt {key}")))?;
+    let encrypted = BASE64
+        .decode(encrypted)
+        .map_err(|error| EnvError::StorageReadError(error.to_string()))?;
+    if encrypted.len() < NONCE_SIZE {
+        return Err(EnvError::StorageReadError(format!(
+            "Invalid encrypted value of {key}"
+        )));
+    }
+    let (nonce, ciphertext) = encrypted.split_at(NONCE_SIZE);
+    let cipher = ChaCha20Poly1305::new(Key::from_slice(encryption_key));
+    let plaintext = cipher
+        .decrypt(
+            Nonce::from_slice(nonce),
+            Payload {
+                msg: ciphertext,
+                aad: key.as_bytes(),
+            },
+        )
+        .map_err(|_| EnvError::StorageReadError(format!("Failed to decrypt {key}")))?;
+    serde_json::from_slice(&plaintext).map_err(EnvError::from)
+}
@@ -0,0 +1,44 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::types::profile::{Profile, UID};
+
+/// An account which is stored on the device and can be switched to without logging in again.
+///
+/// The guest account has `uid == None`.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Account {
+    pub uid: UID,
+    pub email: Option<String>,
+    pub avatar: Option<String>,
+    pub last_used: DateTime<Utc>,
+}
+
+impl Account {
+    pub fn new(profile: &Profile, last_used: DateTime<Utc>) -> Self {
+        Self {
+            uid: profile.uid(),
+            email: profile.auth.as_ref().map(|auth| auth.user.email.to_owned()),
+            avatar: profile
+                .auth
+                .as_ref()
+                .and_then(|auth| auth.user.avatar.to_owned()),
+            last_used,
+        }
+    }
+}
+
+/// Returns the storage key under which the data of an inactive account is kept.
+///
+/// # Examples
+///
+/// ```
+/// use stremio_core::types::accounts::account_storage_key;
+///
+/// assert_eq!(account_storage_key("library", &Some("user_id".to_owned())), "library:user_id");
+/// assert_eq!(account_storage_key("library", &None), "library:guest");
+/// ```
+pub fn account_storage_key(key: &str, uid: &UID) -> String {
+    format!("{key}:{}", uid.as_deref().unwrap_or("guest"))
+}
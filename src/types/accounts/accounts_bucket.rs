@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+
+use crate::types::accounts::Account;
+use crate::types::profile::UID;
+
+#[derive(Default, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub struct AccountsBucket {
+    pub items: Vec<Account>,
+}
+
+impl AccountsBucket {
+    pub fn get(&self, uid: &UID) -> Option<&Account> {
+        self.items.iter().find(|account| account.uid == *uid)
+    }
+    /// Adds the account or replaces the stored one with the same `uid`.
+    pub fn upsert(&mut self, account: Account) {
+        match self.items.iter_mut().find(|item| item.uid == account.uid) {
+            Some(item) => *item = account,
+            None => self.items.push(account),
+        }
+    }
+    /// Returns `true` if the account was removed.
+    pub fn remove(&mut self, uid: &UID) -> bool {
+        let prev_len = self.items.len();
+        self.items.retain(|account| account.uid != *uid);
+        self.items.len() != prev_len
+    }
+}
@@ -0,0 +1,5 @@
+mod account;
+pub use account::*;
+
+mod accounts_bucket;
+pub use accounts_bucket::*;
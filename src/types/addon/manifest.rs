@@ -431,4 +431,9 @@ pub struct ManifestBehaviorHints {
     pub configurable: bool,
     #[serde(default)]
     pub configuration_required: bool,
+    /// Path, relative to the transport url, of a feed with all searchable items of the addon.
+    ///
+    /// It has the same format as Cinemeta's `feed.json` and is used by the local search.
+    #[serde(default)]
+    pub searchable_feed: Option<String>,
 }
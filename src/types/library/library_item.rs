@@ -1,6 +1,8 @@
+use std::collections::BTreeMap;
 use std::marker::PhantomData;
 
 use chrono::{DateTime, Duration, Utc};
+use either::Either;
 use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 use serde_with::{serde_as, DefaultOnError, DefaultOnNull, NoneAsEmptyString};
@@ -8,12 +10,20 @@ use stremio_watched_bitfield::{WatchedBitField, WatchedField};
 use url::Url;
 
 use crate::{
+    constants::{LIBRARY_ITEM_RESUME_POINTS_COUNT, RESUME_POINT_MIN_TIME_OFFSET},
     runtime::Env,
-    types::resource::{MetaItemBehaviorHints, MetaItemPreview, PosterShape, Video},
+    types::{
+        library::WatchStatus,
+        profile::Certification,
+        resource::{MetaItemBehaviorHints, MetaItemPreview, PosterShape, Video},
+    },
 };
 
 pub type LibraryItemId = String;
 
+pub const MIN_RATING: u8 = 1;
+pub const MAX_RATING: u8 = 10;
+
 #[serde_as]
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -39,6 +49,15 @@ pub struct LibraryItem {
     pub state: LibraryItemState,
     #[serde(default)]
     pub behavior_hints: MetaItemBehaviorHints,
+    /// The genres of the meta item, updated with the item.
+    ///
+    /// Omitted when empty so the items stay compatible with the older clients.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub genres: Vec<String>,
+    /// The maturity certification of the meta item, updated with the item.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde_as(deserialize_as = "DefaultOnError")]
+    pub certification: Option<Certification>,
 }
 
 impl LibraryItem {
@@ -84,6 +103,22 @@ impl LibraryItem {
             && !self.temp
     }
 
+    /// Returns the genres stored with the item,
+    /// or the `genres` behavior hint for the items stored before they were
+    pub fn genres(&self) -> impl Iterator<Item = &str> {
+        if self.genres.is_empty() {
+            Either::Left(self.behavior_hints.genres())
+        } else {
+            Either::Right(self.genres.iter().map(String::as_str))
+        }
+    }
+    /// Returns the certification stored with the item,
+    /// or the `certification` behavior hint for the items stored before it was
+    pub fn certification(&self) -> Option<Certification> {
+        self.certification
+            .or_else(|| self.behavior_hints.certification())
+    }
+
     #[inline]
     pub fn eq_no_mtime(&self, other: &LibraryItem) -> bool {
         self.id == other.id
@@ -96,6 +131,8 @@ impl LibraryItem {
             && self.poster == other.poster
             && self.poster_shape == other.poster_shape
             && self.behavior_hints == other.behavior_hints
+            && self.genres == other.genres
+            && self.certification == other.certification
     }
 
     pub fn mark_as_watched<E: Env>(&mut self, is_watched: bool) {
@@ -125,6 +162,8 @@ impl<E: Env + 'static> From<(&MetaItemPreview, PhantomData<E>)> for LibraryItem
             poster: meta_item.poster.to_owned(),
             poster_shape: meta_item.poster_shape.to_owned(),
             behavior_hints: meta_item.behavior_hints.to_owned(),
+            genres: meta_item.genres().map(ToOwned::to_owned).unique().collect(),
+            certification: meta_item.certification(),
         }
     }
 }
@@ -138,6 +177,8 @@ impl From<(&MetaItemPreview, &LibraryItem)> for LibraryItem {
             poster: meta_item.poster.to_owned(),
             poster_shape: meta_item.poster_shape.to_owned(),
             behavior_hints: meta_item.behavior_hints.to_owned(),
+            genres: meta_item.genres().map(ToOwned::to_owned).unique().collect(),
+            certification: meta_item.certification(),
             removed: library_item.removed,
             temp: library_item.temp,
             ctime: library_item.ctime.to_owned(),
@@ -193,9 +234,136 @@ pub struct LibraryItemState {
     /// Default: receive notifications
     #[serde(default)]
     pub no_notif: bool,
+    /// The status set by the user, `None` if it was never set.
+    ///
+    /// Omitted when not set, so the items stay compatible with the older clients.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub watch_status: Option<StoredValue<WatchStatus>>,
+    /// The user's own rating from [`MIN_RATING`] to [`MAX_RATING`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rating: Option<StoredValue<u8>>,
+    /// The user's own free-text notes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<StoredValue<String>>,
+    /// Where the playback was left off in the recently watched videos, by video id.
+    ///
+    /// Kept besides `time_offset`, which the older clients use,
+    /// and omitted when empty so the items stay compatible with them.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    #[serde_as(deserialize_as = "DefaultOnError")]
+    pub resume_points: BTreeMap<String, StoredValue<ResumePoint>>,
+}
+
+/// A value of the [`LibraryItemState`] as it's stored.
+///
+/// A value which can't be read (e.g. a status added by a newer client)
+/// is kept as it is, so it's not lost the next time the item is synced.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum StoredValue<T> {
+    Known(T),
+    Unknown(serde_json::Value),
+}
+
+impl<T> StoredValue<T> {
+    pub fn known(&self) -> Option<&T> {
+        match self {
+            StoredValue::Known(value) => Some(value),
+            StoredValue::Unknown(_) => None,
+        }
+    }
+}
+
+/// Where the playback of a video was left off
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResumePoint {
+    /// In milliseconds
+    pub time_offset: u64,
+    /// In milliseconds
+    pub duration: u64,
+    pub last_watched: DateTime<Utc>,
 }
 
 impl LibraryItemState {
+    /// The status set by the user, `None` if it was never set or it's unknown.
+    pub fn watch_status(&self) -> Option<WatchStatus> {
+        self.watch_status
+            .as_ref()
+            .and_then(StoredValue::known)
+            .copied()
+    }
+    /// The rating set by the user, `None` if it was never set or it can't be read.
+    pub fn rating(&self) -> Option<u8> {
+        self.rating.as_ref().and_then(StoredValue::known).copied()
+    }
+    /// The notes set by the user, `None` if they were never set or they can't be read.
+    pub fn notes(&self) -> Option<&str> {
+        self.notes
+            .as_ref()
+            .and_then(StoredValue::known)
+            .map(String::as_str)
+    }
+    /// Returns the point to resume the video from, if it was left off
+    /// after [`RESUME_POINT_MIN_TIME_OFFSET`].
+    ///
+    /// Falls back to `time_offset` for the last video watched,
+    /// as the items synced from the older clients have no resume points.
+    pub fn resume_point(&self, video_id: &str) -> Option<ResumePoint> {
+        match self
+            .resume_points
+            .get(video_id)
+            .and_then(StoredValue::known)
+        {
+            Some(resume_point) => Some(resume_point.to_owned()),
+            None if self.video_id.as_deref() == Some(video_id) => {
+                self.last_watched.map(|last_watched| ResumePoint {
+                    time_offset: self.time_offset,
+                    duration: self.duration,
+                    last_watched,
+                })
+            }
+            None => None,
+        }
+        .filter(|resume_point| resume_point.time_offset >= RESUME_POINT_MIN_TIME_OFFSET)
+    }
+    /// Sets where the playback of the video was left off,
+    /// dropping the least recently watched videos over [`LIBRARY_ITEM_RESUME_POINTS_COUNT`].
+    ///
+    /// A `time_offset` under [`RESUME_POINT_MIN_TIME_OFFSET`] removes the video's resume point.
+    pub fn set_resume_point(
+        &mut self,
+        video_id: &str,
+        time_offset: u64,
+        duration: u64,
+        now: DateTime<Utc>,
+    ) {
+        if time_offset < RESUME_POINT_MIN_TIME_OFFSET {
+            self.resume_points.remove(video_id);
+            return;
+        }
+        self.resume_points.insert(
+            video_id.to_owned(),
+            StoredValue::Known(ResumePoint {
+                time_offset,
+                duration,
+                last_watched: now,
+            }),
+        );
+        while self.resume_points.len() > LIBRARY_ITEM_RESUME_POINTS_COUNT {
+            let least_recent_video_id = self
+                .resume_points
+                .iter()
+                .min_by_key(|(_, resume_point)| {
+                    resume_point
+                        .known()
+                        .map(|resume_point| resume_point.last_watched)
+                })
+                .map(|(video_id, _)| video_id.to_owned())
+                .expect("Should have resume points over the limit");
+            self.resume_points.remove(&least_recent_video_id);
+        }
+    }
     pub fn watched_bitfield(&self, videos: &[Video]) -> WatchedBitField {
         let video_ids = videos
             .iter()
@@ -3,3 +3,6 @@ pub use library_bucket::*;
 
 mod library_item;
 pub use library_item::*;
+
+mod watch_status;
+pub use watch_status::*;
@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+use strum::EnumIter;
+
+/// The status in which the user tracks a [`LibraryItem`](crate::types::library::LibraryItem),
+/// independent of the watch progress.
+#[derive(
+    Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, EnumIter, Serialize, Deserialize, Debug,
+)]
+#[serde(rename_all = "camelCase")]
+pub enum WatchStatus {
+    PlanToWatch,
+    Watching,
+    Completed,
+    Dropped,
+}
@@ -1,14 +1,20 @@
+pub mod accounts;
 pub mod addon;
 pub mod api;
 pub mod events;
 pub mod library;
 pub mod notifications;
+pub mod outbox;
 pub mod player;
+pub mod playlists;
 pub mod profile;
 pub mod resource;
+pub mod scheduler;
 pub mod search_history;
 pub mod streaming_server;
 pub mod streams;
+pub mod subtitles;
+pub mod watch_history;
 
 mod query_params_encode;
 pub use query_params_encode::*;
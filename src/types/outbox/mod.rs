@@ -0,0 +1,5 @@
+mod outbox_bucket;
+pub use outbox_bucket::*;
+
+mod outbox_request;
+pub use outbox_request::*;
@@ -0,0 +1,91 @@
+use serde::{Deserialize, Serialize};
+
+use crate::types::outbox::OutboxRequest;
+use crate::types::profile::UID;
+
+/// Persisted queue of API mutations which failed to be pushed
+/// and should be retried once the connection is restored.
+#[derive(Default, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub struct OutboxBucket {
+    pub uid: UID,
+    pub items: Vec<OutboxRequest>,
+}
+
+impl OutboxBucket {
+    pub fn new(uid: UID) -> Self {
+        Self { uid, items: vec![] }
+    }
+    /// The number of changes waiting to be pushed to the API.
+    pub fn pending_count(&self) -> usize {
+        self.items.iter().map(OutboxRequest::changes_count).sum()
+    }
+    /// Adds a request to the queue replacing the writes which it supersedes.
+    ///
+    /// There is at most one request for the user and one for the addons,
+    /// library items are merged by id. The latest `mtime` is kept in both cases.
+    pub fn enqueue(&mut self, request: OutboxRequest) {
+        match request {
+            OutboxRequest::LibraryItemsPut(items) => {
+                let queued_items = self.items.iter_mut().find_map(|request| match request {
+                    OutboxRequest::LibraryItemsPut(queued_items) => Some(queued_items),
+                    _ => None,
+                });
+                match queued_items {
+                    Some(queued_items) => {
+                        for item in items {
+                            match queued_items
+                                .iter_mut()
+                                .find(|queued_item| queued_item.id == item.id)
+                            {
+                                Some(queued_item) if queued_item.mtime <= item.mtime => {
+                                    *queued_item = item;
+                                }
+                                Some(_) => {}
+                                None => queued_items.push(item),
+                            }
+                        }
+                    }
+                    None => self.items.push(OutboxRequest::LibraryItemsPut(items)),
+                }
+            }
+            request => {
+                if self
+                    .items
+                    .iter()
+                    .any(|queued_request| queued_request.supersedes(&request))
+                {
+                    return;
+                }
+                self.items
+                    .retain(|queued_request| !request.supersedes(queued_request));
+                self.items.push(request);
+            }
+        }
+    }
+    /// Removes the queued writes which are superseded by a request that has reached the API.
+    ///
+    /// Returns `true` if the queue has changed.
+    pub fn remove_superseded(&mut self, request: &OutboxRequest) -> bool {
+        let prev_items = self.items.to_owned();
+        match request {
+            OutboxRequest::LibraryItemsPut(items) => {
+                self.items.iter_mut().for_each(|queued_request| {
+                    if let OutboxRequest::LibraryItemsPut(queued_items) = queued_request {
+                        queued_items.retain(|queued_item| {
+                            !items.iter().any(|item| {
+                                item.id == queued_item.id && item.mtime >= queued_item.mtime
+                            })
+                        });
+                    }
+                });
+                self.items
+                    .retain(|queued_request| queued_request.changes_count() > 0);
+            }
+            request => {
+                self.items
+                    .retain(|queued_request| !request.supersedes(queued_request));
+            }
+        }
+        self.items != prev_items
+    }
+}
@@ -0,0 +1,59 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::types::addon::Descriptor;
+use crate::types::library::LibraryItem;
+use crate::types::profile::User;
+
+/// A mutation which has to be pushed to the API.
+///
+/// Only the payload is stored, the auth key of the current user is used when it's sent.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[serde(tag = "type", content = "payload")]
+pub enum OutboxRequest {
+    /// `saveUser` request
+    SaveUser {
+        user: User,
+        /// When the request was made, used to tell which of two requests is newer
+        mtime: DateTime<Utc>,
+    },
+    /// `addonCollectionSet` request
+    AddonCollectionSet {
+        addons: Vec<Descriptor>,
+        /// When the request was made, used to tell which of two requests is newer
+        mtime: DateTime<Utc>,
+    },
+    /// `datastorePut` request for the library collection
+    LibraryItemsPut(Vec<LibraryItem>),
+}
+
+impl OutboxRequest {
+    /// The number of changes which this request holds.
+    pub fn changes_count(&self) -> usize {
+        match self {
+            OutboxRequest::SaveUser { .. } | OutboxRequest::AddonCollectionSet { .. } => 1,
+            OutboxRequest::LibraryItemsPut(items) => items.len(),
+        }
+    }
+    /// Whether this request replaces the given request of the same kind,
+    /// i.e. it was made at the same time or after it.
+    ///
+    /// Library items are compared one by one, so this is always `false` for them.
+    pub fn supersedes(&self, request: &OutboxRequest) -> bool {
+        match (self, request) {
+            (
+                OutboxRequest::SaveUser { mtime, .. },
+                OutboxRequest::SaveUser {
+                    mtime: prev_mtime, ..
+                },
+            )
+            | (
+                OutboxRequest::AddonCollectionSet { mtime, .. },
+                OutboxRequest::AddonCollectionSet {
+                    mtime: prev_mtime, ..
+                },
+            ) => mtime >= prev_mtime,
+            _ => false,
+        }
+    }
+}
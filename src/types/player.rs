@@ -1,4 +1,7 @@
 use serde::Serialize;
+use url::Url;
+
+use crate::types::resource::Subtitles;
 
 #[derive(Clone, Serialize, Debug, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
@@ -16,3 +19,86 @@ pub struct IntroData {
     /// and stream duration ([`LibraryItem.state.duration`]) > 0!
     pub duration: Option<u64>,
 }
+
+/// Where the [`PlayerSubtitles`] were found
+#[derive(Clone, Serialize, Debug, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum SubtitlesSource {
+    /// The [`Stream::subtitles`] of the played stream
+    ///
+    /// [`Stream::subtitles`]: crate::types::resource::Stream::subtitles
+    Stream,
+    /// The subtitles resource of an addon
+    #[serde(rename_all = "camelCase")]
+    Addon { transport_url: Url },
+}
+
+/// Subtitles merged from the stream and all the addons
+#[derive(Clone, Serialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayerSubtitles {
+    /// The id of the track, as stored in [`SubtitleTrack::id`].
+    ///
+    /// [`SubtitleTrack::id`]: crate::types::streams::SubtitleTrack::id
+    pub id: String,
+    #[serde(flatten)]
+    pub subtitles: Subtitles,
+    pub source: SubtitlesSource,
+}
+
+/// ISO 639-1, ISO 639-2 (B and T) codes and english names of the same language.
+/// The first entry is the one used in the profile settings.
+const LANGUAGES: &[&[&str]] = &[
+    &["eng", "en", "english"],
+    &["spa", "es", "spanish"],
+    &["fre", "fra", "fr", "french"],
+    &["ger", "deu", "de", "german"],
+    &["ita", "it", "italian"],
+    &["por", "pt", "portuguese"],
+    &["rus", "ru", "russian"],
+    &["ara", "ar", "arabic"],
+    &["chi", "zho", "zh", "chinese"],
+    &["jpn", "ja", "japanese"],
+    &["kor", "ko", "korean"],
+    &["dut", "nld", "nl", "dutch"],
+    &["pol", "pl", "polish"],
+    &["tur", "tr", "turkish"],
+    &["swe", "sv", "swedish"],
+    &["nor", "no", "nb", "nob", "norwegian"],
+    &["dan", "da", "danish"],
+    &["fin", "fi", "finnish"],
+    &["cze", "ces", "cs", "czech"],
+    &["slo", "slk", "sk", "slovak"],
+    &["gre", "ell", "el", "greek"],
+    &["heb", "he", "hebrew"],
+    &["hun", "hu", "hungarian"],
+    &["rum", "ron", "ro", "romanian"],
+    &["bul", "bg", "bulgarian"],
+    &["hrv", "hr", "croatian"],
+    &["srp", "sr", "serbian"],
+    &["slv", "sl", "slovenian"],
+    &["ukr", "uk", "ukrainian"],
+    &["vie", "vi", "vietnamese"],
+    &["tha", "th", "thai"],
+    &["ind", "id", "indonesian"],
+    &["may", "msa", "ms", "malay"],
+    &["per", "fas", "fa", "persian"],
+    &["hin", "hi", "hindi"],
+];
+
+/// Normalizes a subtitles language to the code used in the profile settings.
+///
+/// Unknown languages are only lowercased, so they still match themselves.
+pub fn normalize_language(lang: &str) -> String {
+    let lang = lang.trim().to_lowercase();
+    LANGUAGES
+        .iter()
+        .find(|codes| codes.contains(&lang.as_str()))
+        .map(|codes| codes[0].to_owned())
+        .unwrap_or(lang)
+}
+
+/// Whether both languages are the same, regardless of the code or case used.
+pub fn is_same_language(a: &str, b: &str) -> bool {
+    normalize_language(a) == normalize_language(b)
+}
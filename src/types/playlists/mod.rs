@@ -0,0 +1,5 @@
+mod playlist;
+pub use playlist::*;
+
+mod playlists_bucket;
+pub use playlists_bucket::*;
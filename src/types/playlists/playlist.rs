@@ -0,0 +1,30 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::types::resource::MetaItemPreview;
+
+/// A named list of meta items in the order chosen by the user.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Playlist {
+    pub id: String,
+    pub name: String,
+    pub items: Vec<MetaItemPreview>,
+    pub ctime: DateTime<Utc>,
+    pub mtime: DateTime<Utc>,
+}
+
+impl Playlist {
+    pub fn new(id: String, name: String, now: DateTime<Utc>) -> Self {
+        Self {
+            id,
+            name,
+            items: vec![],
+            ctime: now,
+            mtime: now,
+        }
+    }
+    pub fn contains(&self, meta_item_id: &str) -> bool {
+        self.items.iter().any(|item| item.id == meta_item_id)
+    }
+}
@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+
+use crate::types::playlists::Playlist;
+use crate::types::profile::UID;
+
+#[derive(Default, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub struct PlaylistsBucket {
+    pub uid: UID,
+    pub items: Vec<Playlist>,
+}
+
+impl PlaylistsBucket {
+    pub fn new(uid: UID) -> Self {
+        Self { uid, items: vec![] }
+    }
+    pub fn get(&self, id: &str) -> Option<&Playlist> {
+        self.items.iter().find(|playlist| playlist.id == id)
+    }
+    pub fn get_mut(&mut self, id: &str) -> Option<&mut Playlist> {
+        self.items.iter_mut().find(|playlist| playlist.id == id)
+    }
+}
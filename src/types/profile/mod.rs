@@ -1,11 +1,17 @@
 mod auth;
 pub use auth::*;
 
+mod parental_controls;
+pub use parental_controls::*;
+
 mod profile;
 pub use profile::*;
 
 mod settings;
 pub use settings::*;
 
+mod smart_list;
+pub use smart_list::*;
+
 mod user;
 pub use user::*;
@@ -0,0 +1,120 @@
+use chacha20poly1305::aead::{rand_core::RngCore, OsRng};
+use pbkdf2::pbkdf2_hmac;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// PBKDF2 rounds of the PIN hash, slow enough to make guessing all the PINs expensive
+const PIN_HASH_ROUNDS: u32 = 100_000;
+/// In bytes
+const PIN_SALT_LENGTH: usize = 16;
+
+/// Maturity certification of the content,
+/// ordered from the content suitable for all ages to the adults only content.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Debug)]
+pub enum Certification {
+    G,
+    PG,
+    #[serde(rename = "PG-13")]
+    PG13,
+    R,
+    #[serde(rename = "NC-17")]
+    NC17,
+}
+
+impl Certification {
+    /// Parses both the movie ratings (`PG-13`) and the TV parental guidelines (`TV-14`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stremio_core::types::profile::Certification;
+    ///
+    /// assert_eq!(Certification::parse("pg-13"), Some(Certification::PG13));
+    /// assert_eq!(Certification::parse("TV-MA"), Some(Certification::NC17));
+    /// assert_eq!(Certification::parse("Unrated"), None);
+    /// ```
+    pub fn parse(certification: &str) -> Option<Self> {
+        match certification.trim().to_uppercase().as_str() {
+            "G" | "TV-Y" | "TV-G" => Some(Certification::G),
+            "PG" | "TV-Y7" | "TV-Y7-FV" | "TV-PG" => Some(Certification::PG),
+            "PG-13" | "TV-14" => Some(Certification::PG13),
+            "R" => Some(Certification::R),
+            "NC-17" | "TV-MA" | "X" => Some(Certification::NC17),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Default, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ParentalControls {
+    /// Content rated above this certification is hidden
+    pub max_certification: Option<Certification>,
+    /// Content in any of those genres is hidden, genres are compared case-insensitively
+    #[serde(default)]
+    pub blocked_genres: Vec<String>,
+    /// Whether content without a known certification is hidden when `max_certification` is set
+    #[serde(default)]
+    pub block_unrated: bool,
+    /// PBKDF2-HMAC-SHA256 hash of the PIN required to unlock or change the parental controls
+    pub pin_hash: Option<String>,
+    /// Random salt of the `pin_hash`, generated once per device.
+    ///
+    /// `None` for the unsalted SHA-256 hashes of the PINs set before the salt was introduced,
+    /// they are rehashed on the next successful unlock.
+    #[serde(default)]
+    pub pin_salt: Option<String>,
+}
+
+impl ParentalControls {
+    pub fn is_enabled(&self) -> bool {
+        self.max_certification.is_some() || !self.blocked_genres.is_empty()
+    }
+    pub fn has_pin(&self) -> bool {
+        self.pin_hash.is_some()
+    }
+    /// Always `true` when there is no PIN set
+    pub fn verify_pin(&self, pin: &str) -> bool {
+        match (&self.pin_hash, &self.pin_salt) {
+            (Some(pin_hash), Some(pin_salt)) => *pin_hash == hash_pin(pin, pin_salt),
+            (Some(pin_hash), None) => *pin_hash == hex::encode(Sha256::digest(pin.as_bytes())),
+            (None, _) => true,
+        }
+    }
+    /// Whether the `pin_hash` is an unsalted one which should be rehashed
+    pub fn has_legacy_pin(&self) -> bool {
+        self.pin_hash.is_some() && self.pin_salt.is_none()
+    }
+    pub fn set_pin(&mut self, pin: Option<&str>) {
+        let pin_salt = self.pin_salt.get_or_insert_with(|| {
+            let mut salt = [0; PIN_SALT_LENGTH];
+            OsRng.fill_bytes(&mut salt);
+            hex::encode(salt)
+        });
+        self.pin_hash = pin.map(|pin| hash_pin(pin, pin_salt));
+    }
+    /// Whether content with the given certification and genres can be shown.
+    pub fn allows<'a>(
+        &self,
+        certification: Option<Certification>,
+        mut genres: impl Iterator<Item = &'a str>,
+    ) -> bool {
+        let certification_allowed = match (self.max_certification, certification) {
+            (Some(max_certification), Some(certification)) => certification <= max_certification,
+            (Some(_), None) => !self.block_unrated,
+            (None, _) => true,
+        };
+        certification_allowed
+            && !genres.any(|genre| {
+                self.blocked_genres
+                    .iter()
+                    .any(|blocked_genre| blocked_genre.eq_ignore_ascii_case(genre))
+            })
+    }
+}
+
+fn hash_pin(pin: &str, salt: &str) -> String {
+    let mut hash = [0; 32];
+    pbkdf2_hmac::<Sha256>(pin.as_bytes(), salt.as_bytes(), PIN_HASH_ROUNDS, &mut hash);
+    hex::encode(hash)
+}
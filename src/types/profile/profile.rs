@@ -1,7 +1,7 @@
 use crate::constants::OFFICIAL_ADDONS;
 use crate::runtime::Env;
 use crate::types::addon::Descriptor;
-use crate::types::profile::{Auth, AuthKey, Settings};
+use crate::types::profile::{Auth, AuthKey, ParentalControls, Settings, SmartList};
 use crate::types::{UniqueVec, UniqueVecAdapter};
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
@@ -26,6 +26,10 @@ pub struct Profile {
     #[serde(default)]
     pub addons_locked: bool,
     pub settings: Settings,
+    #[serde(default)]
+    pub parental_controls: ParentalControls,
+    #[serde(default)]
+    pub smart_lists: Vec<SmartList>,
 }
 
 impl Default for Profile {
@@ -35,6 +39,8 @@ impl Default for Profile {
             addons: OFFICIAL_ADDONS.to_owned(),
             addons_locked: false,
             settings: Settings::default(),
+            parental_controls: ParentalControls::default(),
+            smart_lists: vec![],
         }
     }
 }
@@ -1,6 +1,8 @@
-use crate::constants::STREAMING_SERVER_URL;
-use chrono::{DateTime, Utc};
+use crate::constants::{QUIET_HOURS_MAX_UTC_OFFSET, STREAMING_SERVER_URL};
+use crate::types::scheduler::SchedulerJob;
+use chrono::{DateTime, Duration, Timelike, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 use url::Url;
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -38,6 +40,12 @@ pub struct Settings {
     pub pause_on_minimize: bool,
     pub surround_sound: bool,
     pub streaming_server_warning_dismissed: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub notifications: NotificationSettings,
+    /// Interval (in milliseconds) of the [`SchedulerJob`]s which don't run on their default one,
+    /// `None` disables the job.
+    #[serde(default)]
+    pub scheduler_job_intervals: BTreeMap<SchedulerJob, Option<u64>>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -47,6 +55,82 @@ pub enum FrameRateMatchingStrategy {
     FrameRateAndResolution,
 }
 
+/// Which of the new videos of a library item end up in the notifications.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NotificationRule {
+    /// Every video released after the item was last watched
+    AllNewVideos,
+    /// Only the videos coming after the last watched video (by season and episode)
+    AfterLastWatched,
+    /// Only the first video of each season that hadn't started when the item was last watched
+    NewSeasonsOnly,
+    /// No notifications at all
+    Disabled,
+}
+
+/// A range of hours in the local time of the user during which notifications are not pulled.
+///
+/// When `start` is greater than `end` the range wraps around midnight.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuietHours {
+    /// From 0 to 23
+    pub start: u32,
+    /// From 0 to 23
+    pub end: u32,
+    /// The offset (in minutes) of the local time of the user from UTC
+    #[serde(default)]
+    pub utc_offset: i32,
+}
+
+impl QuietHours {
+    pub fn is_valid(&self) -> bool {
+        self.start < 24
+            && self.end < 24
+            && (-QUIET_HOURS_MAX_UTC_OFFSET..=QUIET_HOURS_MAX_UTC_OFFSET).contains(&self.utc_offset)
+    }
+    pub fn contains(&self, time: &DateTime<Utc>) -> bool {
+        let hour = (*time + Duration::minutes(self.utc_offset.into())).hour();
+        if self.start <= self.end {
+            self.start <= hour && hour < self.end
+        } else {
+            self.start <= hour || hour < self.end
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct NotificationSettings {
+    /// How often (in hours) the addons are asked for the last videos of the library items,
+    /// from once an hour to once every [`NOTIFICATIONS_MAX_POLL_INTERVAL`] hours
+    ///
+    /// [`NOTIFICATIONS_MAX_POLL_INTERVAL`]: crate::constants::NOTIFICATIONS_MAX_POLL_INTERVAL
+    pub poll_interval: u32,
+    pub quiet_hours: Option<QuietHours>,
+    /// The rule used for library items without a rule of their own
+    pub default_rule: NotificationRule,
+    /// Rules per [`LibraryItem`](crate::types::library::LibraryItem) id
+    pub rules: HashMap<String, NotificationRule>,
+}
+
+impl NotificationSettings {
+    pub fn rule(&self, id: &str) -> NotificationRule {
+        self.rules.get(id).copied().unwrap_or(self.default_rule)
+    }
+}
+
+impl Default for NotificationSettings {
+    fn default() -> Self {
+        NotificationSettings {
+            poll_interval: 6,
+            quiet_hours: None,
+            default_rule: NotificationRule::AllNewVideos,
+            rules: HashMap::new(),
+        }
+    }
+}
+
 impl Default for Settings {
     fn default() -> Self {
         Settings {
@@ -77,6 +161,8 @@ impl Default for Settings {
             pause_on_minimize: false,
             surround_sound: false,
             streaming_server_warning_dismissed: None,
+            notifications: NotificationSettings::default(),
+            scheduler_job_intervals: BTreeMap::new(),
         }
     }
 }
@@ -0,0 +1,87 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::types::library::{LibraryItem, WatchStatus};
+use crate::types::notifications::NotificationsBucket;
+
+/// A user-defined catalog of the library items matching the saved [`LibraryQuery`].
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SmartList {
+    pub id: String,
+    pub name: String,
+    pub query: LibraryQuery,
+}
+
+/// Every condition which is set has to be met, removed items never match.
+#[derive(Default, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct LibraryQuery {
+    #[serde(default)]
+    pub r#type: Option<String>,
+    /// Compared case-insensitively
+    #[serde(default)]
+    pub genre: Option<String>,
+    #[serde(default)]
+    pub watched: Option<bool>,
+    #[serde(default)]
+    pub watch_status: Option<WatchStatus>,
+    #[serde(default)]
+    pub added_after: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub added_before: Option<DateTime<Utc>>,
+    /// Minimum watch progress in percents
+    #[serde(default)]
+    pub min_progress: Option<u8>,
+    /// Maximum watch progress in percents
+    #[serde(default)]
+    pub max_progress: Option<u8>,
+    /// Whether the item has any new videos
+    #[serde(default)]
+    pub has_notifications: Option<bool>,
+}
+
+impl LibraryQuery {
+    pub fn matches(&self, library_item: &LibraryItem, notifications: &NotificationsBucket) -> bool {
+        let progress = library_item.progress();
+        !library_item.removed
+            && self
+                .r#type
+                .as_ref()
+                .map_or(true, |r#type| library_item.r#type == *r#type)
+            && self.genre.as_ref().map_or(true, |genre| {
+                library_item
+                    .genres()
+                    .any(|item_genre| item_genre.eq_ignore_ascii_case(genre))
+            })
+            && self
+                .watched
+                .map_or(true, |watched| library_item.watched() == watched)
+            && self.watch_status.map_or(true, |watch_status| {
+                library_item.state.watch_status() == Some(watch_status)
+            })
+            && self.added_after.map_or(true, |added_after| {
+                library_item
+                    .ctime
+                    .map_or(false, |ctime| ctime >= added_after)
+            })
+            && self.added_before.map_or(true, |added_before| {
+                library_item
+                    .ctime
+                    .map_or(false, |ctime| ctime < added_before)
+            })
+            && self
+                .min_progress
+                .map_or(true, |min_progress| progress >= min_progress as f64)
+            && self
+                .max_progress
+                .map_or(true, |max_progress| progress <= max_progress as f64)
+            && self.has_notifications.map_or(true, |has_notifications| {
+                notifications
+                    .items
+                    .get(&library_item.id)
+                    .map_or(false, |notifications| !notifications.is_empty())
+                    == has_notifications
+            })
+    }
+}
@@ -15,11 +15,12 @@ use serde_with::{
 use url::Url;
 
 use crate::constants::{
-    CATALOG_RESOURCE_NAME, CINEMETA_TOP_CATALOG_ID, CINEMETA_URL, GENRES_LINK_CATEGORY,
-    IMDB_LINK_CATEGORY, IMDB_TITLE_PATH, IMDB_URL, URI_COMPONENT_ENCODE_SET,
+    CAST_LINK_CATEGORY, CATALOG_RESOURCE_NAME, CINEMETA_TOP_CATALOG_ID, CINEMETA_URL,
+    GENRES_LINK_CATEGORY, IMDB_LINK_CATEGORY, IMDB_TITLE_PATH, IMDB_URL, URI_COMPONENT_ENCODE_SET,
 };
 use crate::deep_links::DiscoverDeepLinks;
 use crate::types::addon::{ExtraValue, ResourcePath, ResourceRequest};
+use crate::types::profile::Certification;
 use crate::types::resource::{Stream, StreamSource};
 use crate::types::{NumberAsString, SortedVec, SortedVecAdapter, UniqueVec, UniqueVecAdapter};
 
@@ -164,6 +165,27 @@ pub struct MetaItemPreview {
     pub behavior_hints: MetaItemBehaviorHints,
 }
 
+impl MetaItemPreview {
+    pub fn certification(&self) -> Option<Certification> {
+        self.behavior_hints.certification()
+    }
+    /// Returns the genres from both the genre links and the behavior hints
+    pub fn genres(&self) -> impl Iterator<Item = &str> {
+        self.links
+            .iter()
+            .filter(|link| link.category == GENRES_LINK_CATEGORY)
+            .map(|link| link.name.as_str())
+            .chain(self.behavior_hints.genres())
+    }
+    /// Returns the names from the cast links
+    pub fn cast(&self) -> impl Iterator<Item = &str> {
+        self.links
+            .iter()
+            .filter(|link| link.category == CAST_LINK_CATEGORY)
+            .map(|link| link.name.as_str())
+    }
+}
+
 impl From<MetaItemPreviewLegacy> for MetaItemPreview {
     fn from(legacy_item: MetaItemPreviewLegacy) -> Self {
         let links = match legacy_item.links {
@@ -393,3 +415,22 @@ pub struct MetaItemBehaviorHints {
     #[serde(flatten)]
     pub other: HashMap<String, serde_json::Value>,
 }
+
+impl MetaItemBehaviorHints {
+    /// The `certification` hint set by the addon, e.g. `PG-13`
+    pub fn certification(&self) -> Option<Certification> {
+        self.other
+            .get("certification")
+            .and_then(|certification| certification.as_str())
+            .and_then(Certification::parse)
+    }
+    /// The `genres` hint set by the addon
+    pub fn genres(&self) -> impl Iterator<Item = &str> {
+        self.other
+            .get("genres")
+            .and_then(|genres| genres.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|genre| genre.as_str())
+    }
+}
@@ -0,0 +1,2 @@
+mod scheduler;
+pub use scheduler::*;
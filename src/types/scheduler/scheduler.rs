@@ -0,0 +1,222 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use strum::{EnumIter, IntoEnumIterator};
+
+use crate::constants::{
+    NOTIFICATIONS_MAX_POLL_INTERVAL, SCHEDULER_JOB_TIMEOUT, SCHEDULER_MAX_BACKOFF,
+    SCHEDULER_MAX_INTERVAL,
+};
+use crate::runtime::msg::Internal;
+use crate::types::profile::Settings;
+
+/// A periodic job which is run by the [`Scheduler`] on every `SchedulerTick` when it's due.
+#[derive(
+    Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, EnumIter, Serialize, Deserialize, Debug,
+)]
+pub enum SchedulerJob {
+    SyncLibraryWithAPI,
+    PullAddonsFromAPI,
+    PullUserFromAPI,
+    PullNotifications,
+    GetEvents,
+    FlushOutbox,
+}
+
+impl SchedulerJob {
+    /// The default interval between runs of the job in milliseconds
+    pub fn default_interval(&self) -> u64 {
+        match self {
+            SchedulerJob::SyncLibraryWithAPI => 15 * 60 * 1000,
+            SchedulerJob::PullAddonsFromAPI => 60 * 60 * 1000,
+            SchedulerJob::PullUserFromAPI => 60 * 60 * 1000,
+            SchedulerJob::PullNotifications => 6 * 60 * 60 * 1000,
+            SchedulerJob::GetEvents => 60 * 60 * 1000,
+            SchedulerJob::FlushOutbox => 5 * 60 * 1000,
+        }
+    }
+    /// Whether the interval (in milliseconds) can be set for a job,
+    /// it's either `None` (disabled) or from 1 to [`SCHEDULER_MAX_INTERVAL`].
+    pub fn is_valid_interval(interval: Option<u64>) -> bool {
+        interval.map_or(true, |interval| {
+            (1..=SCHEDULER_MAX_INTERVAL).contains(&interval)
+        })
+    }
+    /// Jobs which make requests to the API on behalf of the user can run only when logged in.
+    pub fn requires_auth(&self) -> bool {
+        matches!(
+            self,
+            SchedulerJob::SyncLibraryWithAPI
+                | SchedulerJob::PullUserFromAPI
+                | SchedulerJob::FlushOutbox
+        )
+    }
+}
+
+impl From<SchedulerJob> for Internal {
+    fn from(job: SchedulerJob) -> Self {
+        match job {
+            SchedulerJob::SyncLibraryWithAPI => Internal::SyncLibraryWithAPI,
+            SchedulerJob::PullAddonsFromAPI => Internal::PullAddonsFromAPI,
+            SchedulerJob::PullUserFromAPI => Internal::PullUserFromAPI,
+            SchedulerJob::PullNotifications => Internal::PullNotifications,
+            SchedulerJob::GetEvents => Internal::GetEvents,
+            SchedulerJob::FlushOutbox => Internal::FlushOutbox,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SchedulerJobState {
+    /// Interval between runs in milliseconds, `None` if the job is disabled.
+    pub interval: Option<u64>,
+    pub last_run: Option<DateTime<Utc>>,
+    /// When `None` the job will run on the next tick.
+    pub next_run: Option<DateTime<Utc>>,
+    /// Set while the job is in-flight, overlapping runs are coalesced into this one.
+    pub running_since: Option<DateTime<Utc>>,
+    /// Number of consecutive failures, used for the backoff of the next run.
+    pub failures: u32,
+}
+
+impl SchedulerJobState {
+    pub fn new(interval: Option<u64>) -> Self {
+        Self {
+            interval,
+            last_run: None,
+            next_run: None,
+            running_since: None,
+            failures: 0,
+        }
+    }
+    pub fn is_running(&self, now: DateTime<Utc>) -> bool {
+        // a job which never reported back is considered stale after the timeout
+        self.running_since
+            .map(|running_since| {
+                running_since + Duration::milliseconds(SCHEDULER_JOB_TIMEOUT as i64) > now
+            })
+            .unwrap_or_default()
+    }
+    pub fn is_due(&self, now: DateTime<Utc>) -> bool {
+        self.interval.is_some()
+            && !self.is_running(now)
+            && self
+                .next_run
+                .map(|next_run| next_run <= now)
+                .unwrap_or(true)
+    }
+    pub fn start(&mut self, now: DateTime<Utc>) {
+        self.running_since = Some(now);
+    }
+    /// Marks the job as finished and schedules the next run.
+    ///
+    /// After a failure the interval is doubled for every consecutive failure
+    /// up to [`SCHEDULER_MAX_BACKOFF`] (or the interval itself if it's bigger).
+    pub fn finish(&mut self, now: DateTime<Utc>, success: bool) {
+        self.running_since = None;
+        self.last_run = Some(now);
+        self.failures = if success {
+            0
+        } else {
+            self.failures.saturating_add(1)
+        };
+        self.next_run = self.interval.and_then(|interval| {
+            let delay = interval
+                .saturating_mul(2_u64.saturating_pow(self.failures))
+                .min(SCHEDULER_MAX_BACKOFF.max(interval));
+            next_run(now, delay)
+        });
+    }
+    /// Changes the interval and reschedules the next run relative to the last one.
+    pub fn set_interval(&mut self, interval: Option<u64>) {
+        self.interval = interval;
+        self.next_run = self
+            .last_run
+            .zip(interval)
+            .and_then(|(last_run, interval)| next_run(last_run, interval));
+    }
+    /// Clears the runs history while keeping the configured interval.
+    pub fn reset(&mut self) {
+        *self = Self::new(self.interval);
+    }
+}
+
+/// The time of the next run after the given delay (in milliseconds).
+///
+/// The intervals are validated when they're set, but the ones read from the storage may not be,
+/// so the delay is capped at [`SCHEDULER_MAX_INTERVAL`].
+fn next_run(from: DateTime<Utc>, delay: u64) -> Option<DateTime<Utc>> {
+    let delay = delay.min(SCHEDULER_MAX_INTERVAL) as i64;
+    from.checked_add_signed(Duration::milliseconds(delay))
+}
+
+#[derive(Clone, PartialEq, Eq, Serialize, Debug)]
+pub struct Scheduler {
+    pub jobs: BTreeMap<SchedulerJob, SchedulerJobState>,
+}
+
+impl Scheduler {
+    /// Returns the jobs that are due and marks them as running.
+    ///
+    /// Jobs which require authentication are skipped when `authenticated` is `false`.
+    pub fn start_due_jobs(&mut self, now: DateTime<Utc>, authenticated: bool) -> Vec<SchedulerJob> {
+        self.jobs
+            .iter_mut()
+            .filter(|(job, state)| (authenticated || !job.requires_auth()) && state.is_due(now))
+            .map(|(job, state)| {
+                state.start(now);
+                *job
+            })
+            .collect()
+    }
+    /// Clears the runs history of all jobs while keeping their intervals.
+    pub fn reset(&mut self) {
+        self.jobs.values_mut().for_each(SchedulerJobState::reset);
+    }
+    /// Applies the intervals configured in the settings,
+    /// the jobs without one run on their default interval
+    /// and the notifications are pulled on their poll interval.
+    ///
+    /// Returns whether any of the intervals has changed.
+    pub fn update_intervals(&mut self, settings: &Settings) -> bool {
+        SchedulerJob::iter().fold(false, |changed, job| {
+            let default_interval = match job {
+                SchedulerJob::PullNotifications => {
+                    let poll_interval = settings
+                        .notifications
+                        .poll_interval
+                        .clamp(1, NOTIFICATIONS_MAX_POLL_INTERVAL);
+                    u64::from(poll_interval) * 60 * 60 * 1000
+                }
+                _ => job.default_interval(),
+            };
+            let interval = settings
+                .scheduler_job_intervals
+                .get(&job)
+                .copied()
+                .unwrap_or(Some(default_interval));
+            let job_state = self
+                .jobs
+                .entry(job)
+                .or_insert_with(|| SchedulerJobState::new(None));
+            if job_state.interval != interval {
+                job_state.set_interval(interval);
+                true
+            } else {
+                changed
+            }
+        })
+    }
+}
+
+impl Default for Scheduler {
+    fn default() -> Self {
+        Self {
+            jobs: SchedulerJob::iter()
+                .map(|job| (job, SchedulerJobState::new(Some(job.default_interval()))))
+                .collect(),
+        }
+    }
+}
@@ -1,14 +1,40 @@
-use std::collections::HashMap;
+use std::cmp::Ordering;
+use std::collections::{hash_map::Entry, HashMap};
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use crate::constants::SEARCH_HISTORY_MAX_ITEMS;
 use crate::types::profile::UID;
 
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchHistoryItem {
+    pub last_searched: DateTime<Utc>,
+    /// How many times the query was searched
+    pub count: u32,
+}
+
+impl SearchHistoryItem {
+    /// Ranks the frequently searched queries higher,
+    /// halving the weight of the searches for every week since the last one.
+    pub fn score(&self, now: &DateTime<Utc>) -> f64 {
+        let weeks = now
+            .signed_duration_since(self.last_searched)
+            .num_seconds()
+            .max(0) as f64
+            / (7 * 24 * 60 * 60) as f64;
+        self.count as f64 / 2_f64.powf(weeks)
+    }
+}
+
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SearchHistoryBucket {
     pub uid: UID,
-    pub items: HashMap<String, DateTime<Utc>>,
+    pub items: HashMap<String, SearchHistoryItem>,
+    /// When `true` the searches are not recorded
+    #[serde(default)]
+    pub paused: bool,
 }
 
 impl SearchHistoryBucket {
@@ -16,6 +42,69 @@ impl SearchHistoryBucket {
         Self {
             uid,
             items: HashMap::new(),
+            paused: false,
         }
     }
+    /// Records a search of the query, pruning the least recently searched query
+    /// when a new one is added over [`SEARCH_HISTORY_MAX_ITEMS`].
+    ///
+    /// Returns `false` if the history is paused or the query is blank.
+    pub fn record(&mut self, query: &str, now: DateTime<Utc>) -> bool {
+        let query = query.trim();
+        if self.paused || query.is_empty() {
+            return false;
+        }
+        match self.items.entry(query.to_owned()) {
+            Entry::Occupied(mut entry) => {
+                let item = entry.get_mut();
+                item.last_searched = now;
+                item.count = item.count.saturating_add(1);
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(SearchHistoryItem {
+                    last_searched: now,
+                    count: 1,
+                });
+                if self.items.len() > SEARCH_HISTORY_MAX_ITEMS {
+                    let least_recent_query = self
+                        .items
+                        .iter()
+                        .filter(|(other_query, _)| *other_query != query)
+                        .min_by(|(a_query, a_item), (b_query, b_item)| {
+                            a_item
+                                .last_searched
+                                .cmp(&b_item.last_searched)
+                                .then_with(|| a_query.cmp(b_query))
+                        })
+                        .map(|(query, _)| query.to_owned());
+                    if let Some(least_recent_query) = least_recent_query {
+                        self.items.remove(&least_recent_query);
+                    }
+                }
+            }
+        }
+        true
+    }
+    /// The queries starting with the given text, case insensitive,
+    /// ranked by how often and how recently they were searched.
+    pub fn suggestions(&self, text: &str, now: &DateTime<Utc>, limit: usize) -> Vec<&String> {
+        let text = text.trim().to_lowercase();
+        let mut suggestions = self
+            .items
+            .iter()
+            .filter(|(query, _)| query.to_lowercase().starts_with(&text))
+            .map(|(query, item)| (query, item.score(now)))
+            .collect::<Vec<_>>();
+        suggestions.sort_by(|(a_query, a_score), (b_query, b_score)| {
+            b_score
+                .partial_cmp(a_score)
+                .unwrap_or(Ordering::Equal)
+                .then_with(|| a_query.cmp(b_query))
+        });
+        suggestions
+            .into_iter()
+            .map(|(query, _)| query)
+            .take(limit)
+            .collect()
+    }
 }
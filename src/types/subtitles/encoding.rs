@@ -0,0 +1,77 @@
+const UTF8_BOM: &[u8] = &[0xEF, 0xBB, 0xBF];
+const UTF16_LE_BOM: &[u8] = &[0xFF, 0xFE];
+const UTF16_BE_BOM: &[u8] = &[0xFE, 0xFF];
+
+/// The characters of the bytes `0x80..=0xBF` in CP1251,
+/// the bytes `0xC0..=0xFF` are the cyrillic letters `А..=я`.
+const CP1251_TABLE: [char; 64] = [
+    'Ђ', 'Ѓ', '‚', 'ѓ', '„', '…', '†', '‡', '€', '‰', 'Љ', '‹', 'Њ', 'Ќ', 'Ћ', 'Џ', 'ђ', '‘', '’',
+    '“', '”', '•', '–', '—', '\u{FFFD}', '™', 'љ', '›', 'њ', 'ќ', 'ћ', 'џ', '\u{A0}', 'Ў', 'ў',
+    'Ј', '¤', 'Ґ', '¦', '§', 'Ё', '©', 'Є', '«', '¬', '\u{AD}', '®', 'Ї', '°', '±', 'І', 'і', 'ґ',
+    'µ', '¶', '·', 'ё', '№', 'є', '»', 'ј', 'Ѕ', 'ѕ', 'ї',
+];
+
+/// The characters of the bytes `0x80..=0x9F` in CP1252,
+/// the bytes `0xA0..=0xFF` are the same as in latin1.
+const CP1252_TABLE: [char; 32] = [
+    '€', '\u{FFFD}', '‚', 'ƒ', '„', '…', '†', '‡', 'ˆ', '‰', 'Š', '‹', 'Œ', '\u{FFFD}', 'Ž',
+    '\u{FFFD}', '\u{FFFD}', '‘', '’', '“', '”', '•', '–', '—', '˜', '™', 'š', '›', 'œ', '\u{FFFD}',
+    'ž', 'Ÿ',
+];
+
+/// Decodes subtitles in an unknown encoding.
+///
+/// UTF-8 and UTF-16 (with a BOM) are decoded as they are, anything else is either CP1251
+/// or CP1252: cyrillic letters in CP1251 come in runs of bytes `>= 0xC0`,
+/// while the accented latin letters are usually surrounded by ASCII.
+pub fn decode_subtitles(bytes: &[u8]) -> String {
+    if let Some(bytes) = bytes.strip_prefix(UTF16_LE_BOM) {
+        return decode_utf16(bytes, u16::from_le_bytes);
+    }
+    if let Some(bytes) = bytes.strip_prefix(UTF16_BE_BOM) {
+        return decode_utf16(bytes, u16::from_be_bytes);
+    }
+    let bytes = bytes.strip_prefix(UTF8_BOM).unwrap_or(bytes);
+    if let Ok(text) = std::str::from_utf8(bytes) {
+        return text.to_owned();
+    }
+    if is_cp1251(bytes) {
+        bytes
+            .iter()
+            .map(|byte| match byte {
+                0x00..=0x7F => *byte as char,
+                0x80..=0xBF => CP1251_TABLE[(byte - 0x80) as usize],
+                // А is U+0410
+                0xC0..=0xFF => char::from_u32(0x0410 + (byte - 0xC0) as u32).unwrap_or('\u{FFFD}'),
+            })
+            .collect()
+    } else {
+        bytes
+            .iter()
+            .map(|byte| match byte {
+                0x80..=0x9F => CP1252_TABLE[(byte - 0x80) as usize],
+                _ => *byte as char,
+            })
+            .collect()
+    }
+}
+
+fn decode_utf16(bytes: &[u8], from_bytes: fn([u8; 2]) -> u16) -> String {
+    let units = bytes
+        .chunks_exact(2)
+        .map(|pair| from_bytes([pair[0], pair[1]]))
+        .collect::<Vec<_>>();
+    String::from_utf16_lossy(&units)
+}
+
+fn is_cp1251(bytes: &[u8]) -> bool {
+    let is_letter = |index: usize| bytes.get(index).map_or(false, |byte| *byte >= 0xC0);
+    let (letters, in_runs) = (0..bytes.len()).filter(|index| is_letter(*index)).fold(
+        (0_usize, 0_usize),
+        |(letters, in_runs), index| {
+            let in_run = (index > 0 && is_letter(index - 1)) || is_letter(index + 1);
+            (letters + 1, in_runs + usize::from(in_run))
+        },
+    );
+    letters > 0 && in_runs * 2 > letters
+}
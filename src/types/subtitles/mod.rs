@@ -0,0 +1,14 @@
+mod encoding;
+pub use encoding::*;
+
+mod parsed_subtitles;
+pub use parsed_subtitles::*;
+
+mod subtitles_cue;
+pub use subtitles_cue::*;
+
+mod subtitles_format;
+pub use subtitles_format::*;
+
+mod subtitles_sync;
+pub use subtitles_sync::*;
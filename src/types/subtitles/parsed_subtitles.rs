@@ -0,0 +1,316 @@
+use std::iter;
+
+use itertools::Itertools;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::types::subtitles::{decode_subtitles, SubtitlesCue, SubtitlesDrift, SubtitlesFormat};
+
+/// The `Format` of the ASS events when the file has none
+const ASS_DEFAULT_EVENTS_FORMAT: &str =
+    "Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text";
+const ASS_HEADER: &str = "[Script Info]
+ScriptType: v4.00+
+PlayResX: 384
+PlayResY: 288
+
+[V4+ Styles]
+Format: Name, Fontname, Fontsize, PrimaryColour, SecondaryColour, OutlineColour, BackColour, Bold, Italic, Underline, StrikeOut, ScaleX, ScaleY, Spacing, Angle, BorderStyle, Outline, Shadow, Alignment, MarginL, MarginR, MarginV, Encoding
+Style: Default,Arial,20,&H00FFFFFF,&H000000FF,&H00000000,&H00000000,0,0,0,0,100,100,0,0,1,2,2,2,10,10,10,1
+
+[Events]
+";
+
+static ASS_OVERRIDE_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"\{[^}]*\}").unwrap());
+static TAG_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"</?([a-zA-Z]+)[^>]*>").unwrap());
+
+#[derive(Error, Clone, PartialEq, Eq, Debug)]
+pub enum SubtitlesError {
+    #[error("Unknown subtitles format")]
+    UnknownFormat,
+    #[error("No subtitles found")]
+    Empty,
+}
+
+/// The cues of a subtitles file, for the clients without a subtitles renderer
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ParsedSubtitles {
+    /// The format the subtitles were parsed from
+    pub format: SubtitlesFormat,
+    /// Sorted by start time
+    pub cues: Vec<SubtitlesCue>,
+}
+
+impl ParsedSubtitles {
+    /// Parses subtitles of any supported format and encoding
+    pub fn parse(bytes: &[u8]) -> Result<Self, SubtitlesError> {
+        Self::parse_str(&decode_subtitles(bytes))
+    }
+
+    pub fn parse_str(content: &str) -> Result<Self, SubtitlesError> {
+        let content = content.replace("\r\n", "\n").replace('\r', "\n");
+        let format = SubtitlesFormat::detect(&content).ok_or(SubtitlesError::UnknownFormat)?;
+        let cues = match format {
+            SubtitlesFormat::Srt | SubtitlesFormat::WebVtt => parse_timed_blocks(&content),
+            SubtitlesFormat::Ass => parse_ass(&content),
+        }
+        .into_iter()
+        .filter(|cue| cue.end > cue.start && !cue.text.is_empty())
+        .sorted_by_key(|cue| (cue.start, cue.end))
+        .collect::<Vec<_>>();
+        if cues.is_empty() {
+            return Err(SubtitlesError::Empty);
+        }
+        Ok(Self { format, cues })
+    }
+
+    /// Shifts the cues by [`StreamItemState::subtitle_delay`], a positive delay shows them later.
+    ///
+    /// The cues that would end before the start of the video are dropped.
+    ///
+    /// [`StreamItemState::subtitle_delay`]: crate::types::streams::StreamItemState::subtitle_delay
+    pub fn with_delay(self, delay: i64) -> Self {
+        let shift = |time: u64| {
+            time.checked_add_signed(delay)
+                .unwrap_or(if delay < 0 { 0 } else { u64::MAX })
+        };
+        Self {
+            cues: self
+                .cues
+                .into_iter()
+                .map(|cue| SubtitlesCue {
+                    start: shift(cue.start),
+                    end: shift(cue.end),
+                    ..cue
+                })
+                .filter(|cue| cue.end > cue.start)
+                .collect(),
+            ..self
+        }
+    }
+
+    /// Applies the drift of a [`SubtitlesSync`], which a single delay can not correct.
+    ///
+    /// [`SubtitlesSync`]: crate::types::subtitles::SubtitlesSync
+    pub fn with_drift(self, drift: &SubtitlesDrift) -> Self {
+        let shift = |time: u64| u64::try_from(drift.apply(time)).unwrap_or_default();
+        Self {
+            cues: self
+                .cues
+                .into_iter()
+                .map(|cue| SubtitlesCue {
+                    start: shift(cue.start),
+                    end: shift(cue.end),
+                    ..cue
+                })
+                .filter(|cue| cue.end > cue.start)
+                .collect(),
+            ..self
+        }
+    }
+
+    /// The cues shown at the given time (in milliseconds)
+    pub fn active_cues(&self, time: u64) -> impl Iterator<Item = &SubtitlesCue> {
+        self.cues
+            .iter()
+            .take_while(move |cue| cue.start <= time)
+            .filter(move |cue| cue.is_active(time))
+    }
+
+    /// Writes the cues in the given format
+    pub fn to_format(&self, format: SubtitlesFormat) -> String {
+        match format {
+            SubtitlesFormat::Srt => self
+                .cues
+                .iter()
+                .enumerate()
+                .map(|(index, cue)| {
+                    format!(
+                        "{}\n{} --> {}\n{}\n\n",
+                        index + 1,
+                        format_timestamp(cue.start, ','),
+                        format_timestamp(cue.end, ','),
+                        block_text(&cue.text)
+                    )
+                })
+                .collect(),
+            SubtitlesFormat::WebVtt => iter::once("WEBVTT\n\n".to_owned())
+                .chain(self.cues.iter().map(|cue| {
+                    format!(
+                        "{} --> {}\n{}\n\n",
+                        format_timestamp(cue.start, '.'),
+                        format_timestamp(cue.end, '.'),
+                        block_text(&cue.text)
+                    )
+                }))
+                .collect(),
+            SubtitlesFormat::Ass => {
+                iter::once(format!("{ASS_HEADER}Format: {ASS_DEFAULT_EVENTS_FORMAT}\n"))
+                    .chain(self.cues.iter().map(|cue| {
+                        format!(
+                            "Dialogue: 0,{},{},Default,,0,0,0,,{}\n",
+                            format_ass_timestamp(cue.start),
+                            format_ass_timestamp(cue.end),
+                            ass_text(&cue.text)
+                        )
+                    }))
+                    .collect()
+            }
+        }
+    }
+}
+
+/// Parses the SubRip and WebVTT blocks, which are separated by empty lines
+/// and contain an optional identifier, the timing line and the text
+fn parse_timed_blocks(content: &str) -> Vec<SubtitlesCue> {
+    // the blocks are separated by blank lines, which can have trailing whitespace
+    content
+        .lines()
+        .collect::<Vec<_>>()
+        .split(|line| line.trim().is_empty())
+        .filter_map(|block| {
+            let mut lines = block
+                .iter()
+                .skip_while(|line| !line.contains("-->"))
+                .map(|line| line.trim_end());
+            let (start, end) = lines.next()?.split_once("-->")?;
+            // WebVTT cue settings follow the end timestamp
+            let end = end.split_whitespace().next()?;
+            Some(SubtitlesCue {
+                start: parse_timestamp(start)?,
+                end: parse_timestamp(end)?,
+                text: clean_tags(&lines.join("\n")),
+            })
+        })
+        .collect()
+}
+
+fn parse_ass(content: &str) -> Vec<SubtitlesCue> {
+    let events = content
+        .lines()
+        .map(str::trim)
+        .skip_while(|line| !line.eq_ignore_ascii_case("[Events]"))
+        .skip(1)
+        .take_while(|line| !line.starts_with('['))
+        .collect::<Vec<_>>();
+    let format = events
+        .iter()
+        .find_map(|line| line.strip_prefix("Format:"))
+        .unwrap_or(ASS_DEFAULT_EVENTS_FORMAT)
+        .split(',')
+        .map(|field| field.trim().to_lowercase())
+        .collect::<Vec<_>>();
+    let position = |name: &str| format.iter().position(|field| field == name);
+    let (start_position, end_position, text_position) =
+        match (position("start"), position("end"), position("text")) {
+            (Some(start), Some(end), Some(text)) => (start, end, text),
+            _ => return vec![],
+        };
+    events
+        .iter()
+        .filter_map(|line| line.strip_prefix("Dialogue:"))
+        .filter_map(|dialogue| {
+            // the text is the last field and can contain commas
+            let fields = dialogue.splitn(format.len(), ',').collect::<Vec<_>>();
+            let text = fields
+                .get(text_position)?
+                .replace("\\N", "\n")
+                .replace("\\n", "\n")
+                .replace("\\h", " ");
+            Some(SubtitlesCue {
+                start: parse_timestamp(fields.get(start_position)?)?,
+                end: parse_timestamp(fields.get(end_position)?)?,
+                text: ASS_OVERRIDE_REGEX
+                    .replace_all(&text, "")
+                    .lines()
+                    .map(str::trim)
+                    .join("\n")
+                    .trim()
+                    .to_owned(),
+            })
+        })
+        .collect()
+}
+
+/// Parses `[HH:]MM:SS[,.]fff` timestamps, the hours can have any number of digits
+/// and the fraction can be in tenths (ASS uses hundredths) or thousandths of a second
+fn parse_timestamp(timestamp: &str) -> Option<u64> {
+    let timestamp = timestamp.trim();
+    let (clock, fraction) = timestamp
+        .split_once(|c: char| c == ',' || c == '.')
+        .unwrap_or((timestamp, ""));
+    let millis = match fraction.len() {
+        0 => 0,
+        len @ 1..=3 => fraction.parse::<u64>().ok()? * 10_u64.pow(3 - len as u32),
+        _ => fraction.get(..3)?.parse::<u64>().ok()?,
+    };
+    let seconds = clock
+        .split(':')
+        .map(|part| part.trim().parse::<u64>().ok())
+        .collect::<Option<Vec<_>>>()?;
+    let seconds = match seconds.as_slice() {
+        [minutes, seconds] => minutes.checked_mul(60)?.checked_add(*seconds)?,
+        [hours, minutes, seconds] => hours
+            .checked_mul(3600)?
+            .checked_add(minutes.checked_mul(60)?)?
+            .checked_add(*seconds)?,
+        _ => return None,
+    };
+    seconds.checked_mul(1000)?.checked_add(millis)
+}
+
+fn format_timestamp(time: u64, separator: char) -> String {
+    format!(
+        "{:02}:{:02}:{:02}{separator}{:03}",
+        time / 3_600_000,
+        time / 60_000 % 60,
+        time / 1000 % 60,
+        time % 1000
+    )
+}
+
+fn format_ass_timestamp(time: u64) -> String {
+    format!(
+        "{}:{:02}:{:02}.{:02}",
+        time / 3_600_000,
+        time / 60_000 % 60,
+        time / 1000 % 60,
+        time % 1000 / 10
+    )
+}
+
+/// Keeps the tags supported by every format and drops the rest, e.g. `<font>` and WebVTT voices
+fn clean_tags(text: &str) -> String {
+    TAG_REGEX
+        .replace_all(text, |captures: &regex::Captures| {
+            match captures[1].to_lowercase().as_str() {
+                "i" | "b" | "u" => captures[0].to_lowercase(),
+                _ => String::new(),
+            }
+        })
+        .trim()
+        .to_owned()
+}
+
+/// An empty line would end the SubRip or WebVTT block
+fn block_text(text: &str) -> String {
+    text.lines()
+        .filter(|line| !line.trim().is_empty())
+        .join("\n")
+}
+
+fn ass_text(text: &str) -> String {
+    TAG_REGEX
+        .replace_all(text, |captures: &regex::Captures| {
+            let enable = if captures[0].starts_with("</") { 0 } else { 1 };
+            match captures[1].to_lowercase().as_str() {
+                tag @ ("i" | "b" | "u") => format!("{{\\{tag}{enable}}}"),
+                _ => String::new(),
+            }
+        })
+        .lines()
+        .join("\\N")
+}
@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+
+/// A single subtitle shown between `start` and `end`
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SubtitlesCue {
+    /// In milliseconds
+    pub start: u64,
+    /// In milliseconds
+    pub end: u64,
+    /// The lines of the cue separated by `\n`.
+    ///
+    /// Keeps the `<i>`, `<b>` and `<u>` tags supported by both SubRip and WebVTT.
+    pub text: String,
+}
+
+impl SubtitlesCue {
+    /// Whether the cue is shown at the given time (in milliseconds)
+    #[inline]
+    pub fn is_active(&self, time: u64) -> bool {
+        self.start <= time && time < self.end
+    }
+}
@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub enum SubtitlesFormat {
+    /// SubRip
+    #[serde(rename = "srt")]
+    Srt,
+    #[serde(rename = "vtt")]
+    WebVtt,
+    /// Advanced SubStation Alpha, SubStation Alpha is parsed the same way
+    #[serde(rename = "ass")]
+    Ass,
+}
+
+impl SubtitlesFormat {
+    /// Detects the format from the content, the subtitles urls rarely have an extension
+    pub fn detect(content: &str) -> Option<Self> {
+        let content = content.trim_start();
+        if content.starts_with("WEBVTT") {
+            Some(Self::WebVtt)
+        } else if content.starts_with("[Script Info]") || content.contains("[Events]") {
+            Some(Self::Ass)
+        } else if content.contains("-->") {
+            Some(Self::Srt)
+        } else {
+            None
+        }
+    }
+}
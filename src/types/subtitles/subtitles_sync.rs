@@ -0,0 +1,257 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use crate::constants::{
+    SUBTITLES_SYNC_MATCH_TOLERANCE, SUBTITLES_SYNC_MAX_OFFSET, SUBTITLES_SYNC_MIN_MATCHED_CUES,
+};
+use crate::types::subtitles::SubtitlesCue;
+
+/// In milliseconds, the precision of the voted offsets, refined afterwards
+const OFFSET_STEP: i64 = 100;
+/// How many of the most voted offsets are compared by their overlap
+const OFFSET_CANDIDATES: usize = 5;
+/// How much more a drifting correction has to overlap the reference than a constant delay
+const DRIFT_MIN_IMPROVEMENT: f64 = 1.2;
+/// The speed ratios of the common frame rate conversions,
+/// e.g. subtitles timed for a 25 fps release played on a 23.976 fps one
+const FRAME_RATE_RATIOS: [f64; 6] = [
+    25.0 / 23.976,
+    23.976 / 25.0,
+    25.0 / 24.0,
+    24.0 / 25.0,
+    24.0 / 23.976,
+    23.976 / 24.0,
+];
+
+/// When something is said, without the text
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub struct CueTiming {
+    /// In milliseconds
+    pub start: u64,
+    /// In milliseconds
+    pub end: u64,
+}
+
+impl From<&SubtitlesCue> for CueTiming {
+    fn from(cue: &SubtitlesCue) -> Self {
+        Self {
+            start: cue.start,
+            end: cue.end,
+        }
+    }
+}
+
+/// Moves the cues to `time * rate + offset`
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize, Debug)]
+pub struct SubtitlesDrift {
+    pub rate: f64,
+    /// In milliseconds
+    pub offset: i64,
+}
+
+impl SubtitlesDrift {
+    pub fn apply(&self, time: u64) -> i64 {
+        ((time as f64 * self.rate).round() as i64).saturating_add(self.offset)
+    }
+}
+
+/// The proposed correction of subtitles which are out of sync with a reference
+#[derive(Clone, PartialEq, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SubtitlesSync {
+    /// In milliseconds, the constant offset to store as [`StreamItemState::subtitle_delay`]
+    ///
+    /// [`StreamItemState::subtitle_delay`]: crate::types::streams::StreamItemState::subtitle_delay
+    pub delay: i64,
+    /// `Some` when the subtitles drift away from the reference and no delay fits all of them,
+    /// which has to be applied to the cues instead.
+    pub drift: Option<SubtitlesDrift>,
+    /// How many cues match the reference once corrected
+    pub matched_cues: usize,
+}
+
+impl SubtitlesSync {
+    /// Estimates the correction from the cue timings alone.
+    ///
+    /// Every pair of a cue and a close enough reference cue votes for their offset,
+    /// the most voted offsets are compared by how long the cues overlap the reference
+    /// and the best one is refined with the cues matching the reference.
+    /// The same is done for the frame rate conversions to detect a drift.
+    ///
+    /// `None` if not enough cues match the reference.
+    pub fn estimate(cues: &[CueTiming], reference: &[CueTiming]) -> Option<Self> {
+        if cues.len() < SUBTITLES_SYNC_MIN_MATCHED_CUES
+            || reference.len() < SUBTITLES_SYNC_MIN_MATCHED_CUES
+        {
+            return None;
+        }
+        let cues = cues
+            .iter()
+            .copied()
+            .sorted_by_key(|cue| cue.start)
+            .collect::<Vec<_>>();
+        let reference_starts = reference
+            .iter()
+            .map(|timing| timing.start as i64)
+            .sorted()
+            .collect::<Vec<_>>();
+        let reference = union(
+            reference
+                .iter()
+                .map(|timing| (timing.start as i64, timing.end as i64)),
+        );
+        let (constant, constant_overlap) = best_drift(&cues, &reference_starts, &reference, 1.0)?;
+        let constant_offsets = matching_pairs(&cues, &reference_starts, &constant)
+            .into_iter()
+            .map(|(time, reference_time)| reference_time - time as i64)
+            .sorted()
+            .collect::<Vec<_>>();
+        let drift = FRAME_RATE_RATIOS
+            .iter()
+            .filter_map(|rate| best_drift(&cues, &reference_starts, &reference, *rate))
+            .filter(|(_, overlap)| {
+                *overlap as f64 > constant_overlap as f64 * DRIFT_MIN_IMPROVEMENT
+            })
+            .max_by_key(|(_, overlap)| *overlap)
+            .map(|(drift, _)| {
+                let pairs = matching_pairs(&cues, &reference_starts, &drift);
+                (fit_drift(&pairs).unwrap_or(drift), pairs.len())
+            })
+            .filter(|(_, matched_cues)| *matched_cues >= SUBTITLES_SYNC_MIN_MATCHED_CUES);
+        let matched_cues = drift
+            .map(|(_, matched_cues)| matched_cues)
+            .unwrap_or(constant_offsets.len());
+        if matched_cues < SUBTITLES_SYNC_MIN_MATCHED_CUES {
+            return None;
+        }
+        Some(Self {
+            // the median is not thrown off by the cues matching the wrong reference
+            delay: constant_offsets
+                .get(constant_offsets.len() / 2)
+                .copied()
+                .unwrap_or(constant.offset),
+            drift: drift.map(|(drift, _)| drift),
+            matched_cues,
+        })
+    }
+}
+
+/// The most voted offset for the rate, which overlaps the reference the most,
+/// along with the overlap in milliseconds
+fn best_drift(
+    cues: &[CueTiming],
+    reference_starts: &[i64],
+    reference: &[(i64, i64)],
+    rate: f64,
+) -> Option<(SubtitlesDrift, i64)> {
+    let votes = cues
+        .iter()
+        .fold(HashMap::<i64, usize>::new(), |mut votes, cue| {
+            let start = (cue.start as f64 * rate).round() as i64;
+            let from =
+                reference_starts.partition_point(|time| *time < start - SUBTITLES_SYNC_MAX_OFFSET);
+            let to =
+                reference_starts.partition_point(|time| *time <= start + SUBTITLES_SYNC_MAX_OFFSET);
+            for time in &reference_starts[from..to] {
+                let step = ((time - start) as f64 / OFFSET_STEP as f64).round() as i64;
+                *votes.entry(step).or_default() += 1;
+            }
+            votes
+        });
+    votes
+        .into_iter()
+        .sorted_by_key(|(step, votes)| (Reverse(*votes), step.abs(), *step))
+        .take(OFFSET_CANDIDATES)
+        .map(|(step, _)| {
+            let drift = SubtitlesDrift {
+                rate,
+                offset: step * OFFSET_STEP,
+            };
+            let cues = union(
+                cues.iter()
+                    .map(|cue| (drift.apply(cue.start), drift.apply(cue.end))),
+            );
+            (drift, overlap(&cues, reference))
+        })
+        // max_by_key returns the last of the equal elements, prefer the most voted one
+        .rev()
+        .max_by_key(|(_, overlap)| *overlap)
+}
+
+/// The cue times paired with the closest reference start once corrected
+fn matching_pairs(
+    cues: &[CueTiming],
+    reference_starts: &[i64],
+    drift: &SubtitlesDrift,
+) -> Vec<(u64, i64)> {
+    cues.iter()
+        .filter_map(|cue| {
+            let start = drift.apply(cue.start);
+            let index = reference_starts.partition_point(|time| *time < start);
+            [index.checked_sub(1), Some(index)]
+                .into_iter()
+                .flatten()
+                .filter_map(|index| reference_starts.get(index))
+                .min_by_key(|time| (*time - start).abs())
+                .filter(|time| (*time - start).abs() <= SUBTITLES_SYNC_MATCH_TOLERANCE)
+                .map(|time| (cue.start, *time))
+        })
+        .collect()
+}
+
+/// Least squares fit of the reference times to the cue times
+fn fit_drift(pairs: &[(u64, i64)]) -> Option<SubtitlesDrift> {
+    let len = pairs.len() as f64;
+    let mean_time = pairs.iter().map(|(time, _)| *time as f64).sum::<f64>() / len;
+    let mean_reference = pairs.iter().map(|(_, time)| *time as f64).sum::<f64>() / len;
+    let (covariance, variance) = pairs.iter().fold(
+        (0.0, 0.0),
+        |(covariance, variance), (time, reference_time)| {
+            let time = *time as f64 - mean_time;
+            (
+                covariance + time * (*reference_time as f64 - mean_reference),
+                variance + time * time,
+            )
+        },
+    );
+    if variance == 0.0 {
+        return None;
+    }
+    let rate = covariance / variance;
+    Some(SubtitlesDrift {
+        rate,
+        offset: (mean_reference - rate * mean_time).round() as i64,
+    })
+}
+
+/// Merges the overlapping intervals
+fn union(intervals: impl Iterator<Item = (i64, i64)>) -> Vec<(i64, i64)> {
+    intervals
+        .sorted()
+        .fold(Vec::<(i64, i64)>::new(), |mut union, (start, end)| {
+            match union.last_mut() {
+                Some(last) if start <= last.1 => last.1 = last.1.max(end),
+                _ => union.push((start, end)),
+            };
+            union
+        })
+}
+
+/// How long both intervals overlap, in milliseconds
+fn overlap(intervals: &[(i64, i64)], other: &[(i64, i64)]) -> i64 {
+    let (mut index, mut other_index, mut overlap) = (0, 0, 0);
+    while let (Some(interval), Some(other_interval)) =
+        (intervals.get(index), other.get(other_index))
+    {
+        overlap += (interval.1.min(other_interval.1) - interval.0.max(other_interval.0)).max(0);
+        if interval.1 < other_interval.1 {
+            index += 1;
+        } else {
+            other_index += 1;
+        }
+    }
+    overlap
+}
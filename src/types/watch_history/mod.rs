@@ -0,0 +1,5 @@
+mod watch_history_bucket;
+pub use watch_history_bucket::*;
+
+mod watch_history_item;
+pub use watch_history_item::*;
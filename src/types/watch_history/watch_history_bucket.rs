@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+
+use crate::constants::WATCH_HISTORY_MAX_ITEMS;
+use crate::types::profile::UID;
+use crate::types::watch_history::WatchHistoryItem;
+
+/// An append-only log of the playbacks, the oldest first
+#[derive(Default, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub struct WatchHistoryBucket {
+    pub uid: UID,
+    pub items: Vec<WatchHistoryItem>,
+}
+
+impl WatchHistoryBucket {
+    pub fn new(uid: UID) -> Self {
+        Self { uid, items: vec![] }
+    }
+    /// Appends the item and prunes the oldest ones above [`WATCH_HISTORY_MAX_ITEMS`]
+    pub fn push(&mut self, item: WatchHistoryItem) {
+        self.items.push(item);
+        if self.items.len() > WATCH_HISTORY_MAX_ITEMS {
+            self.items
+                .drain(..self.items.len() - WATCH_HISTORY_MAX_ITEMS);
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::types::resource::StreamSource;
+
+/// A single playback of a video
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct WatchHistoryItem {
+    pub meta_id: String,
+    pub video_id: String,
+    pub r#type: String,
+    pub name: String,
+    pub stream_source: StreamSource,
+    /// When the playback has started
+    pub start: DateTime<Utc>,
+    /// When the playback has stopped
+    pub end: DateTime<Utc>,
+    /// The position in the video when the playback has stopped, in milliseconds
+    pub time_offset: u64,
+    /// In milliseconds
+    pub duration: u64,
+}
+
+impl WatchHistoryItem {
+    /// Returns the watch progress percentage at the end of the playback
+    pub fn progress(&self) -> f64 {
+        if self.duration > 0 {
+            (self.time_offset as f64 / self.duration as f64 * 100.0).min(100.0)
+        } else {
+            0.0
+        }
+    }
+}
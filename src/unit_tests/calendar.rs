@@ -0,0 +1,299 @@
+use crate::models::calendar::{icalendar, Calendar, CalendarItem, Grouping, Selected};
+use crate::models::common::{Loadable, ResourceLoadable};
+use crate::models::ctx::{Ctx, CtxError, OtherError};
+use crate::runtime::msg::{Action, ActionCalendar, ActionLoad, Event};
+use crate::runtime::{EnvFutureExt, Runtime, RuntimeAction, RuntimeEvent, TryEnvFuture};
+use crate::types::addon::{
+    Descriptor, Manifest, ManifestResource, ResourcePath, ResourceRequest, ResourceResponse,
+};
+use crate::types::library::{LibraryBucket, LibraryItem, LibraryItemState};
+use crate::types::profile::Profile;
+use crate::types::resource::{MetaItem, MetaItemPreview, SeriesInfo, Video};
+use crate::unit_tests::{default_fetch_handler, Request, TestEnv, EVENTS, FETCH_HANDLER, NOW};
+use chrono::{NaiveDate, TimeZone, Utc};
+use enclose::enclose;
+use futures::future;
+use semver::Version;
+use std::any::Any;
+use std::sync::{Arc, RwLock};
+use stremio_derive::Model;
+use url::Url;
+
+fn video(episode: u32, month: u32, day: u32) -> Video {
+    Video {
+        id: format!("tt1:1:{episode}"),
+        title: format!("Episode {episode}"),
+        released: Some(Utc.with_ymd_and_hms(2020, month, day, 12, 0, 0).unwrap()),
+        overview: None,
+        thumbnail: None,
+        streams: vec![],
+        series_info: Some(SeriesInfo { season: 1, episode }),
+        trailer_streams: vec![],
+    }
+}
+
+#[test]
+fn calendar_load_action() {
+    #[derive(Model, Default, Clone, Debug)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+        calendar: Calendar,
+    }
+    fn fetch_handler(request: Request) -> TryEnvFuture<Box<dyn Any + Send>> {
+        match request {
+            Request { url, .. } if url == "https://addon.com/meta/series/tt1.json" => {
+                future::ok(Box::new(ResourceResponse::Meta {
+                    meta: MetaItem {
+                        preview: MetaItemPreview {
+                            id: "tt1".to_owned(),
+                            r#type: "series".to_owned(),
+                            name: "tt1".to_owned(),
+                            ..Default::default()
+                        },
+                        videos: vec![
+                            Video {
+                                released: Some(
+                                    Utc.with_ymd_and_hms(2019, 12, 25, 12, 0, 0).unwrap(),
+                                ),
+                                ..video(1, 1, 1)
+                            },
+                            video(2, 1, 1),
+                            video(3, 1, 3),
+                            video(4, 1, 8),
+                        ],
+                    },
+                }) as Box<dyn Any + Send>)
+                .boxed_env()
+            }
+            _ => default_fetch_handler(request),
+        }
+    }
+    let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
+    *FETCH_HANDLER.write().unwrap() = Box::new(fetch_handler);
+    *NOW.write().unwrap() = Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap();
+    let addon = Descriptor {
+        manifest: Manifest {
+            id: "id".to_owned(),
+            version: Version::new(0, 0, 1),
+            name: "name".to_owned(),
+            contact_email: None,
+            description: None,
+            logo: None,
+            background: None,
+            types: vec!["series".to_owned()],
+            resources: vec![ManifestResource::Short("meta".to_owned())],
+            id_prefixes: Some(vec!["tt".to_owned()]),
+            catalogs: vec![],
+            addon_catalogs: vec![],
+            behavior_hints: Default::default(),
+        },
+        transport_url: Url::parse("https://addon.com/manifest.json").unwrap(),
+        flags: Default::default(),
+    };
+    let library_item = LibraryItem {
+        id: "tt1".to_owned(),
+        removed: false,
+        temp: false,
+        ctime: Some(Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap()),
+        mtime: Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap(),
+        state: LibraryItemState::default(),
+        name: "tt1".to_owned(),
+        r#type: "series".to_owned(),
+        poster: None,
+        poster_shape: Default::default(),
+        behavior_hints: Default::default(),
+        genres: vec![],
+        certification: None,
+    };
+    let (runtime, rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx {
+                profile: Profile {
+                    addons: vec![addon],
+                    ..Default::default()
+                },
+                library: LibraryBucket::new(None, vec![library_item]),
+                ..Default::default()
+            },
+            calendar: Default::default(),
+        },
+        vec![],
+        1000,
+    );
+    let runtime = Arc::new(RwLock::new(runtime));
+    TestEnv::run_with_runtime(
+        rx,
+        runtime.clone(),
+        enclose!((runtime) move || {
+            let runtime = runtime.read().unwrap();
+            runtime.dispatch(RuntimeAction {
+                field: None,
+                action: Action::Load(ActionLoad::Calendar(Selected::default())),
+            });
+        }),
+    );
+    let groups = |runtime: &Runtime<TestEnv, TestModel>| {
+        runtime
+            .model()
+            .unwrap()
+            .calendar
+            .groups
+            .iter()
+            .map(|group| {
+                (
+                    group.start,
+                    group
+                        .items
+                        .iter()
+                        .map(|item| item.video.id.to_owned())
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        groups(&runtime.read().unwrap()),
+        vec![
+            (
+                NaiveDate::from_ymd_opt(2020, 1, 1).unwrap(),
+                vec!["tt1:1:2".to_owned()]
+            ),
+            (
+                NaiveDate::from_ymd_opt(2020, 1, 3).unwrap(),
+                vec!["tt1:1:3".to_owned()]
+            ),
+            (
+                NaiveDate::from_ymd_opt(2020, 1, 8).unwrap(),
+                vec!["tt1:1:4".to_owned()]
+            ),
+        ],
+        "Upcoming videos are grouped by day"
+    );
+    TestEnv::run(|| {
+        runtime.read().unwrap().dispatch(RuntimeAction {
+            field: None,
+            action: Action::Load(ActionLoad::Calendar(Selected {
+                grouping: Grouping::Week,
+            })),
+        })
+    });
+    assert_eq!(
+        groups(&runtime.read().unwrap()),
+        vec![
+            (
+                NaiveDate::from_ymd_opt(2019, 12, 30).unwrap(),
+                vec!["tt1:1:2".to_owned(), "tt1:1:3".to_owned()]
+            ),
+            (
+                NaiveDate::from_ymd_opt(2020, 1, 6).unwrap(),
+                vec!["tt1:1:4".to_owned()]
+            ),
+        ],
+        "Upcoming videos are grouped by week"
+    );
+}
+
+#[test]
+fn calendar_icalendar() {
+    let item = CalendarItem {
+        meta_item: MetaItemPreview {
+            id: "tt1".to_owned(),
+            r#type: "series".to_owned(),
+            name: "Name, with; special \\ characters".to_owned(),
+            ..Default::default()
+        },
+        request: ResourceRequest::new(
+            Url::parse("https://addon.com/manifest.json").unwrap(),
+            ResourcePath::without_extra("meta", "series", "tt1"),
+        ),
+        video: Video {
+            overview: Some(format!("First line\n{}", "x".repeat(80))),
+            ..video(2, 1, 3)
+        },
+    };
+    let content = icalendar(&[item], Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap());
+    assert_eq!(
+        content,
+        [
+            "BEGIN:VCALENDAR",
+            "VERSION:2.0",
+            "PRODID:-//Stremio//Stremio Core//EN",
+            "CALSCALE:GREGORIAN",
+            "METHOD:PUBLISH",
+            "BEGIN:VEVENT",
+            "UID:tt1.tt1%3A1%3A2@stremio.com",
+            "DTSTAMP:20200101T000000Z",
+            "DTSTART;VALUE=DATE:20200103",
+            "DTEND;VALUE=DATE:20200104",
+            "SUMMARY:Name\\, with\\; special \\\\ characters S01E02 - Episode 2",
+            format!("DESCRIPTION:First line\\n{}", "x".repeat(51)).as_str(),
+            format!(" {}", "x".repeat(29)).as_str(),
+            "URL:stremio:///detail/series/tt1/tt1%3A1%3A2",
+            "END:VEVENT",
+            "END:VCALENDAR",
+            "",
+        ]
+        .join("\r\n"),
+        "Videos are exported as all-day events with escaped and folded lines"
+    );
+}
+
+#[test]
+fn calendar_icalendar_not_loaded() {
+    #[derive(Model, Clone, Default)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+        calendar: Calendar,
+    }
+    let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
+    let (runtime, rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx::default(),
+            calendar: Calendar {
+                selected: Some(Selected::default()),
+                meta_items: vec![ResourceLoadable {
+                    request: ResourceRequest::new(
+                        Url::parse("https://addon.com/manifest.json").unwrap(),
+                        ResourcePath::without_extra("meta", "series", "tt1"),
+                    ),
+                    content: Some(Loadable::Loading),
+                }],
+                ..Default::default()
+            },
+        },
+        vec![],
+        1000,
+    );
+    let runtime = Arc::new(RwLock::new(runtime));
+    TestEnv::run_with_runtime(
+        rx,
+        runtime.clone(),
+        enclose!((runtime) move || {
+            let runtime = runtime.read().unwrap();
+            runtime.dispatch(RuntimeAction {
+                field: None,
+                action: Action::Calendar(ActionCalendar::ExportICalendar),
+            });
+        }),
+    );
+    let events = EVENTS.read().unwrap();
+    assert!(
+        events.iter().any(|event| matches!(
+            event.downcast_ref::<RuntimeEvent<TestEnv, TestModel>>(),
+            Some(RuntimeEvent::CoreEvent(Event::Error {
+                error: CtxError::Other(OtherError::CalendarNotLoaded),
+                ..
+            }))
+        )),
+        "The calendar is not exported while a meta item is loading"
+    );
+    assert!(
+        !events.iter().any(|event| matches!(
+            event.downcast_ref::<RuntimeEvent<TestEnv, TestModel>>(),
+            Some(RuntimeEvent::CoreEvent(Event::ICalendarExported { .. }))
+        )),
+        "No partial file is exported"
+    );
+}
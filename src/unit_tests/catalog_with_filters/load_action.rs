@@ -3,14 +3,18 @@ use crate::models::common::{Loadable, ResourceLoadable};
 use crate::models::ctx::Ctx;
 use crate::runtime::msg::{Action, ActionLoad};
 use crate::runtime::{EnvFutureExt, Runtime, RuntimeAction, RuntimeEvent, TryEnvFuture};
+use crate::types::accounts::AccountsBucket;
 use crate::types::addon::{ExtraValue, ResourcePath, ResourceRequest, ResourceResponse};
 use crate::types::events::DismissedEventsBucket;
 use crate::types::library::LibraryBucket;
 use crate::types::notifications::NotificationsBucket;
+use crate::types::outbox::OutboxBucket;
+use crate::types::playlists::PlaylistsBucket;
 use crate::types::profile::Profile;
 use crate::types::resource::MetaItemPreview;
 use crate::types::search_history::SearchHistoryBucket;
 use crate::types::streams::StreamsBucket;
+use crate::types::watch_history::WatchHistoryBucket;
 use crate::unit_tests::{
     default_fetch_handler, Request, TestEnv, EVENTS, FETCH_HANDLER, REQUESTS, STATES,
 };
@@ -53,6 +57,10 @@ fn default_catalog() {
         NotificationsBucket::new::<TestEnv>(None, vec![]),
         SearchHistoryBucket::default(),
         DismissedEventsBucket::default(),
+        OutboxBucket::default(),
+        AccountsBucket::default(),
+        PlaylistsBucket::default(),
+        WatchHistoryBucket::default(),
     );
     let (discover, effects) = CatalogWithFilters::<MetaItemPreview>::new(&ctx.profile);
     let (runtime, rx) = Runtime::<TestEnv, _>::new(
@@ -154,6 +162,10 @@ fn search_catalog() {
         NotificationsBucket::new::<TestEnv>(None, vec![]),
         SearchHistoryBucket::default(),
         DismissedEventsBucket::default(),
+        OutboxBucket::default(),
+        AccountsBucket::default(),
+        PlaylistsBucket::default(),
+        WatchHistoryBucket::default(),
     );
     let (discover, effects) = CatalogWithFilters::<MetaItemPreview>::new(&ctx.profile);
     let (runtime, rx) = Runtime::<TestEnv, _>::new(
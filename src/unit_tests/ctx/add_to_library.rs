@@ -2,14 +2,18 @@ use crate::constants::{LIBRARY_RECENT_STORAGE_KEY, LIBRARY_STORAGE_KEY};
 use crate::models::ctx::Ctx;
 use crate::runtime::msg::{Action, ActionCtx};
 use crate::runtime::{Env, EnvFutureExt, Runtime, RuntimeAction, TryEnvFuture};
+use crate::types::accounts::AccountsBucket;
 use crate::types::api::{APIResult, SuccessResponse};
 use crate::types::events::DismissedEventsBucket;
 use crate::types::library::{LibraryBucket, LibraryItem, LibraryItemState};
 use crate::types::notifications::NotificationsBucket;
+use crate::types::outbox::OutboxBucket;
+use crate::types::playlists::PlaylistsBucket;
 use crate::types::profile::{Auth, AuthKey, GDPRConsent, Profile, User};
 use crate::types::resource::{MetaItemBehaviorHints, MetaItemPreview, PosterShape};
 use crate::types::search_history::SearchHistoryBucket;
 use crate::types::streams::StreamsBucket;
+use crate::types::watch_history::WatchHistoryBucket;
 use crate::types::True;
 use crate::unit_tests::{
     default_fetch_handler, Request, TestEnv, FETCH_HANDLER, NOW, REQUESTS, STORAGE,
@@ -72,6 +76,8 @@ fn actionctx_addtolibrary() {
         poster: None,
         poster_shape: Default::default(),
         behavior_hints: Default::default(),
+        genres: vec![],
+        certification: None,
     };
     let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
     *FETCH_HANDLER.write().unwrap() = Box::new(fetch_handler);
@@ -109,6 +115,10 @@ fn actionctx_addtolibrary() {
                 NotificationsBucket::new::<TestEnv>(None, vec![]),
                 SearchHistoryBucket::default(),
                 DismissedEventsBucket::default(),
+                OutboxBucket::default(),
+                AccountsBucket::default(),
+                PlaylistsBucket::default(),
+                WatchHistoryBucket::default(),
             ),
         },
         vec![],
@@ -211,6 +221,8 @@ fn actionctx_addtolibrary_already_added() {
             has_scheduled_videos: false,
             other: Default::default(),
         },
+        genres: vec![],
+        certification: None,
     };
     let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
     *NOW.write().unwrap() = Utc.with_ymd_and_hms(2020, 1, 2, 0, 0, 0).unwrap();
@@ -237,6 +249,8 @@ fn actionctx_addtolibrary_already_added() {
                                 ..LibraryItemState::default()
                             },
                             behavior_hints: Default::default(),
+                            genres: vec![],
+                            certification: None,
                         },
                     )]
                     .into_iter()
@@ -246,6 +260,10 @@ fn actionctx_addtolibrary_already_added() {
                 NotificationsBucket::new::<TestEnv>(None, vec![]),
                 SearchHistoryBucket::default(),
                 DismissedEventsBucket::default(),
+                OutboxBucket::default(),
+                AccountsBucket::default(),
+                PlaylistsBucket::default(),
+                WatchHistoryBucket::default(),
             ),
         },
         vec![],
@@ -1,7 +1,11 @@
+use crate::types::accounts::AccountsBucket;
 use crate::types::events::DismissedEventsBucket;
 use crate::types::notifications::NotificationsBucket;
+use crate::types::outbox::OutboxBucket;
+use crate::types::playlists::PlaylistsBucket;
 use crate::types::search_history::SearchHistoryBucket;
 use crate::types::streams::StreamsBucket;
+use crate::types::watch_history::WatchHistoryBucket;
 use crate::{
     constants::{LIBRARY_RECENT_STORAGE_KEY, LIBRARY_STORAGE_KEY, PROFILE_STORAGE_KEY},
     models::ctx::Ctx,
@@ -110,6 +114,10 @@ fn actionctx_authenticate_login() {
         NotificationsBucket::new::<TestEnv>(None, vec![]),
         SearchHistoryBucket::default(),
         DismissedEventsBucket::default(),
+        OutboxBucket::default(),
+        AccountsBucket::default(),
+        PlaylistsBucket::default(),
+        WatchHistoryBucket::default(),
     );
     let (runtime, _rx) = Runtime::<TestEnv, _>::new(TestModel { ctx }, vec![], 1000);
     TestEnv::run(|| {
@@ -251,6 +259,10 @@ fn actionctx_authenticate_login_with_token() {
         NotificationsBucket::new::<TestEnv>(None, vec![]),
         SearchHistoryBucket::default(),
         DismissedEventsBucket::default(),
+        OutboxBucket::default(),
+        AccountsBucket::default(),
+        PlaylistsBucket::default(),
+        WatchHistoryBucket::default(),
     );
     let (runtime, _rx) = Runtime::<TestEnv, _>::new(TestModel { ctx }, vec![], 1000);
     TestEnv::run(|| {
@@ -391,6 +403,10 @@ fn actionctx_authenticate_facebook() {
         NotificationsBucket::new::<TestEnv>(None, vec![]),
         SearchHistoryBucket::default(),
         DismissedEventsBucket::default(),
+        OutboxBucket::default(),
+        AccountsBucket::default(),
+        PlaylistsBucket::default(),
+        WatchHistoryBucket::default(),
     );
     let (runtime, _rx) = Runtime::<TestEnv, _>::new(TestModel { ctx }, vec![], 1000);
     TestEnv::run(|| {
@@ -531,6 +547,10 @@ fn actionctx_authenticate_register() {
         NotificationsBucket::new::<TestEnv>(None, vec![]),
         SearchHistoryBucket::default(),
         DismissedEventsBucket::default(),
+        OutboxBucket::default(),
+        AccountsBucket::default(),
+        PlaylistsBucket::default(),
+        WatchHistoryBucket::default(),
     );
     let (runtime, _rx) = Runtime::<TestEnv, _>::new(TestModel { ctx }, vec![], 1000);
     TestEnv::run(|| {
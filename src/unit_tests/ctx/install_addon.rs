@@ -2,14 +2,18 @@ use crate::constants::PROFILE_STORAGE_KEY;
 use crate::models::ctx::Ctx;
 use crate::runtime::msg::{Action, ActionCtx};
 use crate::runtime::{Env, EnvFutureExt, Runtime, RuntimeAction, TryEnvFuture};
+use crate::types::accounts::AccountsBucket;
 use crate::types::addon::{Descriptor, Manifest};
 use crate::types::api::{APIResult, SuccessResponse};
 use crate::types::events::DismissedEventsBucket;
 use crate::types::library::LibraryBucket;
 use crate::types::notifications::NotificationsBucket;
+use crate::types::outbox::OutboxBucket;
+use crate::types::playlists::PlaylistsBucket;
 use crate::types::profile::{Auth, AuthKey, GDPRConsent, Profile, User};
 use crate::types::search_history::SearchHistoryBucket;
 use crate::types::streams::StreamsBucket;
+use crate::types::watch_history::WatchHistoryBucket;
 use crate::types::True;
 use crate::unit_tests::{
     default_fetch_handler, Request, TestEnv, FETCH_HANDLER, REQUESTS, STORAGE,
@@ -59,6 +63,10 @@ fn actionctx_installaddon_install() {
                 NotificationsBucket::new::<TestEnv>(None, vec![]),
                 SearchHistoryBucket::default(),
                 DismissedEventsBucket::default(),
+                OutboxBucket::default(),
+                AccountsBucket::default(),
+                PlaylistsBucket::default(),
+                WatchHistoryBucket::default(),
             ),
         },
         vec![],
@@ -164,6 +172,10 @@ fn actionctx_installaddon_install_with_user() {
                 NotificationsBucket::new::<TestEnv>(None, vec![]),
                 SearchHistoryBucket::default(),
                 DismissedEventsBucket::default(),
+                OutboxBucket::default(),
+                AccountsBucket::default(),
+                PlaylistsBucket::default(),
+                WatchHistoryBucket::default(),
             ),
         },
         vec![],
@@ -287,6 +299,10 @@ fn actionctx_installaddon_update() {
                 NotificationsBucket::new::<TestEnv>(None, vec![]),
                 SearchHistoryBucket::default(),
                 DismissedEventsBucket::default(),
+                OutboxBucket::default(),
+                AccountsBucket::default(),
+                PlaylistsBucket::default(),
+                WatchHistoryBucket::default(),
             ),
         },
         vec![],
@@ -364,6 +380,10 @@ fn actionctx_installaddon_already_installed() {
                 NotificationsBucket::new::<TestEnv>(None, vec![]),
                 SearchHistoryBucket::default(),
                 DismissedEventsBucket::default(),
+                OutboxBucket::default(),
+                AccountsBucket::default(),
+                PlaylistsBucket::default(),
+                WatchHistoryBucket::default(),
             ),
         },
         vec![],
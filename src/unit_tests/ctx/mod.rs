@@ -6,12 +6,21 @@ mod update_events;
 mod notifications {
     mod update_notifications;
 }
+mod outbox;
+mod parental_controls;
+mod playlists;
 mod pull_addons_from_api;
 mod push_addons_to_api;
+mod rate_library_item;
 mod remove_from_library;
 mod rewind_library_item;
+mod scheduler;
+mod smart_lists;
+mod switch_account;
 mod sync_library_with_api;
 mod uninstall_addon;
 mod update_search_history;
 mod update_settings;
 mod upgrade_addon;
+mod watch_history;
+mod watch_status;
@@ -26,6 +26,7 @@ use crate::{
         Env, EnvFutureExt, Runtime, RuntimeAction, RuntimeEvent, TryEnvFuture,
     },
     types::{
+        accounts::AccountsBucket,
         addon::{
             Descriptor, Manifest, ManifestCatalog, ManifestExtra, ResourcePath, ResourceRequest,
             ResourceResponse,
@@ -33,13 +34,16 @@ use crate::{
         events::DismissedEventsBucket,
         library::{LibraryBucket, LibraryItem, LibraryItemState},
         notifications::{NotificationItem, NotificationsBucket},
-        profile::Profile,
+        outbox::OutboxBucket,
+        playlists::PlaylistsBucket,
+        profile::{NotificationSettings, Profile, Settings},
         resource::{
             MetaItem, MetaItemId, MetaItemPreview, PosterShape, SeriesInfo, Stream, StreamSource,
             Video, VideoId,
         },
         search_history::SearchHistoryBucket,
         streams::StreamsBucket,
+        watch_history::WatchHistoryBucket,
     },
     unit_tests::{
         default_fetch_handler, Request, TestEnv, EVENTS, FETCH_HANDLER, NOW, REQUESTS, STATES,
@@ -55,6 +59,8 @@ struct TestData {
     library_items: Vec<LibraryItem>,
     notification_items: Vec<NotificationItem>,
     result: HashMap<MetaItemId, HashMap<VideoId, NotificationItem>>,
+    #[serde(default)]
+    notification_settings: NotificationSettings,
 }
 
 #[test]
@@ -234,14 +240,24 @@ fn test_pull_notifications_and_play_in_player() {
                             duration: 101,
                             video_id: Some("tt1:1:5".to_string()),
                             no_notif: false,
+                            watch_status: None,
+                            rating: None,
+                            notes: None,
+                            resume_points: Default::default(),
                         },
                         behavior_hints: Default::default(),
+                        genres: vec![],
+                        certification: None,
                     }],
                 ),
                 StreamsBucket::default(),
                 NotificationsBucket::new::<TestEnv>(None, vec![]),
                 SearchHistoryBucket::default(),
                 DismissedEventsBucket::default(),
+                OutboxBucket::default(),
+                AccountsBucket::default(),
+                PlaylistsBucket::default(),
+                WatchHistoryBucket::default(),
             ),
             player: Default::default(),
         },
@@ -384,6 +400,10 @@ fn test_pull_notifications_test_cases() {
                 ctx: Ctx::new(
                     Profile {
                         addons: test.addons,
+                        settings: Settings {
+                            notifications: test.notification_settings,
+                            ..Default::default()
+                        },
                         ..Default::default()
                     },
                     LibraryBucket::new(None, test.library_items),
@@ -391,6 +411,10 @@ fn test_pull_notifications_test_cases() {
                     NotificationsBucket::new::<TestEnv>(None, test.notification_items),
                     SearchHistoryBucket::default(),
                     DismissedEventsBucket::default(),
+                    OutboxBucket::default(),
+                    AccountsBucket::default(),
+                    PlaylistsBucket::default(),
+                    WatchHistoryBucket::default(),
                 ),
             },
             vec![],
@@ -453,8 +477,14 @@ fn test_dismiss_notification() {
                                 video_id: Some("tt1:1".into()),
                                 watched: None,
                                 no_notif: false,
+                                watch_status: None,
+                                rating: None,
+                                notes: None,
+                                resume_points: Default::default(),
                             },
                             behavior_hints: Default::default(),
+                            genres: vec![],
+                            certification: None,
                         },
                         LibraryItem {
                             id: "tt2".to_string(),
@@ -479,8 +509,14 @@ fn test_dismiss_notification() {
                                 video_id: Some("tt1:1".into()),
                                 watched: None,
                                 no_notif: false,
+                                watch_status: None,
+                                rating: None,
+                                notes: None,
+                                resume_points: Default::default(),
                             },
                             behavior_hints: Default::default(),
+                            genres: vec![],
+                            certification: None,
                         },
                     ],
                 ),
@@ -502,6 +538,10 @@ fn test_dismiss_notification() {
                 ),
                 SearchHistoryBucket::default(),
                 DismissedEventsBucket::default(),
+                OutboxBucket::default(),
+                AccountsBucket::default(),
+                PlaylistsBucket::default(),
+                WatchHistoryBucket::default(),
             ),
         },
         vec![],
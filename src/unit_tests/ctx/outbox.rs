@@ -0,0 +1,254 @@
+use std::any::Any;
+
+use chrono::Duration;
+use enclose::enclose;
+use futures::future;
+use std::sync::{Arc, RwLock};
+use stremio_derive::Model;
+
+use crate::{
+    constants::OUTBOX_STORAGE_KEY,
+    models::ctx::Ctx,
+    runtime::{
+        msg::{Action, ActionCtx, Event},
+        EnvError, EnvFutureExt, Runtime, RuntimeAction, RuntimeEvent, TryEnvFuture,
+    },
+    types::{
+        accounts::AccountsBucket,
+        api::{APIResult, SuccessResponse},
+        events::DismissedEventsBucket,
+        library::LibraryBucket,
+        notifications::NotificationsBucket,
+        outbox::{OutboxBucket, OutboxRequest},
+        playlists::PlaylistsBucket,
+        profile::{Auth, AuthKey, Profile, User},
+        search_history::SearchHistoryBucket,
+        streams::StreamsBucket,
+        watch_history::WatchHistoryBucket,
+        True,
+    },
+    unit_tests::{
+        default_fetch_handler, Request, TestEnv, EVENTS, FETCH_HANDLER, REQUESTS, STORAGE,
+    },
+};
+
+fn offline_fetch_handler(request: Request) -> TryEnvFuture<Box<dyn Any + Send>> {
+    match request {
+        Request { url, .. } if url == "https://api.strem.io/api/saveUser" => {
+            future::err(EnvError::Fetch("offline".to_owned())).boxed_env()
+        }
+        _ => default_fetch_handler(request),
+    }
+}
+
+fn online_fetch_handler(request: Request) -> TryEnvFuture<Box<dyn Any + Send>> {
+    match request {
+        Request { url, .. }
+            if url == "https://api.strem.io/api/saveUser"
+                || url == "https://api.strem.io/api/logout" =>
+        {
+            let response = APIResult::Ok(SuccessResponse { success: True {} });
+            future::ok(Box::new(response) as Box<dyn Any + Send>).boxed_env()
+        }
+        _ => default_fetch_handler(request),
+    }
+}
+
+#[test]
+fn actionctx_flushoutbox() {
+    #[derive(Model, Clone, Default)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+    }
+
+    let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
+    *FETCH_HANDLER.write().unwrap() = Box::new(offline_fetch_handler);
+
+    let user = User {
+        id: "user_id".to_owned(),
+        email: "user_email".to_owned(),
+        ..Default::default()
+    };
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx::new(
+                Profile {
+                    auth: Some(Auth {
+                        key: AuthKey("auth_key".to_owned()),
+                        user: user.to_owned(),
+                    }),
+                    ..Default::default()
+                },
+                LibraryBucket::default(),
+                StreamsBucket::default(),
+                NotificationsBucket::new::<TestEnv>(None, vec![]),
+                SearchHistoryBucket::default(),
+                DismissedEventsBucket::default(),
+                OutboxBucket::new(Some("user_id".to_owned())),
+                AccountsBucket::default(),
+                PlaylistsBucket::default(),
+                WatchHistoryBucket::default(),
+            ),
+        },
+        vec![],
+        1000,
+    );
+
+    for _ in 0..2 {
+        TestEnv::run(|| {
+            runtime.dispatch(RuntimeAction {
+                field: None,
+                action: Action::Ctx(ActionCtx::PushUserToAPI),
+            })
+        });
+    }
+    assert_eq!(
+        runtime.model().unwrap().ctx.outbox.items,
+        vec![OutboxRequest::SaveUser {
+            user: user.to_owned(),
+            mtime: TestEnv::now(),
+        }],
+        "Failed push is queued once in the outbox"
+    );
+    assert_eq!(
+        runtime.model().unwrap().ctx.outbox.pending_count(),
+        1,
+        "One change is waiting to sync"
+    );
+    assert_eq!(
+        STORAGE
+            .read()
+            .unwrap()
+            .get(OUTBOX_STORAGE_KEY)
+            .map(|data| serde_json::from_str::<OutboxBucket>(data).unwrap()),
+        Some(runtime.model().unwrap().ctx.outbox.to_owned()),
+        "Outbox persisted in storage"
+    );
+
+    *FETCH_HANDLER.write().unwrap() = Box::new(online_fetch_handler);
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Ctx(ActionCtx::FlushOutbox),
+        })
+    });
+    assert_eq!(REQUESTS.read().unwrap().len(), 3, "Outbox has been flushed");
+    assert!(
+        runtime.model().unwrap().ctx.outbox.items.is_empty(),
+        "Outbox is empty after a successful push"
+    );
+}
+
+#[test]
+fn actionctx_logout_discards_outbox() {
+    #[derive(Model, Clone, Debug)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+    }
+
+    let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
+    *FETCH_HANDLER.write().unwrap() = Box::new(online_fetch_handler);
+
+    let user = User {
+        id: "user_id".to_owned(),
+        ..Default::default()
+    };
+    let mut outbox = OutboxBucket::new(Some("user_id".to_owned()));
+    outbox.enqueue(OutboxRequest::SaveUser {
+        user: user.to_owned(),
+        mtime: TestEnv::now(),
+    });
+    let (runtime, rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx::new(
+                Profile {
+                    auth: Some(Auth {
+                        key: AuthKey("auth_key".to_owned()),
+                        user,
+                    }),
+                    ..Default::default()
+                },
+                LibraryBucket::default(),
+                StreamsBucket::default(),
+                NotificationsBucket::new::<TestEnv>(None, vec![]),
+                SearchHistoryBucket::default(),
+                DismissedEventsBucket::default(),
+                outbox,
+                AccountsBucket::default(),
+                PlaylistsBucket::default(),
+                WatchHistoryBucket::default(),
+            ),
+        },
+        vec![],
+        1000,
+    );
+    let runtime = Arc::new(RwLock::new(runtime));
+    TestEnv::run_with_runtime(
+        rx,
+        runtime.clone(),
+        enclose!((runtime) move || {
+            let runtime = runtime.read().unwrap();
+            runtime.dispatch(RuntimeAction {
+                field: None,
+                action: Action::Ctx(ActionCtx::Logout),
+            });
+        }),
+    );
+    assert!(
+        runtime
+            .read()
+            .unwrap()
+            .model()
+            .unwrap()
+            .ctx
+            .outbox
+            .items
+            .is_empty(),
+        "Outbox is empty after logout"
+    );
+    assert!(
+        EVENTS.read().unwrap().iter().any(|event| matches!(
+            event.downcast_ref::<RuntimeEvent<TestEnv, TestModel>>(),
+            Some(RuntimeEvent::CoreEvent(Event::OutboxDiscarded {
+                uid: Some(uid),
+                pending_count: 1,
+            })) if uid == "user_id"
+        )),
+        "The discarded changes are reported"
+    );
+}
+
+#[test]
+fn outbox_keeps_newer_requests() {
+    let user = User {
+        id: "user_id".to_owned(),
+        ..Default::default()
+    };
+    let older_request = OutboxRequest::SaveUser {
+        user: user.to_owned(),
+        mtime: TestEnv::now(),
+    };
+    let newer_request = OutboxRequest::SaveUser {
+        user,
+        mtime: TestEnv::now() + Duration::seconds(1),
+    };
+    let mut outbox = OutboxBucket::new(Some("user_id".to_owned()));
+    outbox.enqueue(newer_request.to_owned());
+    outbox.enqueue(older_request.to_owned());
+    assert_eq!(
+        outbox.items,
+        vec![newer_request.to_owned()],
+        "Older request doesn't replace the newer one"
+    );
+    assert!(
+        !outbox.remove_superseded(&older_request),
+        "Older request doesn't remove the newer one"
+    );
+    assert!(
+        outbox.remove_superseded(&newer_request),
+        "Newer request removes itself"
+    );
+    assert!(outbox.items.is_empty(), "Outbox is empty");
+}
@@ -0,0 +1,213 @@
+use crate::constants::PROFILE_STORAGE_KEY;
+use crate::models::ctx::Ctx;
+use crate::runtime::msg::{Action, ActionCtx};
+use crate::runtime::{Runtime, RuntimeAction};
+use crate::types::accounts::AccountsBucket;
+use crate::types::events::DismissedEventsBucket;
+use crate::types::library::LibraryBucket;
+use crate::types::notifications::NotificationsBucket;
+use crate::types::outbox::OutboxBucket;
+use crate::types::playlists::PlaylistsBucket;
+use crate::types::profile::{Certification, ParentalControls, Profile};
+use crate::types::search_history::SearchHistoryBucket;
+use crate::types::streams::StreamsBucket;
+use crate::types::watch_history::WatchHistoryBucket;
+use crate::unit_tests::{TestEnv, STORAGE};
+use stremio_derive::Model;
+
+#[test]
+fn actionctx_update_parental_controls() {
+    #[derive(Model, Clone, Default)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+    }
+    let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(TestModel::default(), vec![], 1000);
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Ctx(ActionCtx::UpdateParentalControls {
+                max_certification: Some(Certification::PG),
+                blocked_genres: vec!["Horror".to_owned()],
+                block_unrated: true,
+            }),
+        })
+    });
+    let parental_controls = ParentalControls {
+        max_certification: Some(Certification::PG),
+        blocked_genres: vec!["Horror".to_owned()],
+        block_unrated: true,
+        pin_hash: None,
+        pin_salt: None,
+    };
+    assert_eq!(
+        runtime.model().unwrap().ctx.profile.parental_controls,
+        parental_controls,
+        "Parental controls updated successfully in memory"
+    );
+    assert!(
+        STORAGE
+            .read()
+            .unwrap()
+            .get(PROFILE_STORAGE_KEY)
+            .map_or(false, |data| {
+                serde_json::from_str::<Profile>(data)
+                    .unwrap()
+                    .parental_controls
+                    == parental_controls
+            }),
+        "Parental controls updated successfully in storage"
+    );
+    assert_eq!(
+        runtime.model().unwrap().ctx.parental_controls(),
+        Some(&parental_controls),
+        "Parental controls are applied"
+    );
+}
+
+#[test]
+fn actionctx_parental_controls_pin() {
+    #[derive(Model, Clone, Default)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+    }
+    let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
+    let mut parental_controls = ParentalControls {
+        max_certification: Some(Certification::PG13),
+        ..Default::default()
+    };
+    parental_controls.set_pin(Some("1234"));
+    let profile = Profile {
+        parental_controls: parental_controls.to_owned(),
+        ..Default::default()
+    };
+    let ctx = Ctx::new(
+        profile,
+        LibraryBucket::default(),
+        StreamsBucket::default(),
+        NotificationsBucket::new::<TestEnv>(None, vec![]),
+        SearchHistoryBucket::default(),
+        DismissedEventsBucket::default(),
+        OutboxBucket::default(),
+        AccountsBucket::default(),
+        PlaylistsBucket::default(),
+        WatchHistoryBucket::default(),
+    );
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(TestModel { ctx }, vec![], 1000);
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Ctx(ActionCtx::UpdateParentalControls {
+                max_certification: None,
+                blocked_genres: vec![],
+                block_unrated: false,
+            }),
+        })
+    });
+    assert_eq!(
+        runtime.model().unwrap().ctx.profile.parental_controls,
+        parental_controls,
+        "Parental controls can not be changed while locked"
+    );
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Ctx(ActionCtx::UnlockParentalControls("0000".to_owned())),
+        })
+    });
+    assert!(
+        !runtime.model().unwrap().ctx.parental_controls_unlocked,
+        "Parental controls are not unlocked with a wrong PIN"
+    );
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Ctx(ActionCtx::UnlockParentalControls("1234".to_owned())),
+        })
+    });
+    assert!(
+        runtime.model().unwrap().ctx.parental_controls_unlocked,
+        "Parental controls are unlocked with the right PIN"
+    );
+    assert_eq!(
+        runtime.model().unwrap().ctx.parental_controls(),
+        None,
+        "Parental controls are not applied while unlocked"
+    );
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Ctx(ActionCtx::LockParentalControls),
+        })
+    });
+    assert_eq!(
+        runtime.model().unwrap().ctx.parental_controls(),
+        Some(&parental_controls),
+        "Parental controls are applied again once locked"
+    );
+}
+
+#[test]
+fn actionctx_parental_controls_legacy_pin() {
+    #[derive(Model, Clone, Default)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+    }
+    let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
+    // SHA-256 of "1234"
+    let legacy_pin_hash = "03ac674216f3e15c761ee1a5e255f067953623c8b388b4459e13f978d7c846f4";
+    let ctx = Ctx {
+        profile: Profile {
+            parental_controls: ParentalControls {
+                max_certification: Some(Certification::PG13),
+                pin_hash: Some(legacy_pin_hash.to_owned()),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(TestModel { ctx }, vec![], 1000);
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Ctx(ActionCtx::UnlockParentalControls("1234".to_owned())),
+        })
+    });
+    let parental_controls = runtime
+        .model()
+        .unwrap()
+        .ctx
+        .profile
+        .parental_controls
+        .to_owned();
+    assert!(
+        runtime.model().unwrap().ctx.parental_controls_unlocked,
+        "Parental controls are unlocked with the legacy PIN"
+    );
+    assert!(
+        parental_controls.pin_salt.is_some()
+            && parental_controls.pin_hash.as_deref() != Some(legacy_pin_hash),
+        "Legacy PIN is rehashed with a salt"
+    );
+    assert!(
+        parental_controls.verify_pin("1234") && !parental_controls.verify_pin("0000"),
+        "Rehashed PIN is verified"
+    );
+    assert!(
+        STORAGE
+            .read()
+            .unwrap()
+            .get(PROFILE_STORAGE_KEY)
+            .map_or(false, |data| {
+                serde_json::from_str::<Profile>(data)
+                    .unwrap()
+                    .parental_controls
+                    == parental_controls
+            }),
+        "Rehashed PIN is persisted"
+    );
+}
@@ -0,0 +1,294 @@
+use crate::constants::PLAYLISTS_STORAGE_KEY;
+use crate::models::ctx::Ctx;
+use crate::runtime::msg::{Action, ActionCtx};
+use crate::runtime::{Runtime, RuntimeAction};
+use crate::types::playlists::{Playlist, PlaylistsBucket};
+use crate::types::resource::MetaItemPreview;
+use crate::unit_tests::{TestEnv, NOW, REQUESTS, STORAGE};
+use stremio_derive::Model;
+
+fn meta_item(id: &str) -> MetaItemPreview {
+    MetaItemPreview {
+        id: id.to_owned(),
+        r#type: "movie".to_owned(),
+        name: id.to_owned(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn actionctx_create_playlist_and_add_items() {
+    #[derive(Model, Clone, Default)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+    }
+    let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(TestModel::default(), vec![], 1000);
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Ctx(ActionCtx::CreatePlaylist {
+                id: "weekend".to_owned(),
+                name: "Weekend".to_owned(),
+            }),
+        })
+    });
+    for id in ["tt1", "tt2", "tt3", "tt1"] {
+        TestEnv::run(|| {
+            runtime.dispatch(RuntimeAction {
+                field: None,
+                action: Action::Ctx(ActionCtx::AddToPlaylist {
+                    id: "weekend".to_owned(),
+                    meta_item: meta_item(id),
+                }),
+            })
+        });
+    }
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Ctx(ActionCtx::MovePlaylistItem {
+                id: "weekend".to_owned(),
+                meta_item_id: "tt3".to_owned(),
+                index: 0,
+            }),
+        })
+    });
+    let playlist = Playlist {
+        id: "weekend".to_owned(),
+        name: "Weekend".to_owned(),
+        items: vec![meta_item("tt3"), meta_item("tt1"), meta_item("tt2")],
+        ctime: *NOW.read().unwrap(),
+        mtime: *NOW.read().unwrap(),
+    };
+    assert_eq!(
+        runtime.model().unwrap().ctx.playlists.items,
+        vec![playlist.to_owned()],
+        "Playlist created successfully in memory without duplicated items"
+    );
+    assert!(
+        STORAGE
+            .read()
+            .unwrap()
+            .get(PLAYLISTS_STORAGE_KEY)
+            .map_or(false, |data| {
+                serde_json::from_str::<PlaylistsBucket>(data).unwrap().items == vec![playlist]
+            }),
+        "Playlist saved successfully in storage"
+    );
+    assert!(
+        REQUESTS.read().unwrap().is_empty(),
+        "No requests have been sent"
+    );
+}
+
+#[test]
+fn actionctx_rename_move_and_delete_playlists() {
+    #[derive(Model, Clone, Default)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+    }
+    let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx {
+                playlists: PlaylistsBucket {
+                    uid: None,
+                    items: vec![
+                        Playlist::new("a".to_owned(), "A".to_owned(), *NOW.read().unwrap()),
+                        Playlist::new("b".to_owned(), "B".to_owned(), *NOW.read().unwrap()),
+                        Playlist::new("c".to_owned(), "C".to_owned(), *NOW.read().unwrap()),
+                    ],
+                },
+                ..Default::default()
+            },
+        },
+        vec![],
+        1000,
+    );
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Ctx(ActionCtx::RenamePlaylist {
+                id: "b".to_owned(),
+                name: "Bee".to_owned(),
+            }),
+        })
+    });
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Ctx(ActionCtx::MovePlaylist {
+                id: "a".to_owned(),
+                index: 10,
+            }),
+        })
+    });
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Ctx(ActionCtx::DeletePlaylist("c".to_owned())),
+        })
+    });
+    let playlists = runtime
+        .model()
+        .unwrap()
+        .ctx
+        .playlists
+        .items
+        .iter()
+        .map(|playlist| (playlist.id.to_owned(), playlist.name.to_owned()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        playlists,
+        vec![
+            ("b".to_owned(), "Bee".to_owned()),
+            ("a".to_owned(), "A".to_owned())
+        ],
+        "Playlists renamed, moved and deleted successfully in memory"
+    );
+    assert!(
+        STORAGE
+            .read()
+            .unwrap()
+            .get(PLAYLISTS_STORAGE_KEY)
+            .map_or(false, |data| {
+                serde_json::from_str::<PlaylistsBucket>(data).unwrap()
+                    == runtime.model().unwrap().ctx.playlists
+            }),
+        "Playlists updated successfully in storage"
+    );
+}
+
+#[test]
+fn actionctx_logout_without_playlists() {
+    #[derive(Model, Clone, Default)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+    }
+    let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(TestModel::default(), vec![], 1000);
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Ctx(ActionCtx::Logout),
+        })
+    });
+    assert!(
+        runtime.model().unwrap().ctx.playlists.items.is_empty(),
+        "Playlists are still empty in memory"
+    );
+    assert!(
+        STORAGE.read().unwrap().get(PLAYLISTS_STORAGE_KEY).is_none(),
+        "Playlists not written to storage"
+    );
+}
+
+#[test]
+fn actionctx_logout_keeps_playlists_on_device() {
+    #[derive(Model, Clone, Default)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+    }
+    let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
+    let playlists = PlaylistsBucket {
+        uid: Some("user_id".to_owned()),
+        items: vec![Playlist::new(
+            "a".to_owned(),
+            "A".to_owned(),
+            *NOW.read().unwrap(),
+        )],
+    };
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx {
+                playlists: playlists.to_owned(),
+                ..Default::default()
+            },
+        },
+        vec![],
+        1000,
+    );
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Ctx(ActionCtx::Logout),
+        })
+    });
+    assert_eq!(
+        runtime.model().unwrap().ctx.playlists,
+        PlaylistsBucket::default(),
+        "Playlists reset in memory"
+    );
+    assert!(
+        STORAGE
+            .read()
+            .unwrap()
+            .get(&format!("{PLAYLISTS_STORAGE_KEY}:user_id"))
+            .map_or(false, |data| {
+                serde_json::from_str::<PlaylistsBucket>(data).unwrap() == playlists
+            }),
+        "Playlists kept in storage for the account"
+    );
+}
+
+#[test]
+fn actionctx_create_and_rename_playlist_with_empty_id_or_name() {
+    #[derive(Model, Clone, Default)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+    }
+    let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
+    let playlists = PlaylistsBucket {
+        uid: None,
+        items: vec![Playlist::new(
+            "a".to_owned(),
+            "A".to_owned(),
+            *NOW.read().unwrap(),
+        )],
+    };
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx {
+                playlists: playlists.to_owned(),
+                ..Default::default()
+            },
+        },
+        vec![],
+        1000,
+    );
+    for (id, name) in [("", "Empty"), ("empty", " ")] {
+        TestEnv::run(|| {
+            runtime.dispatch(RuntimeAction {
+                field: None,
+                action: Action::Ctx(ActionCtx::CreatePlaylist {
+                    id: id.to_owned(),
+                    name: name.to_owned(),
+                }),
+            })
+        });
+    }
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Ctx(ActionCtx::RenamePlaylist {
+                id: "a".to_owned(),
+                name: "".to_owned(),
+            }),
+        })
+    });
+    assert_eq!(
+        runtime.model().unwrap().ctx.playlists,
+        playlists,
+        "Playlists not updated in memory"
+    );
+    assert!(
+        STORAGE.read().unwrap().get(PLAYLISTS_STORAGE_KEY).is_none(),
+        "Playlists not written to storage"
+    );
+}
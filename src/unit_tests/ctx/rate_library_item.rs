@@ -0,0 +1,221 @@
+use crate::constants::LIBRARY_RECENT_STORAGE_KEY;
+use crate::models::ctx::Ctx;
+use crate::models::library_with_filters::{
+    LibraryRequest, LibraryWithFilters, NotRemovedFilter, Selected, Sort,
+};
+use crate::runtime::msg::{Action, ActionCtx, ActionLoad};
+use crate::runtime::{Runtime, RuntimeAction};
+use crate::types::library::{LibraryBucket, LibraryItem, LibraryItemState, StoredValue};
+use crate::unit_tests::{TestEnv, NOW, REQUESTS, STORAGE};
+use chrono::{TimeZone, Utc};
+use stremio_derive::Model;
+
+fn library_item(id: &str, rating: Option<u8>) -> LibraryItem {
+    LibraryItem {
+        id: id.to_owned(),
+        removed: false,
+        temp: false,
+        ctime: Some(Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap()),
+        mtime: Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap(),
+        state: LibraryItemState {
+            rating: rating.map(StoredValue::Known),
+            ..LibraryItemState::default()
+        },
+        name: id.to_owned(),
+        r#type: "movie".to_owned(),
+        poster: None,
+        poster_shape: Default::default(),
+        behavior_hints: Default::default(),
+        genres: vec![],
+        certification: None,
+    }
+}
+
+#[test]
+fn actionctx_rate_library_item_and_set_notes() {
+    #[derive(Model, Clone, Default)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+    }
+    let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
+    *NOW.write().unwrap() = Utc.with_ymd_and_hms(2020, 1, 2, 0, 0, 0).unwrap();
+    let library_item = library_item("id", None);
+    let library_item_rated = LibraryItem {
+        mtime: Utc.with_ymd_and_hms(2020, 1, 2, 0, 0, 0).unwrap(),
+        state: LibraryItemState {
+            rating: Some(StoredValue::Known(8)),
+            notes: Some(StoredValue::Known("Rewatch with friends".to_owned())),
+            ..LibraryItemState::default()
+        },
+        ..library_item.to_owned()
+    };
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx {
+                library: LibraryBucket::new(None, vec![library_item.to_owned()]),
+                ..Default::default()
+            },
+        },
+        vec![],
+        1000,
+    );
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Ctx(ActionCtx::RateLibraryItem {
+                id: library_item.id.to_owned(),
+                rating: Some(8),
+            }),
+        })
+    });
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Ctx(ActionCtx::RateLibraryItem {
+                id: library_item.id.to_owned(),
+                rating: Some(11),
+            }),
+        })
+    });
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Ctx(ActionCtx::SetLibraryItemNotes {
+                id: library_item.id.to_owned(),
+                notes: Some(" Rewatch with friends ".to_owned()),
+            }),
+        })
+    });
+    assert_eq!(
+        runtime
+            .model()
+            .unwrap()
+            .ctx
+            .library
+            .items
+            .get(&library_item.id),
+        Some(&library_item_rated),
+        "Rating and notes updated successfully in memory, the invalid rating is ignored"
+    );
+    assert!(
+        STORAGE
+            .read()
+            .unwrap()
+            .get(LIBRARY_RECENT_STORAGE_KEY)
+            .map_or(false, |data| {
+                serde_json::from_str::<LibraryBucket>(data).unwrap()
+                    == LibraryBucket::new(None, vec![library_item_rated.to_owned()])
+            }),
+        "Rating and notes updated successfully in storage"
+    );
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Ctx(ActionCtx::SetLibraryItemNotes {
+                id: library_item.id.to_owned(),
+                notes: Some("".to_owned()),
+            }),
+        })
+    });
+    assert_eq!(
+        runtime
+            .model()
+            .unwrap()
+            .ctx
+            .library
+            .items
+            .get(&library_item.id)
+            .and_then(|library_item| library_item.state.notes.to_owned()),
+        None,
+        "Empty notes are cleared"
+    );
+    assert!(
+        REQUESTS.read().unwrap().is_empty(),
+        "No requests have been sent"
+    );
+}
+
+#[test]
+fn library_with_filters_rating() {
+    #[derive(Model, Clone, Default)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+        library: LibraryWithFilters<NotRemovedFilter>,
+    }
+    let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx {
+                library: LibraryBucket::new(
+                    None,
+                    vec![
+                        library_item("unrated", None),
+                        library_item("good", Some(7)),
+                        library_item("great", Some(10)),
+                        library_item("bad", Some(3)),
+                    ],
+                ),
+                ..Default::default()
+            },
+            library: Default::default(),
+        },
+        vec![],
+        1000,
+    );
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Load(ActionLoad::LibraryWithFilters(Selected {
+                request: LibraryRequest {
+                    r#type: None,
+                    sort: Sort::Rating,
+                    page: Default::default(),
+                    smart_list: None,
+                    watch_status: None,
+                    min_rating: None,
+                },
+            })),
+        })
+    });
+    assert_eq!(
+        runtime
+            .model()
+            .unwrap()
+            .library
+            .catalog
+            .iter()
+            .map(|library_item| library_item.id.as_str())
+            .collect::<Vec<_>>(),
+        vec!["great", "good", "bad", "unrated"],
+        "Items are sorted by the rating"
+    );
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Load(ActionLoad::LibraryWithFilters(Selected {
+                request: LibraryRequest {
+                    r#type: None,
+                    sort: Sort::Rating,
+                    page: Default::default(),
+                    smart_list: None,
+                    watch_status: None,
+                    min_rating: Some(7),
+                },
+            })),
+        })
+    });
+    assert_eq!(
+        runtime
+            .model()
+            .unwrap()
+            .library
+            .catalog
+            .iter()
+            .map(|library_item| library_item.id.as_str())
+            .collect::<Vec<_>>(),
+        vec!["great", "good"],
+        "Items are filtered by the minimum rating"
+    );
+}
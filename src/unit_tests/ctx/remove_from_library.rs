@@ -2,13 +2,17 @@ use crate::constants::{LIBRARY_RECENT_STORAGE_KEY, LIBRARY_STORAGE_KEY};
 use crate::models::ctx::Ctx;
 use crate::runtime::msg::{Action, ActionCtx};
 use crate::runtime::{Env, EnvFutureExt, Runtime, RuntimeAction, TryEnvFuture};
+use crate::types::accounts::AccountsBucket;
 use crate::types::api::{APIResult, SuccessResponse};
 use crate::types::events::DismissedEventsBucket;
 use crate::types::library::{LibraryBucket, LibraryItem};
 use crate::types::notifications::NotificationsBucket;
+use crate::types::outbox::OutboxBucket;
+use crate::types::playlists::PlaylistsBucket;
 use crate::types::profile::{Auth, AuthKey, GDPRConsent, Profile, User};
 use crate::types::search_history::SearchHistoryBucket;
 use crate::types::streams::StreamsBucket;
+use crate::types::watch_history::WatchHistoryBucket;
 use crate::types::True;
 use crate::unit_tests::{
     default_fetch_handler, Request, TestEnv, FETCH_HANDLER, NOW, REQUESTS, STORAGE,
@@ -52,6 +56,8 @@ fn actionctx_removefromlibrary() {
         poster: None,
         poster_shape: Default::default(),
         behavior_hints: Default::default(),
+        genres: vec![],
+        certification: None,
     };
     let library_item_removed = LibraryItem {
         removed: true,
@@ -104,6 +110,10 @@ fn actionctx_removefromlibrary() {
                 NotificationsBucket::new::<TestEnv>(None, vec![]),
                 SearchHistoryBucket::default(),
                 DismissedEventsBucket::default(),
+                OutboxBucket::default(),
+                AccountsBucket::default(),
+                PlaylistsBucket::default(),
+                WatchHistoryBucket::default(),
             ),
         },
         vec![],
@@ -172,6 +182,8 @@ fn actionctx_removefromlibrary_not_added() {
         poster: None,
         poster_shape: Default::default(),
         behavior_hints: Default::default(),
+        genres: vec![],
+        certification: None,
     };
     let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
     STORAGE.write().unwrap().insert(
@@ -192,6 +204,10 @@ fn actionctx_removefromlibrary_not_added() {
                 NotificationsBucket::new::<TestEnv>(None, vec![]),
                 SearchHistoryBucket::default(),
                 DismissedEventsBucket::default(),
+                OutboxBucket::default(),
+                AccountsBucket::default(),
+                PlaylistsBucket::default(),
+                WatchHistoryBucket::default(),
             ),
         },
         vec![],
@@ -2,13 +2,19 @@ use crate::constants::{LIBRARY_RECENT_STORAGE_KEY, LIBRARY_STORAGE_KEY};
 use crate::models::ctx::Ctx;
 use crate::runtime::msg::{Action, ActionCtx};
 use crate::runtime::{Env, EnvFutureExt, Runtime, RuntimeAction, TryEnvFuture};
+use crate::types::accounts::AccountsBucket;
 use crate::types::api::{APIResult, SuccessResponse};
 use crate::types::events::DismissedEventsBucket;
-use crate::types::library::{LibraryBucket, LibraryItem, LibraryItemState};
+use crate::types::library::{
+    LibraryBucket, LibraryItem, LibraryItemState, ResumePoint, StoredValue,
+};
 use crate::types::notifications::NotificationsBucket;
+use crate::types::outbox::OutboxBucket;
+use crate::types::playlists::PlaylistsBucket;
 use crate::types::profile::{Auth, AuthKey, GDPRConsent, Profile, User};
 use crate::types::search_history::SearchHistoryBucket;
 use crate::types::streams::StreamsBucket;
+use crate::types::watch_history::WatchHistoryBucket;
 use crate::types::True;
 use crate::unit_tests::{
     default_fetch_handler, Request, TestEnv, FETCH_HANDLER, NOW, REQUESTS, STORAGE,
@@ -59,6 +65,8 @@ fn actionctx_rewindlibraryitem() {
         poster: None,
         poster_shape: Default::default(),
         behavior_hints: Default::default(),
+        genres: vec![],
+        certification: None,
     };
     let library_item_rewinded = LibraryItem {
         mtime: Utc.with_ymd_and_hms(2020, 1, 2, 0, 0, 0).unwrap(),
@@ -112,6 +120,10 @@ fn actionctx_rewindlibraryitem() {
                 NotificationsBucket::new::<TestEnv>(None, vec![]),
                 SearchHistoryBucket::default(),
                 DismissedEventsBucket::default(),
+                OutboxBucket::default(),
+                AccountsBucket::default(),
+                PlaylistsBucket::default(),
+                WatchHistoryBucket::default(),
             ),
         },
         vec![],
@@ -183,6 +195,8 @@ fn actionctx_rewindlibraryitem_not_added() {
         poster: None,
         poster_shape: Default::default(),
         behavior_hints: Default::default(),
+        genres: vec![],
+        certification: None,
     };
     let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
     STORAGE.write().unwrap().insert(
@@ -203,6 +217,10 @@ fn actionctx_rewindlibraryitem_not_added() {
                 NotificationsBucket::new::<TestEnv>(None, vec![]),
                 SearchHistoryBucket::default(),
                 DismissedEventsBucket::default(),
+                OutboxBucket::default(),
+                AccountsBucket::default(),
+                PlaylistsBucket::default(),
+                WatchHistoryBucket::default(),
             ),
         },
         vec![],
@@ -245,3 +263,80 @@ fn actionctx_rewindlibraryitem_not_added() {
         "No requests have been sent"
     );
 }
+
+#[test]
+fn actionctx_rewindlibraryitem_resume_point() {
+    #[derive(Model, Clone, Default)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+    }
+    let resume_point = |time_offset| {
+        StoredValue::Known(ResumePoint {
+            time_offset,
+            duration: 1_000_000,
+            last_watched: Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap(),
+        })
+    };
+    let library_item = LibraryItem {
+        id: "tt1".to_owned(),
+        removed: false,
+        temp: false,
+        ctime: Some(Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap()),
+        mtime: Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap(),
+        state: LibraryItemState {
+            time_offset: 20_000,
+            video_id: Some("tt1:1:1".to_owned()),
+            resume_points: [
+                ("tt1:1:1".to_owned(), resume_point(20_000)),
+                ("tt1:1:3".to_owned(), resume_point(300_000)),
+            ]
+            .into(),
+            ..LibraryItemState::default()
+        },
+        name: "name".to_owned(),
+        r#type: "series".to_owned(),
+        poster: None,
+        poster_shape: Default::default(),
+        behavior_hints: Default::default(),
+        genres: vec![],
+        certification: None,
+    };
+    let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx {
+                library: LibraryBucket::new(None, vec![library_item.to_owned()]),
+                ..Default::default()
+            },
+        },
+        vec![],
+        1000,
+    );
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Ctx(ActionCtx::RewindLibraryItem(library_item.id.to_owned())),
+        })
+    });
+    let state = runtime
+        .model()
+        .unwrap()
+        .ctx
+        .library
+        .items
+        .get(&library_item.id)
+        .map(|library_item| library_item.state.to_owned())
+        .expect("Should have the library item");
+    assert_eq!(state.time_offset, 0, "The item has been rewinded");
+    assert_eq!(
+        state.resume_points,
+        [("tt1:1:3".to_owned(), resume_point(300_000))].into(),
+        "Only the resume point of the last watched video has been cleared"
+    );
+    assert_eq!(
+        state.resume_point("tt1:1:1"),
+        None,
+        "The last watched video starts over"
+    );
+}
@@ -0,0 +1,306 @@
+use std::any::Any;
+use std::collections::BTreeMap;
+
+use chrono::Duration;
+use futures::future;
+use stremio_derive::Model;
+
+use crate::{
+    constants::{PROFILE_STORAGE_KEY, SCHEDULER_MAX_INTERVAL},
+    models::ctx::Ctx,
+    runtime::{
+        msg::{Action, ActionCtx},
+        Env, EnvFutureExt, Runtime, RuntimeAction, TryEnvFuture,
+    },
+    types::{
+        accounts::AccountsBucket,
+        api::{APIError, APIResult, GetModalResponse, GetNotificationResponse},
+        events::DismissedEventsBucket,
+        library::LibraryBucket,
+        notifications::NotificationsBucket,
+        outbox::OutboxBucket,
+        playlists::PlaylistsBucket,
+        profile::Profile,
+        scheduler::{SchedulerJob, SchedulerJobState},
+        search_history::SearchHistoryBucket,
+        streams::StreamsBucket,
+        watch_history::WatchHistoryBucket,
+    },
+    unit_tests::{default_fetch_handler, Request, TestEnv, FETCH_HANDLER, NOW, REQUESTS, STORAGE},
+};
+
+fn fetch_handler(request: Request) -> TryEnvFuture<Box<dyn Any + Send>> {
+    match request {
+        Request { url, .. } if url == "https://api.strem.io/api/getModal" => {
+            let response: APIResult<Option<GetModalResponse>> = APIResult::Ok(None);
+            future::ok(Box::new(response) as Box<dyn Any + Send>).boxed_env()
+        }
+        Request { url, .. } if url == "https://api.strem.io/api/getNotification" => {
+            let response: APIResult<Option<GetNotificationResponse>> = APIResult::Ok(None);
+            future::ok(Box::new(response) as Box<dyn Any + Send>).boxed_env()
+        }
+        _ => default_fetch_handler(request),
+    }
+}
+
+fn failing_fetch_handler(request: Request) -> TryEnvFuture<Box<dyn Any + Send>> {
+    match request {
+        Request { url, .. } if url == "https://api.strem.io/api/getModal" => {
+            let response: APIResult<Option<GetModalResponse>> = APIResult::Err(APIError {
+                message: "error".to_owned(),
+                code: 0,
+            });
+            future::ok(Box::new(response) as Box<dyn Any + Send>).boxed_env()
+        }
+        _ => fetch_handler(request),
+    }
+}
+
+#[test]
+fn actionctx_scheduler_tick() {
+    #[derive(Model, Clone, Debug)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+    }
+
+    let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
+    *FETCH_HANDLER.write().unwrap() = Box::new(fetch_handler);
+
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx::default(),
+        },
+        vec![],
+        1000,
+    );
+
+    let now = TestEnv::now();
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Ctx(ActionCtx::SchedulerTick),
+        })
+    });
+
+    {
+        let model = runtime.model().unwrap();
+        let events_job = model
+            .ctx
+            .scheduler
+            .jobs
+            .get(&SchedulerJob::GetEvents)
+            .unwrap();
+        assert_eq!(events_job.last_run, Some(now), "Events job should have run");
+        assert_eq!(
+            events_job.next_run,
+            Some(now + Duration::hours(1)),
+            "Events job should be scheduled after the interval"
+        );
+        assert!(
+            events_job.running_since.is_none(),
+            "Events job should have finished"
+        );
+        let library_job = model
+            .ctx
+            .scheduler
+            .jobs
+            .get(&SchedulerJob::SyncLibraryWithAPI)
+            .unwrap();
+        assert!(
+            library_job.last_run.is_none() && library_job.running_since.is_none(),
+            "Library sync job should not run without a user"
+        );
+    }
+    assert_eq!(REQUESTS.read().unwrap().len(), 2, "Events requests sent");
+
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Ctx(ActionCtx::SchedulerTick),
+        })
+    });
+    assert_eq!(
+        REQUESTS.read().unwrap().len(),
+        2,
+        "No requests should be sent before the interval passes"
+    );
+
+    *NOW.write().unwrap() = now + Duration::hours(1);
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Ctx(ActionCtx::SchedulerTick),
+        })
+    });
+    assert_eq!(
+        REQUESTS.read().unwrap().len(),
+        4,
+        "Events requests should be sent again after the interval"
+    );
+}
+
+#[test]
+fn actionctx_scheduler_tick_backoff() {
+    #[derive(Model, Clone, Debug)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+    }
+
+    let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
+    *FETCH_HANDLER.write().unwrap() = Box::new(failing_fetch_handler);
+
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx::default(),
+        },
+        vec![],
+        1000,
+    );
+
+    let now = TestEnv::now();
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Ctx(ActionCtx::SchedulerTick),
+        })
+    });
+
+    let model = runtime.model().unwrap();
+    let events_job = model
+        .ctx
+        .scheduler
+        .jobs
+        .get(&SchedulerJob::GetEvents)
+        .unwrap();
+    assert_eq!(events_job.failures, 1, "Events job should have failed");
+    assert_eq!(
+        events_job.next_run,
+        Some(now + Duration::hours(2)),
+        "Events job should back off after a failure"
+    );
+}
+
+#[test]
+fn actionctx_update_scheduler_job_interval() {
+    #[derive(Model, Clone, Debug)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+    }
+
+    let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
+
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx::default(),
+        },
+        vec![],
+        1000,
+    );
+
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Ctx(ActionCtx::UpdateSchedulerJobInterval {
+                job: SchedulerJob::GetEvents,
+                interval: None,
+            }),
+        })
+    });
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Ctx(ActionCtx::SchedulerTick),
+        })
+    });
+
+    assert!(
+        REQUESTS.read().unwrap().is_empty(),
+        "Disabled job should not send requests"
+    );
+
+    let profile = STORAGE
+        .read()
+        .unwrap()
+        .get(PROFILE_STORAGE_KEY)
+        .map(|data| serde_json::from_str::<Profile>(data).unwrap())
+        .expect("Profile should be stored");
+    assert_eq!(
+        profile.settings.scheduler_job_intervals,
+        BTreeMap::from([(SchedulerJob::GetEvents, None)]),
+        "Interval is stored in the profile settings"
+    );
+    let ctx = Ctx::new(
+        profile,
+        LibraryBucket::default(),
+        StreamsBucket::default(),
+        NotificationsBucket::new::<TestEnv>(None, vec![]),
+        SearchHistoryBucket::default(),
+        DismissedEventsBucket::default(),
+        OutboxBucket::default(),
+        AccountsBucket::default(),
+        PlaylistsBucket::default(),
+        WatchHistoryBucket::default(),
+    );
+    assert_eq!(
+        ctx.scheduler.jobs[&SchedulerJob::GetEvents].interval,
+        None,
+        "Stored interval is applied when the ctx is loaded"
+    );
+}
+
+#[test]
+fn actionctx_update_scheduler_job_interval_invalid() {
+    #[derive(Model, Clone, Debug)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+    }
+
+    let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
+
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx::default(),
+        },
+        vec![],
+        1000,
+    );
+
+    for interval in [0, SCHEDULER_MAX_INTERVAL + 1, u64::MAX] {
+        TestEnv::run(|| {
+            runtime.dispatch(RuntimeAction {
+                field: None,
+                action: Action::Ctx(ActionCtx::UpdateSchedulerJobInterval {
+                    job: SchedulerJob::GetEvents,
+                    interval: Some(interval),
+                }),
+            })
+        });
+        assert!(
+            runtime
+                .model()
+                .unwrap()
+                .ctx
+                .profile
+                .settings
+                .scheduler_job_intervals
+                .is_empty(),
+            "Interval of {interval} milliseconds is rejected"
+        );
+    }
+}
+
+#[test]
+fn scheduler_job_next_run_is_capped() {
+    let now = TestEnv::now();
+    let mut job_state = SchedulerJobState::new(Some(u64::MAX));
+    job_state.finish(now, false);
+    assert_eq!(
+        job_state.next_run,
+        Some(now + Duration::milliseconds(SCHEDULER_MAX_INTERVAL as i64)),
+        "Next run of a stored interval above the maximum is capped"
+    );
+}
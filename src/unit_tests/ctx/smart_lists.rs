@@ -0,0 +1,264 @@
+use crate::constants::{GENRES_LINK_CATEGORY, PROFILE_STORAGE_KEY, SMART_LISTS_STORAGE_KEY};
+use crate::models::common::ParentalControlsAdapter;
+use crate::models::ctx::Ctx;
+use crate::runtime::msg::{Action, ActionCtx};
+use crate::runtime::{Env, EnvFutureExt, Runtime, RuntimeAction, TryEnvFuture};
+use crate::types::api::{APIResult, SuccessResponse};
+use crate::types::library::LibraryItem;
+use crate::types::notifications::NotificationsBucket;
+use crate::types::profile::{
+    Auth, AuthKey, GDPRConsent, LibraryQuery, ParentalControls, Profile, SmartList, User,
+};
+use crate::types::resource::{Link, MetaItemPreview};
+use crate::types::True;
+use crate::unit_tests::{
+    default_fetch_handler, Request, TestEnv, FETCH_HANDLER, REQUESTS, STORAGE,
+};
+use futures::future;
+use std::any::Any;
+use std::marker::PhantomData;
+use stremio_derive::Model;
+
+#[test]
+fn actionctx_save_smart_list() {
+    #[derive(Model, Clone, Default)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+    }
+    let smart_list = SmartList {
+        id: "unfinished_anime".to_owned(),
+        name: "Unfinished anime".to_owned(),
+        query: LibraryQuery {
+            r#type: Some("series".to_owned()),
+            genre: Some("Anime".to_owned()),
+            watched: Some(false),
+            min_progress: Some(1),
+            ..Default::default()
+        },
+    };
+    let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(TestModel::default(), vec![], 1000);
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Ctx(ActionCtx::SaveSmartList(smart_list.to_owned())),
+        })
+    });
+    assert_eq!(
+        runtime.model().unwrap().ctx.profile.smart_lists,
+        vec![smart_list.to_owned()],
+        "Smart list added successfully in memory"
+    );
+    let renamed_smart_list = SmartList {
+        name: "Anime to finish".to_owned(),
+        ..smart_list
+    };
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Ctx(ActionCtx::SaveSmartList(renamed_smart_list.to_owned())),
+        })
+    });
+    assert_eq!(
+        runtime.model().unwrap().ctx.profile.smart_lists,
+        vec![renamed_smart_list.to_owned()],
+        "Smart list replaced successfully in memory"
+    );
+    assert!(
+        STORAGE
+            .read()
+            .unwrap()
+            .get(PROFILE_STORAGE_KEY)
+            .map_or(false, |data| {
+                serde_json::from_str::<Profile>(data).unwrap().smart_lists
+                    == vec![renamed_smart_list]
+            }),
+        "Smart list saved successfully in storage"
+    );
+    assert!(
+        REQUESTS.read().unwrap().is_empty(),
+        "No requests have been sent"
+    );
+}
+
+#[test]
+fn actionctx_delete_smart_list() {
+    #[derive(Model, Clone, Default)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+    }
+    let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx {
+                profile: Profile {
+                    smart_lists: vec![SmartList {
+                        id: "movies".to_owned(),
+                        name: "Movies".to_owned(),
+                        query: LibraryQuery {
+                            r#type: Some("movie".to_owned()),
+                            ..Default::default()
+                        },
+                    }],
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        },
+        vec![],
+        1000,
+    );
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Ctx(ActionCtx::DeleteSmartList("movies".to_owned())),
+        })
+    });
+    assert!(
+        runtime.model().unwrap().ctx.profile.smart_lists.is_empty(),
+        "Smart list deleted successfully in memory"
+    );
+    assert!(
+        STORAGE
+            .read()
+            .unwrap()
+            .get(PROFILE_STORAGE_KEY)
+            .map_or(false, |data| {
+                serde_json::from_str::<Profile>(data)
+                    .unwrap()
+                    .smart_lists
+                    .is_empty()
+            }),
+        "Smart list deleted successfully in storage"
+    );
+}
+
+#[test]
+fn actionctx_logout_keeps_smart_lists_on_device() {
+    #[derive(Model, Clone, Default)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+    }
+    fn fetch_handler(request: Request) -> TryEnvFuture<Box<dyn Any + Send>> {
+        match request {
+            Request { url, method, .. }
+                if url == "https://api.strem.io/api/logout" && method == "POST" =>
+            {
+                future::ok(
+                    Box::new(APIResult::Ok(SuccessResponse { success: True {} }))
+                        as Box<dyn Any + Send>,
+                )
+                .boxed_env()
+            }
+            _ => default_fetch_handler(request),
+        }
+    }
+    let smart_list = SmartList {
+        id: "movies".to_owned(),
+        name: "Movies".to_owned(),
+        query: LibraryQuery {
+            r#type: Some("movie".to_owned()),
+            ..Default::default()
+        },
+    };
+    let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
+    *FETCH_HANDLER.write().unwrap() = Box::new(fetch_handler);
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx {
+                profile: Profile {
+                    auth: Some(Auth {
+                        key: AuthKey("auth_key".to_owned()),
+                        user: User {
+                            id: "user_id".to_owned(),
+                            email: "user_email".to_owned(),
+                            fb_id: None,
+                            avatar: None,
+                            last_modified: TestEnv::now(),
+                            date_registered: TestEnv::now(),
+                            trakt: None,
+                            premium_expire: None,
+                            gdpr_consent: GDPRConsent {
+                                tos: true,
+                                privacy: true,
+                                marketing: true,
+                                from: Some("tests".to_owned()),
+                            },
+                        },
+                    }),
+                    smart_lists: vec![smart_list.to_owned()],
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        },
+        vec![],
+        1000,
+    );
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Ctx(ActionCtx::Logout),
+        })
+    });
+    assert!(
+        runtime.model().unwrap().ctx.profile.smart_lists.is_empty(),
+        "Smart lists not carried over to the guest in memory"
+    );
+    assert!(
+        STORAGE
+            .read()
+            .unwrap()
+            .get(&format!("{SMART_LISTS_STORAGE_KEY}:user_id"))
+            .map_or(false, |data| {
+                serde_json::from_str::<Vec<SmartList>>(data).unwrap() == vec![smart_list]
+            }),
+        "Smart lists kept in storage for the account"
+    );
+}
+
+#[test]
+fn library_query_matches_meta_genres() {
+    let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
+    let meta_preview = MetaItemPreview {
+        id: "tt1".to_owned(),
+        r#type: "series".to_owned(),
+        name: "name".to_owned(),
+        links: vec![Link {
+            name: "Anime".to_owned(),
+            category: GENRES_LINK_CATEGORY.to_owned(),
+            url: "stremio:///discover".parse().unwrap(),
+        }],
+        ..Default::default()
+    };
+    let library_item = LibraryItem {
+        removed: false,
+        ..LibraryItem::from((&meta_preview, PhantomData::<TestEnv>))
+    };
+    assert_eq!(
+        library_item.genres,
+        vec!["Anime".to_owned()],
+        "Genres are stored from the genre links"
+    );
+    assert!(
+        LibraryQuery {
+            genre: Some("anime".to_owned()),
+            ..Default::default()
+        }
+        .matches(
+            &library_item,
+            &NotificationsBucket::new::<TestEnv>(None, vec![])
+        ),
+        "Item matches its genre"
+    );
+    assert!(
+        !library_item.is_allowed(&ParentalControls {
+            blocked_genres: vec!["Anime".to_owned()],
+            ..Default::default()
+        }),
+        "Item is hidden by its blocked genre"
+    );
+}
@@ -0,0 +1,384 @@
+use std::sync::{Arc, RwLock};
+
+use chrono::{TimeZone, Utc};
+use enclose::enclose;
+use stremio_derive::Model;
+
+use crate::{
+    constants::{PROFILE_STORAGE_KEY, SEARCH_HISTORY_STORAGE_KEY},
+    models::ctx::{Ctx, CtxError, CtxStatus, OtherError},
+    runtime::{
+        msg::{Action, ActionCtx, Event},
+        Runtime, RuntimeAction, RuntimeEvent,
+    },
+    types::{
+        accounts::{account_storage_key, Account, AccountsBucket},
+        api::AuthRequest,
+        events::DismissedEventsBucket,
+        library::LibraryBucket,
+        notifications::NotificationsBucket,
+        outbox::OutboxBucket,
+        playlists::PlaylistsBucket,
+        profile::{Auth, AuthKey, Certification, ParentalControls, Profile, User},
+        search_history::{SearchHistoryBucket, SearchHistoryItem},
+        streams::StreamsBucket,
+        watch_history::WatchHistoryBucket,
+    },
+    unit_tests::{TestEnv, EVENTS, STORAGE},
+};
+
+#[test]
+fn actionctx_switch_account() {
+    #[derive(Model, Clone, Default)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+    }
+
+    let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
+
+    let uid = Some("user_id".to_owned());
+    let profile = Profile {
+        auth: Some(Auth {
+            key: AuthKey("auth_key".to_owned()),
+            user: User {
+                id: "user_id".to_owned(),
+                email: "user_email".to_owned(),
+                ..Default::default()
+            },
+        }),
+        ..Default::default()
+    };
+    let guest_search_history = SearchHistoryBucket {
+        uid: None,
+        items: [(
+            "guest query".to_owned(),
+            SearchHistoryItem {
+                last_searched: Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap(),
+                count: 1,
+            },
+        )]
+        .into(),
+        paused: false,
+    };
+    STORAGE.write().unwrap().insert(
+        account_storage_key(SEARCH_HISTORY_STORAGE_KEY, &None),
+        serde_json::to_string(&guest_search_history).unwrap(),
+    );
+    let guest_account = Account {
+        uid: None,
+        email: None,
+        avatar: None,
+        last_used: Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap(),
+    };
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx::new(
+                profile.to_owned(),
+                LibraryBucket::new(uid.to_owned(), vec![]),
+                StreamsBucket::new(uid.to_owned()),
+                NotificationsBucket::new::<TestEnv>(uid.to_owned(), vec![]),
+                SearchHistoryBucket::new(uid.to_owned()),
+                DismissedEventsBucket::new(uid.to_owned()),
+                OutboxBucket::new(uid.to_owned()),
+                AccountsBucket {
+                    items: vec![guest_account],
+                },
+                PlaylistsBucket::new(uid.to_owned()),
+                WatchHistoryBucket::new(uid.to_owned()),
+            ),
+        },
+        vec![],
+        1000,
+    );
+
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Ctx(ActionCtx::SwitchAccount(None)),
+        })
+    });
+    assert_eq!(
+        runtime.model().unwrap().ctx.profile,
+        Profile::default(),
+        "Guest profile is active"
+    );
+    assert_eq!(
+        runtime.model().unwrap().ctx.search_history,
+        guest_search_history,
+        "Guest search history is restored"
+    );
+    assert_eq!(
+        STORAGE
+            .read()
+            .unwrap()
+            .get(&account_storage_key(PROFILE_STORAGE_KEY, &uid))
+            .map(|data| serde_json::from_str::<Profile>(data).unwrap()),
+        Some(profile.to_owned()),
+        "User profile is kept in storage"
+    );
+    assert_eq!(
+        STORAGE
+            .read()
+            .unwrap()
+            .get(SEARCH_HISTORY_STORAGE_KEY)
+            .map(|data| serde_json::from_str::<SearchHistoryBucket>(data).unwrap()),
+        Some(guest_search_history),
+        "Active search history is updated in storage"
+    );
+    assert_eq!(
+        runtime
+            .model()
+            .unwrap()
+            .ctx
+            .accounts
+            .items
+            .iter()
+            .map(|account| account.uid.to_owned())
+            .collect::<Vec<_>>(),
+        vec![None, uid.to_owned()],
+        "Both accounts are stored on the device"
+    );
+
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Ctx(ActionCtx::SwitchAccount(uid.to_owned())),
+        })
+    });
+    assert_eq!(
+        runtime.model().unwrap().ctx.profile,
+        profile,
+        "User profile is active again"
+    );
+    assert_eq!(
+        runtime.model().unwrap().ctx.search_history,
+        SearchHistoryBucket::new(uid),
+        "User search history is restored"
+    );
+}
+
+#[test]
+fn actionctx_switch_account_keeps_parental_controls() {
+    #[derive(Model, Clone, Default)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+    }
+
+    let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
+
+    let uid = Some("user_id".to_owned());
+    let mut parental_controls = ParentalControls {
+        max_certification: Some(Certification::PG),
+        ..Default::default()
+    };
+    parental_controls.set_pin(Some("1234"));
+    let profile = Profile {
+        auth: Some(Auth {
+            key: AuthKey("auth_key".to_owned()),
+            user: User {
+                id: "user_id".to_owned(),
+                email: "user_email".to_owned(),
+                ..Default::default()
+            },
+        }),
+        parental_controls: parental_controls.to_owned(),
+        ..Default::default()
+    };
+    let guest_account = Account {
+        uid: None,
+        email: None,
+        avatar: None,
+        last_used: Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap(),
+    };
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx {
+                profile,
+                library: LibraryBucket::new(uid.to_owned(), vec![]),
+                accounts: AccountsBucket {
+                    items: vec![guest_account],
+                },
+                ..Default::default()
+            },
+        },
+        vec![],
+        1000,
+    );
+
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Ctx(ActionCtx::SwitchAccount(None)),
+        })
+    });
+    assert_eq!(
+        runtime.model().unwrap().ctx.profile.uid(),
+        None,
+        "Guest profile is active"
+    );
+    assert_eq!(
+        runtime.model().unwrap().ctx.profile.parental_controls,
+        parental_controls,
+        "Parental controls of the device are kept"
+    );
+    assert_eq!(
+        runtime.model().unwrap().ctx.parental_controls(),
+        Some(&parental_controls),
+        "Parental controls stay locked"
+    );
+}
+
+#[test]
+fn actionctx_switch_account_not_found() {
+    #[derive(Model, Clone, Default)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+    }
+
+    let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
+
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(TestModel::default(), vec![], 1000);
+
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Ctx(ActionCtx::SwitchAccount(Some("user_id".to_owned()))),
+        })
+    });
+    assert_eq!(
+        runtime.model().unwrap().ctx.profile,
+        Profile::default(),
+        "Profile is not changed"
+    );
+    assert!(
+        STORAGE.read().unwrap().is_empty(),
+        "Nothing is written to storage"
+    );
+}
+
+#[test]
+fn actionctx_switch_account_while_logging_in() {
+    #[derive(Model, Clone, Debug)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+    }
+
+    let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
+
+    let uid = Some("user_id".to_owned());
+    let account = Account {
+        uid: uid.to_owned(),
+        email: Some("user_email".to_owned()),
+        avatar: None,
+        last_used: Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap(),
+    };
+    let (runtime, rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx {
+                status: CtxStatus::Loading(AuthRequest::default()),
+                accounts: AccountsBucket {
+                    items: vec![account],
+                },
+                ..Default::default()
+            },
+        },
+        vec![],
+        1000,
+    );
+    let runtime = Arc::new(RwLock::new(runtime));
+    TestEnv::run_with_runtime(
+        rx,
+        runtime.clone(),
+        enclose!((runtime, uid) move || {
+            let runtime = runtime.read().unwrap();
+            runtime.dispatch(RuntimeAction {
+                field: None,
+                action: Action::Ctx(ActionCtx::SwitchAccount(uid)),
+            });
+        }),
+    );
+    assert_eq!(
+        runtime.read().unwrap().model().unwrap().ctx.profile,
+        Profile::default(),
+        "Profile is not changed"
+    );
+    assert!(
+        STORAGE.read().unwrap().is_empty(),
+        "Nothing is written to storage"
+    );
+    assert!(
+        EVENTS.read().unwrap().iter().any(|event| matches!(
+            event.downcast_ref::<RuntimeEvent<TestEnv, TestModel>>(),
+            Some(RuntimeEvent::CoreEvent(Event::Error {
+                error: CtxError::Other(OtherError::LoginInProgress),
+                source,
+            })) if **source == Event::AccountSwitched { uid: uid.to_owned() }
+        )),
+        "Switching accounts is rejected while logging in"
+    );
+}
+
+#[test]
+fn actionctx_switch_account_already_active() {
+    #[derive(Model, Clone, Debug)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+    }
+
+    let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
+
+    let guest_account = Account {
+        uid: None,
+        email: None,
+        avatar: None,
+        last_used: Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap(),
+    };
+    let (runtime, rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx {
+                accounts: AccountsBucket {
+                    items: vec![guest_account.to_owned()],
+                },
+                ..Default::default()
+            },
+        },
+        vec![],
+        1000,
+    );
+    let runtime = Arc::new(RwLock::new(runtime));
+    TestEnv::run_with_runtime(
+        rx,
+        runtime.clone(),
+        enclose!((runtime) move || {
+            let runtime = runtime.read().unwrap();
+            runtime.dispatch(RuntimeAction {
+                field: None,
+                action: Action::Ctx(ActionCtx::SwitchAccount(None)),
+            });
+        }),
+    );
+    assert_eq!(
+        runtime.read().unwrap().model().unwrap().ctx.accounts.items,
+        vec![guest_account],
+        "Accounts are not changed"
+    );
+    assert!(
+        STORAGE.read().unwrap().is_empty(),
+        "Nothing is written to storage"
+    );
+    assert!(
+        EVENTS.read().unwrap().iter().any(|event| matches!(
+            event.downcast_ref::<RuntimeEvent<TestEnv, TestModel>>(),
+            Some(RuntimeEvent::CoreEvent(Event::Error {
+                error: CtxError::Other(OtherError::AccountAlreadyActive),
+                source,
+            })) if **source == Event::AccountSwitched { uid: None }
+        )),
+        "Switching to the active account is rejected"
+    );
+}
@@ -2,13 +2,17 @@ use crate::constants::LIBRARY_RECENT_STORAGE_KEY;
 use crate::models::ctx::Ctx;
 use crate::runtime::msg::{Action, ActionCtx};
 use crate::runtime::{Env, EnvFutureExt, Runtime, RuntimeAction, TryEnvFuture};
+use crate::types::accounts::AccountsBucket;
 use crate::types::api::{APIResult, LibraryItemModified, LibraryItemsResponse, SuccessResponse};
 use crate::types::events::DismissedEventsBucket;
 use crate::types::library::{LibraryBucket, LibraryItem};
 use crate::types::notifications::NotificationsBucket;
+use crate::types::outbox::OutboxBucket;
+use crate::types::playlists::PlaylistsBucket;
 use crate::types::profile::{Auth, AuthKey, GDPRConsent, Profile, User};
 use crate::types::search_history::SearchHistoryBucket;
 use crate::types::streams::StreamsBucket;
+use crate::types::watch_history::WatchHistoryBucket;
 use crate::types::True;
 use crate::unit_tests::{
     default_fetch_handler, Request, TestEnv, FETCH_HANDLER, REQUESTS, STORAGE,
@@ -36,6 +40,10 @@ fn actionctx_synclibrarywithapi() {
         NotificationsBucket::new::<TestEnv>(None, vec![]),
         SearchHistoryBucket::default(),
         DismissedEventsBucket::default(),
+        OutboxBucket::default(),
+        AccountsBucket::default(),
+        PlaylistsBucket::default(),
+        WatchHistoryBucket::default(),
     );
     let (runtime, _rx) = Runtime::<TestEnv, _>::new(TestModel { ctx }, vec![], 1000);
     TestEnv::run(|| {
@@ -70,6 +78,8 @@ fn actionctx_synclibrarywithapi_with_user() {
             mtime: Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap(),
             state: Default::default(),
             behavior_hints: Default::default(),
+            genres: vec![],
+            certification: None,
         };
         static ref LOCAL_NEWER_ITEM: LibraryItem = LibraryItem {
             id: "id2".to_owned(),
@@ -83,6 +93,8 @@ fn actionctx_synclibrarywithapi_with_user() {
             mtime: Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap(),
             state: Default::default(),
             behavior_hints: Default::default(),
+            genres: vec![],
+            certification: None,
         };
         static ref REMOTE_NEWER_ITEM: LibraryItem = LibraryItem {
             id: "id3".to_owned(),
@@ -96,6 +108,8 @@ fn actionctx_synclibrarywithapi_with_user() {
             mtime: Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap(),
             state: Default::default(),
             behavior_hints: Default::default(),
+            genres: vec![],
+            certification: None,
         };
         static ref LOCAL_ONLY_ITEM: LibraryItem = LibraryItem {
             id: "id4".to_owned(),
@@ -109,6 +123,8 @@ fn actionctx_synclibrarywithapi_with_user() {
             mtime: Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap(),
             state: Default::default(),
             behavior_hints: Default::default(),
+            genres: vec![],
+            certification: None,
         };
         static ref LOCAL_OLD_REMOVED_ITEM: LibraryItem = LibraryItem {
             id: "id5".to_owned(),
@@ -122,6 +138,8 @@ fn actionctx_synclibrarywithapi_with_user() {
             mtime: Utc::now() - Duration::days(367),
             state: Default::default(),
             behavior_hints: Default::default(),
+            genres: vec![],
+            certification: None,
         };
         static ref LOCAL_NEW_REMOVED_ITEM: LibraryItem = LibraryItem {
             id: "id6".to_owned(),
@@ -135,6 +153,8 @@ fn actionctx_synclibrarywithapi_with_user() {
             mtime: Utc::now() - Duration::days(3),
             state: Default::default(),
             behavior_hints: Default::default(),
+            genres: vec![],
+            certification: None,
         };
         static ref LOCAL_OTHER_TYPE_ITEM: LibraryItem = LibraryItem {
             id: "id7".to_owned(),
@@ -148,6 +168,8 @@ fn actionctx_synclibrarywithapi_with_user() {
             mtime: Utc::now(),
             state: Default::default(),
             behavior_hints: Default::default(),
+            genres: vec![],
+            certification: None,
         };
     }
     fn fetch_handler(request: Request) -> TryEnvFuture<Box<dyn Any + Send>> {
@@ -293,6 +315,10 @@ fn actionctx_synclibrarywithapi_with_user() {
                 NotificationsBucket::new::<TestEnv>(None, vec![]),
                 SearchHistoryBucket::default(),
                 DismissedEventsBucket::default(),
+                OutboxBucket::default(),
+                AccountsBucket::default(),
+                PlaylistsBucket::default(),
+                WatchHistoryBucket::default(),
             ),
         },
         vec![],
@@ -430,6 +456,10 @@ fn actionctx_synclibrarywithapi_with_user_empty_library() {
                 NotificationsBucket::new::<TestEnv>(None, vec![]),
                 SearchHistoryBucket::default(),
                 DismissedEventsBucket::default(),
+                OutboxBucket::default(),
+                AccountsBucket::default(),
+                PlaylistsBucket::default(),
+                WatchHistoryBucket::default(),
             ),
         },
         vec![],
@@ -1,7 +1,8 @@
+use chrono::{Duration, TimeZone, Utc};
 use stremio_derive::Model;
 
 use crate::{
-    constants::SEARCH_HISTORY_STORAGE_KEY,
+    constants::{SEARCH_HISTORY_MAX_ITEMS, SEARCH_HISTORY_STORAGE_KEY},
     models::{
         catalogs_with_extra::{CatalogsWithExtra, Selected},
         ctx::Ctx,
@@ -11,9 +12,17 @@ use crate::{
         Env, Runtime, RuntimeAction,
     },
     types::{
-        addon::ExtraValue, events::DismissedEventsBucket, library::LibraryBucket,
-        notifications::NotificationsBucket, profile::Profile, search_history::SearchHistoryBucket,
+        accounts::AccountsBucket,
+        addon::ExtraValue,
+        events::DismissedEventsBucket,
+        library::LibraryBucket,
+        notifications::NotificationsBucket,
+        outbox::OutboxBucket,
+        playlists::PlaylistsBucket,
+        profile::Profile,
+        search_history::{SearchHistoryBucket, SearchHistoryItem},
         streams::StreamsBucket,
+        watch_history::WatchHistoryBucket,
     },
     unit_tests::{TestEnv, STORAGE},
 };
@@ -36,6 +45,10 @@ fn test_search_history_update() {
         NotificationsBucket::new::<TestEnv>(None, vec![]),
         SearchHistoryBucket::default(),
         DismissedEventsBucket::default(),
+        OutboxBucket::default(),
+        AccountsBucket::default(),
+        PlaylistsBucket::default(),
+        WatchHistoryBucket::default(),
     );
 
     let catalogs_with_extra = CatalogsWithExtra::default();
@@ -72,7 +85,11 @@ fn test_search_history_update() {
 
     assert_eq!(
         runtime.model().unwrap().ctx.search_history.items.get(query),
-        Some(date).as_ref(),
+        Some(SearchHistoryItem {
+            last_searched: date,
+            count: 1
+        })
+        .as_ref(),
         "Should have updated search history"
     );
 
@@ -109,6 +126,10 @@ fn test_search_history_clear_items() {
         NotificationsBucket::new::<TestEnv>(None, vec![]),
         SearchHistoryBucket::default(),
         DismissedEventsBucket::default(),
+        OutboxBucket::default(),
+        AccountsBucket::default(),
+        PlaylistsBucket::default(),
+        WatchHistoryBucket::default(),
     );
 
     let catalogs_with_extra = CatalogsWithExtra::default();
@@ -157,3 +178,158 @@ fn test_search_history_clear_items() {
         "Should have cleared search history"
     );
 }
+
+#[test]
+fn test_search_history_remove_and_pause() {
+    #[derive(Model, Clone, Debug)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+        catalogs_with_extra: CatalogsWithExtra,
+    }
+
+    let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
+
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx::default(),
+            catalogs_with_extra: CatalogsWithExtra::default(),
+        },
+        vec![],
+        1000,
+    );
+
+    let search = |query: &str| {
+        TestEnv::run(|| {
+            runtime.dispatch(RuntimeAction {
+                field: None,
+                action: Action::Load(ActionLoad::CatalogsWithExtra(Selected {
+                    r#type: None,
+                    extra: vec![ExtraValue {
+                        name: "search".to_owned(),
+                        value: query.to_owned(),
+                    }],
+                })),
+            })
+        })
+    };
+
+    search("superman");
+    search("spider-man");
+    search("superman");
+    search("batman");
+
+    let search_history = runtime.model().unwrap().ctx.search_history.to_owned();
+    assert_eq!(
+        search_history.items.get("superman").map(|item| item.count),
+        Some(2),
+        "Should have counted the searches of the query"
+    );
+    assert_eq!(
+        search_history.suggestions("S", &TestEnv::now(), 5),
+        vec!["superman", "spider-man"],
+        "Should have suggested the queries starting with the text, the most searched first"
+    );
+
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Ctx(ActionCtx::RemoveSearchHistoryItem("superman".to_owned())),
+        })
+    });
+    assert!(
+        !runtime
+            .model()
+            .unwrap()
+            .ctx
+            .search_history
+            .items
+            .contains_key("superman"),
+        "Should have removed the query"
+    );
+
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Ctx(ActionCtx::PauseSearchHistory(true)),
+        })
+    });
+    search("wonder woman");
+    assert!(
+        !runtime
+            .model()
+            .unwrap()
+            .ctx
+            .search_history
+            .items
+            .contains_key("wonder woman"),
+        "Should not have recorded the query while paused"
+    );
+    assert!(
+        STORAGE
+            .read()
+            .unwrap()
+            .get(SEARCH_HISTORY_STORAGE_KEY)
+            .map_or(false, |data| {
+                serde_json::from_str::<SearchHistoryBucket>(data)
+                    .unwrap()
+                    .paused
+            }),
+        "Should have stored the paused search history"
+    );
+
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Ctx(ActionCtx::Logout),
+        })
+    });
+    assert!(
+        !runtime.model().unwrap().ctx.search_history.paused,
+        "Should have reset the pause of the search history after the logout"
+    );
+}
+
+#[test]
+fn test_search_history_record_prunes_least_recent() {
+    let now = Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap();
+    let mut search_history = SearchHistoryBucket {
+        uid: None,
+        items: (0..SEARCH_HISTORY_MAX_ITEMS)
+            .map(|index| {
+                (
+                    format!("query {index}"),
+                    SearchHistoryItem {
+                        last_searched: now + Duration::minutes(index as i64),
+                        count: 1,
+                    },
+                )
+            })
+            .collect(),
+        paused: false,
+    };
+    let now = now + Duration::days(1);
+
+    assert!(search_history.record("query 1", now));
+    assert_eq!(
+        search_history.items.len(),
+        SEARCH_HISTORY_MAX_ITEMS,
+        "Should not have pruned when searching a recorded query"
+    );
+
+    assert!(search_history.record("new query", now));
+    assert_eq!(
+        search_history.items.len(),
+        SEARCH_HISTORY_MAX_ITEMS,
+        "Should have pruned a single query"
+    );
+    assert!(
+        !search_history.items.contains_key("query 0"),
+        "Should have pruned the least recently searched query"
+    );
+    assert!(
+        search_history.items.contains_key("query 1")
+            && search_history.items.contains_key("new query"),
+        "Should have kept the recently searched queries"
+    );
+}
@@ -1,14 +1,20 @@
-use crate::constants::PROFILE_STORAGE_KEY;
+use crate::constants::{NOTIFICATIONS_MAX_POLL_INTERVAL, PROFILE_STORAGE_KEY};
 use crate::models::ctx::Ctx;
 use crate::runtime::msg::{Action, ActionCtx};
 use crate::runtime::{Runtime, RuntimeAction};
+use crate::types::accounts::AccountsBucket;
 use crate::types::events::DismissedEventsBucket;
 use crate::types::library::LibraryBucket;
 use crate::types::notifications::NotificationsBucket;
-use crate::types::profile::{Profile, Settings};
+use crate::types::outbox::OutboxBucket;
+use crate::types::playlists::PlaylistsBucket;
+use crate::types::profile::{NotificationSettings, Profile, QuietHours, Settings};
+use crate::types::scheduler::SchedulerJob;
 use crate::types::search_history::SearchHistoryBucket;
 use crate::types::streams::StreamsBucket;
+use crate::types::watch_history::WatchHistoryBucket;
 use crate::unit_tests::{TestEnv, REQUESTS, STORAGE};
+use chrono::{TimeZone, Utc};
 use stremio_derive::Model;
 
 #[test]
@@ -31,6 +37,10 @@ fn actionctx_updatesettings() {
         NotificationsBucket::new::<TestEnv>(None, vec![]),
         SearchHistoryBucket::default(),
         DismissedEventsBucket::default(),
+        OutboxBucket::default(),
+        AccountsBucket::default(),
+        PlaylistsBucket::default(),
+        WatchHistoryBucket::default(),
     );
     let (runtime, _rx) = Runtime::<TestEnv, _>::new(TestModel { ctx }, vec![], 1000);
     TestEnv::run(|| {
@@ -90,6 +100,10 @@ fn actionctx_updatesettings_not_changed() {
                 NotificationsBucket::new::<TestEnv>(None, vec![]),
                 SearchHistoryBucket::default(),
                 DismissedEventsBucket::default(),
+                OutboxBucket::default(),
+                AccountsBucket::default(),
+                PlaylistsBucket::default(),
+                WatchHistoryBucket::default(),
             ),
         },
         vec![],
@@ -121,3 +135,96 @@ fn actionctx_updatesettings_not_changed() {
         "No requests have been sent"
     );
 }
+
+#[test]
+fn actionctx_updatesettings_notifications_poll_interval() {
+    #[derive(Model, Clone, Default)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+    }
+    let settings = Settings {
+        notifications: NotificationSettings {
+            poll_interval: 12,
+            ..Default::default()
+        },
+        ..Settings::default()
+    };
+    let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(TestModel::default(), vec![], 1000);
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Ctx(ActionCtx::UpdateSettings(settings.to_owned())),
+        })
+    });
+    assert_eq!(
+        runtime.model().unwrap().ctx.scheduler.jobs[&SchedulerJob::PullNotifications].interval,
+        Some(12 * 60 * 60 * 1000),
+        "Notifications are pulled on the poll interval"
+    );
+    for poll_interval in [0, NOTIFICATIONS_MAX_POLL_INTERVAL + 1, u32::MAX] {
+        TestEnv::run(|| {
+            runtime.dispatch(RuntimeAction {
+                field: None,
+                action: Action::Ctx(ActionCtx::UpdateSettings(Settings {
+                    notifications: NotificationSettings {
+                        poll_interval,
+                        ..Default::default()
+                    },
+                    ..Settings::default()
+                })),
+            })
+        });
+        assert_eq!(
+            runtime.model().unwrap().ctx.profile.settings,
+            settings,
+            "Poll interval of {poll_interval} hours is rejected"
+        );
+    }
+}
+
+#[test]
+fn actionctx_updatesettings_quiet_hours() {
+    #[derive(Model, Clone, Default)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+    }
+    let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(TestModel::default(), vec![], 1000);
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Ctx(ActionCtx::UpdateSettings(Settings {
+                notifications: NotificationSettings {
+                    quiet_hours: Some(QuietHours {
+                        start: 22,
+                        end: 24,
+                        utc_offset: 0,
+                    }),
+                    ..Default::default()
+                },
+                ..Settings::default()
+            })),
+        })
+    });
+    assert_eq!(
+        runtime.model().unwrap().ctx.profile.settings,
+        Settings::default(),
+        "Quiet hours out of range are rejected"
+    );
+    let quiet_hours = QuietHours {
+        start: 22,
+        end: 7,
+        utc_offset: 3 * 60,
+    };
+    assert!(
+        quiet_hours.contains(&Utc.with_ymd_and_hms(2020, 1, 1, 20, 0, 0).unwrap()),
+        "23:00 local time is in the quiet hours"
+    );
+    assert!(
+        !quiet_hours.contains(&Utc.with_ymd_and_hms(2020, 1, 1, 4, 0, 0).unwrap()),
+        "07:00 local time is not in the quiet hours"
+    );
+}
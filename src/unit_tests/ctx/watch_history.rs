@@ -0,0 +1,220 @@
+use crate::constants::{META_RESOURCE_NAME, STREAM_RESOURCE_NAME, WATCH_HISTORY_STORAGE_KEY};
+use crate::models::ctx::Ctx;
+use crate::models::player::{Player, Selected as PlayerSelected};
+use crate::models::watch_history::{Selected, WatchHistory};
+use crate::runtime::msg::{Action, ActionLoad, ActionPlayer};
+use crate::runtime::{EnvFutureExt, Runtime, RuntimeAction, TryEnvFuture};
+use crate::types::addon::{ResourcePath, ResourceRequest, ResourceResponse};
+use crate::types::library::{LibraryBucket, LibraryItem, LibraryItemState};
+use crate::types::resource::{MetaItem, MetaItemPreview, Stream, StreamSource};
+use crate::types::watch_history::{WatchHistoryBucket, WatchHistoryItem};
+use crate::unit_tests::{default_fetch_handler, Request, TestEnv, FETCH_HANDLER, NOW, STORAGE};
+use chrono::{TimeZone, Utc};
+use futures::future;
+use std::any::Any;
+use stremio_derive::Model;
+
+fn watch_history_item(meta_id: &str, day: u32) -> WatchHistoryItem {
+    WatchHistoryItem {
+        meta_id: meta_id.to_owned(),
+        video_id: meta_id.to_owned(),
+        r#type: "movie".to_owned(),
+        name: meta_id.to_owned(),
+        stream_source: StreamSource::Url {
+            url: "https://source_url".parse().unwrap(),
+        },
+        start: Utc.with_ymd_and_hms(2020, 1, day, 0, 0, 0).unwrap(),
+        end: Utc.with_ymd_and_hms(2020, 1, day, 1, 0, 0).unwrap(),
+        time_offset: 1000,
+        duration: 10000,
+    }
+}
+
+#[test]
+fn player_records_watch_history_item() {
+    #[derive(Model, Default, Clone, Debug)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+        player: Player,
+    }
+    fn fetch_handler(request: Request) -> TryEnvFuture<Box<dyn Any + Send>> {
+        match request {
+            Request { url, .. } if url == "https://transport_url/meta/movie/tt1.json" => {
+                future::ok(Box::new(ResourceResponse::Meta {
+                    meta: MetaItem {
+                        preview: MetaItemPreview {
+                            id: "tt1".to_owned(),
+                            r#type: "movie".to_owned(),
+                            name: "tt1".to_owned(),
+                            ..Default::default()
+                        },
+                        videos: vec![],
+                    },
+                }) as Box<dyn Any + Send>)
+                .boxed_env()
+            }
+            _ => default_fetch_handler(request),
+        }
+    }
+    let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
+    *FETCH_HANDLER.write().unwrap() = Box::new(fetch_handler);
+    *NOW.write().unwrap() = Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap();
+    let library_item = LibraryItem {
+        id: "tt1".to_owned(),
+        removed: false,
+        temp: false,
+        ctime: Some(Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap()),
+        mtime: Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap(),
+        state: LibraryItemState::default(),
+        name: "tt1".to_owned(),
+        r#type: "movie".to_owned(),
+        poster: None,
+        poster_shape: Default::default(),
+        behavior_hints: Default::default(),
+        genres: vec![],
+        certification: None,
+    };
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx {
+                library: LibraryBucket::new(None, vec![library_item]),
+                ..Default::default()
+            },
+            player: Default::default(),
+        },
+        vec![],
+        1000,
+    );
+    let stream = Stream {
+        source: StreamSource::Url {
+            url: "https://source_url".parse().unwrap(),
+        },
+        name: None,
+        description: None,
+        thumbnail: None,
+        subtitles: vec![],
+        behavior_hints: Default::default(),
+    };
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Load(ActionLoad::Player(Box::new(PlayerSelected {
+                stream,
+                stream_request: Some(ResourceRequest {
+                    base: "https://transport_url/manifest.json".parse().unwrap(),
+                    path: ResourcePath::without_extra(STREAM_RESOURCE_NAME, "movie", "tt1"),
+                }),
+                meta_request: Some(ResourceRequest {
+                    base: "https://transport_url/manifest.json".parse().unwrap(),
+                    path: ResourcePath::without_extra(META_RESOURCE_NAME, "movie", "tt1"),
+                }),
+                subtitles_path: None,
+            }))),
+        })
+    });
+    assert!(
+        runtime.model().unwrap().ctx.watch_history.items.is_empty(),
+        "Nothing is recorded before the playback has started"
+    );
+    *NOW.write().unwrap() = Utc.with_ymd_and_hms(2020, 1, 1, 10, 0, 0).unwrap();
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Player(ActionPlayer::TimeChanged {
+                time: 1000,
+                duration: 10000,
+                device: "chrome".to_owned(),
+            }),
+        })
+    });
+    *NOW.write().unwrap() = Utc.with_ymd_and_hms(2020, 1, 1, 11, 0, 0).unwrap();
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Unload,
+        })
+    });
+    let watch_history_item = WatchHistoryItem {
+        start: Utc.with_ymd_and_hms(2020, 1, 1, 10, 0, 0).unwrap(),
+        end: Utc.with_ymd_and_hms(2020, 1, 1, 11, 0, 0).unwrap(),
+        ..watch_history_item("tt1", 1)
+    };
+    assert_eq!(
+        runtime.model().unwrap().ctx.watch_history.items,
+        vec![watch_history_item.to_owned()],
+        "Playback recorded successfully in memory"
+    );
+    assert!(
+        STORAGE
+            .read()
+            .unwrap()
+            .get(WATCH_HISTORY_STORAGE_KEY)
+            .map_or(false, |data| {
+                serde_json::from_str::<WatchHistoryBucket>(data)
+                    .unwrap()
+                    .items
+                    == vec![watch_history_item]
+            }),
+        "Playback recorded successfully in storage"
+    );
+}
+
+#[test]
+fn watch_history_filters() {
+    #[derive(Model, Default, Clone, Debug)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+        watch_history: WatchHistory,
+    }
+    let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx {
+                watch_history: WatchHistoryBucket {
+                    uid: None,
+                    items: vec![
+                        watch_history_item("tt1", 1),
+                        watch_history_item("tt2", 2),
+                        watch_history_item("tt1", 3),
+                    ],
+                },
+                ..Default::default()
+            },
+            watch_history: Default::default(),
+        },
+        vec![],
+        1000,
+    );
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Load(ActionLoad::WatchHistory(Selected::default())),
+        })
+    });
+    assert_eq!(
+        runtime.model().unwrap().watch_history.items,
+        vec![
+            watch_history_item("tt1", 3),
+            watch_history_item("tt2", 2),
+            watch_history_item("tt1", 1),
+        ],
+        "Playbacks are listed with the most recent first"
+    );
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Load(ActionLoad::WatchHistory(Selected {
+                meta_id: Some("tt1".to_owned()),
+                from: Some(Utc.with_ymd_and_hms(2020, 1, 2, 0, 0, 0).unwrap()),
+                ..Default::default()
+            })),
+        })
+    });
+    assert_eq!(
+        runtime.model().unwrap().watch_history.items,
+        vec![watch_history_item("tt1", 3)],
+        "Playbacks are filtered by the meta item and the date"
+    );
+}
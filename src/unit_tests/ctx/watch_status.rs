@@ -0,0 +1,182 @@
+use crate::constants::LIBRARY_RECENT_STORAGE_KEY;
+use crate::models::ctx::Ctx;
+use crate::models::library_with_filters::{
+    LibraryRequest, LibraryWithFilters, NotRemovedFilter, Selected, Sort,
+};
+use crate::runtime::msg::{Action, ActionCtx, ActionLoad};
+use crate::runtime::{Runtime, RuntimeAction};
+use crate::types::library::{
+    LibraryBucket, LibraryItem, LibraryItemState, StoredValue, WatchStatus,
+};
+use crate::unit_tests::{TestEnv, NOW, REQUESTS, STORAGE};
+use chrono::{TimeZone, Utc};
+use stremio_derive::Model;
+
+fn library_item(id: &str, watch_status: Option<WatchStatus>) -> LibraryItem {
+    LibraryItem {
+        id: id.to_owned(),
+        removed: false,
+        temp: false,
+        ctime: Some(Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap()),
+        mtime: Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap(),
+        state: LibraryItemState {
+            watch_status: watch_status.map(StoredValue::Known),
+            ..LibraryItemState::default()
+        },
+        name: id.to_owned(),
+        r#type: "series".to_owned(),
+        poster: None,
+        poster_shape: Default::default(),
+        behavior_hints: Default::default(),
+        genres: vec![],
+        certification: None,
+    }
+}
+
+#[test]
+fn actionctx_set_library_item_watch_status() {
+    #[derive(Model, Clone, Default)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+    }
+    let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
+    *NOW.write().unwrap() = Utc.with_ymd_and_hms(2020, 1, 2, 0, 0, 0).unwrap();
+    let library_item = library_item("id", None);
+    let library_item_planned = LibraryItem {
+        mtime: Utc.with_ymd_and_hms(2020, 1, 2, 0, 0, 0).unwrap(),
+        state: LibraryItemState {
+            watch_status: Some(StoredValue::Known(WatchStatus::PlanToWatch)),
+            ..LibraryItemState::default()
+        },
+        ..library_item.to_owned()
+    };
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx {
+                library: LibraryBucket::new(None, vec![library_item.to_owned()]),
+                ..Default::default()
+            },
+        },
+        vec![],
+        1000,
+    );
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Ctx(ActionCtx::SetLibraryItemWatchStatus {
+                id: library_item.id.to_owned(),
+                status: Some(WatchStatus::PlanToWatch),
+            }),
+        })
+    });
+    assert_eq!(
+        runtime
+            .model()
+            .unwrap()
+            .ctx
+            .library
+            .items
+            .get(&library_item.id),
+        Some(&library_item_planned),
+        "Watch status updated successfully in memory"
+    );
+    assert!(
+        STORAGE
+            .read()
+            .unwrap()
+            .get(LIBRARY_RECENT_STORAGE_KEY)
+            .map_or(false, |data| {
+                serde_json::from_str::<LibraryBucket>(data).unwrap()
+                    == LibraryBucket::new(None, vec![library_item_planned])
+            }),
+        "Watch status updated successfully in storage"
+    );
+    assert!(
+        REQUESTS.read().unwrap().is_empty(),
+        "No requests have been sent"
+    );
+}
+
+#[test]
+fn library_with_filters_watch_status() {
+    #[derive(Model, Clone, Default)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+        library: LibraryWithFilters<NotRemovedFilter>,
+    }
+    let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx {
+                library: LibraryBucket::new(
+                    None,
+                    vec![
+                        library_item("none", None),
+                        library_item("completed", Some(WatchStatus::Completed)),
+                        library_item("planned", Some(WatchStatus::PlanToWatch)),
+                    ],
+                ),
+                ..Default::default()
+            },
+            library: Default::default(),
+        },
+        vec![],
+        1000,
+    );
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Load(ActionLoad::LibraryWithFilters(Selected {
+                request: LibraryRequest {
+                    r#type: None,
+                    sort: Sort::WatchStatus,
+                    page: Default::default(),
+                    smart_list: None,
+                    watch_status: None,
+                    min_rating: None,
+                },
+            })),
+        })
+    });
+    assert_eq!(
+        runtime
+            .model()
+            .unwrap()
+            .library
+            .catalog
+            .iter()
+            .map(|library_item| library_item.id.as_str())
+            .collect::<Vec<_>>(),
+        vec!["planned", "completed", "none"],
+        "Items are sorted by the watch status"
+    );
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Load(ActionLoad::LibraryWithFilters(Selected {
+                request: LibraryRequest {
+                    r#type: None,
+                    sort: Sort::WatchStatus,
+                    page: Default::default(),
+                    smart_list: None,
+                    watch_status: Some(WatchStatus::Completed),
+                    min_rating: None,
+                },
+            })),
+        })
+    });
+    assert_eq!(
+        runtime
+            .model()
+            .unwrap()
+            .library
+            .catalog
+            .iter()
+            .map(|library_item| library_item.id.as_str())
+            .collect::<Vec<_>>(),
+        vec!["completed"],
+        "Items are filtered by the watch status"
+    );
+}
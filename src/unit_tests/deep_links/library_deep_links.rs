@@ -1,5 +1,7 @@
 use crate::deep_links::LibraryDeepLinks;
 use crate::models::library_with_filters::{LibraryRequest, Sort};
+use crate::types::library::WatchStatus;
+use crate::types::profile::{LibraryQuery, SmartList};
 
 #[test]
 fn library_deep_links_string() {
@@ -15,6 +17,9 @@ fn library_deep_links_request_type() {
         r#type: Some("movie".to_string()),
         sort: Sort::LastWatched,
         page: Default::default(),
+        smart_list: None,
+        watch_status: None,
+        min_rating: None,
     };
     let ldl = LibraryDeepLinks::from((&root, &request));
     assert_eq!(
@@ -30,6 +35,9 @@ fn library_deep_links_request_no_type() {
         r#type: None,
         sort: Sort::LastWatched,
         page: Default::default(),
+        smart_list: None,
+        watch_status: None,
+        min_rating: None,
     };
     let ldl = LibraryDeepLinks::from((&root, &request));
     assert_eq!(
@@ -37,3 +45,59 @@ fn library_deep_links_request_no_type() {
         "stremio:///library?sort=lastwatched".to_string()
     );
 }
+
+#[test]
+fn library_deep_links_smart_list() {
+    let root = "library".to_string();
+    let smart_list = SmartList {
+        id: "unfinished anime".to_string(),
+        name: "Unfinished anime".to_string(),
+        query: LibraryQuery {
+            r#type: Some("series".to_string()),
+            genre: Some("Anime".to_string()),
+            watched: Some(false),
+            ..Default::default()
+        },
+    };
+    let ldl = LibraryDeepLinks::from((&root, &smart_list));
+    assert_eq!(
+        ldl.library,
+        "stremio:///library/series?sort=lastwatched&smartList=unfinished%20anime".to_string()
+    );
+}
+
+#[test]
+fn library_deep_links_request_watch_status() {
+    let root = "library".to_string();
+    let request = LibraryRequest {
+        r#type: Some("series".to_string()),
+        sort: Sort::WatchStatus,
+        page: Default::default(),
+        smart_list: None,
+        watch_status: Some(WatchStatus::PlanToWatch),
+        min_rating: None,
+    };
+    let ldl = LibraryDeepLinks::from((&root, &request));
+    assert_eq!(
+        ldl.library,
+        "stremio:///library/series?sort=watchstatus&watchStatus=planToWatch".to_string()
+    );
+}
+
+#[test]
+fn library_deep_links_request_min_rating() {
+    let root = "library".to_string();
+    let request = LibraryRequest {
+        r#type: None,
+        sort: Sort::Rating,
+        page: Default::default(),
+        smart_list: None,
+        watch_status: None,
+        min_rating: Some(7),
+    };
+    let ldl = LibraryDeepLinks::from((&root, &request));
+    assert_eq!(
+        ldl.library,
+        "stremio:///library?sort=rating&minRating=7".to_string()
+    );
+}
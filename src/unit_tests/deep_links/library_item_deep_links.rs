@@ -76,8 +76,14 @@ fn library_item_deep_links_no_video() {
             video_id: None,
             watched: None,
             no_notif: true,
+            watch_status: None,
+            rating: None,
+            notes: None,
+            resume_points: Default::default(),
         },
         behavior_hints: Default::default(),
+        genres: vec![],
+        certification: None,
     };
     let lidl = LibraryItemDeepLinks::from((
         &lib_item,
@@ -124,8 +130,14 @@ fn library_item_deep_links_state_video_id_no_time_offset_infuse_player() {
             video_id: Some("tt13622776:1:5".to_string()),
             watched: None,
             no_notif: true,
+            watch_status: None,
+            rating: None,
+            notes: None,
+            resume_points: Default::default(),
         },
         behavior_hints: Default::default(),
+        genres: vec![],
+        certification: None,
     };
     let lidl = LibraryItemDeepLinks::from((
         &lib_item,
@@ -177,8 +189,14 @@ fn library_item_deep_links_state_video_id() {
             video_id: Some("tt13622776:1:5".to_string()),
             watched: None,
             no_notif: true,
+            watch_status: None,
+            rating: None,
+            notes: None,
+            resume_points: Default::default(),
         },
         behavior_hints: Default::default(),
+        genres: vec![],
+        certification: None,
     };
     let lidl = LibraryItemDeepLinks::from((
         &lib_item,
@@ -228,6 +246,10 @@ fn library_item_deep_links_behavior_hints_default_video_id() {
             video_id: None,
             watched: None,
             no_notif: true,
+            watch_status: None,
+            rating: None,
+            notes: None,
+            resume_points: Default::default(),
         },
         behavior_hints: MetaItemBehaviorHints {
             default_video_id: Some("tt13622776:1:5".to_string()),
@@ -235,6 +257,8 @@ fn library_item_deep_links_behavior_hints_default_video_id() {
             has_scheduled_videos: false,
             other: Default::default(),
         },
+        genres: vec![],
+        certification: None,
     };
     let lidl = LibraryItemDeepLinks::from((
         &lib_item,
@@ -284,6 +308,10 @@ fn library_item_deep_links_state_and_behavior_hints_default_video_id() {
             video_id: Some("video_id".to_string()),
             watched: None,
             no_notif: true,
+            watch_status: None,
+            rating: None,
+            notes: None,
+            resume_points: Default::default(),
         },
         behavior_hints: MetaItemBehaviorHints {
             default_video_id: Some("bh_video_id".to_string()),
@@ -291,6 +319,8 @@ fn library_item_deep_links_state_and_behavior_hints_default_video_id() {
             has_scheduled_videos: false,
             other: Default::default(),
         },
+        genres: vec![],
+        certification: None,
     };
     let lidl = LibraryItemDeepLinks::from((
         &lib_item,
@@ -340,6 +370,10 @@ fn library_item_deep_links_state_no_time_offset_and_behavior_hints_default_video
             video_id: Some("video_id".to_string()),
             watched: None,
             no_notif: true,
+            watch_status: None,
+            rating: None,
+            notes: None,
+            resume_points: Default::default(),
         },
         behavior_hints: MetaItemBehaviorHints {
             default_video_id: Some("bh_video_id".to_string()),
@@ -347,6 +381,8 @@ fn library_item_deep_links_state_no_time_offset_and_behavior_hints_default_video
             has_scheduled_videos: false,
             other: Default::default(),
         },
+        genres: vec![],
+        certification: None,
     };
     let lidl = LibraryItemDeepLinks::from((
         &lib_item,
@@ -1,6 +1,8 @@
 use crate::models::ctx::Ctx;
 use crate::models::streaming_server::StreamingServer;
-use crate::runtime::{Env, EnvFuture, EnvFutureExt, Model, Runtime, RuntimeEvent, TryEnvFuture};
+use crate::runtime::{
+    Env, EnvFuture, EnvFutureExt, Model, Runtime, RuntimeEvent, StorageEncryptionKey, TryEnvFuture,
+};
 use chrono::{DateTime, Utc};
 use enclose::enclose;
 use futures::channel::mpsc::Receiver;
@@ -19,6 +21,8 @@ lazy_static! {
         RwLock::new(Box::new(default_fetch_handler));
     pub static ref REQUESTS: RwLock<Vec<Request>> = Default::default();
     pub static ref STORAGE: RwLock<BTreeMap<String, String>> = Default::default();
+    pub static ref STORAGE_ENCRYPTION_KEY: RwLock<Option<StorageEncryptionKey>> =
+        Default::default();
     pub static ref EVENTS: RwLock<Vec<Box<dyn Any + Send + Sync + 'static>>> = Default::default();
     pub static ref STATES: RwLock<Vec<Box<dyn Any + Send + Sync + 'static>>> = Default::default();
     pub static ref NOW: RwLock<DateTime<Utc>> = RwLock::new(Utc::now());
@@ -61,6 +65,7 @@ impl TestEnv {
         *FETCH_HANDLER.write().unwrap() = Box::new(default_fetch_handler);
         *REQUESTS.write().unwrap() = vec![];
         *STORAGE.write().unwrap() = BTreeMap::new();
+        *STORAGE_ENCRYPTION_KEY.write().unwrap() = None;
         *EVENTS.write().unwrap() = vec![];
         *STATES.write().unwrap() = vec![];
         *NOW.write().unwrap() = Utc::now();
@@ -135,6 +140,9 @@ impl Env for TestEnv {
         };
         future::ok(()).boxed_env()
     }
+    fn storage_encryption_key() -> Option<StorageEncryptionKey> {
+        *STORAGE_ENCRYPTION_KEY.read().unwrap()
+    }
     fn exec_concurrent<F: Future<Output = ()> + 'static>(future: F) {
         tokio_current_thread::spawn(future);
     }
@@ -0,0 +1,188 @@
+use crate::constants::{CAST_LINK_CATEGORY, GENRES_LINK_CATEGORY};
+use crate::models::ctx::Ctx;
+use crate::models::for_you::{ForYou, Reason, Selected};
+use crate::runtime::msg::{Action, ActionLoad};
+use crate::runtime::{EnvFutureExt, Runtime, RuntimeAction, TryEnvFuture};
+use crate::types::addon::{
+    Descriptor, Manifest, ManifestCatalog, ManifestResource, ResourceResponse,
+};
+use crate::types::library::{LibraryBucket, LibraryItem, LibraryItemState, StoredValue};
+use crate::types::profile::Profile;
+use crate::types::resource::{Link, MetaItem, MetaItemPreview};
+use crate::unit_tests::{default_fetch_handler, Request, TestEnv, FETCH_HANDLER, NOW};
+use chrono::{TimeZone, Utc};
+use enclose::enclose;
+use futures::future;
+use semver::Version;
+use std::any::Any;
+use std::sync::{Arc, RwLock};
+use stremio_derive::Model;
+use url::Url;
+
+fn meta_item(id: &str, genres: &[&str], cast: &[&str]) -> MetaItemPreview {
+    MetaItemPreview {
+        id: id.to_owned(),
+        r#type: "movie".to_owned(),
+        name: id.to_owned(),
+        links: genres
+            .iter()
+            .map(|genre| (genre, GENRES_LINK_CATEGORY))
+            .chain(cast.iter().map(|name| (name, CAST_LINK_CATEGORY)))
+            .map(|(name, category)| Link {
+                name: name.to_string(),
+                category: category.to_owned(),
+                url: Url::parse("https://addon.com").unwrap(),
+            })
+            .collect(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn for_you_load_action() {
+    #[derive(Model, Default, Clone, Debug)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+        for_you: ForYou,
+    }
+    fn fetch_handler(request: Request) -> TryEnvFuture<Box<dyn Any + Send>> {
+        match request {
+            Request { url, .. } if url == "https://addon.com/meta/movie/tt1.json" => {
+                future::ok(Box::new(ResourceResponse::Meta {
+                    meta: MetaItem {
+                        preview: meta_item("tt1", &["Drama", "Crime"], &["Actor"]),
+                        videos: vec![],
+                    },
+                }) as Box<dyn Any + Send>)
+                .boxed_env()
+            }
+            Request { url, .. } if url == "https://addon.com/catalog/movie/top.json" => {
+                future::ok(Box::new(ResourceResponse::Metas {
+                    metas: vec![
+                        meta_item("tt1", &["Drama", "Crime"], &["Actor"]),
+                        meta_item("tt2", &["Comedy"], &[]),
+                        meta_item("tt3", &["drama"], &[]),
+                        meta_item("tt4", &["Drama"], &["Actor"]),
+                    ],
+                }) as Box<dyn Any + Send>)
+                .boxed_env()
+            }
+            _ => default_fetch_handler(request),
+        }
+    }
+    let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
+    *FETCH_HANDLER.write().unwrap() = Box::new(fetch_handler);
+    *NOW.write().unwrap() = Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap();
+    let addon = Descriptor {
+        manifest: Manifest {
+            id: "id".to_owned(),
+            version: Version::new(0, 0, 1),
+            name: "name".to_owned(),
+            contact_email: None,
+            description: None,
+            logo: None,
+            background: None,
+            types: vec!["movie".to_owned()],
+            resources: vec![
+                ManifestResource::Short("catalog".to_owned()),
+                ManifestResource::Short("meta".to_owned()),
+            ],
+            id_prefixes: Some(vec!["tt".to_owned()]),
+            catalogs: vec![ManifestCatalog {
+                id: "top".to_owned(),
+                r#type: "movie".to_owned(),
+                name: None,
+                extra: Default::default(),
+            }],
+            addon_catalogs: vec![],
+            behavior_hints: Default::default(),
+        },
+        transport_url: Url::parse("https://addon.com/manifest.json").unwrap(),
+        flags: Default::default(),
+    };
+    let library_item = LibraryItem {
+        id: "tt1".to_owned(),
+        removed: false,
+        temp: false,
+        ctime: Some(Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap()),
+        mtime: Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap(),
+        state: LibraryItemState {
+            last_watched: Some(Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap()),
+            times_watched: 1,
+            rating: Some(StoredValue::Known(9)),
+            ..Default::default()
+        },
+        name: "tt1".to_owned(),
+        r#type: "movie".to_owned(),
+        poster: None,
+        poster_shape: Default::default(),
+        behavior_hints: Default::default(),
+        genres: vec![],
+        certification: None,
+    };
+    let (runtime, rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx {
+                profile: Profile {
+                    addons: vec![addon],
+                    ..Default::default()
+                },
+                library: LibraryBucket::new(None, vec![library_item]),
+                ..Default::default()
+            },
+            for_you: Default::default(),
+        },
+        vec![],
+        1000,
+    );
+    let runtime = Arc::new(RwLock::new(runtime));
+    TestEnv::run_with_runtime(
+        rx,
+        runtime.clone(),
+        enclose!((runtime) move || {
+            let runtime = runtime.read().unwrap();
+            runtime.dispatch(RuntimeAction {
+                field: None,
+                action: Action::Load(ActionLoad::ForYou(Selected {
+                    r#type: Some("movie".to_owned()),
+                })),
+            });
+        }),
+    );
+    let items = runtime
+        .read()
+        .unwrap()
+        .model()
+        .unwrap()
+        .for_you
+        .items
+        .iter()
+        .map(|recommendation| {
+            (
+                recommendation.meta_item.id.to_owned(),
+                recommendation.score,
+                recommendation.reasons.to_owned(),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        items,
+        vec![
+            (
+                "tt4".to_owned(),
+                1.0,
+                vec![
+                    Reason::Genre("Drama".to_owned()),
+                    Reason::Cast("Actor".to_owned())
+                ]
+            ),
+            (
+                "tt3".to_owned(),
+                0.7,
+                vec![Reason::Genre("drama".to_owned())]
+            ),
+        ],
+        "Items are scored by the genres and the cast of the library items"
+    );
+}
@@ -0,0 +1,182 @@
+use crate::models::ctx::Ctx;
+use crate::models::global_search::{GlobalSearch, Selected};
+use crate::models::local_search::{
+    LocalSearch, Searchable, SearchableItemsResponse, SearchableSource,
+};
+use crate::runtime::msg::{Action, ActionLoad};
+use crate::runtime::{EnvFutureExt, Runtime, RuntimeAction, TryEnvFuture};
+use crate::types::addon::{
+    Descriptor, ExtraProp, Manifest, ManifestCatalog, ManifestExtra, ManifestResource,
+    ResourceResponse,
+};
+use crate::types::profile::Profile;
+use crate::types::resource::MetaItemPreview;
+use crate::unit_tests::{default_fetch_handler, Request, TestEnv, FETCH_HANDLER, REQUESTS};
+use enclose::enclose;
+use futures::future;
+use semver::Version;
+use std::any::Any;
+use std::sync::{Arc, RwLock};
+use stremio_derive::Model;
+use url::Url;
+
+fn searchable(id: &str, name: &str) -> Searchable {
+    Searchable {
+        id: id.to_owned(),
+        name: name.to_owned(),
+        r#type: "series".to_owned(),
+        poster: None,
+        imdb_rating: None,
+        popularity: None,
+        release_info: None,
+        genres: vec![],
+        aka: vec![],
+        certification: None,
+        source: Default::default(),
+    }
+}
+
+#[test]
+fn global_search_merges_sources() {
+    #[derive(Model, Default, Clone, Debug)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+        local_search: LocalSearch,
+        global_search: GlobalSearch,
+    }
+    fn fetch_handler(request: Request) -> TryEnvFuture<Box<dyn Any + Send>> {
+        match request {
+            Request { url, .. } if url == "https://cinemeta-catalogs.strem.io/feed.json" => {
+                future::ok(Box::new(SearchableItemsResponse(vec![
+                    searchable("tt1", "Breaking Bad"),
+                    searchable("tt2", "The Office"),
+                ])) as Box<dyn Any + Send>)
+                .boxed_env()
+            }
+            Request { url, .. } if url.starts_with("https://addon.com/catalog/series/search/") => {
+                future::ok(Box::new(ResourceResponse::Metas {
+                    metas: vec![
+                        MetaItemPreview {
+                            id: "tt2".to_owned(),
+                            r#type: "series".to_owned(),
+                            name: "The Office".to_owned(),
+                            ..Default::default()
+                        },
+                        MetaItemPreview {
+                            id: "tt3".to_owned(),
+                            r#type: "series".to_owned(),
+                            name: "Office Ladies".to_owned(),
+                            ..Default::default()
+                        },
+                    ],
+                }) as Box<dyn Any + Send>)
+                .boxed_env()
+            }
+            _ => default_fetch_handler(request),
+        }
+    }
+    let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
+    *FETCH_HANDLER.write().unwrap() = Box::new(fetch_handler);
+    let addon = Descriptor {
+        manifest: Manifest {
+            id: "addon".to_owned(),
+            version: Version::new(0, 0, 1),
+            name: "addon".to_owned(),
+            contact_email: None,
+            description: None,
+            logo: None,
+            background: None,
+            types: vec!["series".to_owned()],
+            resources: vec![ManifestResource::Short("catalog".to_owned())],
+            id_prefixes: Some(vec!["tt".to_owned()]),
+            catalogs: vec![ManifestCatalog {
+                id: "search".to_owned(),
+                r#type: "series".to_owned(),
+                name: None,
+                extra: ManifestExtra::Full {
+                    props: vec![ExtraProp {
+                        name: "search".to_owned(),
+                        is_required: true,
+                        options: vec![],
+                        options_limit: Default::default(),
+                    }],
+                },
+            }],
+            addon_catalogs: vec![],
+            behavior_hints: Default::default(),
+        },
+        transport_url: Url::parse("https://addon.com/manifest.json").unwrap(),
+        flags: Default::default(),
+    };
+    let (runtime, rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx {
+                profile: Profile {
+                    addons: vec![addon],
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            local_search: Default::default(),
+            global_search: Default::default(),
+        },
+        vec![],
+        1000,
+    );
+    let runtime = Arc::new(RwLock::new(runtime));
+    TestEnv::run_with_runtime(
+        rx,
+        runtime.clone(),
+        enclose!((runtime) move || {
+            let runtime = runtime.read().unwrap();
+            runtime.dispatch(RuntimeAction {
+                field: None,
+                action: Action::Load(ActionLoad::GlobalSearch(Selected {
+                    query: "office".to_owned(),
+                })),
+            });
+        }),
+    );
+    let model = runtime.read().unwrap().model().unwrap().to_owned();
+    assert_eq!(
+        model
+            .global_search
+            .items
+            .iter()
+            .map(|item| (item.searchable.id.as_str(), item.sources.to_owned()))
+            .collect::<Vec<_>>(),
+        vec![
+            (
+                "tt3",
+                vec![SearchableSource::Addon {
+                    transport_url: Url::parse("https://addon.com/manifest.json").unwrap()
+                }]
+            ),
+            (
+                "tt2",
+                vec![
+                    SearchableSource::Cinemeta,
+                    SearchableSource::Addon {
+                        transport_url: Url::parse("https://addon.com/manifest.json").unwrap()
+                    }
+                ]
+            ),
+        ],
+        "Items are de-duplicated by id and ranked by the relevance of their titles"
+    );
+    assert!(
+        model.ctx.search_history.items.contains_key("office"),
+        "The query is recorded in the search history"
+    );
+    assert_eq!(
+        REQUESTS
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|request| request.url == "https://cinemeta-catalogs.strem.io/feed.json")
+            .count(),
+        1,
+        "The feed is fetched once, by the local search shared with the autocompletion"
+    );
+}
@@ -3,13 +3,17 @@ use crate::models::ctx::Ctx;
 use crate::models::link::Link;
 use crate::runtime::msg::{Action, ActionLink, ActionLoad};
 use crate::runtime::{EnvFutureExt, Runtime, RuntimeAction, TryEnvFuture};
+use crate::types::accounts::AccountsBucket;
 use crate::types::api::{APIResult, LinkAuthKey, LinkCodeResponse, LinkDataResponse};
 use crate::types::events::DismissedEventsBucket;
 use crate::types::library::LibraryBucket;
 use crate::types::notifications::NotificationsBucket;
+use crate::types::outbox::OutboxBucket;
+use crate::types::playlists::PlaylistsBucket;
 use crate::types::profile::Profile;
 use crate::types::search_history::SearchHistoryBucket;
 use crate::types::streams::StreamsBucket;
+use crate::types::watch_history::WatchHistoryBucket;
 use crate::unit_tests::{default_fetch_handler, Request, TestEnv, FETCH_HANDLER, REQUESTS};
 use futures::future;
 use std::any::Any;
@@ -64,6 +68,10 @@ fn create_link_code() {
             NotificationsBucket::new::<TestEnv>(None, vec![]),
             SearchHistoryBucket::default(),
             DismissedEventsBucket::default(),
+            OutboxBucket::default(),
+            AccountsBucket::default(),
+            PlaylistsBucket::default(),
+            WatchHistoryBucket::default(),
         ),
         link: Link::default(),
     };
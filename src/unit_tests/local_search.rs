@@ -0,0 +1,407 @@
+use crate::constants::{CINEMETA_CATALOGS_URL, LOCAL_SEARCH_STORAGE_KEY};
+use crate::models::ctx::Ctx;
+use crate::models::local_search::{
+    LocalSearch, Searchable, SearchableFeed, SearchableItemsResponse, SearchableSource,
+};
+use crate::runtime::msg::{Action, ActionCtx, ActionLoad, ActionSearch};
+use crate::runtime::{EnvFutureExt, Runtime, RuntimeAction, TryEnvFuture};
+use crate::types::addon::{Descriptor, Manifest, ManifestBehaviorHints};
+use crate::types::library::{LibraryBucket, LibraryItem, LibraryItemState};
+use crate::types::profile::Profile;
+use crate::types::resource::MetaItemPreview;
+use crate::unit_tests::{
+    default_fetch_handler, Request, TestEnv, FETCH_HANDLER, NOW, REQUESTS, STORAGE,
+};
+use chrono::{Duration, TimeZone, Utc};
+use enclose::enclose;
+use futures::future;
+use semver::Version;
+use std::any::Any;
+use std::sync::{Arc, RwLock};
+use stremio_derive::Model;
+use url::Url;
+
+fn searchable(id: &str, name: &str) -> Searchable {
+    Searchable {
+        id: id.to_owned(),
+        name: name.to_owned(),
+        r#type: "series".to_owned(),
+        poster: None,
+        imdb_rating: None,
+        popularity: None,
+        release_info: None,
+        genres: vec![],
+        aka: vec![],
+        certification: None,
+        source: Default::default(),
+    }
+}
+
+#[test]
+fn local_search_sources() {
+    #[derive(Model, Default, Clone, Debug)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+        local_search: LocalSearch,
+    }
+    fn fetch_handler(request: Request) -> TryEnvFuture<Box<dyn Any + Send>> {
+        match request {
+            Request { url, .. } if url == "https://cinemeta-catalogs.strem.io/feed.json" => {
+                future::ok(Box::new(SearchableItemsResponse(vec![
+                    searchable("tt1", "Breaking Bad"),
+                    searchable("tt2", "The Office"),
+                ])) as Box<dyn Any + Send>)
+                .boxed_env()
+            }
+            Request { url, .. } if url == "https://anime.com/feed.json" => future::ok(Box::new(
+                SearchableItemsResponse(vec![searchable("kitsu:1", "Naruto")]),
+            )
+                as Box<dyn Any + Send>)
+            .boxed_env(),
+            _ => default_fetch_handler(request),
+        }
+    }
+    let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
+    *FETCH_HANDLER.write().unwrap() = Box::new(fetch_handler);
+    let addon = Descriptor {
+        manifest: Manifest {
+            id: "anime".to_owned(),
+            version: Version::new(0, 0, 1),
+            name: "anime".to_owned(),
+            contact_email: None,
+            description: None,
+            logo: None,
+            background: None,
+            types: vec!["series".to_owned()],
+            resources: vec![],
+            id_prefixes: Some(vec!["kitsu:".to_owned()]),
+            catalogs: vec![],
+            addon_catalogs: vec![],
+            behavior_hints: ManifestBehaviorHints {
+                searchable_feed: Some("feed.json".to_owned()),
+                ..Default::default()
+            },
+        },
+        transport_url: Url::parse("https://anime.com/manifest.json").unwrap(),
+        flags: Default::default(),
+    };
+    let library_item = LibraryItem {
+        id: "tt1".to_owned(),
+        removed: false,
+        temp: false,
+        ctime: Some(Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap()),
+        mtime: Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap(),
+        state: LibraryItemState::default(),
+        name: "Breaking Bad".to_owned(),
+        r#type: "series".to_owned(),
+        poster: None,
+        poster_shape: Default::default(),
+        behavior_hints: Default::default(),
+        genres: vec![],
+        certification: None,
+    };
+    let (runtime, rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx {
+                profile: Profile {
+                    addons: vec![addon],
+                    ..Default::default()
+                },
+                library: LibraryBucket::new(None, vec![library_item]),
+                ..Default::default()
+            },
+            local_search: Default::default(),
+        },
+        vec![],
+        1000,
+    );
+    let runtime = Arc::new(RwLock::new(runtime));
+    TestEnv::run_with_runtime(
+        rx,
+        runtime.clone(),
+        enclose!((runtime) move || {
+            let runtime = runtime.read().unwrap();
+            runtime.dispatch(RuntimeAction {
+                field: None,
+                action: Action::Load(ActionLoad::LocalSearch),
+            });
+        }),
+    );
+    let search = |query: &str| {
+        TestEnv::run(|| {
+            runtime.read().unwrap().dispatch(RuntimeAction {
+                field: None,
+                action: Action::Search(ActionSearch::Search {
+                    search_query: query.to_owned(),
+                    max_results: 10,
+                }),
+            })
+        });
+        runtime
+            .read()
+            .unwrap()
+            .model()
+            .unwrap()
+            .local_search
+            .search_results
+            .first()
+            .map(|searchable| (searchable.id.to_owned(), searchable.source.to_owned()))
+    };
+    assert_eq!(
+        search("Naruto"),
+        Some((
+            "kitsu:1".to_owned(),
+            SearchableSource::Addon {
+                transport_url: Url::parse("https://anime.com/manifest.json").unwrap()
+            }
+        )),
+        "Items of the addon's feed are searchable"
+    );
+    assert_eq!(
+        search("Breaking Bad"),
+        Some(("tt1".to_owned(), SearchableSource::Library)),
+        "Library items take precedence over the same catalog items"
+    );
+    assert_eq!(
+        search("The Office"),
+        Some(("tt2".to_owned(), SearchableSource::Cinemeta)),
+        "Cinemeta items are searchable"
+    );
+    TestEnv::run(|| {
+        runtime.read().unwrap().dispatch(RuntimeAction {
+            field: None,
+            action: Action::Ctx(ActionCtx::AddToLibrary(MetaItemPreview {
+                id: "tt2".to_owned(),
+                r#type: "series".to_owned(),
+                name: "The Office".to_owned(),
+                ..Default::default()
+            })),
+        })
+    });
+    assert_eq!(
+        runtime
+            .read()
+            .unwrap()
+            .model()
+            .unwrap()
+            .local_search
+            .library_records
+            .len(),
+        2,
+        "Library records are updated when the library changes"
+    );
+    assert_eq!(
+        search("The Office"),
+        Some(("tt2".to_owned(), SearchableSource::Library)),
+        "Newly added library items are searchable"
+    );
+}
+
+#[test]
+fn local_search_persisted_feeds() {
+    #[derive(Model, Default, Clone, Debug)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+        local_search: LocalSearch,
+    }
+    fn fetch_handler(request: Request) -> TryEnvFuture<Box<dyn Any + Send>> {
+        match request {
+            Request { url, .. } if url == "https://cinemeta-catalogs.strem.io/feed.json" => {
+                future::ok(Box::new(SearchableItemsResponse(vec![
+                    searchable("tt1", "Breaking Bad"),
+                    searchable("tt2", "The Office"),
+                ])) as Box<dyn Any + Send>)
+                .boxed_env()
+            }
+            _ => default_fetch_handler(request),
+        }
+    }
+    let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
+    *FETCH_HANDLER.write().unwrap() = Box::new(fetch_handler);
+    let last_updated = Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap();
+    *NOW.write().unwrap() = last_updated + Duration::hours(1);
+    STORAGE.write().unwrap().insert(
+        LOCAL_SEARCH_STORAGE_KEY.to_owned(),
+        serde_json::to_string(&vec![SearchableFeed {
+            url: CINEMETA_CATALOGS_URL.to_owned(),
+            last_updated,
+            items: vec![searchable("tt1", "Breaking Bad")],
+        }])
+        .unwrap(),
+    );
+    let (runtime, rx) = Runtime::<TestEnv, _>::new(TestModel::default(), vec![], 1000);
+    let runtime = Arc::new(RwLock::new(runtime));
+    TestEnv::run_with_runtime(
+        rx,
+        runtime.clone(),
+        enclose!((runtime) move || {
+            let runtime = runtime.read().unwrap();
+            runtime.dispatch(RuntimeAction {
+                field: None,
+                action: Action::Load(ActionLoad::LocalSearch),
+            });
+        }),
+    );
+    assert!(
+        REQUESTS.read().unwrap().is_empty(),
+        "The persisted feed is not stale and is not fetched"
+    );
+    assert!(
+        runtime
+            .read()
+            .unwrap()
+            .model()
+            .unwrap()
+            .local_search
+            .searcher
+            .is_some(),
+        "The index is built when the persisted feed is loaded"
+    );
+    TestEnv::run(|| {
+        runtime.read().unwrap().dispatch(RuntimeAction {
+            field: None,
+            action: Action::Search(ActionSearch::Search {
+                search_query: "Breaking Bad".to_owned(),
+                max_results: 10,
+            }),
+        })
+    });
+    assert_eq!(
+        runtime
+            .read()
+            .unwrap()
+            .model()
+            .unwrap()
+            .local_search
+            .search_results
+            .first()
+            .map(|searchable| searchable.id.to_owned()),
+        Some("tt1".to_owned()),
+        "The persisted feed is searchable"
+    );
+    *NOW.write().unwrap() = last_updated + Duration::days(2);
+    TestEnv::run(|| {
+        runtime.read().unwrap().dispatch(RuntimeAction {
+            field: None,
+            action: Action::Load(ActionLoad::LocalSearch),
+        })
+    });
+    assert_eq!(
+        REQUESTS.read().unwrap().len(),
+        1,
+        "The stale feed is fetched again"
+    );
+    let feeds = serde_json::from_str::<Vec<SearchableFeed>>(
+        STORAGE
+            .read()
+            .unwrap()
+            .get(LOCAL_SEARCH_STORAGE_KEY)
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(feeds.len(), 1);
+    assert_eq!(feeds[0].last_updated, last_updated + Duration::days(2));
+    assert_eq!(feeds[0].items.len(), 2, "The refreshed feed is persisted");
+}
+
+#[test]
+fn local_search_query() {
+    #[derive(Model, Default, Clone, Debug)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+        local_search: LocalSearch,
+    }
+    fn fetch_handler(request: Request) -> TryEnvFuture<Box<dyn Any + Send>> {
+        match request {
+            Request { url, .. } if url == "https://cinemeta-catalogs.strem.io/feed.json" => {
+                future::ok(Box::new(SearchableItemsResponse(vec![
+                    Searchable {
+                        release_info: Some("2008-2013".to_owned()),
+                        ..searchable("tt1", "Breaking Bad")
+                    },
+                    Searchable {
+                        r#type: "movie".to_owned(),
+                        release_info: Some("2001".to_owned()),
+                        ..searchable("tt2", "Amélie")
+                    },
+                    Searchable {
+                        aka: vec!["ナルト".to_owned()],
+                        ..searchable("tt3", "Naruto")
+                    },
+                ])) as Box<dyn Any + Send>)
+                .boxed_env()
+            }
+            _ => default_fetch_handler(request),
+        }
+    }
+    let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
+    *FETCH_HANDLER.write().unwrap() = Box::new(fetch_handler);
+    let (runtime, rx) = Runtime::<TestEnv, _>::new(TestModel::default(), vec![], 1000);
+    let runtime = Arc::new(RwLock::new(runtime));
+    TestEnv::run_with_runtime(
+        rx,
+        runtime.clone(),
+        enclose!((runtime) move || {
+            let runtime = runtime.read().unwrap();
+            runtime.dispatch(RuntimeAction {
+                field: None,
+                action: Action::Load(ActionLoad::LocalSearch),
+            });
+        }),
+    );
+    let search = |query: &str| {
+        TestEnv::run(|| {
+            runtime.read().unwrap().dispatch(RuntimeAction {
+                field: None,
+                action: Action::Search(ActionSearch::Search {
+                    search_query: query.to_owned(),
+                    max_results: 10,
+                }),
+            })
+        });
+        runtime
+            .read()
+            .unwrap()
+            .model()
+            .unwrap()
+            .local_search
+            .search_results
+            .first()
+            .map(|searchable| searchable.id.to_owned())
+    };
+    assert_eq!(
+        search("breakng bad"),
+        Some("tt1".to_owned()),
+        "Typos are tolerated"
+    );
+    assert_eq!(
+        search("amelie"),
+        Some("tt2".to_owned()),
+        "Diacritics are normalized"
+    );
+    assert_eq!(
+        search("ナルト"),
+        Some("tt3".to_owned()),
+        "Alternate titles are matched"
+    );
+    assert_eq!(
+        search("ナル"),
+        Some("tt3".to_owned()),
+        "Kana are transliterated"
+    );
+    assert_eq!(search("type:movie amelie"), Some("tt2".to_owned()));
+    assert_eq!(
+        search("type:series amelie"),
+        None,
+        "Items of other types are filtered out"
+    );
+    assert_eq!(search("breaking year:2010"), Some("tt1".to_owned()));
+    assert_eq!(
+        search("breaking year:2015"),
+        None,
+        "Items released in other years are filtered out"
+    );
+}
@@ -0,0 +1,138 @@
+use crate::constants::{META_RESOURCE_NAME, STREAM_RESOURCE_NAME};
+use crate::models::common::{Loadable, ResourceError};
+use crate::models::ctx::Ctx;
+use crate::models::meta_details::{MetaDetails, Selected};
+use crate::runtime::msg::{Action, ActionLoad};
+use crate::runtime::{EnvFutureExt, Runtime, RuntimeAction, TryEnvFuture};
+use crate::types::addon::{Descriptor, Manifest, ManifestResource, ResourcePath, ResourceResponse};
+use crate::types::profile::{Certification, ParentalControls, Profile};
+use crate::types::resource::{MetaItem, MetaItemPreview, Stream, StreamSource};
+use crate::unit_tests::{default_fetch_handler, Request, TestEnv, FETCH_HANDLER};
+use futures::future;
+use semver::Version;
+use std::any::Any;
+use stremio_derive::Model;
+use url::Url;
+
+#[test]
+fn restricted_meta_streams() {
+    #[derive(Model, Default, Clone, Debug)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+        meta_details: MetaDetails,
+    }
+    fn fetch_handler(request: Request) -> TryEnvFuture<Box<dyn Any + Send>> {
+        match request {
+            Request { url, .. } if url == "https://addon.com/meta/movie/tt1.json" => {
+                future::ok(Box::new(ResourceResponse::Meta {
+                    meta: MetaItem {
+                        preview: MetaItemPreview {
+                            id: "tt1".to_owned(),
+                            r#type: "movie".to_owned(),
+                            ..Default::default()
+                        },
+                        videos: vec![],
+                    },
+                }) as Box<dyn Any + Send>)
+                .boxed_env()
+            }
+            Request { url, .. } if url == "https://addon.com/stream/movie/tt1.json" => {
+                future::ok(Box::new(ResourceResponse::Streams {
+                    streams: vec![Stream {
+                        source: StreamSource::Url {
+                            url: "https://source_url".parse().unwrap(),
+                        },
+                        name: None,
+                        description: None,
+                        thumbnail: None,
+                        subtitles: vec![],
+                        behavior_hints: Default::default(),
+                    }],
+                }) as Box<dyn Any + Send>)
+                .boxed_env()
+            }
+            _ => default_fetch_handler(request),
+        }
+    }
+    let addon = Descriptor {
+        manifest: Manifest {
+            id: "id".to_owned(),
+            version: Version::new(0, 0, 1),
+            name: "name".to_owned(),
+            contact_email: None,
+            description: None,
+            logo: None,
+            background: None,
+            types: vec!["movie".to_owned()],
+            resources: vec![
+                ManifestResource::Short(META_RESOURCE_NAME.to_owned()),
+                ManifestResource::Short(STREAM_RESOURCE_NAME.to_owned()),
+            ],
+            id_prefixes: Some(vec!["tt".to_owned()]),
+            catalogs: vec![],
+            addon_catalogs: vec![],
+            behavior_hints: Default::default(),
+        },
+        transport_url: Url::parse("https://addon.com/manifest.json").unwrap(),
+        flags: Default::default(),
+    };
+    let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
+    *FETCH_HANDLER.write().unwrap() = Box::new(fetch_handler);
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx {
+                profile: Profile {
+                    addons: vec![addon],
+                    // the meta item has no certification, so it's restricted
+                    parental_controls: ParentalControls {
+                        max_certification: Some(Certification::PG),
+                        block_unrated: true,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            meta_details: Default::default(),
+        },
+        vec![],
+        1000,
+    );
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Load(ActionLoad::MetaDetails(Selected {
+                meta_path: ResourcePath {
+                    resource: META_RESOURCE_NAME.to_owned(),
+                    r#type: "movie".to_owned(),
+                    id: "tt1".to_owned(),
+                    extra: vec![],
+                },
+                stream_path: Some(ResourcePath {
+                    resource: STREAM_RESOURCE_NAME.to_owned(),
+                    r#type: "movie".to_owned(),
+                    id: "tt1".to_owned(),
+                    extra: vec![],
+                }),
+                guess_stream: false,
+            })),
+        })
+    });
+    let meta_details = runtime.model().unwrap().meta_details.to_owned();
+    assert!(
+        meta_details.meta_items.iter().all(|meta_item| matches!(
+            meta_item.content,
+            Some(Loadable::Err(ResourceError::Restricted))
+        )),
+        "Meta item is restricted"
+    );
+    assert!(
+        meta_details.streams.is_empty(),
+        "Streams of the restricted meta item are dropped"
+    );
+    assert!(
+        meta_details.suggested_stream.is_none(),
+        "No stream is suggested for the restricted meta item"
+    );
+}
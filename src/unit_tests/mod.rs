@@ -1,12 +1,18 @@
 mod env;
 pub use env::*;
 
+mod calendar;
 mod catalog_with_filters;
 mod ctx;
 mod data_export;
 mod deep_links;
+mod for_you;
+mod global_search;
 mod link;
+mod local_search;
 mod meta_details;
 mod player;
 mod serde;
 mod streaming_server;
+mod subtitles;
+mod watch_stats;
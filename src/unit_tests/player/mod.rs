@@ -1 +1,4 @@
 mod next_stream;
+mod parental_controls;
+mod queue;
+mod subtitles;
@@ -0,0 +1,108 @@
+use crate::{
+    constants::{META_RESOURCE_NAME, STREAM_RESOURCE_NAME},
+    models::{
+        ctx::Ctx,
+        player::{Player, Selected},
+    },
+    runtime::{
+        msg::{Action, ActionLoad},
+        EnvFutureExt, Runtime, RuntimeAction, TryEnvFuture,
+    },
+    types::{
+        addon::{ResourcePath, ResourceRequest, ResourceResponse},
+        profile::{Certification, ParentalControls, Profile},
+        resource::{MetaItem, MetaItemPreview, Stream, StreamSource},
+    },
+    unit_tests::{default_fetch_handler, Request, TestEnv, FETCH_HANDLER},
+};
+use futures::future;
+use std::any::Any;
+use stremio_derive::Model;
+
+fn create_request(resource: &str, id: &str) -> ResourceRequest {
+    ResourceRequest {
+        base: "https://transport_url/manifest.json".parse().unwrap(),
+        path: ResourcePath {
+            resource: resource.to_owned(),
+            r#type: "movie".to_owned(),
+            id: id.to_owned(),
+            extra: vec![],
+        },
+    }
+}
+
+#[test]
+fn restricted_meta_item() {
+    #[derive(Model, Default, Clone, Debug)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+        player: Player,
+    }
+
+    fn fetch_handler(request: Request) -> TryEnvFuture<Box<dyn Any + Send>> {
+        match request {
+            Request { url, .. } if url == "https://transport_url/meta/movie/tt1.json" => {
+                future::ok(Box::new(ResourceResponse::Meta {
+                    meta: MetaItem {
+                        preview: MetaItemPreview {
+                            id: "tt1".to_owned(),
+                            r#type: "movie".to_owned(),
+                            ..Default::default()
+                        },
+                        videos: vec![],
+                    },
+                }) as Box<dyn Any + Send>)
+                .boxed_env()
+            }
+            _ => default_fetch_handler(request),
+        }
+    }
+
+    let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
+    *FETCH_HANDLER.write().unwrap() = Box::new(fetch_handler);
+
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx {
+                profile: Profile {
+                    // the meta item has no certification, so it's restricted
+                    parental_controls: ParentalControls {
+                        max_certification: Some(Certification::PG),
+                        block_unrated: true,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            player: Player::default(),
+        },
+        vec![],
+        1000,
+    );
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Load(ActionLoad::Player(Box::new(Selected {
+                stream: Stream {
+                    source: StreamSource::Url {
+                        url: "https://source_url".parse().unwrap(),
+                    },
+                    name: None,
+                    description: None,
+                    thumbnail: None,
+                    subtitles: vec![],
+                    behavior_hints: Default::default(),
+                },
+                stream_request: Some(create_request(STREAM_RESOURCE_NAME, "tt1")),
+                meta_request: Some(create_request(META_RESOURCE_NAME, "tt1")),
+                subtitles_path: None,
+            }))),
+        })
+    });
+    assert!(
+        runtime.model().unwrap().player.selected.is_none(),
+        "Restricted meta item is not played"
+    );
+}
@@ -0,0 +1,387 @@
+use crate::{
+    constants::{META_RESOURCE_NAME, STREAM_RESOURCE_NAME},
+    models::{
+        ctx::Ctx,
+        player::{Player, QueueItem, Selected},
+    },
+    runtime::{
+        msg::{Action, ActionLoad, ActionPlayer},
+        EnvFutureExt, Runtime, RuntimeAction, TryEnvFuture,
+    },
+    types::{
+        addon::{ResourcePath, ResourceRequest, ResourceResponse},
+        profile::{Profile, Settings},
+        resource::{MetaItem, MetaItemPreview, SeriesInfo, Stream, StreamSource, Video},
+    },
+    unit_tests::{default_fetch_handler, Request, TestEnv, FETCH_HANDLER},
+};
+use futures::future;
+use std::any::Any;
+use stremio_derive::Model;
+
+fn create_request(resource: &str, r#type: &str, id: &str) -> ResourceRequest {
+    ResourceRequest {
+        base: "https://transport_url/manifest.json".parse().unwrap(),
+        path: ResourcePath {
+            resource: resource.to_owned(),
+            r#type: r#type.to_owned(),
+            id: id.to_owned(),
+            extra: vec![],
+        },
+    }
+}
+
+fn create_video(id: &str, series_info: Option<SeriesInfo>) -> Video {
+    Video {
+        id: id.to_owned(),
+        title: id.to_owned(),
+        released: None,
+        overview: None,
+        thumbnail: None,
+        streams: vec![],
+        series_info,
+        trailer_streams: vec![],
+    }
+}
+
+fn create_episode(episode: u32) -> Video {
+    create_video(
+        &format!("tt123456:1:{episode}"),
+        Some(SeriesInfo { season: 1, episode }),
+    )
+}
+
+fn create_queue_item(r#type: &str, meta_id: &str, video: Video) -> QueueItem {
+    QueueItem {
+        meta_request: create_request(META_RESOURCE_NAME, r#type, meta_id),
+        meta_item: MetaItemPreview {
+            id: meta_id.to_owned(),
+            r#type: r#type.to_owned(),
+            ..Default::default()
+        },
+        video,
+        stream: None,
+    }
+}
+
+#[test]
+fn queue() {
+    #[derive(Model, Default, Clone, Debug)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+        player: Player,
+    }
+
+    fn fetch_handler(request: Request) -> TryEnvFuture<Box<dyn Any + Send>> {
+        match request {
+            Request { url, .. } if url == "https://transport_url/meta/series/tt123456.json" => {
+                future::ok(Box::new(ResourceResponse::Meta {
+                    meta: MetaItem {
+                        preview: MetaItemPreview {
+                            id: "tt123456".to_owned(),
+                            r#type: "series".to_owned(),
+                            ..Default::default()
+                        },
+                        videos: vec![create_episode(1), create_episode(2), create_episode(3)],
+                    },
+                }) as Box<dyn Any + Send>)
+                .boxed_env()
+            }
+            Request { url, .. }
+                if url == "https://transport_url/stream/series/tt123456%3A1%3A3.json" =>
+            {
+                future::ok(
+                    Box::new(ResourceResponse::Streams { streams: vec![] }) as Box<dyn Any + Send>
+                )
+                .boxed_env()
+            }
+            _ => default_fetch_handler(request),
+        }
+    }
+
+    let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
+
+    *FETCH_HANDLER.write().unwrap() = Box::new(fetch_handler);
+
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx {
+                profile: Profile {
+                    settings: Settings {
+                        binge_watching: true,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            player: Player::default(),
+        },
+        vec![],
+        1000,
+    );
+    let dispatch = |action: Action| {
+        TestEnv::run(|| {
+            runtime.dispatch(RuntimeAction {
+                field: None,
+                action,
+            })
+        })
+    };
+    let load = |video_id: &str| {
+        dispatch(Action::Load(ActionLoad::Player(Box::new(Selected {
+            stream: Stream {
+                source: StreamSource::Url {
+                    url: "https://source_url".parse().unwrap(),
+                },
+                name: None,
+                description: None,
+                thumbnail: None,
+                subtitles: vec![],
+                behavior_hints: Default::default(),
+            },
+            stream_request: Some(create_request(STREAM_RESOURCE_NAME, "series", video_id)),
+            meta_request: Some(create_request(META_RESOURCE_NAME, "series", "tt123456")),
+            subtitles_path: None,
+        }))))
+    };
+    let queued_video_ids = || {
+        runtime
+            .model()
+            .unwrap()
+            .player
+            .queue
+            .iter()
+            .map(|item| item.video.id.to_owned())
+            .collect::<Vec<_>>()
+    };
+
+    dispatch(Action::Player(ActionPlayer::Enqueue(Box::new(
+        create_queue_item("movie", "tt999", create_video("tt999", None)),
+    ))));
+    dispatch(Action::Player(ActionPlayer::Enqueue(Box::new(
+        create_queue_item("series", "tt123456", create_episode(2)),
+    ))));
+    dispatch(Action::Player(ActionPlayer::MoveQueueItem {
+        from: 1,
+        to: 0,
+    }));
+    assert_eq!(
+        queued_video_ids(),
+        vec!["tt123456:1:2", "tt999"],
+        "Queue is built and reordered before the player is loaded"
+    );
+
+    load("tt123456:1:1");
+    assert_eq!(
+        runtime
+            .model()
+            .unwrap()
+            .player
+            .next_video
+            .as_ref()
+            .map(|video| video.id.to_owned()),
+        Some("tt123456:1:2".to_owned()),
+        "Queued items are played before the next video of the series"
+    );
+
+    load("tt123456:1:2");
+    assert_eq!(
+        queued_video_ids(),
+        vec!["tt999"],
+        "Playing a queued item removes it from the queue"
+    );
+
+    dispatch(Action::Player(ActionPlayer::Dequeue { index: 0 }));
+    assert!(queued_video_ids().is_empty(), "Queue item is removed");
+    assert_eq!(
+        runtime
+            .model()
+            .unwrap()
+            .player
+            .next_video
+            .as_ref()
+            .map(|video| video.id.to_owned()),
+        Some("tt123456:1:3".to_owned()),
+        "Next video of the series is played once the queue is empty"
+    );
+}
+
+#[test]
+fn queue_play_through() {
+    #[derive(Model, Default, Clone, Debug)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+        player: Player,
+    }
+
+    fn fetch_handler(request: Request) -> TryEnvFuture<Box<dyn Any + Send>> {
+        match request {
+            Request { url, .. } if url == "https://transport_url/meta/series/tt123456.json" => {
+                future::ok(Box::new(ResourceResponse::Meta {
+                    meta: MetaItem {
+                        preview: MetaItemPreview {
+                            id: "tt123456".to_owned(),
+                            r#type: "series".to_owned(),
+                            ..Default::default()
+                        },
+                        videos: vec![create_episode(1), create_episode(2), create_episode(3)],
+                    },
+                }) as Box<dyn Any + Send>)
+                .boxed_env()
+            }
+            Request { url, .. } if url == "https://transport_url/meta/movie/tt999.json" => {
+                future::ok(Box::new(ResourceResponse::Meta {
+                    meta: MetaItem {
+                        preview: MetaItemPreview {
+                            id: "tt999".to_owned(),
+                            r#type: "movie".to_owned(),
+                            ..Default::default()
+                        },
+                        videos: vec![],
+                    },
+                }) as Box<dyn Any + Send>)
+                .boxed_env()
+            }
+            _ => default_fetch_handler(request),
+        }
+    }
+
+    let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
+
+    *FETCH_HANDLER.write().unwrap() = Box::new(fetch_handler);
+
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx {
+                profile: Profile {
+                    settings: Settings {
+                        binge_watching: true,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            player: Player::default(),
+        },
+        vec![],
+        1000,
+    );
+    let dispatch = |action: Action| {
+        TestEnv::run(|| {
+            runtime.dispatch(RuntimeAction {
+                field: None,
+                action,
+            })
+        })
+    };
+    let stream = |url: &str| Stream {
+        source: StreamSource::Url {
+            url: url.parse().unwrap(),
+        },
+        name: None,
+        description: None,
+        thumbnail: None,
+        subtitles: vec![],
+        behavior_hints: Default::default(),
+    };
+    let load = |r#type: &str, meta_id: &str, video_id: &str, stream: Stream| {
+        dispatch(Action::Load(ActionLoad::Player(Box::new(Selected {
+            stream,
+            stream_request: Some(create_request(STREAM_RESOURCE_NAME, r#type, video_id)),
+            meta_request: Some(create_request(META_RESOURCE_NAME, r#type, meta_id)),
+            subtitles_path: None,
+        }))))
+    };
+    let next = || {
+        let model = runtime.model().unwrap();
+        (
+            model
+                .player
+                .next_video
+                .as_ref()
+                .map(|video| (video.id.to_owned(), video.streams.to_owned())),
+            model.player.next_stream.to_owned(),
+            model.player.queue.len(),
+        )
+    };
+
+    dispatch(Action::Player(ActionPlayer::Enqueue(Box::new(QueueItem {
+        stream: Some(stream("https://movie_url")),
+        ..create_queue_item("movie", "tt999", create_video("tt999", None))
+    }))));
+    dispatch(Action::Player(ActionPlayer::Enqueue(Box::new(QueueItem {
+        stream: Some(stream("https://episode_url")),
+        ..create_queue_item("series", "tt123456", create_episode(3))
+    }))));
+
+    load(
+        "series",
+        "tt123456",
+        "tt123456:1:1",
+        stream("https://source_url"),
+    );
+    assert_eq!(
+        next(),
+        (
+            Some(("tt999".to_owned(), vec![stream("https://movie_url")])),
+            Some(stream("https://movie_url")),
+            2
+        ),
+        "The movie at the head of the queue is played next with its stream"
+    );
+
+    dispatch(Action::Player(ActionPlayer::Ended));
+    dispatch(Action::Player(ActionPlayer::NextVideo));
+    assert_eq!(
+        next().2,
+        1,
+        "The head of the queue is popped once when the video ends"
+    );
+    assert_eq!(
+        runtime
+            .model()
+            .unwrap()
+            .player
+            .next_queue_item
+            .as_ref()
+            .map(|item| item.meta_request.path.id.to_owned()),
+        Some("tt999".to_owned()),
+        "The popped item is kept until it's loaded"
+    );
+
+    load("movie", "tt999", "tt999", stream("https://movie_url"));
+    assert_eq!(
+        next(),
+        (
+            Some((
+                "tt123456:1:3".to_owned(),
+                vec![stream("https://episode_url")]
+            )),
+            Some(stream("https://episode_url")),
+            1
+        ),
+        "The queued episode is played after the movie"
+    );
+
+    dispatch(Action::Player(ActionPlayer::NextVideo));
+    load(
+        "series",
+        "tt123456",
+        "tt123456:1:3",
+        stream("https://episode_url"),
+    );
+    assert_eq!(
+        next(),
+        (None, None, 0),
+        "The queue is played through to the last episode of the series"
+    );
+    assert_eq!(
+        runtime.model().unwrap().player.next_queue_item,
+        None,
+        "No queued item is left"
+    );
+}
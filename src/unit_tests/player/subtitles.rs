@@ -0,0 +1,252 @@
+use crate::{
+    constants::{META_RESOURCE_NAME, STREAM_RESOURCE_NAME, SUBTITLES_RESOURCE_NAME},
+    models::{
+        ctx::Ctx,
+        player::{Player, Selected, VideoParams},
+    },
+    runtime::{
+        msg::{Action, ActionLoad, ActionPlayer},
+        EnvFutureExt, Runtime, RuntimeAction, TryEnvFuture,
+    },
+    types::{
+        addon::{
+            Descriptor, Manifest, ManifestResource, ResourcePath, ResourceRequest, ResourceResponse,
+        },
+        profile::{Profile, Settings},
+        resource::{MetaItem, MetaItemPreview, Stream, StreamSource, Subtitles},
+        streams::{StreamItemState, SubtitleTrack},
+    },
+    unit_tests::{default_fetch_handler, Request, TestEnv, FETCH_HANDLER},
+};
+use futures::future;
+use semver::Version;
+use std::any::Any;
+use stremio_derive::Model;
+use url::Url;
+
+fn create_subtitles(lang: &str, url: &str) -> Subtitles {
+    Subtitles {
+        lang: lang.to_owned(),
+        url: url.parse().unwrap(),
+    }
+}
+
+fn create_stream_state(subtitle_track: SubtitleTrack) -> StreamItemState {
+    StreamItemState {
+        subtitle_track: Some(subtitle_track),
+        subtitle_delay: None,
+        audio_track: None,
+        audio_delay: None,
+        playback_speed: None,
+        player_type: None,
+    }
+}
+
+#[test]
+fn aggregated_subtitles() {
+    #[derive(Model, Default, Clone, Debug)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+        player: Player,
+    }
+
+    fn fetch_handler(request: Request) -> TryEnvFuture<Box<dyn Any + Send>> {
+        match request {
+            Request { url, .. } if url == "https://transport_url/meta/movie/tt1.json" => {
+                future::ok(Box::new(ResourceResponse::Meta {
+                    meta: MetaItem {
+                        preview: MetaItemPreview {
+                            id: "tt1".to_owned(),
+                            r#type: "movie".to_owned(),
+                            ..Default::default()
+                        },
+                        videos: vec![],
+                    },
+                }) as Box<dyn Any + Send>)
+                .boxed_env()
+            }
+            Request { url, .. } if url.starts_with("https://addon.com/subtitles/movie/tt1/") => {
+                future::ok(Box::new(ResourceResponse::Subtitles {
+                    subtitles: vec![
+                        create_subtitles("en", "https://stream.com/eng.srt"),
+                        create_subtitles("fr", "https://addon.com/fre.srt"),
+                        create_subtitles("spa", "https://addon.com/spa.srt"),
+                        create_subtitles("ger", "https://addon.com/ger.srt"),
+                    ],
+                }) as Box<dyn Any + Send>)
+                .boxed_env()
+            }
+            _ => default_fetch_handler(request),
+        }
+    }
+
+    let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
+    *FETCH_HANDLER.write().unwrap() = Box::new(fetch_handler);
+
+    let addon = Descriptor {
+        manifest: Manifest {
+            id: "addon".to_owned(),
+            version: Version::new(0, 0, 1),
+            name: "addon".to_owned(),
+            contact_email: None,
+            description: None,
+            logo: None,
+            background: None,
+            types: vec!["movie".to_owned()],
+            resources: vec![ManifestResource::Short(SUBTITLES_RESOURCE_NAME.to_owned())],
+            id_prefixes: Some(vec!["tt".to_owned()]),
+            catalogs: vec![],
+            addon_catalogs: vec![],
+            behavior_hints: Default::default(),
+        },
+        transport_url: Url::parse("https://addon.com/manifest.json").unwrap(),
+        flags: Default::default(),
+    };
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx {
+                profile: Profile {
+                    addons: vec![addon],
+                    settings: Settings {
+                        subtitles_language: Some("fre".to_owned()),
+                        secondary_subtitles_language: Some("spa".to_owned()),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            player: Player::default(),
+        },
+        vec![],
+        1000,
+    );
+    let dispatch = |action: Action| {
+        TestEnv::run(|| {
+            runtime.dispatch(RuntimeAction {
+                field: None,
+                action,
+            })
+        })
+    };
+    let aggregated_ids = || {
+        runtime
+            .model()
+            .unwrap()
+            .player
+            .aggregated_subtitles
+            .iter()
+            .map(|subtitles| subtitles.id.to_owned())
+            .collect::<Vec<_>>()
+    };
+    let selected_id = || {
+        runtime
+            .model()
+            .unwrap()
+            .player
+            .selected_subtitles
+            .as_ref()
+            .map(|subtitles| subtitles.id.to_owned())
+    };
+    let request = |resource: &str| ResourceRequest {
+        base: "https://transport_url/manifest.json".parse().unwrap(),
+        path: ResourcePath {
+            resource: resource.to_owned(),
+            r#type: "movie".to_owned(),
+            id: "tt1".to_owned(),
+            extra: vec![],
+        },
+    };
+
+    dispatch(Action::Load(ActionLoad::Player(Box::new(Selected {
+        stream: Stream {
+            source: StreamSource::Url {
+                url: "https://source_url".parse().unwrap(),
+            },
+            name: None,
+            description: None,
+            thumbnail: None,
+            subtitles: vec![create_subtitles("eng", "https://stream.com/eng.srt")],
+            behavior_hints: Default::default(),
+        },
+        stream_request: Some(request(STREAM_RESOURCE_NAME)),
+        meta_request: Some(request(META_RESOURCE_NAME)),
+        subtitles_path: Some(ResourcePath {
+            resource: SUBTITLES_RESOURCE_NAME.to_owned(),
+            r#type: "movie".to_owned(),
+            id: "tt1".to_owned(),
+            extra: vec![],
+        }),
+    }))));
+    assert_eq!(
+        aggregated_ids(),
+        vec!["stream_0"],
+        "Stream subtitles are available before the video params"
+    );
+    assert_eq!(
+        selected_id(),
+        None,
+        "No subtitles in the preferred languages"
+    );
+
+    dispatch(Action::Player(ActionPlayer::VideoParamsChanged {
+        video_params: Some(VideoParams {
+            hash: Some("hash".to_owned()),
+            size: None,
+            filename: None,
+            timing_hints: vec![],
+        }),
+    }));
+    assert_eq!(
+        aggregated_ids(),
+        vec![
+            "https://addon.com/manifest.json_1",
+            "https://addon.com/manifest.json_2",
+            "stream_0",
+            "https://addon.com/manifest.json_3",
+        ],
+        "Duplicates are removed and the preferred languages are ranked first"
+    );
+    assert_eq!(
+        selected_id(),
+        Some("https://addon.com/manifest.json_1".to_owned()),
+        "Subtitles in the primary language are selected"
+    );
+
+    dispatch(Action::Player(ActionPlayer::StreamStateChanged {
+        state: create_stream_state(SubtitleTrack {
+            id: "https://addon.com/manifest.json_3".to_owned(),
+            embedded: false,
+            language: Some("ger".to_owned()),
+        }),
+    }));
+    assert_eq!(
+        aggregated_ids().first().map(String::as_str),
+        Some("https://addon.com/manifest.json_3"),
+        "The previously selected track is ranked first"
+    );
+    assert_eq!(
+        selected_id(),
+        Some("https://addon.com/manifest.json_3".to_owned()),
+        "The previously selected track is selected"
+    );
+
+    dispatch(Action::Player(ActionPlayer::StreamStateChanged {
+        state: create_stream_state(SubtitleTrack {
+            id: "1".to_owned(),
+            embedded: true,
+            language: Some("eng".to_owned()),
+        }),
+    }));
+    assert_eq!(
+        aggregated_ids().first().map(String::as_str),
+        Some("stream_0"),
+        "Subtitles in the language of the embedded track are ranked first"
+    );
+    assert_eq!(
+        selected_id(),
+        None,
+        "Nothing is recommended over an embedded track"
+    );
+}
@@ -3,7 +3,7 @@ use crate::types::addon::{
 };
 use crate::types::api::{APIError, AuthRequest};
 use crate::types::library::LibraryItemState;
-use crate::types::profile::{Auth, AuthKey, GDPRConsent, Settings, User};
+use crate::types::profile::{Auth, AuthKey, GDPRConsent, ParentalControls, Settings, User};
 use crate::types::resource::{
     MetaItem, MetaItemBehaviorHints, PosterShape, SeriesInfo, StreamBehaviorHints, StreamSource,
     Subtitles,
@@ -182,7 +182,7 @@ impl DefaultTokens for ManifestBehaviorHints {
         vec![
             Token::Struct {
                 name: "ManifestBehaviorHints",
-                len: 4,
+                len: 5,
             },
             Token::Str("adult"),
             Token::Bool(false),
@@ -192,6 +192,8 @@ impl DefaultTokens for ManifestBehaviorHints {
             Token::Bool(false),
             Token::Str("configurationRequired"),
             Token::Bool(false),
+            Token::Str("searchableFeed"),
+            Token::None,
             Token::StructEnd,
         ]
     }
@@ -438,6 +440,29 @@ impl DefaultTokens for Settings {
     }
 }
 
+impl DefaultTokens for ParentalControls {
+    fn default_tokens() -> Vec<Token> {
+        vec![
+            Token::Struct {
+                name: "ParentalControls",
+                len: 5,
+            },
+            Token::Str("maxCertification"),
+            Token::None,
+            Token::Str("blockedGenres"),
+            Token::Seq { len: Some(0) },
+            Token::SeqEnd,
+            Token::Str("blockUnrated"),
+            Token::Bool(false),
+            Token::Str("pinHash"),
+            Token::None,
+            Token::Str("pinSalt"),
+            Token::None,
+            Token::StructEnd,
+        ]
+    }
+}
+
 impl DefaultTokens for AuthRequest {
     fn default_tokens() -> Vec<Token> {
         vec![
@@ -21,6 +21,8 @@ fn library_item() {
                 mtime: Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap(),
                 state: LibraryItemState::default(),
                 behavior_hints: MetaItemBehaviorHints::default(),
+                genres: vec![],
+                certification: None,
             },
             LibraryItem {
                 id: "id".to_owned(),
@@ -34,6 +36,8 @@ fn library_item() {
                 mtime: Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap(),
                 state: LibraryItemState::default(),
                 behavior_hints: MetaItemBehaviorHints::default(),
+                genres: vec![],
+                certification: None,
             },
         ]
         .readable(),
@@ -120,6 +124,8 @@ fn library_item() {
                 mtime: Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap(),
                 state: LibraryItemState::default(),
                 behavior_hints: MetaItemBehaviorHints::default(),
+                genres: vec![],
+                certification: None,
             },
             LibraryItem {
                 id: "id".to_owned(),
@@ -133,6 +139,8 @@ fn library_item() {
                 mtime: Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap(),
                 state: LibraryItemState::default(),
                 behavior_hints: MetaItemBehaviorHints::default(),
+                genres: vec![],
+                certification: None,
             },
             LibraryItem {
                 id: "id".to_owned(),
@@ -146,6 +154,8 @@ fn library_item() {
                 mtime: Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap(),
                 state: LibraryItemState::default(),
                 behavior_hints: MetaItemBehaviorHints::default(),
+                genres: vec![],
+                certification: None,
             },
             LibraryItem {
                 id: "id".to_owned(),
@@ -159,6 +169,8 @@ fn library_item() {
                 mtime: Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap(),
                 state: LibraryItemState::default(),
                 behavior_hints: MetaItemBehaviorHints::default(),
+                genres: vec![],
+                certification: None,
             },
             LibraryItem {
                 id: "id".to_owned(),
@@ -172,6 +184,8 @@ fn library_item() {
                 mtime: Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap(),
                 state: LibraryItemState::default(),
                 behavior_hints: MetaItemBehaviorHints::default(),
+                genres: vec![],
+                certification: None,
             },
         ]
         .readable(),
@@ -1,6 +1,6 @@
-use crate::types::library::LibraryItemState;
+use crate::types::library::{LibraryItemState, ResumePoint, StoredValue, WatchStatus};
 use chrono::{TimeZone, Utc};
-use serde_test::{assert_de_tokens, assert_tokens, Token};
+use serde_test::{assert_de_tokens, assert_ser_tokens, assert_tokens, Token};
 
 #[test]
 fn library_item_state() {
@@ -17,6 +17,10 @@ fn library_item_state() {
                 video_id: Some("tt2934286:1:5".to_owned()),
                 watched: Some("tt2934286:1:5:5:eJyTZwAAAEAAIA==".parse().unwrap()),
                 no_notif: true,
+                watch_status: None,
+                rating: None,
+                notes: None,
+                resume_points: Default::default(),
             },
             LibraryItemState {
                 last_watched: None,
@@ -29,6 +33,10 @@ fn library_item_state() {
                 video_id: None,
                 watched: None,
                 no_notif: false,
+                watch_status: None,
+                rating: None,
+                notes: None,
+                resume_points: Default::default(),
             },
         ],
         &[
@@ -102,6 +110,10 @@ fn library_item_state() {
                 video_id: None,
                 watched: None,
                 no_notif: false,
+                watch_status: None,
+                rating: None,
+                notes: None,
+                resume_points: Default::default(),
             },
             LibraryItemState {
                 last_watched: None,
@@ -114,6 +126,10 @@ fn library_item_state() {
                 video_id: None,
                 watched: None,
                 no_notif: false,
+                watch_status: None,
+                rating: None,
+                notes: None,
+                resume_points: Default::default(),
             },
         ],
         &[
@@ -169,3 +185,220 @@ fn library_item_state() {
         ],
     );
 }
+
+#[test]
+fn library_item_state_watch_status() {
+    assert_ser_tokens(
+        &LibraryItemState {
+            watch_status: Some(StoredValue::Known(WatchStatus::PlanToWatch)),
+            ..Default::default()
+        },
+        &[
+            Token::Struct {
+                name: "LibraryItemState",
+                len: 11,
+            },
+            Token::Str("lastWatched"),
+            Token::None,
+            Token::Str("timeWatched"),
+            Token::U64(0),
+            Token::Str("timeOffset"),
+            Token::U64(0),
+            Token::Str("overallTimeWatched"),
+            Token::U64(0),
+            Token::Str("timesWatched"),
+            Token::U32(0),
+            Token::Str("flaggedWatched"),
+            Token::U32(0),
+            Token::Str("duration"),
+            Token::U64(0),
+            Token::Str("video_id"),
+            Token::None,
+            Token::Str("watched"),
+            Token::None,
+            Token::Str("noNotif"),
+            Token::Bool(false),
+            Token::Str("watchStatus"),
+            Token::Some,
+            Token::UnitVariant {
+                name: "WatchStatus",
+                variant: "planToWatch",
+            },
+            Token::StructEnd,
+        ],
+    );
+    assert_de_tokens(
+        &vec![
+            LibraryItemState {
+                watch_status: Some(StoredValue::Known(WatchStatus::Dropped)),
+                ..Default::default()
+            },
+            LibraryItemState {
+                watch_status: Some(StoredValue::Unknown("rewatching".into())),
+                ..Default::default()
+            },
+        ],
+        &[
+            Token::Seq { len: Some(2) },
+            Token::Struct {
+                name: "LibraryItemState",
+                len: 8,
+            },
+            Token::Str("timeWatched"),
+            Token::U64(0),
+            Token::Str("timeOffset"),
+            Token::U64(0),
+            Token::Str("overallTimeWatched"),
+            Token::U64(0),
+            Token::Str("timesWatched"),
+            Token::U32(0),
+            Token::Str("flaggedWatched"),
+            Token::U32(0),
+            Token::Str("duration"),
+            Token::U64(0),
+            Token::Str("watchStatus"),
+            Token::Some,
+            Token::Str("dropped"),
+            Token::StructEnd,
+            Token::Struct {
+                name: "LibraryItemState",
+                len: 8,
+            },
+            Token::Str("timeWatched"),
+            Token::U64(0),
+            Token::Str("timeOffset"),
+            Token::U64(0),
+            Token::Str("overallTimeWatched"),
+            Token::U64(0),
+            Token::Str("timesWatched"),
+            Token::U32(0),
+            Token::Str("flaggedWatched"),
+            Token::U32(0),
+            Token::Str("duration"),
+            Token::U64(0),
+            Token::Str("watchStatus"),
+            Token::Some,
+            Token::Str("rewatching"),
+            Token::StructEnd,
+            Token::SeqEnd,
+        ],
+    );
+}
+
+#[test]
+fn library_item_state_resume_points() {
+    assert_tokens(
+        &LibraryItemState {
+            resume_points: [(
+                "tt2934286:1:5".to_owned(),
+                StoredValue::Known(ResumePoint {
+                    time_offset: 60_000,
+                    duration: 1_200_000,
+                    last_watched: Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap(),
+                }),
+            )]
+            .into(),
+            ..Default::default()
+        },
+        &[
+            Token::Struct {
+                name: "LibraryItemState",
+                len: 11,
+            },
+            Token::Str("lastWatched"),
+            Token::None,
+            Token::Str("timeWatched"),
+            Token::U64(0),
+            Token::Str("timeOffset"),
+            Token::U64(0),
+            Token::Str("overallTimeWatched"),
+            Token::U64(0),
+            Token::Str("timesWatched"),
+            Token::U32(0),
+            Token::Str("flaggedWatched"),
+            Token::U32(0),
+            Token::Str("duration"),
+            Token::U64(0),
+            Token::Str("video_id"),
+            Token::None,
+            Token::Str("watched"),
+            Token::None,
+            Token::Str("noNotif"),
+            Token::Bool(false),
+            Token::Str("resumePoints"),
+            Token::Map { len: Some(1) },
+            Token::Str("tt2934286:1:5"),
+            Token::Struct {
+                name: "ResumePoint",
+                len: 3,
+            },
+            Token::Str("timeOffset"),
+            Token::U64(60_000),
+            Token::Str("duration"),
+            Token::U64(1_200_000),
+            Token::Str("lastWatched"),
+            Token::Str("2020-01-01T00:00:00Z"),
+            Token::StructEnd,
+            Token::MapEnd,
+            Token::StructEnd,
+        ],
+    );
+}
+
+#[test]
+fn library_item_state_unknown_values() {
+    assert_tokens(
+        &LibraryItemState {
+            watch_status: Some(StoredValue::Unknown("rewatching".into())),
+            rating: Some(StoredValue::Unknown("8".into())),
+            notes: Some(StoredValue::Unknown(true.into())),
+            resume_points: [(
+                "tt2934286:1:5".to_owned(),
+                StoredValue::Unknown("01:00".into()),
+            )]
+            .into(),
+            ..Default::default()
+        },
+        &[
+            Token::Struct {
+                name: "LibraryItemState",
+                len: 14,
+            },
+            Token::Str("lastWatched"),
+            Token::None,
+            Token::Str("timeWatched"),
+            Token::U64(0),
+            Token::Str("timeOffset"),
+            Token::U64(0),
+            Token::Str("overallTimeWatched"),
+            Token::U64(0),
+            Token::Str("timesWatched"),
+            Token::U32(0),
+            Token::Str("flaggedWatched"),
+            Token::U32(0),
+            Token::Str("duration"),
+            Token::U64(0),
+            Token::Str("video_id"),
+            Token::None,
+            Token::Str("watched"),
+            Token::None,
+            Token::Str("noNotif"),
+            Token::Bool(false),
+            Token::Str("watchStatus"),
+            Token::Some,
+            Token::Str("rewatching"),
+            Token::Str("rating"),
+            Token::Some,
+            Token::Str("8"),
+            Token::Str("notes"),
+            Token::Some,
+            Token::Bool(true),
+            Token::Str("resumePoints"),
+            Token::Map { len: Some(1) },
+            Token::Str("tt2934286:1:5"),
+            Token::Str("01:00"),
+            Token::MapEnd,
+            Token::StructEnd,
+        ],
+    );
+}
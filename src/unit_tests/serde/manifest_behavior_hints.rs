@@ -9,11 +9,12 @@ fn manifest_behavior_hints() {
             p2p: true,
             configurable: true,
             configuration_required: true,
+            searchable_feed: Some("feed.json".to_owned()),
         },
         &[
             Token::Struct {
                 name: "ManifestBehaviorHints",
-                len: 4,
+                len: 5,
             },
             Token::Str("adult"),
             Token::Bool(true),
@@ -23,6 +24,9 @@ fn manifest_behavior_hints() {
             Token::Bool(true),
             Token::Str("configurationRequired"),
             Token::Bool(true),
+            Token::Str("searchableFeed"),
+            Token::Some,
+            Token::Str("feed.json"),
             Token::StructEnd,
         ],
     );
@@ -32,6 +36,7 @@ fn manifest_behavior_hints() {
             p2p: false,
             configurable: false,
             configuration_required: false,
+            searchable_feed: None,
         },
         &[
             Token::Struct {
@@ -1,4 +1,4 @@
-use crate::types::profile::{Auth, Profile, Settings};
+use crate::types::profile::{Auth, ParentalControls, Profile, Settings};
 use crate::unit_tests::serde::default_tokens_ext::DefaultTokens;
 use serde_test::{assert_de_tokens, assert_tokens, Configure, Token};
 
@@ -11,12 +11,16 @@ fn profile() {
                 addons: vec![],
                 addons_locked: false,
                 settings: Settings::default(),
+                parental_controls: ParentalControls::default(),
+                smart_lists: vec![],
             },
             Profile {
                 auth: None,
                 addons: vec![],
                 addons_locked: false,
                 settings: Settings::default(),
+                parental_controls: ParentalControls::default(),
+                smart_lists: vec![],
             },
         ]
         .readable(),
@@ -25,7 +29,7 @@ fn profile() {
                 Token::Seq { len: Some(2) },
                 Token::Struct {
                     name: "Profile",
-                    len: 4,
+                    len: 6,
                 },
                 Token::Str("auth"),
                 Token::Some,
@@ -40,11 +44,16 @@ fn profile() {
                 Token::Str("settings"),
             ],
             Settings::default_tokens(),
+            vec![Token::Str("parentalControls")],
+            ParentalControls::default_tokens(),
             vec![
+                Token::Str("smartLists"),
+                Token::Seq { len: Some(0) },
+                Token::SeqEnd,
                 Token::StructEnd,
                 Token::Struct {
                     name: "Profile",
-                    len: 4,
+                    len: 6,
                 },
                 Token::Str("auth"),
                 Token::None,
@@ -56,7 +65,15 @@ fn profile() {
                 Token::Str("settings"),
             ],
             Settings::default_tokens(),
-            vec![Token::StructEnd, Token::SeqEnd],
+            vec![Token::Str("parentalControls")],
+            ParentalControls::default_tokens(),
+            vec![
+                Token::Str("smartLists"),
+                Token::Seq { len: Some(0) },
+                Token::SeqEnd,
+                Token::StructEnd,
+                Token::SeqEnd,
+            ],
         ]
         .concat(),
     );
@@ -66,6 +83,8 @@ fn profile() {
             addons: vec![],
             addons_locked: false,
             settings: Settings::default(),
+            parental_controls: ParentalControls::default(),
+            smart_lists: vec![],
         }
         .readable(),
         &[
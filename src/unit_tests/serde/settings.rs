@@ -1,6 +1,10 @@
-use crate::types::profile::{FrameRateMatchingStrategy, Settings};
+use crate::types::profile::{
+    FrameRateMatchingStrategy, NotificationRule, NotificationSettings, QuietHours, Settings,
+};
+use crate::types::scheduler::SchedulerJob;
 use chrono::{TimeZone, Utc};
 use serde_test::{assert_de_tokens, assert_tokens, Token};
+use std::collections::{BTreeMap, HashMap};
 use url::Url;
 
 #[test]
@@ -36,11 +40,22 @@ fn settings() {
             streaming_server_warning_dismissed: Some(
                 Utc.with_ymd_and_hms(2021, 1, 1, 0, 0, 0).unwrap(),
             ),
+            notifications: NotificationSettings {
+                poll_interval: 12,
+                quiet_hours: Some(QuietHours {
+                    start: 22,
+                    end: 7,
+                    utc_offset: 120,
+                }),
+                default_rule: NotificationRule::AfterLastWatched,
+                rules: HashMap::from([("tt1".to_owned(), NotificationRule::NewSeasonsOnly)]),
+            },
+            scheduler_job_intervals: BTreeMap::from([(SchedulerJob::GetEvents, None)]),
         },
         &[
             Token::Struct {
                 name: "Settings",
-                len: 27,
+                len: 29,
             },
             Token::Str("interfaceLanguage"),
             Token::Str("interface_language"),
@@ -105,6 +120,48 @@ fn settings() {
             Token::Str("streamingServerWarningDismissed"),
             Token::Some,
             Token::Str("2021-01-01T00:00:00Z"),
+            Token::Str("notifications"),
+            Token::Struct {
+                name: "NotificationSettings",
+                len: 4,
+            },
+            Token::Str("pollInterval"),
+            Token::U32(12),
+            Token::Str("quietHours"),
+            Token::Some,
+            Token::Struct {
+                name: "QuietHours",
+                len: 3,
+            },
+            Token::Str("start"),
+            Token::U32(22),
+            Token::Str("end"),
+            Token::U32(7),
+            Token::Str("utcOffset"),
+            Token::I32(120),
+            Token::StructEnd,
+            Token::Str("defaultRule"),
+            Token::UnitVariant {
+                name: "NotificationRule",
+                variant: "AfterLastWatched",
+            },
+            Token::Str("rules"),
+            Token::Map { len: Some(1) },
+            Token::Str("tt1"),
+            Token::UnitVariant {
+                name: "NotificationRule",
+                variant: "NewSeasonsOnly",
+            },
+            Token::MapEnd,
+            Token::StructEnd,
+            Token::Str("schedulerJobIntervals"),
+            Token::Map { len: Some(1) },
+            Token::UnitVariant {
+                name: "SchedulerJob",
+                variant: "GetEvents",
+            },
+            Token::None,
+            Token::MapEnd,
             Token::StructEnd,
         ],
     );
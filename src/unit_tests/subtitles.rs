@@ -0,0 +1,225 @@
+use crate::types::subtitles::{
+    decode_subtitles, CueTiming, ParsedSubtitles, SubtitlesCue, SubtitlesError, SubtitlesFormat,
+    SubtitlesSync,
+};
+
+const SRT: &str = "1\r\n00:00:01,000 --> 00:00:02,500\r\n<i>Hello</i>\r\n<font color=\"red\">world</font>\r\n\r\n2\r\n00:00:03,000 --> 00:00:04,000\r\nBye\r\n";
+
+/// Irregular, non overlapping timings like the ones of speech
+fn reference_timings(count: u64) -> Vec<CueTiming> {
+    (0..count)
+        .map(|index| {
+            let start = index * 6000 + index * index * 37 % 2000;
+            CueTiming {
+                start,
+                end: start + 1500 + index * 13 % 1000,
+            }
+        })
+        .collect()
+}
+
+fn cue(start: u64, end: u64, text: &str) -> SubtitlesCue {
+    SubtitlesCue {
+        start,
+        end,
+        text: text.to_owned(),
+    }
+}
+
+#[test]
+fn parse_srt_to_webvtt() {
+    let subtitles = ParsedSubtitles::parse(SRT.as_bytes()).expect("Should parse SRT");
+    assert_eq!(subtitles.format, SubtitlesFormat::Srt);
+    assert_eq!(
+        subtitles.cues,
+        vec![
+            cue(1000, 2500, "<i>Hello</i>\nworld"),
+            cue(3000, 4000, "Bye")
+        ],
+        "Unsupported tags are removed"
+    );
+    assert_eq!(
+        subtitles.to_format(SubtitlesFormat::WebVtt),
+        "WEBVTT\n\n00:00:01.000 --> 00:00:02.500\n<i>Hello</i>\nworld\n\n00:00:03.000 --> 00:00:04.000\nBye\n\n"
+    );
+    assert_eq!(
+        subtitles.active_cues(3500).collect::<Vec<_>>(),
+        vec![&cue(3000, 4000, "Bye")]
+    );
+}
+
+#[test]
+fn parse_srt_whitespace_separators() {
+    let srt =
+        "1\n00:00:01,000 --> 00:00:02,000\nHello\n \t\n2\n00:00:03,000 --> 00:00:04,000\nBye\n";
+    let subtitles = ParsedSubtitles::parse_str(srt).expect("Should parse SRT");
+    assert_eq!(
+        subtitles.cues,
+        vec![cue(1000, 2000, "Hello"), cue(3000, 4000, "Bye")],
+        "Whitespace-only lines separate the cues"
+    );
+}
+
+#[test]
+fn parse_webvtt() {
+    let vtt = "WEBVTT\n\nNOTE a comment\n\nintro\n01:02.5 --> 01:04.000 align:start\n<v Roger>Hi\n";
+    let subtitles = ParsedSubtitles::parse_str(vtt).expect("Should parse WebVTT");
+    assert_eq!(subtitles.format, SubtitlesFormat::WebVtt);
+    assert_eq!(subtitles.cues, vec![cue(62_500, 64_000, "Hi")]);
+}
+
+#[test]
+fn parse_ass_to_srt() {
+    let ass = "[Script Info]\nTitle: test\n\n[Events]\nFormat: Layer, Start, End, Style, Text\nDialogue: 0,0:00:05.20,0:00:06.00,Default,{\\an8}Second, with comma\nDialogue: 0,0:00:01.00,0:00:02.00,Default,First\\Nline\n";
+    let subtitles = ParsedSubtitles::parse_str(ass).expect("Should parse ASS");
+    assert_eq!(subtitles.format, SubtitlesFormat::Ass);
+    assert_eq!(
+        subtitles.cues,
+        vec![
+            cue(1000, 2000, "First\nline"),
+            cue(5200, 6000, "Second, with comma")
+        ],
+        "Cues are sorted and override tags are removed"
+    );
+    assert_eq!(
+        subtitles.to_format(SubtitlesFormat::Srt),
+        "1\n00:00:01,000 --> 00:00:02,000\nFirst\nline\n\n2\n00:00:05,200 --> 00:00:06,000\nSecond, with comma\n\n"
+    );
+    assert!(subtitles
+        .to_format(SubtitlesFormat::Ass)
+        .ends_with("Dialogue: 0,0:00:01.00,0:00:02.00,Default,,0,0,0,,First\\Nline\nDialogue: 0,0:00:05.20,0:00:06.00,Default,,0,0,0,,Second, with comma\n"));
+}
+
+#[test]
+fn subtitles_delay() {
+    let subtitles = ParsedSubtitles::parse(SRT.as_bytes()).expect("Should parse SRT");
+    assert_eq!(
+        subtitles.clone().with_delay(500).cues,
+        vec![
+            cue(1500, 3000, "<i>Hello</i>\nworld"),
+            cue(3500, 4500, "Bye")
+        ]
+    );
+    assert_eq!(
+        subtitles.with_delay(-3000).cues,
+        vec![cue(0, 1000, "Bye")],
+        "Cues ending before the start are dropped"
+    );
+}
+
+#[test]
+fn subtitles_out_of_range_timestamps() {
+    let subtitles = ParsedSubtitles::parse_str(
+        "1\n99999999999999999:00:00,000 --> 99999999999999999:00:01,000\nHuge\n\n2\n00:00:01,000 --> 00:00:02,000\nBye\n",
+    )
+    .expect("Should parse SRT");
+    assert_eq!(
+        subtitles.cues,
+        vec![cue(1000, 2000, "Bye")],
+        "Cues with out of range timestamps are dropped"
+    );
+    assert_eq!(
+        subtitles.clone().with_delay(i64::MAX).cues,
+        vec![cue(i64::MAX as u64 + 1000, i64::MAX as u64 + 2000, "Bye")],
+        "Cues are shifted without overflowing"
+    );
+    assert_eq!(
+        subtitles.with_delay(i64::MIN).cues,
+        vec![],
+        "Cues shifted before the start are dropped"
+    );
+}
+
+#[test]
+fn subtitles_errors() {
+    assert_eq!(
+        ParsedSubtitles::parse_str("not subtitles"),
+        Err(SubtitlesError::UnknownFormat)
+    );
+    assert_eq!(
+        ParsedSubtitles::parse_str("WEBVTT\n\n"),
+        Err(SubtitlesError::Empty)
+    );
+}
+
+#[test]
+fn subtitles_encoding() {
+    // "Привет мир" in CP1251
+    let cp1251 = [0xCF, 0xF0, 0xE8, 0xE2, 0xE5, 0xF2, 0x20, 0xEC, 0xE8, 0xF0];
+    assert_eq!(decode_subtitles(&cp1251), "Привет мир");
+    // "It’s a café…" in CP1252, the quote and the ellipsis are not in latin1
+    let cp1252 = [
+        0x49, 0x74, 0x92, 0x73, 0x20, 0x61, 0x20, 0x63, 0x61, 0x66, 0xE9, 0x85,
+    ];
+    assert_eq!(decode_subtitles(&cp1252), "It’s a café…");
+    assert_eq!(
+        decode_subtitles("\u{FEFF}Ünïcode".as_bytes()),
+        "Ünïcode",
+        "UTF-8 BOM is removed"
+    );
+    assert_eq!(
+        decode_subtitles(&[0xFF, 0xFE, 0x48, 0x00, 0x69, 0x00]),
+        "Hi",
+        "UTF-16 with BOM is decoded"
+    );
+}
+
+#[test]
+fn subtitles_sync_delay() {
+    let reference = reference_timings(100);
+    // the subtitles are shown too early
+    let cues = reference
+        .iter()
+        .skip(3)
+        .map(|timing| CueTiming {
+            start: timing.start - 2300,
+            end: timing.end - 2300,
+        })
+        .collect::<Vec<_>>();
+    let sync = SubtitlesSync::estimate(&cues, &reference).expect("Should sync");
+    assert_eq!(sync.delay, 2300);
+    assert_eq!(sync.drift, None);
+    assert_eq!(sync.matched_cues, 97);
+}
+
+#[test]
+fn subtitles_sync_drift() {
+    let reference = reference_timings(100);
+    // subtitles timed for another frame rate, also shown too late
+    let rate = 23.976 / 25.0;
+    let cues = reference
+        .iter()
+        .map(|timing| CueTiming {
+            start: (timing.start as f64 * rate).round() as u64 + 1000,
+            end: (timing.end as f64 * rate).round() as u64 + 1000,
+        })
+        .collect::<Vec<_>>();
+    let sync = SubtitlesSync::estimate(&cues, &reference).expect("Should sync");
+    let drift = sync.drift.expect("Should detect the drift");
+    assert!((drift.rate - 25.0 / 23.976).abs() < 0.0001);
+    assert!((drift.offset + 1043).abs() <= 5);
+    assert_eq!(sync.matched_cues, 100);
+    let subtitles = ParsedSubtitles {
+        format: SubtitlesFormat::Srt,
+        cues: cues
+            .iter()
+            .map(|timing| cue(timing.start, timing.end, "text"))
+            .collect(),
+    }
+    .with_drift(&drift);
+    assert!(subtitles
+        .cues
+        .iter()
+        .zip(&reference)
+        .all(|(cue, timing)| cue.start.abs_diff(timing.start) <= 5));
+}
+
+#[test]
+fn subtitles_sync_too_few_cues() {
+    let reference = reference_timings(4);
+    assert_eq!(
+        SubtitlesSync::estimate(&reference, &reference),
+        None,
+        "Not enough cues to match"
+    );
+}
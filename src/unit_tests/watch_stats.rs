@@ -0,0 +1,187 @@
+use crate::models::ctx::Ctx;
+use crate::models::watch_stats::{
+    GenreStats, MonthStats, Selected, Streaks, TypeStats, WatchStats,
+};
+use crate::runtime::msg::{Action, ActionLoad};
+use crate::runtime::{Runtime, RuntimeAction};
+use crate::types::library::{LibraryBucket, LibraryItem, LibraryItemState};
+use crate::types::resource::StreamSource;
+use crate::types::watch_history::{WatchHistoryBucket, WatchHistoryItem};
+use crate::unit_tests::{TestEnv, NOW};
+use chrono::{TimeZone, Utc};
+use stremio_derive::Model;
+
+fn library_item(id: &str, r#type: &str, last_watched: (i32, u32, u32), time: u64) -> LibraryItem {
+    let (year, month, day) = last_watched;
+    LibraryItem {
+        id: id.to_owned(),
+        removed: false,
+        temp: false,
+        ctime: Some(Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap()),
+        mtime: Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap(),
+        state: LibraryItemState {
+            last_watched: Some(Utc.with_ymd_and_hms(year, month, day, 12, 0, 0).unwrap()),
+            time_watched: time,
+            overall_time_watched: time,
+            times_watched: 1,
+            ..LibraryItemState::default()
+        },
+        name: id.to_owned(),
+        r#type: r#type.to_owned(),
+        poster: None,
+        poster_shape: Default::default(),
+        behavior_hints: Default::default(),
+        genres: vec![],
+        certification: None,
+    }
+}
+
+#[test]
+fn watch_stats_load_action() {
+    #[derive(Model, Clone, Default)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+        watch_stats: WatchStats,
+    }
+    let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
+    *NOW.write().unwrap() = Utc.with_ymd_and_hms(2021, 3, 3, 0, 0, 0).unwrap();
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx {
+                library: LibraryBucket::new(
+                    None,
+                    vec![
+                        LibraryItem {
+                            genres: vec!["Comedy".to_owned()],
+                            ..library_item("movie", "movie", (2020, 12, 31), 100)
+                        },
+                        LibraryItem {
+                            genres: vec!["Drama".to_owned(), "Comedy".to_owned()],
+                            ..library_item("series_1", "series", (2021, 3, 1), 300)
+                        },
+                        LibraryItem {
+                            genres: vec!["Drama".to_owned()],
+                            ..library_item("series_2", "series", (2021, 3, 2), 200)
+                        },
+                        library_item("series_3", "series", (2021, 2, 1), 50),
+                    ],
+                ),
+                // a previous play of the series, before it was last watched
+                watch_history: WatchHistoryBucket {
+                    items: vec![WatchHistoryItem {
+                        meta_id: "series_1".to_owned(),
+                        video_id: "series_1:1:1".to_owned(),
+                        r#type: "series".to_owned(),
+                        name: "series_1".to_owned(),
+                        stream_source: StreamSource::Url {
+                            url: "https://source_url".parse().unwrap(),
+                        },
+                        start: Utc.with_ymd_and_hms(2021, 2, 28, 12, 0, 0).unwrap(),
+                        end: Utc.with_ymd_and_hms(2021, 2, 28, 13, 0, 0).unwrap(),
+                        time_offset: 1000,
+                        duration: 10000,
+                    }],
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            watch_stats: Default::default(),
+        },
+        vec![],
+        1000,
+    );
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Load(ActionLoad::WatchStats(Selected { year: None })),
+        })
+    });
+    let model = runtime.model().unwrap();
+    let stats = model.watch_stats.stats.as_ref().unwrap();
+    assert_eq!(stats.items, 4, "All the played items are counted");
+    assert_eq!(
+        stats.overall_time_watched, 650,
+        "The time watched is summed"
+    );
+    assert_eq!(
+        stats.types,
+        vec![
+            TypeStats {
+                r#type: "series".to_owned(),
+                items: 3,
+                time_watched: 550,
+            },
+            TypeStats {
+                r#type: "movie".to_owned(),
+                items: 1,
+                time_watched: 100,
+            },
+        ],
+        "The types are sorted by the time watched"
+    );
+    assert_eq!(
+        stats.genres,
+        vec![
+            GenreStats {
+                genre: "Drama".to_owned(),
+                items: 2,
+                time_watched: 500,
+            },
+            GenreStats {
+                genre: "Comedy".to_owned(),
+                items: 2,
+                time_watched: 400,
+            },
+        ],
+        "The items are counted in each of their genres"
+    );
+    assert_eq!(
+        stats.streaks,
+        Streaks {
+            current: 3,
+            longest: 3
+        },
+        "The streaks are counted from the plays and the last watched days"
+    );
+    assert_eq!(
+        stats
+            .months
+            .iter()
+            .map(|month| (month.year, month.month, month.items))
+            .collect::<Vec<_>>(),
+        vec![(2020, 12, 1), (2021, 2, 1), (2021, 3, 2)],
+        "The months are sorted from the oldest"
+    );
+    assert_eq!(
+        stats
+            .most_binged
+            .iter()
+            .map(|library_item| library_item.id.as_str())
+            .collect::<Vec<_>>(),
+        vec!["series_1", "series_2", "series_3"],
+        "Only series are in the most binged"
+    );
+    let year_in_review = model.watch_stats.year_in_review.as_ref().unwrap();
+    assert_eq!(year_in_review.year, 2021, "The current year is reviewed");
+    assert_eq!(
+        year_in_review.items, 3,
+        "Only the items of the year are reviewed"
+    );
+    assert_eq!(year_in_review.months.len(), 12, "All the months are listed");
+    assert_eq!(
+        year_in_review.months[2],
+        MonthStats {
+            year: 2021,
+            month: 3,
+            items: 2,
+            time_watched: 500,
+        },
+        "The items are grouped by month"
+    );
+    assert_eq!(
+        year_in_review.busiest_month,
+        Some(3),
+        "The month with the most time watched is the busiest"
+    );
+}
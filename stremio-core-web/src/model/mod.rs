@@ -1,5 +1,8 @@
 pub mod deep_links_ext;
 
+mod serialize_calendar;
+use serialize_calendar::*;
+
 mod serialize_catalogs_with_extra;
 use serialize_catalogs_with_extra::*;
 
@@ -12,6 +15,12 @@ use serialize_ctx::*;
 mod serialize_discover;
 use serialize_discover::*;
 
+mod serialize_for_you;
+use serialize_for_you::*;
+
+mod serialize_global_search;
+use serialize_global_search::*;
+
 mod serialize_installed_addons;
 use serialize_installed_addons::*;
 
@@ -27,12 +36,21 @@ use serialize_meta_details::*;
 mod serialize_player;
 use serialize_player::*;
 
+mod serialize_playlists;
+use serialize_playlists::*;
+
 mod serialize_remote_addons;
 use serialize_remote_addons::*;
 
 mod serialize_streaming_server;
 use serialize_streaming_server::*;
 
+mod serialize_watch_history;
+use serialize_watch_history::*;
+
+mod serialize_watch_stats;
+use serialize_watch_stats::*;
+
 mod serialize_data_export;
 use serialize_data_export::*;
 
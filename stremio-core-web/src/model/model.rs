@@ -7,24 +7,32 @@ use wasm_bindgen::JsValue;
 use stremio_core::{
     models::{
         addon_details::AddonDetails,
+        calendar::Calendar,
         catalog_with_filters::CatalogWithFilters,
         catalogs_with_extra::CatalogsWithExtra,
         continue_watching_preview::ContinueWatchingPreview,
         ctx::Ctx,
         data_export::DataExport,
+        for_you::ForYou,
+        global_search::GlobalSearch,
         installed_addons_with_filters::InstalledAddonsWithFilters,
         library_with_filters::{ContinueWatchingFilter, LibraryWithFilters, NotRemovedFilter},
         link::Link,
         local_search::LocalSearch,
         meta_details::MetaDetails,
         player::Player,
+        playlists::Playlists,
         streaming_server::StreamingServer,
+        watch_history::WatchHistory,
+        watch_stats::WatchStats,
     },
     runtime::Effects,
     types::{
-        addon::DescriptorPreview, api::LinkAuthKey, events::DismissedEventsBucket,
-        library::LibraryBucket, notifications::NotificationsBucket, profile::Profile,
+        accounts::AccountsBucket, addon::DescriptorPreview, api::LinkAuthKey,
+        events::DismissedEventsBucket, library::LibraryBucket, notifications::NotificationsBucket,
+        outbox::OutboxBucket, playlists::PlaylistsBucket, profile::Profile,
         resource::MetaItemPreview, search_history::SearchHistoryBucket, streams::StreamsBucket,
+        watch_history::WatchHistoryBucket,
     },
     Model,
 };
@@ -32,10 +40,12 @@ use stremio_core::{
 use crate::{
     env::WebEnv,
     model::{
-        serialize_catalogs_with_extra, serialize_continue_watching_preview, serialize_ctx,
-        serialize_data_export, serialize_discover, serialize_installed_addons, serialize_library,
-        serialize_local_search, serialize_meta_details, serialize_player, serialize_remote_addons,
-        serialize_streaming_server,
+        serialize_calendar, serialize_catalogs_with_extra, serialize_continue_watching_preview,
+        serialize_ctx, serialize_data_export, serialize_discover, serialize_for_you,
+        serialize_global_search, serialize_installed_addons, serialize_library,
+        serialize_local_search, serialize_meta_details, serialize_player, serialize_playlists,
+        serialize_remote_addons, serialize_streaming_server, serialize_watch_history,
+        serialize_watch_stats,
     },
 };
 
@@ -48,21 +58,30 @@ pub struct WebModel {
     pub data_export: DataExport,
     pub continue_watching_preview: ContinueWatchingPreview,
     pub board: CatalogsWithExtra,
+    /// The recommendations row of the board
+    pub for_you: ForYou,
     pub discover: CatalogWithFilters<MetaItemPreview>,
     pub library: LibraryWithFilters<NotRemovedFilter>,
     pub continue_watching: LibraryWithFilters<ContinueWatchingFilter>,
     pub search: CatalogsWithExtra,
     /// Pre-loaded results for local search
     pub local_search: LocalSearch,
+    /// Merged results of the local feeds, the library and the addon catalogs
+    pub global_search: GlobalSearch,
     pub meta_details: MetaDetails,
     pub remote_addons: CatalogWithFilters<DescriptorPreview>,
     pub installed_addons: InstalledAddonsWithFilters,
     pub addon_details: AddonDetails,
     pub streaming_server: StreamingServer,
     pub player: Player,
+    pub calendar: Calendar,
+    pub playlists: Playlists,
+    pub watch_history: WatchHistory,
+    pub watch_stats: WatchStats,
 }
 
 impl WebModel {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         profile: Profile,
         library: LibraryBucket,
@@ -70,14 +89,29 @@ impl WebModel {
         notifications: NotificationsBucket,
         search_history: SearchHistoryBucket,
         dismissed_events: DismissedEventsBucket,
+        outbox: OutboxBucket,
+        accounts: AccountsBucket,
+        playlists: PlaylistsBucket,
+        watch_history: WatchHistoryBucket,
     ) -> (WebModel, Effects) {
         let (continue_watching_preview, continue_watching_preview_effects) =
-            ContinueWatchingPreview::new(&library, &notifications);
+            ContinueWatchingPreview::new(
+                &library,
+                &notifications,
+                Some(&profile.parental_controls),
+            );
         let (discover, discover_effects) = CatalogWithFilters::<MetaItemPreview>::new(&profile);
-        let (library_, library_effects) =
-            LibraryWithFilters::<NotRemovedFilter>::new(&library, &notifications);
+        let (library_, library_effects) = LibraryWithFilters::<NotRemovedFilter>::new(
+            &library,
+            &notifications,
+            Some(&profile.parental_controls),
+        );
         let (continue_watching, continue_watching_effects) =
-            LibraryWithFilters::<ContinueWatchingFilter>::new(&library, &notifications);
+            LibraryWithFilters::<ContinueWatchingFilter>::new(
+                &library,
+                &notifications,
+                Some(&profile.parental_controls),
+            );
         let (remote_addons, remote_addons_effects) =
             CatalogWithFilters::<DescriptorPreview>::new(&profile);
         let (installed_addons, installed_addons_effects) =
@@ -92,12 +126,18 @@ impl WebModel {
                 notifications,
                 search_history,
                 dismissed_events,
+                outbox,
+                accounts,
+                playlists,
+                watch_history,
             ),
             auth_link: Default::default(),
             data_export: Default::default(),
             local_search,
+            global_search: Default::default(),
             continue_watching_preview,
             board: Default::default(),
+            for_you: Default::default(),
             discover,
             library: library_,
             continue_watching,
@@ -108,6 +148,10 @@ impl WebModel {
             addon_details: Default::default(),
             streaming_server,
             player: Default::default(),
+            calendar: Default::default(),
+            playlists: Default::default(),
+            watch_history: Default::default(),
+            watch_stats: Default::default(),
         };
         (
             model,
@@ -133,7 +177,12 @@ impl WebModel {
                 self.streaming_server.base_url.as_ref(),
                 &self.ctx.profile.settings,
             ),
-            WebModelField::Board => serialize_catalogs_with_extra(&self.board, &self.ctx),
+            WebModelField::Board => serialize_catalogs_with_extra(
+                &self.board,
+                &self.ctx,
+                self.streaming_server.base_url.as_ref(),
+            ),
+            WebModelField::ForYou => serialize_for_you(&self.for_you),
             WebModelField::Discover => {
                 serialize_discover(&self.discover, &self.ctx, &self.streaming_server)
             }
@@ -149,8 +198,13 @@ impl WebModel {
                 self.streaming_server.base_url.as_ref(),
                 "continuewatching".to_owned(),
             ),
-            WebModelField::Search => serialize_catalogs_with_extra(&self.search, &self.ctx),
+            WebModelField::Search => serialize_catalogs_with_extra(
+                &self.search,
+                &self.ctx,
+                self.streaming_server.base_url.as_ref(),
+            ),
             WebModelField::LocalSearch => serialize_local_search(&self.local_search),
+            WebModelField::GlobalSearch => serialize_global_search(&self.global_search),
             WebModelField::MetaDetails => {
                 serialize_meta_details(&self.meta_details, &self.ctx, &self.streaming_server)
             }
@@ -164,6 +218,18 @@ impl WebModel {
             WebModelField::Player => {
                 serialize_player(&self.player, &self.ctx, &self.streaming_server)
             }
+            WebModelField::Calendar => serialize_calendar(
+                &self.calendar,
+                &self.ctx,
+                self.streaming_server.base_url.as_ref(),
+            ),
+            WebModelField::Playlists => serialize_playlists(&self.playlists, &self.ctx),
+            WebModelField::WatchHistory => serialize_watch_history(&self.watch_history),
+            WebModelField::WatchStats => serialize_watch_stats(
+                &self.watch_stats,
+                &self.ctx,
+                self.streaming_server.base_url.as_ref(),
+            ),
         }
     }
 }
@@ -0,0 +1,112 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use gloo_utils::format::JsValueSerdeExt;
+use serde::Serialize;
+use url::Url;
+use wasm_bindgen::JsValue;
+
+use stremio_core::deep_links::{MetaItemDeepLinks, VideoDeepLinks};
+use stremio_core::models::calendar::{Calendar, Selected};
+use stremio_core::models::common::Loadable;
+use stremio_core::models::ctx::Ctx;
+use stremio_core::types::resource::SeriesInfo;
+
+use crate::model::deep_links_ext::DeepLinksExt;
+
+mod model {
+    use super::*;
+    #[derive(Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct MetaItem<'a> {
+        pub id: &'a String,
+        pub r#type: &'a String,
+        pub name: &'a String,
+        pub poster: &'a Option<Url>,
+        pub deep_links: MetaItemDeepLinks,
+    }
+    #[derive(Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct Video<'a> {
+        pub id: &'a String,
+        pub title: &'a String,
+        pub released: &'a Option<DateTime<Utc>>,
+        pub thumbnail: &'a Option<String>,
+        #[serde(flatten)]
+        pub series_info: &'a Option<SeriesInfo>,
+        pub deep_links: VideoDeepLinks,
+    }
+    #[derive(Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct CalendarItem<'a> {
+        pub meta_item: MetaItem<'a>,
+        pub video: Video<'a>,
+    }
+    #[derive(Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct CalendarGroup<'a> {
+        pub start: &'a NaiveDate,
+        pub items: Vec<CalendarItem<'a>>,
+    }
+    #[derive(Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct Calendar<'a> {
+        pub selected: &'a Option<Selected>,
+        /// Whether some of the meta items or catalogs are still loading
+        pub loading: bool,
+        pub groups: Vec<CalendarGroup<'a>>,
+    }
+}
+
+pub fn serialize_calendar(
+    calendar: &Calendar,
+    ctx: &Ctx,
+    streaming_server_url: Option<&Url>,
+) -> JsValue {
+    let loading = calendar
+        .meta_items
+        .iter()
+        .any(|meta_item| matches!(meta_item.content, Some(Loadable::Loading)))
+        || calendar
+            .catalogs
+            .iter()
+            .any(|catalog| matches!(catalog.content, Some(Loadable::Loading)));
+    <JsValue as JsValueSerdeExt>::from_serde(&model::Calendar {
+        selected: &calendar.selected,
+        loading,
+        groups: calendar
+            .groups
+            .iter()
+            .map(|group| model::CalendarGroup {
+                start: &group.start,
+                items: group
+                    .items
+                    .iter()
+                    .map(|item| model::CalendarItem {
+                        meta_item: model::MetaItem {
+                            id: &item.meta_item.id,
+                            r#type: &item.meta_item.r#type,
+                            name: &item.meta_item.name,
+                            poster: &item.meta_item.poster,
+                            deep_links: MetaItemDeepLinks::from(&item.request.path)
+                                .into_web_deep_links(),
+                        },
+                        video: model::Video {
+                            id: &item.video.id,
+                            title: &item.video.title,
+                            released: &item.video.released,
+                            thumbnail: &item.video.thumbnail,
+                            series_info: &item.video.series_info,
+                            deep_links: VideoDeepLinks::from((
+                                &item.video,
+                                &item.request,
+                                &streaming_server_url.cloned(),
+                                &ctx.profile.settings,
+                            ))
+                            .into_web_deep_links(),
+                        },
+                    })
+                    .collect(),
+            })
+            .collect(),
+    })
+    .expect("JsValue from model::Calendar")
+}
@@ -2,11 +2,16 @@ use crate::model::deep_links_ext::DeepLinksExt;
 use gloo_utils::format::JsValueSerdeExt;
 use itertools::Itertools;
 use serde::Serialize;
-use stremio_core::deep_links::{DiscoverDeepLinks, MetaItemDeepLinks};
+use stremio_core::constants::BOARD_ROW_ITEMS_COUNT;
+use stremio_core::deep_links::{
+    DiscoverDeepLinks, LibraryDeepLinks, LibraryItemDeepLinks, MetaItemDeepLinks,
+};
 use stremio_core::models::catalogs_with_extra::{CatalogsWithExtra, Selected};
 use stremio_core::models::common::Loadable;
 use stremio_core::models::ctx::Ctx;
 use stremio_core::types::resource::PosterShape;
+use stremio_core::types::streams::StreamsItemKey;
+use url::Url;
 use wasm_bindgen::JsValue;
 
 mod model {
@@ -44,15 +49,38 @@ mod model {
     }
     #[derive(Serialize)]
     #[serde(rename_all = "camelCase")]
+    pub struct LibraryItem<'a> {
+        #[serde(rename = "_id")]
+        pub id: &'a String,
+        pub name: &'a String,
+        pub r#type: &'a String,
+        pub poster: &'a Option<Url>,
+        pub poster_shape: &'a PosterShape,
+        pub progress: f64,
+        pub watched: bool,
+        pub deep_links: LibraryItemDeepLinks,
+    }
+    #[derive(Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct SmartListCatalog<'a> {
+        pub id: &'a String,
+        pub name: &'a String,
+        pub items: Vec<LibraryItem<'a>>,
+        pub deep_links: LibraryDeepLinks,
+    }
+    #[derive(Serialize)]
+    #[serde(rename_all = "camelCase")]
     pub struct CatalogsWithExtra<'a> {
         pub selected: &'a Option<Selected>,
         pub catalogs: Vec<ResourceLoadable<'a>>,
+        pub smart_lists: Vec<SmartListCatalog<'a>>,
     }
 }
 
 pub fn serialize_catalogs_with_extra(
     catalogs_with_extra: &CatalogsWithExtra,
     ctx: &Ctx,
+    streaming_server_url: Option<&Url>,
 ) -> JsValue {
     <JsValue as JsValueSerdeExt>::from_serde(&model::CatalogsWithExtra {
         selected: &catalogs_with_extra.selected,
@@ -100,7 +128,7 @@ pub fn serialize_catalogs_with_extra(
                                 meta_items
                                     .iter()
                                     .unique_by(|meta_item| &meta_item.id)
-                                    .take(10)
+                                    .take(BOARD_ROW_ITEMS_COUNT)
                                     .map(|meta_item| model::MetaItemPreview {
                                         meta_item,
                                         poster_shape: poster_shape
@@ -128,6 +156,49 @@ pub fn serialize_catalogs_with_extra(
                 },
             )
             .collect::<Vec<_>>(),
+        smart_lists: catalogs_with_extra
+            .smart_lists
+            .iter()
+            .map(|smart_list_catalog| model::SmartListCatalog {
+                id: &smart_list_catalog.smart_list.id,
+                name: &smart_list_catalog.smart_list.name,
+                items: smart_list_catalog
+                    .items
+                    .iter()
+                    .take(BOARD_ROW_ITEMS_COUNT)
+                    .map(|library_item| {
+                        let streams_item =
+                            library_item.state.video_id.as_ref().and_then(|video_id| {
+                                ctx.streams.items.get(&StreamsItemKey {
+                                    meta_id: library_item.id.to_owned(),
+                                    video_id: video_id.to_owned(),
+                                })
+                            });
+                        model::LibraryItem {
+                            id: &library_item.id,
+                            name: &library_item.name,
+                            r#type: &library_item.r#type,
+                            poster: &library_item.poster,
+                            poster_shape: &library_item.poster_shape,
+                            progress: library_item.progress(),
+                            watched: library_item.watched(),
+                            deep_links: LibraryItemDeepLinks::from((
+                                library_item,
+                                streams_item,
+                                streaming_server_url,
+                                &ctx.profile.settings,
+                            ))
+                            .into_web_deep_links(),
+                        }
+                    })
+                    .collect(),
+                deep_links: LibraryDeepLinks::from((
+                    &"library".to_owned(),
+                    &smart_list_catalog.smart_list,
+                ))
+                .into_web_deep_links(),
+            })
+            .collect(),
     })
     .expect("JsValue from model::CatalogsWithExtra")
 }
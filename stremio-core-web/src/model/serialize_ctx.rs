@@ -16,7 +16,13 @@ mod model {
 
     use stremio_core::deep_links::SearchHistoryItemDeepLinks;
     use stremio_core::types::{
-        events::Events, notifications::NotificationItem, profile::Profile, resource::MetaItemId,
+        accounts::Account,
+        addon::Descriptor,
+        events::Events,
+        notifications::NotificationItem,
+        profile::{Auth, Certification, Settings, SmartList},
+        resource::MetaItemId,
+        scheduler::Scheduler,
     };
 
     use crate::model::deep_links_ext::DeepLinksExt;
@@ -24,11 +30,46 @@ mod model {
     #[derive(Serialize)]
     #[serde(rename_all = "camelCase")]
     pub struct Ctx<'a> {
-        /// keep the original Profile model inside.
-        pub profile: &'a Profile,
+        pub profile: Profile<'a>,
         pub notifications: Notifications<'a>,
         pub search_history: Vec<SearchHistoryItem<'a>>,
+        /// Whether the searches are not recorded in the search history
+        pub search_history_paused: bool,
         pub events: &'a Events,
+        pub scheduler: &'a Scheduler,
+        pub outbox: Outbox,
+        /// The accounts which can be switched to
+        pub accounts: &'a [Account],
+        /// Whether the parental controls are temporarily lifted with the PIN
+        pub parental_controls_unlocked: bool,
+    }
+
+    #[derive(Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct Profile<'a> {
+        pub auth: &'a Option<Auth>,
+        pub addons: &'a [Descriptor],
+        pub addons_locked: bool,
+        pub settings: &'a Settings,
+        pub parental_controls: ParentalControls<'a>,
+        pub smart_lists: &'a [SmartList],
+    }
+
+    #[derive(Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct ParentalControls<'a> {
+        pub max_certification: Option<Certification>,
+        pub blocked_genres: &'a [String],
+        pub block_unrated: bool,
+        /// Override the PIN hash, it must not leave the core
+        pub has_pin: bool,
+    }
+
+    #[derive(Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct Outbox {
+        /// The number of changes waiting to be pushed to the API
+        pub pending: usize,
     }
 
     #[derive(Serialize)]
@@ -44,13 +85,27 @@ mod model {
     #[serde(rename_all = "camelCase")]
     pub struct SearchHistoryItem<'a> {
         pub query: &'a String,
+        /// How many times the query was searched
+        pub count: u32,
         pub deep_links: SearchHistoryItemDeepLinks,
     }
 
     impl<'a> From<&'a stremio_core::models::ctx::Ctx> for Ctx<'a> {
         fn from(ctx: &'a stremio_core::models::ctx::Ctx) -> Self {
             Self {
-                profile: &ctx.profile,
+                profile: Profile {
+                    auth: &ctx.profile.auth,
+                    addons: &ctx.profile.addons,
+                    addons_locked: ctx.profile.addons_locked,
+                    settings: &ctx.profile.settings,
+                    parental_controls: ParentalControls {
+                        max_certification: ctx.profile.parental_controls.max_certification,
+                        blocked_genres: &ctx.profile.parental_controls.blocked_genres,
+                        block_unrated: ctx.profile.parental_controls.block_unrated,
+                        has_pin: ctx.profile.parental_controls.has_pin(),
+                    },
+                    smart_lists: &ctx.profile.smart_lists,
+                },
                 notifications: Notifications {
                     items: ctx
                         .notifications
@@ -67,13 +122,23 @@ mod model {
                     .search_history
                     .items
                     .iter()
-                    .sorted_by(|(_, a_date), (_, b_date)| Ord::cmp(b_date, a_date))
-                    .map(|(query, ..)| SearchHistoryItem {
+                    .sorted_by(|(_, a_item), (_, b_item)| {
+                        Ord::cmp(&b_item.last_searched, &a_item.last_searched)
+                    })
+                    .map(|(query, item)| SearchHistoryItem {
                         query,
+                        count: item.count,
                         deep_links: SearchHistoryItemDeepLinks::from(query).into_web_deep_links(),
                     })
                     .collect(),
+                search_history_paused: ctx.search_history.paused,
                 events: &ctx.events,
+                scheduler: &ctx.scheduler,
+                outbox: Outbox {
+                    pending: ctx.outbox.pending_count(),
+                },
+                accounts: &ctx.accounts.items,
+                parental_controls_unlocked: ctx.parental_controls_unlocked,
             }
         }
     }
@@ -0,0 +1,68 @@
+use gloo_utils::format::JsValueSerdeExt;
+use serde::Serialize;
+use wasm_bindgen::JsValue;
+
+use stremio_core::deep_links::MetaItemDeepLinks;
+use stremio_core::models::common::Loadable;
+use stremio_core::models::for_you::{ForYou, Reason, Selected};
+
+use crate::model::deep_links_ext::DeepLinksExt;
+
+mod model {
+    use super::*;
+    #[derive(Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct MetaItemPreview<'a> {
+        #[serde(flatten)]
+        pub meta_item: &'a stremio_core::types::resource::MetaItemPreview,
+        pub score: f64,
+        pub reasons: &'a Vec<Reason>,
+        pub deep_links: MetaItemDeepLinks,
+    }
+    #[derive(Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct ForYou<'a> {
+        pub selected: &'a Option<Selected>,
+        pub content: Option<Loadable<Vec<MetaItemPreview<'a>>, String>>,
+    }
+}
+
+/// The recommendations are shown as a single catalog row
+pub fn serialize_for_you(for_you: &ForYou) -> JsValue {
+    let is_loading = for_you
+        .seeds
+        .iter()
+        .any(|seed| matches!(seed.content, Some(Loadable::Loading)))
+        || for_you
+            .catalogs
+            .iter()
+            .any(|catalog| matches!(catalog.content, Some(Loadable::Loading)));
+    <JsValue as JsValueSerdeExt>::from_serde(&model::ForYou {
+        selected: &for_you.selected,
+        content: for_you.selected.as_ref().map(|_| {
+            if !for_you.items.is_empty() {
+                Loadable::Ready(
+                    for_you
+                        .items
+                        .iter()
+                        .map(|recommendation| model::MetaItemPreview {
+                            meta_item: &recommendation.meta_item,
+                            score: recommendation.score,
+                            reasons: &recommendation.reasons,
+                            deep_links: MetaItemDeepLinks::from((
+                                &recommendation.meta_item,
+                                &recommendation.request,
+                            ))
+                            .into_web_deep_links(),
+                        })
+                        .collect(),
+                )
+            } else if is_loading {
+                Loadable::Loading
+            } else {
+                Loadable::Err("No recommendations yet".to_owned())
+            }
+        }),
+    })
+    .expect("JsValue from model::ForYou")
+}
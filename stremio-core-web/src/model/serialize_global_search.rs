@@ -0,0 +1,67 @@
+use gloo_utils::format::JsValueSerdeExt;
+use serde::Serialize;
+use url::Url;
+use wasm_bindgen::JsValue;
+
+use stremio_core::constants::META_RESOURCE_NAME;
+use stremio_core::deep_links::MetaItemDeepLinks;
+use stremio_core::models::common::Loadable;
+use stremio_core::models::global_search::{GlobalSearch, Selected};
+use stremio_core::models::local_search::SearchableSource;
+use stremio_core::types::addon::ResourcePath;
+
+use crate::model::deep_links_ext::DeepLinksExt;
+
+mod model {
+    use super::*;
+
+    #[derive(Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct GlobalSearch<'a> {
+        pub selected: &'a Option<Selected>,
+        /// Whether some of the addons have not answered yet
+        pub loading: bool,
+        pub items: Vec<GlobalSearchItem<'a>>,
+    }
+
+    #[derive(Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct GlobalSearchItem<'a> {
+        pub id: &'a String,
+        pub r#type: &'a String,
+        pub name: &'a String,
+        pub poster: &'a Option<Url>,
+        pub release_info: &'a Option<String>,
+        pub sources: &'a Vec<SearchableSource>,
+        pub deep_links: MetaItemDeepLinks,
+    }
+}
+
+pub fn serialize_global_search(global_search: &GlobalSearch) -> JsValue {
+    <JsValue as JsValueSerdeExt>::from_serde(&model::GlobalSearch {
+        selected: &global_search.selected,
+        loading: global_search
+            .catalogs
+            .iter()
+            .any(|catalog| matches!(catalog.content, Some(Loadable::Loading))),
+        items: global_search
+            .items
+            .iter()
+            .map(|item| model::GlobalSearchItem {
+                id: &item.searchable.id,
+                r#type: &item.searchable.r#type,
+                name: &item.searchable.name,
+                poster: &item.searchable.poster,
+                release_info: &item.searchable.release_info,
+                sources: &item.sources,
+                deep_links: MetaItemDeepLinks::from(&ResourcePath::without_extra(
+                    META_RESOURCE_NAME,
+                    &item.searchable.r#type,
+                    &item.searchable.id,
+                ))
+                .into_web_deep_links(),
+            })
+            .collect(),
+    })
+    .expect("JsValue from model::GlobalSearch")
+}
@@ -4,6 +4,7 @@ use serde::Serialize;
 use stremio_core::deep_links::{LibraryDeepLinks, LibraryItemDeepLinks};
 use stremio_core::models::ctx::Ctx;
 use stremio_core::models::library_with_filters::{LibraryWithFilters, Selected, Sort};
+use stremio_core::types::library::WatchStatus;
 use stremio_core::types::resource::PosterShape;
 use stremio_core::types::streams::StreamsItemKey;
 use url::Url;
@@ -23,6 +24,8 @@ mod model {
         pub notifications: usize,
         pub progress: f64,
         pub watched: bool,
+        pub watch_status: Option<WatchStatus>,
+        pub rating: Option<u8>,
         pub deep_links: LibraryItemDeepLinks,
     }
     #[derive(Serialize)]
@@ -41,6 +44,13 @@ mod model {
     }
     #[derive(Serialize)]
     #[serde(rename_all = "camelCase")]
+    pub struct SelectableWatchStatus<'a> {
+        pub watch_status: &'a Option<WatchStatus>,
+        pub selected: &'a bool,
+        pub deep_links: LibraryDeepLinks,
+    }
+    #[derive(Serialize)]
+    #[serde(rename_all = "camelCase")]
     pub struct SelectablePage {
         pub deep_links: LibraryDeepLinks,
     }
@@ -49,6 +59,7 @@ mod model {
     pub struct Selectable<'a> {
         pub types: Vec<SelectableType<'a>>,
         pub sorts: Vec<SelectableSort<'a>>,
+        pub watch_statuses: Vec<SelectableWatchStatus<'a>>,
         pub next_page: bool,
     }
     #[derive(Serialize)]
@@ -90,6 +101,17 @@ pub fn serialize_library<F>(
                         .into_web_deep_links(),
                 })
                 .collect(),
+            watch_statuses: library
+                .selectable
+                .watch_statuses
+                .iter()
+                .map(|selectable_watch_status| model::SelectableWatchStatus {
+                    watch_status: &selectable_watch_status.watch_status,
+                    selected: &selectable_watch_status.selected,
+                    deep_links: LibraryDeepLinks::from((&root, &selectable_watch_status.request))
+                        .into_web_deep_links(),
+                })
+                .collect(),
             next_page: library.selectable.next_page.is_some(),
         },
         catalog: library
@@ -122,6 +144,8 @@ pub fn serialize_library<F>(
                         .map_or(0, |item| item.len()),
                     progress: library_item.progress(),
                     watched: library_item.watched(),
+                    watch_status: library_item.state.watch_status(),
+                    rating: library_item.state.rating(),
                     deep_links: LibraryItemDeepLinks::from((
                         library_item,
                         streams_item,
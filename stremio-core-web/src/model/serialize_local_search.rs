@@ -3,8 +3,8 @@ use itertools::Itertools;
 use serde::Serialize;
 use wasm_bindgen::JsValue;
 
-use stremio_core::deep_links::LocalSearchItemDeepLinks;
-use stremio_core::models::local_search::{LocalSearch, Searchable};
+use stremio_core::deep_links::{LocalSearchItemDeepLinks, SearchHistoryItemDeepLinks};
+use stremio_core::models::local_search::{LocalSearch, Searchable, SearchableSource};
 
 use crate::model::deep_links_ext::DeepLinksExt;
 
@@ -16,12 +16,23 @@ mod model {
     pub struct LocalSearch<'a> {
         /// The results of the search autocompletion
         pub items: Vec<LocalSearchItem<'a>>,
+        /// The searched queries starting with the typed one
+        pub search_history: Vec<SearchHistoryItem<'a>>,
+    }
+
+    #[derive(Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct SearchHistoryItem<'a> {
+        pub query: &'a String,
+        pub deep_links: SearchHistoryItemDeepLinks,
     }
 
     #[derive(Serialize)]
     #[serde(rename_all = "camelCase")]
     pub struct LocalSearchItem<'a> {
         pub query: &'a String,
+        /// Used for badging the items of the library and of the addons
+        pub source: &'a SearchableSource,
         pub deep_links: LocalSearchItemDeepLinks,
     }
 }
@@ -30,14 +41,22 @@ pub fn serialize_local_search(local_search: &LocalSearch) -> JsValue {
     <JsValue as JsValueSerdeExt>::from_serde(&model::LocalSearch {
         items: local_search
             .search_results
-            .to_owned()
             .iter()
-            .map(|Searchable { name, .. }| model::LocalSearchItem {
+            .map(|Searchable { name, source, .. }| model::LocalSearchItem {
                 query: name,
+                source,
                 deep_links: LocalSearchItemDeepLinks::from(name).into_web_deep_links(),
             })
             .unique_by(|i| i.query)
             .collect(),
+        search_history: local_search
+            .search_history_results
+            .iter()
+            .map(|query| model::SearchHistoryItem {
+                query,
+                deep_links: SearchHistoryItemDeepLinks::from(query).into_web_deep_links(),
+            })
+            .collect(),
     })
     .expect("JsValue from model::LocalSearch")
 }
@@ -11,6 +11,7 @@ use stremio_core::models::streaming_server::StreamingServer;
 use stremio_core::runtime::Env;
 use stremio_core::types::{
     addon::{ResourcePath, ResourceRequest},
+    player::SubtitlesSource,
     streams::StreamItemState,
 };
 use url::Url;
@@ -55,6 +56,14 @@ mod model {
     }
     #[derive(Serialize)]
     #[serde(rename_all = "camelCase")]
+    pub struct QueueItem<'a> {
+        pub meta_item: &'a stremio_core::types::resource::MetaItemPreview,
+        pub video: &'a stremio_core::types::resource::Video,
+        pub stream: &'a Option<stremio_core::types::resource::Stream>,
+        pub deep_links: VideoDeepLinks,
+    }
+    #[derive(Serialize)]
+    #[serde(rename_all = "camelCase")]
     pub struct MetaItem<'a> {
         #[serde(flatten)]
         pub meta_item: &'a stremio_core::types::resource::MetaItem,
@@ -70,6 +79,14 @@ mod model {
     }
     #[derive(Serialize)]
     #[serde(rename_all = "camelCase")]
+    pub struct PlayerSubtitles<'a> {
+        #[serde(flatten)]
+        pub subtitles: &'a stremio_core::types::player::PlayerSubtitles,
+        /// The addon name, `None` for the subtitles of the stream
+        pub origin: Option<&'a String>,
+    }
+    #[derive(Serialize)]
+    #[serde(rename_all = "camelCase")]
     pub struct LibraryItemState<'a> {
         pub time_offset: &'a u64,
         #[serde(rename = "video_id")]
@@ -96,9 +113,17 @@ mod model {
         pub selected: Option<Selected<'a>>,
         pub meta_item: Option<Loadable<model::MetaItem<'a>, &'a ResourceError>>,
         pub subtitles: Vec<model::Subtitles<'a>>,
+        /// The stream and addons subtitles, best match first
+        pub aggregated_subtitles: Vec<model::PlayerSubtitles<'a>>,
+        pub selected_subtitles: Option<model::PlayerSubtitles<'a>>,
+        pub subtitles_sync: Option<&'a stremio_core::types::subtitles::SubtitlesSync>,
         pub next_video: Option<Video<'a>>,
+        /// Played before the next video
+        pub queue: Vec<QueueItem<'a>>,
         pub series_info: Option<&'a stremio_core::types::resource::SeriesInfo>,
         pub library_item: Option<LibraryItem<'a>>,
+        /// Where the video was left off, for the resume prompt
+        pub resume_point: Option<&'a stremio_core::types::library::ResumePoint>,
         pub stream_state: Option<&'a StreamItemState>,
         #[serde(skip_serializing_if = "Option::is_none")]
         pub intro_outro: Option<&'a stremio_core::types::player::IntroOutro>,
@@ -182,6 +207,16 @@ pub fn serialize_player(player: &Player, ctx: &Ctx, streaming_server: &Streaming
                     })
             })
             .collect(),
+        aggregated_subtitles: player
+            .aggregated_subtitles
+            .iter()
+            .map(|subtitles| serialize_player_subtitles(subtitles, ctx))
+            .collect(),
+        selected_subtitles: player
+            .selected_subtitles
+            .as_ref()
+            .map(|subtitles| serialize_player_subtitles(subtitles, ctx)),
+        subtitles_sync: player.subtitles_sync.as_ref(),
         next_video: player
             .selected
             .as_ref()
@@ -222,15 +257,47 @@ pub fn serialize_player(player: &Player, ctx: &Ctx, streaming_server: &Streaming
                         _ => None,
                     })
                     .unwrap_or_default(),
-                deep_links: VideoDeepLinks::from((
-                    video,
-                    stream_request,
-                    meta_request,
-                    &streaming_server.base_url,
-                    &ctx.profile.settings,
-                ))
+                // the queued item can be of another meta item
+                deep_links: match &player.next_queue_item {
+                    Some(next_queue_item) => VideoDeepLinks::from((
+                        video,
+                        &next_queue_item.meta_request,
+                        &streaming_server.base_url,
+                        &ctx.profile.settings,
+                    )),
+                    None => VideoDeepLinks::from((
+                        video,
+                        stream_request,
+                        meta_request,
+                        &streaming_server.base_url,
+                        &ctx.profile.settings,
+                    )),
+                }
                 .into_web_deep_links(),
             }),
+        queue: player
+            .queue
+            .iter()
+            .map(|item| {
+                // the player link plays the queued stream
+                let mut video = item.video.to_owned();
+                if let Some(stream) = &item.stream {
+                    video.streams = vec![stream.to_owned()];
+                }
+                model::QueueItem {
+                    meta_item: &item.meta_item,
+                    video: &item.video,
+                    stream: &item.stream,
+                    deep_links: VideoDeepLinks::from((
+                        &video,
+                        &item.meta_request,
+                        &streaming_server.base_url,
+                        &ctx.profile.settings,
+                    ))
+                    .into_web_deep_links(),
+                }
+            })
+            .collect(),
         series_info: player.series_info.as_ref(),
         library_item: player
             .library_item
@@ -242,6 +309,7 @@ pub fn serialize_player(player: &Player, ctx: &Ctx, streaming_server: &Streaming
                     video_id: &library_item.state.video_id,
                 },
             }),
+        resume_point: player.resume_point.as_ref(),
         stream_state: player.stream_state.as_ref(),
         intro_outro: player.intro_outro.as_ref(),
         title: player.selected.as_ref().and_then(|selected| {
@@ -306,3 +374,21 @@ pub fn serialize_player(player: &Player, ctx: &Ctx, streaming_server: &Streaming
     })
     .expect("JsValue from model::Player")
 }
+
+fn serialize_player_subtitles<'a>(
+    subtitles: &'a stremio_core::types::player::PlayerSubtitles,
+    ctx: &'a Ctx,
+) -> model::PlayerSubtitles<'a> {
+    model::PlayerSubtitles {
+        subtitles,
+        origin: match &subtitles.source {
+            SubtitlesSource::Addon { transport_url } => ctx
+                .profile
+                .addons
+                .iter()
+                .find(|addon| &addon.transport_url == transport_url)
+                .map(|addon| &addon.manifest.name),
+            SubtitlesSource::Stream => None,
+        },
+    }
+}
@@ -0,0 +1,95 @@
+use gloo_utils::format::JsValueSerdeExt;
+use serde::Serialize;
+use wasm_bindgen::JsValue;
+
+use stremio_core::constants::META_RESOURCE_NAME;
+use stremio_core::deep_links::MetaItemDeepLinks;
+use stremio_core::models::ctx::Ctx;
+use stremio_core::models::playlists::{Playlists, Selected};
+use stremio_core::types::addon::ResourcePath;
+
+use crate::model::deep_links_ext::DeepLinksExt;
+
+mod model {
+    use super::*;
+    #[derive(Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct PlaylistPreview<'a> {
+        pub id: &'a String,
+        pub name: &'a String,
+        pub items_count: usize,
+        pub selected: bool,
+    }
+    #[derive(Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct MetaItemPreview<'a> {
+        #[serde(flatten)]
+        pub meta_item: &'a stremio_core::types::resource::MetaItemPreview,
+        pub in_library: bool,
+        pub watched: bool,
+        pub deep_links: MetaItemDeepLinks,
+    }
+    #[derive(Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct Playlist<'a> {
+        pub id: &'a String,
+        pub name: &'a String,
+        pub items: Vec<MetaItemPreview<'a>>,
+    }
+    #[derive(Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct Playlists<'a> {
+        pub selected: &'a Option<Selected>,
+        pub playlists: Vec<PlaylistPreview<'a>>,
+        pub playlist: Option<Playlist<'a>>,
+    }
+}
+
+pub fn serialize_playlists(playlists: &Playlists, ctx: &Ctx) -> JsValue {
+    <JsValue as JsValueSerdeExt>::from_serde(&model::Playlists {
+        selected: &playlists.selected,
+        playlists: ctx
+            .playlists
+            .items
+            .iter()
+            .map(|playlist| model::PlaylistPreview {
+                id: &playlist.id,
+                name: &playlist.name,
+                items_count: playlist.items.len(),
+                selected: playlists
+                    .playlist
+                    .as_ref()
+                    .map_or(false, |selected| selected.id == playlist.id),
+            })
+            .collect(),
+        playlist: playlists.playlist.as_ref().map(|playlist| model::Playlist {
+            id: &playlist.id,
+            name: &playlist.name,
+            items: playlist
+                .items
+                .iter()
+                .map(|meta_item| {
+                    let library_item = ctx
+                        .library
+                        .items
+                        .get(&meta_item.id)
+                        .filter(|library_item| !library_item.removed);
+                    model::MetaItemPreview {
+                        meta_item,
+                        in_library: library_item.is_some(),
+                        watched: library_item
+                            .map(|library_item| library_item.watched())
+                            .unwrap_or_default(),
+                        deep_links: MetaItemDeepLinks::from(&ResourcePath::without_extra(
+                            META_RESOURCE_NAME,
+                            &meta_item.r#type,
+                            &meta_item.id,
+                        ))
+                        .into_web_deep_links(),
+                    }
+                })
+                .collect(),
+        }),
+    })
+    .expect("JsValue from model::Playlists")
+}
@@ -0,0 +1,51 @@
+use gloo_utils::format::JsValueSerdeExt;
+use serde::Serialize;
+use wasm_bindgen::JsValue;
+
+use stremio_core::constants::META_RESOURCE_NAME;
+use stremio_core::deep_links::MetaItemDeepLinks;
+use stremio_core::models::watch_history::{Selected, WatchHistory};
+use stremio_core::types::addon::ResourcePath;
+
+use crate::model::deep_links_ext::DeepLinksExt;
+
+mod model {
+    use super::*;
+    #[derive(Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct WatchHistoryItem<'a> {
+        #[serde(flatten)]
+        pub item: &'a stremio_core::types::watch_history::WatchHistoryItem,
+        pub progress: f64,
+        pub deep_links: MetaItemDeepLinks,
+    }
+    #[derive(Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct WatchHistory<'a> {
+        pub selected: &'a Option<Selected>,
+        pub items: Vec<WatchHistoryItem<'a>>,
+        pub next_page: &'a Option<Selected>,
+    }
+}
+
+pub fn serialize_watch_history(watch_history: &WatchHistory) -> JsValue {
+    <JsValue as JsValueSerdeExt>::from_serde(&model::WatchHistory {
+        selected: &watch_history.selected,
+        items: watch_history
+            .items
+            .iter()
+            .map(|item| model::WatchHistoryItem {
+                item,
+                progress: item.progress(),
+                deep_links: MetaItemDeepLinks::from(&ResourcePath::without_extra(
+                    META_RESOURCE_NAME,
+                    &item.r#type,
+                    &item.meta_id,
+                ))
+                .into_web_deep_links(),
+            })
+            .collect(),
+        next_page: &watch_history.next_page,
+    })
+    .expect("JsValue from model::WatchHistory")
+}
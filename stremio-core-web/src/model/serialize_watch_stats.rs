@@ -0,0 +1,121 @@
+use gloo_utils::format::JsValueSerdeExt;
+use serde::Serialize;
+use url::Url;
+use wasm_bindgen::JsValue;
+
+use stremio_core::deep_links::LibraryItemDeepLinks;
+use stremio_core::models::ctx::Ctx;
+use stremio_core::models::watch_stats::{
+    GenreStats, MonthStats, Selected, Streaks, TypeStats, WatchStats,
+};
+use stremio_core::types::resource::PosterShape;
+use stremio_core::types::streams::StreamsItemKey;
+
+use crate::model::deep_links_ext::DeepLinksExt;
+
+mod model {
+    use super::*;
+    #[derive(Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct LibraryItem<'a> {
+        #[serde(rename = "_id")]
+        pub id: &'a String,
+        pub name: &'a String,
+        pub r#type: &'a String,
+        pub poster: &'a Option<Url>,
+        pub poster_shape: &'a PosterShape,
+        pub overall_time_watched: u64,
+        pub times_watched: u32,
+        pub deep_links: LibraryItemDeepLinks,
+    }
+    #[derive(Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct Stats<'a> {
+        pub items: usize,
+        pub watched_items: usize,
+        pub time_watched: u64,
+        pub overall_time_watched: u64,
+        pub types: &'a Vec<TypeStats>,
+        pub genres: &'a Vec<GenreStats>,
+        pub streaks: &'a Streaks,
+        pub months: &'a Vec<MonthStats>,
+        pub most_binged: Vec<LibraryItem<'a>>,
+    }
+    #[derive(Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct YearInReview<'a> {
+        pub year: i32,
+        pub items: usize,
+        pub overall_time_watched: u64,
+        pub types: &'a Vec<TypeStats>,
+        pub genres: &'a Vec<GenreStats>,
+        pub months: &'a Vec<MonthStats>,
+        pub busiest_month: Option<u32>,
+        pub top_items: Vec<LibraryItem<'a>>,
+    }
+    #[derive(Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct WatchStats<'a> {
+        pub selected: &'a Option<Selected>,
+        pub stats: Option<Stats<'a>>,
+        pub year_in_review: Option<YearInReview<'a>>,
+    }
+}
+
+pub fn serialize_watch_stats(
+    watch_stats: &WatchStats,
+    ctx: &Ctx,
+    streaming_server_url: Option<&Url>,
+) -> JsValue {
+    let library_item = |library_item: &'_ stremio_core::types::library::LibraryItem| {
+        let streams_item = library_item.state.video_id.as_ref().and_then(|video_id| {
+            ctx.streams.items.get(&StreamsItemKey {
+                meta_id: library_item.id.to_owned(),
+                video_id: video_id.to_owned(),
+            })
+        });
+        model::LibraryItem {
+            id: &library_item.id,
+            name: &library_item.name,
+            r#type: &library_item.r#type,
+            poster: &library_item.poster,
+            poster_shape: &library_item.poster_shape,
+            overall_time_watched: library_item.state.overall_time_watched,
+            times_watched: library_item.state.times_watched,
+            deep_links: LibraryItemDeepLinks::from((
+                library_item,
+                streams_item,
+                streaming_server_url,
+                &ctx.profile.settings,
+            ))
+            .into_web_deep_links(),
+        }
+    };
+    <JsValue as JsValueSerdeExt>::from_serde(&model::WatchStats {
+        selected: &watch_stats.selected,
+        stats: watch_stats.stats.as_ref().map(|stats| model::Stats {
+            items: stats.items,
+            watched_items: stats.watched_items,
+            time_watched: stats.time_watched,
+            overall_time_watched: stats.overall_time_watched,
+            types: &stats.types,
+            genres: &stats.genres,
+            streaks: &stats.streaks,
+            months: &stats.months,
+            most_binged: stats.most_binged.iter().map(library_item).collect(),
+        }),
+        year_in_review: watch_stats.year_in_review.as_ref().map(|year_in_review| {
+            model::YearInReview {
+                year: year_in_review.year,
+                items: year_in_review.items,
+                overall_time_watched: year_in_review.overall_time_watched,
+                types: &year_in_review.types,
+                genres: &year_in_review.genres,
+                months: &year_in_review.months,
+                busiest_month: year_in_review.busiest_month,
+                top_items: year_in_review.top_items.iter().map(library_item).collect(),
+            }
+        }),
+    })
+    .expect("JsValue from model::WatchStats")
+}
@@ -10,16 +10,26 @@ use wasm_bindgen::{prelude::wasm_bindgen, JsValue};
 
 use stremio_core::{
     constants::{
-        DISMISSED_EVENTS_STORAGE_KEY, LIBRARY_RECENT_STORAGE_KEY, LIBRARY_STORAGE_KEY,
-        NOTIFICATIONS_STORAGE_KEY, PROFILE_STORAGE_KEY, SEARCH_HISTORY_STORAGE_KEY,
-        STREAMS_STORAGE_KEY,
+        ACCOUNTS_STORAGE_KEY, DISMISSED_EVENTS_STORAGE_KEY, LIBRARY_RECENT_STORAGE_KEY,
+        LIBRARY_STORAGE_KEY, NOTIFICATIONS_STORAGE_KEY, OUTBOX_STORAGE_KEY, PLAYLISTS_STORAGE_KEY,
+        PROFILE_STORAGE_KEY, SEARCH_HISTORY_STORAGE_KEY, STREAMS_STORAGE_KEY,
+        WATCH_HISTORY_STORAGE_KEY,
     },
     models::common::Loadable,
     runtime::{msg::Action, Env, EnvError, Runtime, RuntimeAction, RuntimeEvent},
     types::{
-        events::DismissedEventsBucket, library::LibraryBucket, notifications::NotificationsBucket,
-        profile::Profile, resource::Stream, search_history::SearchHistoryBucket,
+        accounts::AccountsBucket,
+        events::DismissedEventsBucket,
+        library::LibraryBucket,
+        notifications::NotificationsBucket,
+        outbox::OutboxBucket,
+        playlists::PlaylistsBucket,
+        profile::Profile,
+        resource::Stream,
+        search_history::SearchHistoryBucket,
         streams::StreamsBucket,
+        subtitles::{ParsedSubtitles, SubtitlesFormat},
+        watch_history::WatchHistoryBucket,
     },
 };
 
@@ -65,13 +75,17 @@ pub async fn initialize_runtime(emit_to_ui: js_sys::Function) -> Result<(), JsVa
     match env_init_result {
         Ok(_) => {
             let storage_result = try_join!(
-                WebEnv::get_storage::<Profile>(PROFILE_STORAGE_KEY),
-                WebEnv::get_storage::<LibraryBucket>(LIBRARY_RECENT_STORAGE_KEY),
-                WebEnv::get_storage::<LibraryBucket>(LIBRARY_STORAGE_KEY),
-                WebEnv::get_storage::<StreamsBucket>(STREAMS_STORAGE_KEY),
+                WebEnv::get_encrypted_storage::<Profile>(PROFILE_STORAGE_KEY),
+                WebEnv::get_encrypted_storage::<LibraryBucket>(LIBRARY_RECENT_STORAGE_KEY),
+                WebEnv::get_encrypted_storage::<LibraryBucket>(LIBRARY_STORAGE_KEY),
+                WebEnv::get_encrypted_storage::<StreamsBucket>(STREAMS_STORAGE_KEY),
                 WebEnv::get_storage::<NotificationsBucket>(NOTIFICATIONS_STORAGE_KEY),
                 WebEnv::get_storage::<SearchHistoryBucket>(SEARCH_HISTORY_STORAGE_KEY),
                 WebEnv::get_storage::<DismissedEventsBucket>(DISMISSED_EVENTS_STORAGE_KEY),
+                WebEnv::get_encrypted_storage::<OutboxBucket>(OUTBOX_STORAGE_KEY),
+                WebEnv::get_storage::<AccountsBucket>(ACCOUNTS_STORAGE_KEY),
+                WebEnv::get_storage::<PlaylistsBucket>(PLAYLISTS_STORAGE_KEY),
+                WebEnv::get_encrypted_storage::<WatchHistoryBucket>(WATCH_HISTORY_STORAGE_KEY),
             );
             match storage_result {
                 Ok((
@@ -82,6 +96,10 @@ pub async fn initialize_runtime(emit_to_ui: js_sys::Function) -> Result<(), JsVa
                     notifications_bucket,
                     search_history_bucket,
                     dismissed_events_bucket,
+                    outbox_bucket,
+                    accounts_bucket,
+                    playlists_bucket,
+                    watch_history_bucket,
                 )) => {
                     let profile = profile.unwrap_or_default();
                     let mut library = LibraryBucket::new(profile.uid(), vec![]);
@@ -99,6 +117,12 @@ pub async fn initialize_runtime(emit_to_ui: js_sys::Function) -> Result<(), JsVa
                         search_history_bucket.unwrap_or(SearchHistoryBucket::new(profile.uid()));
                     let dismissed_events_bucket = dismissed_events_bucket
                         .unwrap_or(DismissedEventsBucket::new(profile.uid()));
+                    let outbox_bucket =
+                        outbox_bucket.unwrap_or_else(|| OutboxBucket::new(profile.uid()));
+                    let playlists_bucket =
+                        playlists_bucket.unwrap_or_else(|| PlaylistsBucket::new(profile.uid()));
+                    let watch_history_bucket = watch_history_bucket
+                        .unwrap_or_else(|| WatchHistoryBucket::new(profile.uid()));
                     let (model, effects) = WebModel::new(
                         profile,
                         library,
@@ -106,6 +130,10 @@ pub async fn initialize_runtime(emit_to_ui: js_sys::Function) -> Result<(), JsVa
                         notifications_bucket,
                         search_history_bucket,
                         dismissed_events_bucket,
+                        outbox_bucket,
+                        accounts_bucket.unwrap_or_default(),
+                        playlists_bucket,
+                        watch_history_bucket,
                     );
                     let (runtime, rx) = Runtime::<WebEnv, _>::new(
                         model,
@@ -237,3 +265,28 @@ pub fn decode_stream(stream: JsValue) -> JsValue {
         _ => JsValue::NULL,
     }
 }
+
+#[wasm_bindgen]
+pub fn parse_subtitles(content: Vec<u8>) -> JsValue {
+    match ParsedSubtitles::parse(&content) {
+        Ok(subtitles) => <JsValue as JsValueSerdeExt>::from_serde(&subtitles)
+            .expect("JsValue from ParsedSubtitles"),
+        _ => JsValue::NULL,
+    }
+}
+
+/// Converts the subtitles to the given format, applying the `subtitleDelay` of the stream state.
+///
+/// Returns `null` if the format is unknown or the subtitles can not be parsed
+#[wasm_bindgen]
+pub fn convert_subtitles(content: Vec<u8>, format: JsValue, delay: JsValue) -> JsValue {
+    let format: SubtitlesFormat = match JsValueSerdeExt::into_serde(&format) {
+        Ok(format) => format,
+        Err(_) => return JsValue::NULL,
+    };
+    let delay = delay.as_f64().map(|delay| delay as i64).unwrap_or_default();
+    match ParsedSubtitles::parse(&content) {
+        Ok(subtitles) => JsValue::from_str(&subtitles.with_delay(delay).to_format(format)),
+        _ => JsValue::NULL,
+    }
+}